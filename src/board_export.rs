@@ -0,0 +1,447 @@
+use std::{
+    fmt::Write,
+    fs::{create_dir_all, write},
+    path::Path,
+};
+
+use ggez::{
+    glam::{vec2, Vec2},
+    graphics::Color,
+    GameError, GameResult,
+};
+use ggez_no_re::util::ResultExtToGameError;
+use image::RgbaImage;
+use tiny_skia::{
+    FillRule, LineCap, LineJoin, Paint, Path as SkiaPath, PathBuilder, Pixmap, Stroke, Transform,
+};
+
+use crate::{
+    game::{Game, MeepleKind},
+    game_client::{
+        MEEPLE_CENTER, MEEPLE_HEAD_POINT, MEEPLE_HEAD_RADIUS, MEEPLE_POINTS, MEEPLE_SIZE,
+    },
+};
+
+const BACKGROUND_COLOR: Color = Color::WHITE;
+const OUTLINE_COLOR: Color = Color {
+    r: 0.0,
+    g: 0.0,
+    b: 0.0,
+    a: 0.5,
+};
+const OUTLINE_WIDTH: f32 = 0.015;
+
+/// Blank space left around the board, in tiles.
+const MARGIN: f32 = 0.25;
+
+/// A shape on the board, measured in tiles from the origin tile.
+#[derive(Debug)]
+enum Shape {
+    Polygon {
+        points: Vec<Vec2>,
+        color: Color,
+    },
+    Line {
+        points: Vec<Vec2>,
+        width: f32,
+        color: Color,
+    },
+    Ellipse {
+        center: Vec2,
+        radii: Vec2,
+        color: Color,
+    },
+}
+
+/// A picture of the board, with the placed tiles, the meeples on them, and an outline
+/// around every group. Can be written out as an SVG at any size, or rasterized to a PNG
+/// without a window.
+pub struct BoardImage {
+    shapes: Vec<Shape>,
+    top_left: Vec2,
+    size: Vec2,
+}
+
+impl BoardImage {
+    pub fn new(game: &mut Game) -> BoardImage {
+        let mut shapes = Vec::new();
+
+        for (pos, tile) in &game.placed_tiles {
+            let offset = vec2(pos.0 as f32, pos.1 as f32);
+            for (i, segment) in tile.segments.iter().enumerate() {
+                shapes.push(Shape::Polygon {
+                    points: tile.segment_polygon(i).map(|v| v + offset).collect(),
+                    color: segment.stype.color(),
+                });
+                for (location, outline, color) in segment
+                    .attributes
+                    .iter()
+                    .filter_map(|attribute| attribute.icon())
+                {
+                    shapes.push(Shape::Polygon {
+                        points: outline.iter().map(|v| *v + location + offset).collect(),
+                        color,
+                    });
+                }
+            }
+        }
+
+        let group_idents: Vec<_> = game.groups.keys().collect();
+        for group_ident in group_idents {
+            let Some(shape_details) = game.get_group_shape_details(group_ident) else {
+                continue;
+            };
+            for line in &shape_details.outline {
+                shapes.push(Shape::Line {
+                    points: line.clone(),
+                    width: OUTLINE_WIDTH,
+                    color: OUTLINE_COLOR,
+                });
+            }
+        }
+
+        for &((pos, seg_index), player, kind) in
+            game.groups.values().flat_map(|group| &group.meeples)
+        {
+            let Some(tile) = game.placed_tiles.get(&pos) else {
+                continue;
+            };
+            let spot = tile.segments[seg_index].meeple_spot + vec2(pos.0 as f32, pos.1 as f32);
+            let color = game.players[player].color;
+            meeple_of_kind(&mut shapes, spot, color, MEEPLE_SIZE, kind);
+        }
+
+        let (min, max) = game.placed_tiles.keys().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), pos| {
+                let pos = vec2(pos.0 as f32, pos.1 as f32);
+                (min.min(pos), max.max(pos + Vec2::ONE))
+            },
+        );
+        let (min, max) = if game.placed_tiles.is_empty() {
+            (Vec2::ZERO, Vec2::ONE)
+        } else {
+            (min, max)
+        };
+        BoardImage {
+            shapes,
+            top_left: min - Vec2::splat(MARGIN),
+            size: max - min + Vec2::splat(MARGIN * 2.0),
+        }
+    }
+
+    /// The size of the image in pixels, when each tile is `tile_size` pixels across.
+    pub fn dimensions(&self, tile_size: u32) -> (u32, u32) {
+        let size = (self.size * tile_size as f32).ceil();
+        (size.x as u32, size.y as u32)
+    }
+
+    fn to_pixels(&self, point: Vec2, tile_size: u32) -> Vec2 {
+        (point - self.top_left) * tile_size as f32
+    }
+
+    fn skia_path(&self, points: &[Vec2], tile_size: u32, close: bool) -> Option<SkiaPath> {
+        let mut path = PathBuilder::new();
+        for (i, point) in points.iter().enumerate() {
+            let point = self.to_pixels(*point, tile_size);
+            if i == 0 {
+                path.move_to(point.x, point.y);
+            } else {
+                path.line_to(point.x, point.y);
+            }
+        }
+        if close {
+            path.close();
+        }
+        path.finish()
+    }
+
+    pub fn to_svg(&self, tile_size: u32) -> String {
+        let scale = tile_size as f32;
+        let (width, height) = self.dimensions(tile_size);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+        );
+        let _ = writeln!(
+            svg,
+            "<rect width=\"100%\" height=\"100%\" {}/>",
+            svg_paint("fill", BACKGROUND_COLOR)
+        );
+        let points = |points: &[Vec2]| {
+            points
+                .iter()
+                .map(|point| {
+                    let point = self.to_pixels(*point, tile_size);
+                    format!("{:.2},{:.2}", point.x, point.y)
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        for shape in &self.shapes {
+            let _ = match shape {
+                Shape::Polygon { points: verts, color } => writeln!(
+                    svg,
+                    "<polygon points=\"{}\" {}/>",
+                    points(verts),
+                    svg_paint("fill", *color)
+                ),
+                Shape::Line {
+                    points: verts,
+                    width,
+                    color,
+                } => writeln!(
+                    svg,
+                    "<polyline points=\"{}\" fill=\"none\" stroke-width=\"{:.2}\" stroke-linecap=\"round\" stroke-linejoin=\"round\" {}/>",
+                    points(verts),
+                    width * scale,
+                    svg_paint("stroke", *color)
+                ),
+                Shape::Ellipse {
+                    center,
+                    radii,
+                    color,
+                } => {
+                    let center = self.to_pixels(*center, tile_size);
+                    writeln!(
+                        svg,
+                        "<ellipse cx=\"{:.2}\" cy=\"{:.2}\" rx=\"{:.2}\" ry=\"{:.2}\" {}/>",
+                        center.x,
+                        center.y,
+                        radii.x * scale,
+                        radii.y * scale,
+                        svg_paint("fill", *color)
+                    )
+                }
+            };
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Rasterizes the image in software, so it works without a graphics context.
+    pub fn to_png(&self, tile_size: u32) -> GameResult<RgbaImage> {
+        let scale = tile_size as f32;
+        let (width, height) = self.dimensions(tile_size);
+        let mut pixmap = Pixmap::new(width, height).ok_or_else(|| {
+            GameError::CustomError(format!("Can't export a {width}x{height} image"))
+        })?;
+        pixmap.fill(skia_color(BACKGROUND_COLOR));
+        for shape in &self.shapes {
+            let mut paint = Paint::default();
+            paint.anti_alias = true;
+            match shape {
+                Shape::Polygon { points, color } => {
+                    let Some(path) = self.skia_path(points, tile_size, true) else {
+                        continue;
+                    };
+                    paint.set_color(skia_color(*color));
+                    pixmap.fill_path(
+                        &path,
+                        &paint,
+                        FillRule::Winding,
+                        Transform::identity(),
+                        None,
+                    );
+                }
+                Shape::Line {
+                    points,
+                    width,
+                    color,
+                } => {
+                    let Some(path) = self.skia_path(points, tile_size, false) else {
+                        continue;
+                    };
+                    let stroke = Stroke {
+                        width: width * scale,
+                        line_cap: LineCap::Round,
+                        line_join: LineJoin::Round,
+                        ..Default::default()
+                    };
+                    paint.set_color(skia_color(*color));
+                    pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+                }
+                Shape::Ellipse {
+                    center,
+                    radii,
+                    color,
+                } => {
+                    let center = self.to_pixels(*center, tile_size);
+                    let radii = *radii * scale;
+                    let Some(path) = tiny_skia::Rect::from_xywh(
+                        center.x - radii.x,
+                        center.y - radii.y,
+                        radii.x * 2.0,
+                        radii.y * 2.0,
+                    )
+                    .and_then(PathBuilder::from_oval) else {
+                        continue;
+                    };
+                    paint.set_color(skia_color(*color));
+                    pixmap.fill_path(
+                        &path,
+                        &paint,
+                        FillRule::Winding,
+                        Transform::identity(),
+                        None,
+                    );
+                }
+            }
+        }
+        let pixels = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let pixel = pixel.demultiply();
+                [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+            })
+            .collect();
+        RgbaImage::from_raw(width, height, pixels)
+            .ok_or_else(|| GameError::CustomError("Rasterized image is the wrong size".into()))
+    }
+
+    /// Rasterizes the image at whatever tile size keeps it within `max_size` pixels on
+    /// its longest side, for previews.
+    pub fn thumbnail(&self, max_size: u32) -> GameResult<RgbaImage> {
+        let tile_size = (max_size as f32 / self.size.max_element()).floor().max(1.0);
+        self.to_png(tile_size as u32)
+    }
+
+    /// Writes the image to an `.svg` or `.png` file, picked by the extension of the path.
+    pub fn save(&self, path: impl AsRef<Path>, tile_size: u32) -> GameResult<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("svg") => Ok(write(path, self.to_svg(tile_size))?),
+            Some("png") => self.to_png(tile_size)?.save(path).to_gameerror(),
+            _ => Err(GameError::CustomError(format!(
+                "Can't export to {}; use a .svg or .png file",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Mirrors `GameClient::draw_meeple_of_kind`.
+fn meeple_of_kind(shapes: &mut Vec<Shape>, pos: Vec2, color: Color, scale: f32, kind: MeepleKind) {
+    match kind {
+        MeepleKind::Normal => meeple(shapes, pos, color, scale),
+        MeepleKind::Large => meeple(shapes, pos, color, scale * 1.5),
+        MeepleKind::Builder => {
+            meeple(shapes, pos, color, scale * 0.8);
+            shapes.push(Shape::Ellipse {
+                center: pos,
+                radii: Vec2::splat(scale * 20.0),
+                color: Color::WHITE,
+            });
+        }
+        MeepleKind::Pig => {
+            let size = scale * 200.0;
+            shapes.push(Shape::Ellipse {
+                center: pos,
+                radii: vec2(size * 0.45, size * 0.3),
+                color,
+            });
+            shapes.push(Shape::Ellipse {
+                center: pos + vec2(size * 0.45, -size * 0.1),
+                radii: Vec2::splat(size * 0.15),
+                color,
+            });
+        }
+        MeepleKind::Abbot => {
+            meeple(shapes, pos, color, scale);
+            let (left, right) = (pos.x - scale * 50.0, pos.x + scale * 50.0);
+            let (top, bottom) = (pos.y, pos.y + scale * 15.0);
+            shapes.push(Shape::Polygon {
+                points: vec![
+                    vec2(left, top),
+                    vec2(right, top),
+                    vec2(right, bottom),
+                    vec2(left, bottom),
+                ],
+                color: Color::WHITE,
+            });
+        }
+        MeepleKind::Shepherd => {
+            meeple(shapes, pos, color, scale);
+            shapes.push(Shape::Line {
+                points: vec![
+                    pos + vec2(scale * 110.0, scale * 80.0),
+                    pos + vec2(scale * 110.0, -scale * 110.0),
+                    pos + vec2(scale * 80.0, -scale * 130.0),
+                ],
+                width: scale * 12.0,
+                color: Color::from_rgb(120, 80, 40),
+            });
+        }
+    }
+}
+
+fn meeple(shapes: &mut Vec<Shape>, pos: Vec2, color: Color, scale: f32) {
+    let scale = scale * 200.0;
+    shapes.push(Shape::Polygon {
+        points: MEEPLE_POINTS
+            .iter()
+            .map(|p| (*p - MEEPLE_CENTER) * scale + pos)
+            .collect(),
+        color,
+    });
+    shapes.push(Shape::Ellipse {
+        center: (MEEPLE_HEAD_POINT - MEEPLE_CENTER) * scale + pos,
+        radii: Vec2::splat(scale * MEEPLE_HEAD_RADIUS),
+        color,
+    });
+}
+
+fn svg_paint(attribute: &str, color: Color) -> String {
+    let (r, g, b, a) = color.to_rgba();
+    if a == u8::MAX {
+        format!("{attribute}=\"#{r:02x}{g:02x}{b:02x}\"")
+    } else {
+        format!(
+            "{attribute}=\"#{r:02x}{g:02x}{b:02x}\" {attribute}-opacity=\"{:.3}\"",
+            color.a
+        )
+    }
+}
+
+fn skia_color(color: Color) -> tiny_skia::Color {
+    let (r, g, b, a) = color.to_rgba();
+    tiny_skia::Color::from_rgba8(r, g, b, a)
+}
+
+#[cfg(test)]
+mod test {
+    use ggez::{graphics::Color, GameResult};
+
+    use crate::{
+        game::{player::Player, Game},
+        pos::GridPos,
+        tile::tile_definitions::{CROSSROADS, STRAIGHT_ROAD},
+    };
+
+    use super::BoardImage;
+
+    #[test]
+    fn export_board() -> GameResult {
+        let mut game = Game::new();
+        let player_ident = game.players.insert(Player::new(Color::RED));
+        game.place_tile(CROSSROADS.clone(), GridPos(0, 0))?;
+        game.place_tile(STRAIGHT_ROAD.clone(), GridPos(1, 0))?;
+        game.place_meeple((GridPos(0, 0), 2), player_ident)?;
+
+        let image = BoardImage::new(&mut game);
+        assert_eq!(image.dimensions(100), (250, 150));
+
+        let svg = image.to_svg(100);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("<polyline"));
+        assert!(svg.contains("fill=\"#ff0000\""));
+
+        let png = image.to_png(10)?;
+        assert_eq!(png.dimensions(), (25, 15));
+        assert_eq!(image.thumbnail(100)?.dimensions(), (100, 60));
+        Ok(())
+    }
+}
@@ -7,10 +7,10 @@ use discord_sdk::activity::{ActivityBuilder, ActivityKind, Assets, Button, Party
 use ggez::{
     glam::{vec2, Vec2},
     graphics::Color,
-    GameError,
 };
 use log::warn;
-use player::{Player, PlayerType};
+use player::{Player, PlayerType, Prisoner};
+use rules::{river_turns_back, RuleError};
 use serde::{Deserialize, Serialize};
 use slotmap::{DefaultKey, SlotMap};
 
@@ -19,7 +19,7 @@ use crate::{
     pos::GridPos,
     tile::{
         GridBorderCoordinate, Opposite, Orientation, Segment, SegmentAttribute, SegmentBorderPiece,
-        SegmentType, Tile,
+        SegmentType, Tile, TradeGood,
     },
     util::{Bag, HashMapBag, MinByF32Key},
     LATEST_RELEASE_LINK,
@@ -27,11 +27,15 @@ use crate::{
 use ggez_no_re::line::Line;
 
 pub mod player {
-    use std::{net::IpAddr, time::Duration};
+    use std::{collections::HashMap, net::IpAddr, time::Duration};
 
     use ggez::graphics::Color;
     use serde::{Deserialize, Serialize};
 
+    use crate::{pos::GridPos, tile::TradeGood};
+
+    use super::{bot::BotDifficulty, MeepleKind, PlayerIdentifier};
+
     #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
     pub enum ConnectionState {
         Disconnected,
@@ -49,6 +53,9 @@ pub mod player {
             address: IpAddr,
             connection_state: ConnectionState,
         },
+        Bot {
+            difficulty: BotDifficulty,
+        },
     }
 
     impl PlayerType {
@@ -90,9 +97,26 @@ pub mod player {
         }
     }
 
+    /// A meeple captured by another player's tower, held until it's exchanged or ransomed.
+    #[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+    pub struct Prisoner {
+        pub owner: PlayerIdentifier,
+        pub kind: MeepleKind,
+        /// The tower that captured it.
+        pub tower: GridPos,
+    }
+
     #[derive(Clone, Serialize, Deserialize, Debug)]
     pub struct Player {
         pub meeples: usize,
+        pub large_meeples: usize,
+        pub builders: usize,
+        pub pigs: usize,
+        pub abbots: usize,
+        pub shepherds: usize,
+        pub tower_pieces: usize,
+        pub prisoners: Vec<Prisoner>,
+        pub goods: HashMap<TradeGood, usize>,
         pub score: usize,
         pub color: Color,
         pub ptype: PlayerType,
@@ -106,22 +130,176 @@ pub mod player {
         pub fn new_inner(color: Color, ptype: PlayerType) -> Player {
             Player {
                 meeples: 7,
+                large_meeples: 0,
+                builders: 0,
+                pigs: 0,
+                abbots: 0,
+                shepherds: 0,
+                tower_pieces: 0,
+                prisoners: Vec::new(),
+                goods: HashMap::new(),
                 score: 0,
                 color,
                 ptype,
             }
         }
+
+        /// How many meeples of the given kind the player has left to place.
+        pub fn supply(&self, kind: MeepleKind) -> usize {
+            match kind {
+                MeepleKind::Normal => self.meeples,
+                MeepleKind::Large => self.large_meeples,
+                MeepleKind::Builder => self.builders,
+                MeepleKind::Pig => self.pigs,
+                MeepleKind::Abbot => self.abbots,
+                MeepleKind::Shepherd => self.shepherds,
+            }
+        }
+
+        pub fn supply_mut(&mut self, kind: MeepleKind) -> &mut usize {
+            match kind {
+                MeepleKind::Normal => &mut self.meeples,
+                MeepleKind::Large => &mut self.large_meeples,
+                MeepleKind::Builder => &mut self.builders,
+                MeepleKind::Pig => &mut self.pigs,
+                MeepleKind::Abbot => &mut self.abbots,
+                MeepleKind::Shepherd => &mut self.shepherds,
+            }
+        }
     }
 }
 
+pub mod bot;
 pub mod debug_game_configs;
+pub mod rules;
+pub mod setup;
 
 pub type SegmentIndex = usize;
 pub type SegmentIdentifier = (GridPos, SegmentIndex);
 pub type GroupIdentifier = DefaultKey;
 pub type EdgeIdentifier = (GridPos, Orientation);
 pub type PlayerIdentifier = DefaultKey;
-pub type PlacedMeeple = (SegmentIdentifier, PlayerIdentifier);
+pub type PlacedMeeple = (SegmentIdentifier, PlayerIdentifier, MeepleKind);
+pub type LegalMove = (GridPos, usize, Option<(SegmentIndex, MeepleKind)>);
+
+const GOODS_MAJORITY_SCORE: usize = 10;
+/// Awarded to the owner of the meeple the fairy is protecting when its group is scored.
+const FAIRY_SCORE: usize = 3;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MeepleKind {
+    #[default]
+    Normal,
+    /// From the Inns & Cathedrals expansion; counts as two meeples when deciding who owns a group.
+    Large,
+    /// From the Traders & Builders expansion; joins one of its owner's roads or cities, and
+    /// grants them an extra turn whenever they extend it.
+    Builder,
+    /// From the Traders & Builders expansion; joins one of its owner's farms, and makes it
+    /// worth an extra point per city to them.
+    Pig,
+    /// From the Abbots & Gardens expansion; the only meeple that can tend a garden, and may
+    /// be called back early in place of placing a meeple to score its monastary or garden.
+    Abbot,
+    /// From the Hills & Sheep expansion; tends a flock on a farm, without counting towards
+    /// who owns it.
+    Shepherd,
+}
+
+impl MeepleKind {
+    pub const ALL: [MeepleKind; 6] = [
+        MeepleKind::Normal,
+        MeepleKind::Large,
+        MeepleKind::Builder,
+        MeepleKind::Pig,
+        MeepleKind::Abbot,
+        MeepleKind::Shepherd,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MeepleKind::Normal => "Meeple",
+            MeepleKind::Large => "Large Meeple",
+            MeepleKind::Builder => "Builder",
+            MeepleKind::Pig => "Pig",
+            MeepleKind::Abbot => "Abbot",
+            MeepleKind::Shepherd => "Shepherd",
+        }
+    }
+
+    /// How much the meeple counts for when deciding who owns a group. Builders and pigs
+    /// don't count at all, and can only join groups their owner already has a stake in.
+    pub fn strength(&self) -> usize {
+        match self {
+            MeepleKind::Normal | MeepleKind::Abbot => 1,
+            MeepleKind::Large => 2,
+            MeepleKind::Builder | MeepleKind::Pig | MeepleKind::Shepherd => 0,
+        }
+    }
+
+    pub fn placeable_on(&self, stype: SegmentType) -> bool {
+        match self {
+            MeepleKind::Normal | MeepleKind::Large => stype != SegmentType::Garden,
+            MeepleKind::Builder => matches!(stype, SegmentType::City | SegmentType::Road),
+            MeepleKind::Pig | MeepleKind::Shepherd => stype == SegmentType::Farm,
+            MeepleKind::Abbot => matches!(stype, SegmentType::Monastary | SegmentType::Garden),
+        }
+    }
+
+    /// Checks whether the player may add a meeple of this kind to a group already holding
+    /// the given meeples.
+    pub fn can_join(
+        &self,
+        meeples: &[PlacedMeeple],
+        player: PlayerIdentifier,
+    ) -> Result<(), RuleError> {
+        if self.strength() > 0 {
+            if !meeples.is_empty() {
+                return Err(RuleError::GroupOccupied);
+            }
+        } else if *self == MeepleKind::Shepherd {
+            if meeples
+                .iter()
+                .any(|(_, _, other)| *other == MeepleKind::Shepherd)
+            {
+                return Err(RuleError::GroupOccupied);
+            }
+        } else if !meeples
+            .iter()
+            .any(|(_, owner, other)| *owner == player && other.strength() > 0)
+        {
+            return Err(RuleError::NoFollowerInGroup);
+        }
+        Ok(())
+    }
+}
+
+/// A token drawn by a shepherd from the Hills & Sheep expansion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SheepToken {
+    /// Some number of sheep, each worth a point when the flock is herded in.
+    Sheep(usize),
+    /// Scatters every flock on the farm.
+    Wolf,
+}
+
+impl SheepToken {
+    /// Every token that starts in the bag.
+    pub fn full_bag() -> Vec<SheepToken> {
+        [(1, 4), (2, 5), (3, 5), (4, 4)]
+            .into_iter()
+            .flat_map(|(sheep, count)| (0..count).map(move |_| SheepToken::Sheep(sheep)))
+            .chain([SheepToken::Wolf; 2])
+            .collect()
+    }
+
+    pub fn sheep(&self) -> usize {
+        match self {
+            SheepToken::Sheep(sheep) => *sheep,
+            SheepToken::Wolf => 0,
+        }
+    }
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ScoringDetails {
@@ -163,40 +341,134 @@ pub struct SegmentGroup {
 impl SegmentGroup {
     fn compute_owners(
         &self,
+        hills: &HashSet<GridPos>,
     ) -> Option<(
-        HashMap<PlayerIdentifier, Vec<(GridPos, usize)>>,
+        HashMap<PlayerIdentifier, Vec<(SegmentIdentifier, MeepleKind)>>,
         Vec<PlayerIdentifier>,
     )> {
-        let Bag(meeples_by_player) = self.meeples.iter().map(|&(k, v)| (v, k)).collect();
-        let Some(highest_count) = meeples_by_player.values().map(Vec::len).max() else {
-            // nobody placed any meeples on the group
-            return None;
-        };
-        let scoring_players: Vec<_> = meeples_by_player
+        compute_meeple_owners(&self.meeples, hills)
+    }
+}
+
+/// Groups meeples by player, along with the players who have the most of them.
+/// Large meeples count as two, and builders, pigs and shepherds don't count at all.
+/// A tie is won by whoever has a meeple on a hill, if anyone does.
+#[allow(clippy::type_complexity)]
+fn compute_meeple_owners(
+    meeples: &[PlacedMeeple],
+    hills: &HashSet<GridPos>,
+) -> Option<(
+    HashMap<PlayerIdentifier, Vec<(SegmentIdentifier, MeepleKind)>>,
+    Vec<PlayerIdentifier>,
+)> {
+    let Bag(meeples_by_player) = meeples
+        .iter()
+        .map(|&(seg_ident, player_ident, kind)| (player_ident, (seg_ident, kind)))
+        .collect();
+    let strength = |meeples: &Vec<(SegmentIdentifier, MeepleKind)>| -> usize {
+        meeples.iter().map(|(_, kind)| kind.strength()).sum()
+    };
+    let Some(highest_strength) = meeples_by_player.values().map(strength).max() else {
+        // nobody placed any meeples on the group
+        return None;
+    };
+    let mut scoring_players: Vec<_> = meeples_by_player
+        .iter()
+        .filter_map(|(player_ident, meeples)| {
+            (highest_strength > 0 && strength(meeples) == highest_strength).then_some(*player_ident)
+        })
+        .collect();
+    let on_hill = |player_ident: &PlayerIdentifier| {
+        meeples_by_player[player_ident]
             .iter()
-            .filter_map(|(player_ident, meeples)| {
-                (meeples.len() == highest_count).then_some(*player_ident)
-            })
-            .collect();
-        Some((meeples_by_player, scoring_players))
+            .any(|((pos, _), kind)| kind.strength() > 0 && hills.contains(pos))
+    };
+    if scoring_players.iter().any(on_hill) {
+        scoring_players.retain(on_hill);
     }
+    Some((meeples_by_player, scoring_players))
 }
 
+#[derive(Clone, Debug)]
 pub struct ScoringResult {
     pub meeple_location: Vec2,
     pub meeple_color: Color,
     pub score: usize,
 }
 
+/// How farms are scored at the end of the game.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FarmerRules {
+    /// Meeples can't be placed on farms at all.
+    Disabled,
+    /// Each completed city is awarded once, to whoever has the most farmers in the farms around it.
+    FirstEdition,
+    /// Every farm is worth 4 points for each completed city it borders.
+    SecondEdition,
+    /// Every farm is worth 3 points for each completed city it borders.
+    #[default]
+    ThirdEdition,
+}
+
+impl FarmerRules {
+    pub const ALL: [FarmerRules; 4] = [
+        FarmerRules::ThirdEdition,
+        FarmerRules::SecondEdition,
+        FarmerRules::FirstEdition,
+        FarmerRules::Disabled,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FarmerRules::Disabled => "No Farmers",
+            FarmerRules::FirstEdition => "1st Edition Farmers",
+            FarmerRules::SecondEdition => "2nd Edition Farmers",
+            FarmerRules::ThirdEdition => "3rd Edition Farmers",
+        }
+    }
+
+    fn city_score(&self) -> usize {
+        match self {
+            FarmerRules::Disabled => 0,
+            FarmerRules::FirstEdition | FarmerRules::SecondEdition => 4,
+            FarmerRules::ThirdEdition => 3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ScoringRules {
+    pub farmers: FarmerRules,
+    /// Completed cities only two tiles large score 1 point per tile instead of 2.
+    pub small_cities: bool,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Game {
     pub local_player: PlayerType,
     pub library: Vec<Tile>,
+    #[serde(with = "crate::save::map_as_pairs")]
     pub placed_tiles: HashMap<GridPos, Tile>,
     pub groups: SlotMap<GroupIdentifier, SegmentGroup>,
+    #[serde(with = "crate::save::map_as_pairs")]
     pub group_associations: HashMap<SegmentIdentifier, GroupIdentifier>,
     pub players: SlotMap<PlayerIdentifier, Player>,
     pub valid_placements: Vec<GridPos>,
+    pub scoring_rules: ScoringRules,
+    /// Where the dragon is, once a volcano has been placed.
+    pub dragon: Option<GridPos>,
+    /// The segment holding the meeple the fairy is protecting.
+    pub fairy: Option<SegmentIdentifier>,
+    /// How tall each tower is, by the tile it was built on.
+    #[serde(with = "crate::save::map_as_pairs")]
+    pub towers: HashMap<GridPos, usize>,
+    /// Tiles with a hill on them, whose meeples win ties for a group.
+    pub hills: HashSet<GridPos>,
+    /// The sheep each shepherd is tending, by the segment the shepherd is on.
+    #[serde(with = "crate::save::map_as_pairs")]
+    pub flocks: HashMap<SegmentIdentifier, Vec<SheepToken>>,
+    /// Tokens yet to be drawn by shepherds.
+    pub sheep_bag: Vec<SheepToken>,
 }
 
 impl Game {
@@ -218,6 +490,13 @@ impl Game {
             group_associations: HashMap::new(),
             players: SlotMap::new(),
             valid_placements: Vec::new(),
+            scoring_rules: ScoringRules::default(),
+            dragon: None,
+            fairy: None,
+            towers: HashMap::new(),
+            hills: HashSet::new(),
+            flocks: HashMap::new(),
+            sheep_bag: Vec::new(),
         }
     }
 
@@ -225,7 +504,7 @@ impl Game {
         &mut self,
         tile: Tile,
         pos: GridPos,
-    ) -> Result<Vec<GroupIdentifier>, GameError> {
+    ) -> Result<Vec<GroupIdentifier>, RuleError> {
         let mut new_group_insertions: HashMap<SegmentIdentifier, HashSet<GroupIdentifier>> =
             HashMap::new();
         let mut uninserted_segments: Vec<_> = (0..tile.segments.len()).map(|_| true).collect();
@@ -247,9 +526,7 @@ impl Game {
             }
 
             let Some(mounts) = tile.validate_mounting(adjacent_tile, orientation) else {
-                return Err(GameError::CustomError(
-                    "Attempt to place invalid tile!".to_string(),
-                ));
+                return Err(RuleError::InvalidTilePlacement);
             };
 
             for mount in mounts {
@@ -360,6 +637,9 @@ impl Game {
         }
 
         // put tile on board
+        if tile.has_attribute(|a| matches!(a, SegmentAttribute::Hill { .. })) {
+            self.hills.insert(pos);
+        }
         self.placed_tiles.insert(pos, tile);
 
         // check for completed monastaries
@@ -367,11 +647,10 @@ impl Game {
             let Some(tile) = self.placed_tiles.get(&adjacent_pos) else {
                 continue;
             };
-            let Some((seg_index, _)) = tile
-                .segments
-                .iter()
-                .enumerate()
-                .find(|(_, seg)| seg.stype == SegmentType::Monastary)
+            let Some((seg_index, _)) =
+                tile.segments.iter().enumerate().find(|(_, seg)| {
+                    matches!(seg.stype, SegmentType::Monastary | SegmentType::Garden)
+                })
             else {
                 continue;
             };
@@ -433,32 +712,64 @@ impl Game {
     pub fn score_group(&mut self, group_ident: GroupIdentifier) -> Vec<ScoringResult> {
         let mut scoring_result = Vec::new();
         let group = self.groups.get(group_ident).unwrap();
+        if group.gtype == SegmentType::Farm
+            && self.scoring_rules.farmers == FarmerRules::FirstEdition
+        {
+            return self.score_farms_by_city();
+        }
 
         // determine which players are earning score for the group
-        let (meeples_by_player, scoring_players) = group.compute_owners().unwrap_or_default();
+        let (meeples_by_player, scoring_players) =
+            group.compute_owners(&self.hills).unwrap_or_default();
 
         let group_score = self.compute_group_score(group);
+        // pigs are worth an extra point to their owner for each city the farm supplies
+        let pig_bonus = match group.gtype {
+            SegmentType::Farm => self.adjacent_completed_cities(group).len(),
+            _ => 0,
+        };
+        let mut player_scores: HashMap<PlayerIdentifier, usize> = scoring_players
+            .iter()
+            .map(|player_ident| {
+                let has_pig = meeples_by_player[player_ident]
+                    .iter()
+                    .any(|(_, kind)| *kind == MeepleKind::Pig);
+                (
+                    *player_ident,
+                    group_score + if has_pig { pig_bonus } else { 0 },
+                )
+            })
+            .collect();
+
+        // the fairy rewards whoever it's protecting, whether or not they own the group
+        let fairy_owner = group
+            .meeples
+            .iter()
+            .find(|(seg_ident, _, _)| Some(*seg_ident) == self.fairy)
+            .map(|(_, player_ident, _)| *player_ident);
+        if let Some(fairy_owner) = fairy_owner {
+            *player_scores.entry(fairy_owner).or_default() += FAIRY_SCORE;
+            self.fairy = None;
+        }
 
-        for player_ident in &scoring_players {
+        for (player_ident, score) in &player_scores {
             let player = self.players.get_mut(*player_ident).unwrap();
-            player.score += group_score;
+            player.score += score;
         }
 
         // return and remove meeples
         for (player_ident, meeples) in meeples_by_player {
-            let first_meeple = meeples.first().unwrap();
+            let (first_meeple, _) = meeples.first().unwrap();
             let player = self.players.get_mut(player_ident).unwrap();
-            player.meeples += meeples.len();
+            for (_, kind) in &meeples {
+                *player.supply_mut(*kind) += 1;
+            }
             let color = player.color;
             scoring_result.push(ScoringResult {
                 meeple_location: self.segment_by_ident(*first_meeple).unwrap().meeple_spot
                     + Vec2::from(first_meeple.0),
                 meeple_color: color,
-                score: if scoring_players.contains(&player_ident) {
-                    group_score
-                } else {
-                    0
-                },
+                score: player_scores.get(&player_ident).copied().unwrap_or(0),
             });
         }
 
@@ -491,6 +802,125 @@ impl Game {
         scoring_result
     }
 
+    /// Scores every farm at once, since under first edition rules each completed city goes
+    /// to the players with the most farmers across all of the farms bordering it.
+    fn score_farms_by_city(&mut self) -> Vec<ScoringResult> {
+        let city_score = FarmerRules::FirstEdition.city_score();
+        let farm_idents: Vec<_> = self
+            .groups
+            .iter()
+            .filter(|(_, group)| group.gtype == SegmentType::Farm && !group.meeples.is_empty())
+            .map(|(farm_ident, _)| farm_ident)
+            .collect();
+        let Bag(farms_by_city) = farm_idents
+            .iter()
+            .flat_map(|&farm_ident| {
+                self.adjacent_completed_cities(&self.groups[farm_ident])
+                    .into_iter()
+                    .map(move |city_ident| (city_ident, farm_ident))
+            })
+            .collect();
+        // the players who won each city, and whether they have a pig in one of its farms
+        let city_winners: HashMap<GroupIdentifier, Vec<(PlayerIdentifier, bool)>> = farms_by_city
+            .into_iter()
+            .map(|(city_ident, farms)| {
+                let meeples: Vec<_> = farms
+                    .iter()
+                    .flat_map(|farm_ident| self.groups[*farm_ident].meeples.iter().copied())
+                    .collect();
+                let (meeples_by_player, winners) =
+                    compute_meeple_owners(&meeples, &self.hills).unwrap_or_default();
+                let winners = winners
+                    .into_iter()
+                    .map(|winner| {
+                        let has_pig = meeples_by_player[&winner]
+                            .iter()
+                            .any(|(_, kind)| *kind == MeepleKind::Pig);
+                        (winner, has_pig)
+                    })
+                    .collect();
+                (city_ident, winners)
+            })
+            .collect();
+        let mut player_scores: HashMap<PlayerIdentifier, usize> = HashMap::new();
+        for (player_ident, has_pig) in city_winners.values().flatten() {
+            *player_scores.entry(*player_ident).or_default() += city_score + *has_pig as usize;
+        }
+        for (player_ident, score) in &player_scores {
+            self.players.get_mut(*player_ident).unwrap().score += score;
+        }
+
+        let mut scoring_result = Vec::new();
+        let mut shown_players = HashSet::new();
+        for farm_ident in farm_idents {
+            let farm = &self.groups[farm_ident];
+            let (meeples_by_player, owners) = farm.compute_owners(&self.hills).unwrap_or_default();
+            let farm_score = self
+                .adjacent_completed_cities(farm)
+                .into_iter()
+                .filter(|city_ident| {
+                    city_winners[city_ident]
+                        .iter()
+                        .any(|(winner, _)| owners.contains(winner))
+                })
+                .count()
+                * city_score;
+
+            for (player_ident, meeples) in meeples_by_player {
+                let (first_meeple, _) = meeples.first().unwrap();
+                let player = self.players.get_mut(player_ident).unwrap();
+                for (_, kind) in &meeples {
+                    *player.supply_mut(*kind) += 1;
+                }
+                let color = player.color;
+                scoring_result.push(ScoringResult {
+                    meeple_location: self.segment_by_ident(*first_meeple).unwrap().meeple_spot
+                        + Vec2::from(first_meeple.0),
+                    meeple_color: color,
+                    // a player's winnings are only shown over one of their farmers
+                    score: if shown_players.insert(player_ident) {
+                        player_scores.get(&player_ident).copied().unwrap_or(0)
+                    } else {
+                        0
+                    },
+                });
+            }
+
+            let owners = owners
+                .into_iter()
+                .map(|id| (id, self.players.get(id).unwrap().color))
+                .collect();
+            let farm = self.groups.get_mut(farm_ident).unwrap();
+            farm.meeples.clear();
+            farm.scoring_details = Some(ScoringDetails {
+                score: farm_score,
+                owners,
+            });
+        }
+
+        scoring_result
+    }
+
+    /// The completed cities which border a farm.
+    fn adjacent_completed_cities(&self, farm: &SegmentGroup) -> HashSet<GroupIdentifier> {
+        let mut cities = HashSet::new();
+        for (pos, seg_index) in farm.segments.iter().copied() {
+            let tile = self.placed_tiles.get(&pos).unwrap();
+            for (adj_seg_index, _) in tile
+                .adjacent_segments(seg_index)
+                .filter(|(_, seg)| seg.stype == SegmentType::City)
+            {
+                let (city_group, city_group_ident) = self
+                    .group_and_key_by_seg_ident((pos, adj_seg_index))
+                    .unwrap();
+                if city_group.free_edges.is_empty() {
+                    cities.insert(city_group_ident);
+                }
+            }
+        }
+        cities
+    }
+
     fn compute_group_score(&self, group: &SegmentGroup) -> usize {
         match group.gtype {
             SegmentType::City | SegmentType::Road => {
@@ -516,34 +946,42 @@ impl Game {
                     .values()
                     .flat_map(|scores| scores.iter().max())
                     .sum();
+                // inns and cathedrals double a finished group, but leave an unfinished one worthless
+                let enhanced = group.segments.iter().any(|seg_ident| {
+                    self.segment_by_ident(*seg_ident)
+                        .unwrap()
+                        .attributes
+                        .iter()
+                        .any(|a| {
+                            matches!(
+                                a,
+                                SegmentAttribute::Inn { .. } | SegmentAttribute::Cathedral { .. }
+                            )
+                        })
+                });
+                let complete = group.free_edges.is_empty();
+                if enhanced && !complete {
+                    return 0;
+                }
                 let base_score = match group.gtype {
-                    SegmentType::City if !group.free_edges.is_empty() => 1,
+                    SegmentType::City if !complete => 1,
+                    SegmentType::City
+                        if self.scoring_rules.small_cities && tile_scores.len() == 2 =>
+                    {
+                        1
+                    }
                     SegmentType::City => 2,
                     SegmentType::Road => 1,
                     _ => unimplemented!("unimplemented segment type"),
                 };
-                base_score * tile_span
+                let multiplier = if enhanced { 2 } else { 1 };
+                base_score * tile_span * multiplier
             }
             SegmentType::Farm => {
-                let mut cities = HashSet::new();
-                for (pos, seg_index) in group.segments.clone() {
-                    let tile = self.placed_tiles.get(&pos).unwrap();
-                    for adj_seg_index in tile
-                        .adjacent_segments(seg_index)
-                        .filter_map(|(i, seg)| (seg.stype == SegmentType::City).then_some(i))
-                        .collect::<Vec<_>>()
-                    {
-                        let (city_group, city_group_ident) = self
-                            .group_and_key_by_seg_ident((pos, adj_seg_index))
-                            .unwrap();
-                        if city_group.free_edges.is_empty() {
-                            cities.insert(city_group_ident);
-                        }
-                    }
-                }
-                cities.len() * 3
+                self.adjacent_completed_cities(group).len()
+                    * self.scoring_rules.farmers.city_score()
             }
-            SegmentType::Monastary => {
+            SegmentType::Monastary | SegmentType::Garden => {
                 group
                     .segments
                     .first()
@@ -558,21 +996,295 @@ impl Game {
         }
     }
 
+    /// Gives the goods found in a completed city to the player who completed it.
+    pub fn collect_goods(&mut self, group_ident: GroupIdentifier, player_ident: PlayerIdentifier) {
+        let goods: Vec<_> = self.groups[group_ident]
+            .segments
+            .iter()
+            .flat_map(|seg_ident| &self.segment_by_ident(*seg_ident).unwrap().attributes)
+            .filter_map(|attribute| match attribute {
+                SegmentAttribute::Goods { good, .. } => Some(*good),
+                _ => None,
+            })
+            .collect();
+        let player = self.players.get_mut(player_ident).unwrap();
+        for good in goods {
+            *player.goods.entry(good).or_default() += 1;
+        }
+    }
+
+    /// Awards the players holding the most of each good at the end of the game.
+    pub fn score_goods(&mut self) -> Vec<(PlayerIdentifier, TradeGood)> {
+        let mut winners = Vec::new();
+        for good in TradeGood::ALL {
+            let count = |player: &Player| player.goods.get(&good).copied().unwrap_or(0);
+            let most = self.players.values().map(count).max().unwrap_or(0);
+            if most == 0 {
+                continue;
+            }
+            for (player_ident, player) in &mut self.players {
+                if count(player) == most {
+                    player.score += GOODS_MAJORITY_SCORE;
+                    winners.push((player_ident, good));
+                }
+            }
+        }
+        winners
+    }
+
+    /// Whether the tile at the given position extends a road or city holding the player's builder.
+    pub fn extends_builder(&self, pos: GridPos, player_ident: PlayerIdentifier) -> bool {
+        let Some(tile) = self.placed_tiles.get(&pos) else {
+            return false;
+        };
+        (0..tile.segments.len()).any(|seg_index| {
+            self.group_and_key_by_seg_ident((pos, seg_index))
+                .is_some_and(|(group, _)| {
+                    group.meeples.iter().any(|&(_, owner, kind)| {
+                        kind == MeepleKind::Builder && owner == player_ident
+                    })
+                })
+        })
+    }
+
+    /// The monastary or garden holding the player's abbot, if they've placed it.
+    pub fn abbot_group(&self, player_ident: PlayerIdentifier) -> Option<GroupIdentifier> {
+        self.groups.iter().find_map(|(group_ident, group)| {
+            group
+                .meeples
+                .iter()
+                .any(|&(_, owner, kind)| kind == MeepleKind::Abbot && owner == player_ident)
+                .then_some(group_ident)
+        })
+    }
+
+    /// Calls the player's abbot back from its monastary or garden, scoring it as it stands.
+    pub fn retrieve_abbot(
+        &mut self,
+        player_ident: PlayerIdentifier,
+    ) -> Result<Vec<ScoringResult>, RuleError> {
+        let group_ident = self
+            .abbot_group(player_ident)
+            .ok_or(RuleError::NoAbbotPlaced)?;
+        let scoring_result = self.score_group(group_ident);
+        // the group can still be built around, so its score isn't final
+        self.groups[group_ident].scoring_details = None;
+        Ok(scoring_result)
+    }
+
+    /// Whether a group has been closed off, so that it can no longer grow.
+    pub fn is_group_complete(&self, group: &SegmentGroup) -> bool {
+        match group.gtype {
+            SegmentType::Monastary | SegmentType::Garden => group
+                .segments
+                .first()
+                .unwrap()
+                .0
+                .surrounding()
+                .all(|pos| self.placed_tiles.contains_key(&pos)),
+            _ => group.free_edges.is_empty(),
+        }
+    }
+
+    /// The player whose meeple the fairy is protecting.
+    pub fn fairy_owner(&self) -> Option<PlayerIdentifier> {
+        let fairy = self.fairy?;
+        let (group, _) = self.group_and_key_by_seg_ident(fairy)?;
+        group
+            .meeples
+            .iter()
+            .find(|(seg_ident, _, _)| *seg_ident == fairy)
+            .map(|(_, player_ident, _)| *player_ident)
+    }
+
+    /// Takes a single meeple off the board, without returning it to anyone.
+    fn take_meeple(&mut self, seg_ident: SegmentIdentifier) -> Option<PlacedMeeple> {
+        let group = self.group_by_seg_ident_mut(seg_ident)?;
+        let index = group
+            .meeples
+            .iter()
+            .position(|(meeple_seg_ident, _, _)| *meeple_seg_ident == seg_ident)?;
+        let meeple = group.meeples.remove(index);
+        group.scoring_details = None;
+        if self.fairy == Some(seg_ident) {
+            self.fairy = None;
+        }
+        if let Some(flock) = self.flocks.remove(&seg_ident) {
+            self.sheep_bag.extend(flock);
+        }
+        Some(meeple)
+    }
+
+    /// Removes a single meeple from the board, returning it to its owner.
+    pub fn remove_meeple(&mut self, seg_ident: SegmentIdentifier) -> Option<PlacedMeeple> {
+        let meeple = self.take_meeple(seg_ident)?;
+        let (_, player_ident, kind) = meeple;
+        *self.players[player_ident].supply_mut(kind) += 1;
+        Some(meeple)
+    }
+
+    /// The shepherds on the given farm, and their owners.
+    fn shepherds(
+        &self,
+        group_ident: GroupIdentifier,
+    ) -> Vec<(SegmentIdentifier, PlayerIdentifier)> {
+        self.groups[group_ident]
+            .meeples
+            .iter()
+            .filter(|(_, _, kind)| *kind == MeepleKind::Shepherd)
+            .map(|(seg_ident, player_ident, _)| (*seg_ident, *player_ident))
+            .collect()
+    }
+
+    /// Sends every shepherd on the farm home, and puts their flocks back in the bag.
+    pub fn scatter_flocks(&mut self, group_ident: GroupIdentifier) {
+        for (seg_ident, _) in self.shepherds(group_ident) {
+            self.remove_meeple(seg_ident);
+        }
+    }
+
+    /// Herds in every flock on the farm. Each shepherd's owner scores a point for every
+    /// sheep on the farm, then the shepherds go home and the sheep go back in the bag.
+    pub fn herd_flocks(&mut self, group_ident: GroupIdentifier) -> Vec<ScoringResult> {
+        let shepherds = self.shepherds(group_ident);
+        let score: usize = shepherds
+            .iter()
+            .flat_map(|(seg_ident, _)| self.flocks.get(seg_ident).into_iter().flatten())
+            .map(SheepToken::sheep)
+            .sum();
+        let mut scored_players = HashSet::new();
+        let mut scoring_result = Vec::new();
+        for (seg_ident, player_ident) in shepherds {
+            let first_shepherd = scored_players.insert(player_ident);
+            if first_shepherd {
+                self.players[player_ident].score += score;
+            }
+            scoring_result.push(ScoringResult {
+                meeple_location: self.segment_by_ident(seg_ident).unwrap().meeple_spot
+                    + Vec2::from(seg_ident.0),
+                meeple_color: self.players[player_ident].color,
+                score: if first_shepherd { score } else { 0 },
+            });
+        }
+        self.scatter_flocks(group_ident);
+        scoring_result
+    }
+
+    /// Whether a tower can be built, or built higher, on the tile.
+    pub fn has_tower_foundation(&self, pos: GridPos) -> bool {
+        self.placed_tiles.get(&pos).is_some_and(|tile| {
+            tile.has_attribute(|a| matches!(a, SegmentAttribute::TowerFoundation { .. }))
+        })
+    }
+
+    /// The meeples a tower of the given height can see: any on its own tile, or on a tile
+    /// at most as many spaces away as it is tall, in a straight line.
+    pub fn tower_sight(&self, pos: GridPos, height: usize) -> Vec<PlacedMeeple> {
+        let sighted: Vec<GridPos> = std::iter::once(pos)
+            .chain(Orientation::iter_with_offsets().flat_map(|(_, offset)| {
+                std::iter::successors(Some(pos), move |pos| Some(*pos + offset))
+                    .skip(1)
+                    .take(height)
+            }))
+            .collect();
+        self.groups
+            .values()
+            .flat_map(|group| &group.meeples)
+            .filter(|(seg_ident, _, _)| sighted.contains(&seg_ident.0))
+            .copied()
+            .collect()
+    }
+
+    /// Takes a meeple prisoner with the given player's tower. A player's own meeple is simply
+    /// returned to them, and if the owner is already holding one of the captor's meeples, the
+    /// two are exchanged straight away.
+    pub fn capture_meeple(
+        &mut self,
+        seg_ident: SegmentIdentifier,
+        captor: PlayerIdentifier,
+        tower: GridPos,
+    ) -> Option<PlacedMeeple> {
+        let meeple = self.take_meeple(seg_ident)?;
+        let (_, owner, kind) = meeple;
+        if owner == captor {
+            *self.players[owner].supply_mut(kind) += 1;
+        } else if let Some(index) = self.players[owner]
+            .prisoners
+            .iter()
+            .position(|prisoner| prisoner.owner == captor)
+        {
+            let prisoner = self.players[owner].prisoners.remove(index);
+            *self.players[captor].supply_mut(prisoner.kind) += 1;
+            *self.players[owner].supply_mut(kind) += 1;
+        } else {
+            self.players[captor]
+                .prisoners
+                .push(Prisoner { owner, kind, tower });
+        }
+        Some(meeple)
+    }
+
+    /// Releases one of the player's meeples held prisoner by the captor, for a price.
+    pub fn ransom_prisoner(
+        &mut self,
+        player_ident: PlayerIdentifier,
+        captor: PlayerIdentifier,
+        cost: usize,
+    ) -> Option<Prisoner> {
+        let index = self.players[captor]
+            .prisoners
+            .iter()
+            .position(|prisoner| prisoner.owner == player_ident)?;
+        let prisoner = self.players[captor].prisoners.remove(index);
+        let player = &mut self.players[player_ident];
+        player.score -= cost;
+        *player.supply_mut(prisoner.kind) += 1;
+        self.players[captor].score += cost;
+        Some(prisoner)
+    }
+
+    /// The dragon eats every meeple on the tile, apart from one protected by the fairy.
+    pub fn eat_meeples(&mut self, pos: GridPos) -> usize {
+        let Some(tile) = self.placed_tiles.get(&pos) else {
+            return 0;
+        };
+        (0..tile.segments.len())
+            .filter(|seg_index| self.fairy != Some((pos, *seg_index)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .filter_map(|seg_index| self.remove_meeple((pos, seg_index)))
+            .count()
+    }
+
+    /// Whether meeples can be placed on segments of this type under the game's rules.
+    pub fn meeple_placeable(&self, stype: SegmentType) -> bool {
+        stype.placeable()
+            && !(stype == SegmentType::Farm && self.scoring_rules.farmers == FarmerRules::Disabled)
+    }
+
     pub fn place_meeple(
         &mut self,
         seg_ident: SegmentIdentifier,
         player_ident: PlayerIdentifier,
-    ) -> Result<(), GameError> {
+    ) -> Result<(), RuleError> {
+        self.place_meeple_of_kind(seg_ident, player_ident, MeepleKind::Normal)
+    }
+
+    pub fn place_meeple_of_kind(
+        &mut self,
+        seg_ident: SegmentIdentifier,
+        player_ident: PlayerIdentifier,
+        kind: MeepleKind,
+    ) -> Result<(), RuleError> {
         let player = self.players.get_mut(player_ident).unwrap();
-        if player.meeples == 0 {
-            return Err(GameError::CustomError(
-                "Player has no meeples remaining!".to_string(),
-            ));
+        let supply = player.supply_mut(kind);
+        if *supply == 0 {
+            return Err(RuleError::NoMeeplesRemaining);
         }
-        player.meeples -= 1;
+        *supply -= 1;
 
         let group = self.group_by_seg_ident_mut(seg_ident).unwrap();
-        group.meeples.push((seg_ident, player_ident));
+        group.meeples.push((seg_ident, player_ident, kind));
 
         Ok(())
     }
@@ -596,7 +1308,7 @@ impl Game {
 
     fn compute_group_scoring_details(&self, group: &SegmentGroup) -> ScoringDetails {
         let group_score = self.compute_group_score(group);
-        let (_, scoring_players) = group.compute_owners().unwrap_or_default();
+        let (_, scoring_players) = group.compute_owners(&self.hills).unwrap_or_default();
         ScoringDetails {
             score: group_score,
             owners: scoring_players
@@ -887,7 +1599,33 @@ impl Game {
                 return false;
             }
         }
-        is_adjacent_tile
+        is_adjacent_tile && self.is_valid_river_position(tile, pos)
+    }
+
+    /// River tiles drawn during the game have to carry on the river from one of its open
+    /// ends, without turning it back on itself.
+    fn is_valid_river_position(&self, tile: &Tile, pos: GridPos) -> bool {
+        let river_edges = tile.river_edges();
+        if river_edges.is_empty() {
+            return true;
+        }
+        let continues_river = river_edges.iter().any(|orientation| {
+            self.placed_tiles
+                .get(&(pos + orientation.offset()))
+                .is_some_and(|adjacent| adjacent.river_edges().contains(&orientation.opposite()))
+        });
+        let tile_at = |check_pos: GridPos| {
+            if check_pos == pos {
+                Some(tile)
+            } else {
+                self.placed_tiles.get(&check_pos)
+            }
+        };
+        continues_river
+            && !self.placed_tiles.keys().chain([&pos]).any(|check_pos| {
+                Orientation::iter_with_offsets()
+                    .any(|(orientation, _)| river_turns_back(tile_at, *check_pos, orientation))
+            })
     }
 
     pub fn placeable_positions(&self, tile: &Tile) -> Vec<GridPos> {
@@ -904,6 +1642,119 @@ impl Game {
             .collect()
     }
 
+    /// Lists every distinct way of placing the tile at some position and rotation, either
+    /// without a meeple or with each kind the player has left on each segment it could go
+    /// on. Rotations that are indistinguishable due to the tile's symmetry are only listed
+    /// once.
+    pub fn legal_moves(&self, tile: &Tile, player_ident: PlayerIdentifier) -> Vec<LegalMove> {
+        let kinds: Vec<MeepleKind> = self
+            .players
+            .get(player_ident)
+            .map(|player| {
+                MeepleKind::ALL
+                    .into_iter()
+                    .filter(|kind| player.supply(*kind) > 0)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut moves = Vec::new();
+        for pos in self.valid_placements.iter().cloned() {
+            let mut seen_signatures = Vec::new();
+            for rotation in 0..4 {
+                let mut tile = tile.clone();
+                tile.rotate_to(rotation);
+                if !self.is_valid_tile_position(&tile, pos) {
+                    continue;
+                }
+                let signature = tile.rotation_signature();
+                if seen_signatures.contains(&signature) {
+                    continue;
+                }
+                seen_signatures.push(signature);
+
+                moves.push((pos, rotation, None));
+                if kinds.is_empty() || self.dragon == Some(pos) {
+                    continue;
+                }
+                let joined_meeples = self.joined_meeples(&tile, pos);
+                for (seg_index, segment) in tile.segments.iter().enumerate() {
+                    if !self.meeple_placeable(segment.stype) {
+                        continue;
+                    }
+                    let meeples = joined_meeples
+                        .get(&seg_index)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default();
+                    moves.extend(
+                        kinds
+                            .iter()
+                            .filter(|kind| {
+                                kind.placeable_on(segment.stype)
+                                    && kind.can_join(meeples, player_ident).is_ok()
+                            })
+                            .map(|kind| (pos, rotation, Some((seg_index, *kind)))),
+                    );
+                }
+            }
+        }
+        moves
+    }
+
+    /// The meeples already in the groups each segment of the tile would join if the tile
+    /// were placed at the given position.
+    fn joined_meeples(
+        &self,
+        tile: &Tile,
+        pos: GridPos,
+    ) -> HashMap<SegmentIndex, Vec<PlacedMeeple>> {
+        let mut joined_groups: HashMap<SegmentIndex, Vec<GroupIdentifier>> = HashMap::new();
+        for (orientation, offset) in Orientation::iter_with_offsets() {
+            let adjacent_pos = pos + offset;
+            let Some(mounts) = self
+                .placed_tiles
+                .get(&adjacent_pos)
+                .and_then(|adjacent_tile| tile.validate_mounting(adjacent_tile, orientation))
+            else {
+                continue;
+            };
+            for mount in mounts {
+                if let Some((_, group_ident)) =
+                    self.group_and_key_by_seg_ident((adjacent_pos, mount.to_segment))
+                {
+                    joined_groups.place(mount.from_segment, group_ident);
+                }
+            }
+        }
+
+        // segments of the new tile can bridge several existing groups together, so keep
+        // gathering the groups linked through them until it settles
+        joined_groups
+            .keys()
+            .map(|&seg_index| {
+                let mut groups: HashSet<GroupIdentifier> =
+                    joined_groups[&seg_index].iter().cloned().collect();
+                loop {
+                    let linked: Vec<GroupIdentifier> = joined_groups
+                        .values()
+                        .filter(|others| others.iter().any(|other| groups.contains(other)))
+                        .flatten()
+                        .filter(|other| !groups.contains(*other))
+                        .cloned()
+                        .collect();
+                    if linked.is_empty() {
+                        break;
+                    }
+                    groups.extend(linked);
+                }
+                let meeples = groups
+                    .iter()
+                    .flat_map(|group_ident| self.groups[*group_ident].meeples.iter().cloned())
+                    .collect();
+                (seg_index, meeples)
+            })
+            .collect()
+    }
+
     pub fn draw_placeable_tile(&mut self) -> Option<(Tile, Vec<GridPos>)> {
         for _ in 0..self.library.len() {
             let Some(next_tile) = self.library.pop() else {
@@ -948,14 +1799,19 @@ mod test {
     use ggez::{graphics::Color, GameResult};
 
     use crate::{
-        game::{debug_game_configs::river_test, Game},
+        game::{
+            debug_game_configs::river_test, rules::RuleError, FarmerRules, Game, MeepleKind,
+            PlayerIdentifier, ScoringRules,
+        },
         pos::GridPos,
         tile::{
             tile_definitions::{
-                rivers_1::MONASTARY_POND, CROSSROADS, CURVE_ROAD, MONASTARY, STARTING_TILE,
-                STRAIGHT_ROAD,
+                abbots_and_gardens::GARDEN, hills_and_sheep::HILL_STRAIGHT_ROAD,
+                inns_and_cathedrals::INN_STRAIGHT_ROAD, rivers_1::MONASTARY_POND,
+                traders_and_builders::WINE_EDGE_CITY, CROSSROADS, CURVE_ROAD, EDGE_CITY, MONASTARY,
+                STARTING_TILE, STRAIGHT_ROAD,
             },
-            SegmentType,
+            SegmentType, TradeGood,
         },
     };
 
@@ -1059,6 +1915,215 @@ mod test {
         Ok(())
     }
 
+    fn two_tile_city_with_farmers(
+        scoring_rules: ScoringRules,
+    ) -> GameResult<(Game, PlayerIdentifier, PlayerIdentifier)> {
+        let mut game = Game::new();
+        game.scoring_rules = scoring_rules;
+        let red = game.players.insert(Player::new(Color::RED));
+        let blue = game.players.insert(Player::new(Color::BLUE));
+        game.place_tile(EDGE_CITY.clone(), GridPos(0, 0))?;
+        game.place_tile(EDGE_CITY.clone().rotated().rotated(), GridPos(0, -1))?;
+        game.place_meeple((GridPos(0, 0), 1), red)?;
+        game.place_meeple((GridPos(0, -1), 1), blue)?;
+        game.place_meeple((GridPos(0, -1), 1), blue)?;
+        Ok((game, red, blue))
+    }
+
+    fn score_farms(game: &mut Game) {
+        while let Some(farm_ident) = game
+            .groups
+            .iter()
+            .find(|(_, group)| group.gtype == SegmentType::Farm && !group.meeples.is_empty())
+            .map(|(farm_ident, _)| farm_ident)
+        {
+            game.score_group(farm_ident);
+        }
+    }
+
+    #[test]
+    pub fn test_farm_scoring_rules() -> GameResult {
+        for (farmers, red_score, blue_score) in [
+            (FarmerRules::ThirdEdition, 3, 3),
+            (FarmerRules::SecondEdition, 4, 4),
+            (FarmerRules::FirstEdition, 0, 4),
+        ] {
+            let (mut game, red, blue) = two_tile_city_with_farmers(ScoringRules {
+                farmers,
+                small_cities: false,
+            })?;
+            score_farms(&mut game);
+            assert_eq!(game.players[red].score, red_score, "{farmers:?}");
+            assert_eq!(game.players[blue].score, blue_score, "{farmers:?}");
+            assert_eq!(game.players[blue].meeples, 7);
+        }
+
+        let (game, _, _) = two_tile_city_with_farmers(ScoringRules {
+            farmers: FarmerRules::Disabled,
+            small_cities: false,
+        })?;
+        assert!(!game.meeple_placeable(SegmentType::Farm));
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_pig_scoring() -> GameResult {
+        let (mut game, red, blue) = two_tile_city_with_farmers(ScoringRules::default())?;
+        game.players[blue].pigs = 1;
+        game.place_meeple_of_kind((GridPos(0, -1), 1), blue, MeepleKind::Pig)?;
+        score_farms(&mut game);
+        assert_eq!(game.players[red].score, 3);
+        assert_eq!(game.players[blue].score, 4);
+        assert_eq!(game.players[blue].pigs, 1);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_goods_scoring() -> GameResult {
+        let mut game = Game::new();
+        let red = game.players.insert(Player::new(Color::RED));
+        let blue = game.players.insert(Player::new(Color::BLUE));
+        game.place_tile(WINE_EDGE_CITY.clone(), GridPos(0, 0))?;
+        let closed_groups =
+            game.place_tile(EDGE_CITY.clone().rotated().rotated(), GridPos(0, -1))?;
+        let city = closed_groups
+            .into_iter()
+            .find(|group_ident| game.groups[*group_ident].gtype == SegmentType::City)
+            .unwrap();
+        game.collect_goods(city, red);
+        assert_eq!(game.players[red].goods[&TradeGood::Wine], 1);
+        assert_eq!(game.score_goods(), vec![(red, TradeGood::Wine)]);
+        assert_eq!(game.players[red].score, 10);
+        assert_eq!(game.players[blue].score, 0);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_small_city_scoring() -> GameResult {
+        for (small_cities, score) in [(false, 4), (true, 2)] {
+            let (game, _, _) = two_tile_city_with_farmers(ScoringRules {
+                farmers: FarmerRules::default(),
+                small_cities,
+            })?;
+            let city = game
+                .groups
+                .values()
+                .find(|group| group.gtype == SegmentType::City)
+                .unwrap();
+            assert!(city.free_edges.is_empty());
+            assert_eq!(game.compute_group_score(city), score);
+        }
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_hill_tie_break() -> GameResult {
+        let mut game = Game::new();
+        let red = game.players.insert(Player::new(Color::RED));
+        let blue = game.players.insert(Player::new(Color::BLUE));
+        game.place_tile(HILL_STRAIGHT_ROAD.clone(), GridPos(0, 0))?;
+        game.place_tile(STRAIGHT_ROAD.clone(), GridPos(1, 0))?;
+        game.place_meeple((GridPos(1, 0), 1), red)?;
+        game.place_meeple((GridPos(0, 0), 1), blue)?;
+
+        let (road, _) = game.group_and_key_by_seg_ident((GridPos(0, 0), 1)).unwrap();
+        assert_eq!(road.compute_owners(&game.hills).unwrap().1, vec![blue]);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_inn_scoring_and_large_meeples() -> GameResult {
+        let mut game = Game::new();
+        let red = game.players.insert(Player::new(Color::RED));
+        let blue = game.players.insert(Player::new(Color::BLUE));
+        game.players[red].large_meeples = 1;
+        game.place_tile(CROSSROADS.clone(), GridPos(-1, 0))?;
+        game.place_tile(INN_STRAIGHT_ROAD.clone(), GridPos(0, 0))?;
+        game.place_meeple_of_kind((GridPos(0, 0), 1), red, MeepleKind::Large)?;
+        game.place_meeple((GridPos(-1, 0), 2), blue)?;
+
+        let (road, road_ident) = game.group_and_key_by_seg_ident((GridPos(0, 0), 1)).unwrap();
+        assert_eq!(game.compute_group_score(road), 0);
+        assert_eq!(road.compute_owners(&game.hills).unwrap().1, vec![red]);
+
+        game.place_meeple((GridPos(-1, 0), 2), blue)?;
+        let road = &game.groups[road_ident];
+        assert_eq!(road.compute_owners(&game.hills).unwrap().1.len(), 2);
+
+        game.place_tile(CROSSROADS.clone(), GridPos(1, 0))?;
+        let (road, road_ident) = game.group_and_key_by_seg_ident((GridPos(0, 0), 1)).unwrap();
+        assert!(road.free_edges.is_empty());
+        assert_eq!(game.compute_group_score(road), 6);
+        game.score_group(road_ident);
+        assert_eq!(game.players[red].score, 6);
+        assert_eq!(game.players[blue].score, 6);
+        assert_eq!(game.players[red].large_meeples, 1);
+        assert_eq!(game.players[blue].meeples, 7);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_abbot_retrieval() -> GameResult {
+        let mut game = Game::new();
+        let red = game.players.insert(Player::new(Color::RED));
+        game.players[red].abbots = 1;
+        game.place_tile(GARDEN.clone(), GridPos(0, 0))?;
+        assert!(!MeepleKind::Normal.placeable_on(SegmentType::Garden));
+        game.place_meeple_of_kind((GridPos(0, 0), 0), red, MeepleKind::Abbot)?;
+        game.place_tile(MONASTARY.clone(), GridPos(1, 0))?;
+        game.place_tile(MONASTARY.clone(), GridPos(0, 1))?;
+
+        let scoring_result = game.retrieve_abbot(red)?;
+        assert_eq!(scoring_result.len(), 1);
+        assert_eq!(scoring_result[0].score, 3);
+        assert_eq!(game.players[red].score, 3);
+        assert_eq!(game.players[red].abbots, 1);
+        assert!(matches!(
+            game.retrieve_abbot(red),
+            Err(RuleError::NoAbbotPlaced)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_legal_moves() -> GameResult {
+        let mut game = Game::new();
+        let player_ident = game.players.insert(Player::new(Color::RED));
+        game.place_tile(STARTING_TILE.clone(), GridPos(0, 0))?;
+        game.place_meeple((GridPos(0, 0), 1), player_ident)?;
+        let moves: Vec<_> = game
+            .legal_moves(&STRAIGHT_ROAD, player_ident)
+            .into_iter()
+            .filter(|(pos, _, _)| *pos == GridPos(1, 0))
+            .collect();
+        assert_eq!(
+            moves,
+            vec![
+                (GridPos(1, 0), 0, None),
+                (GridPos(1, 0), 0, Some((0, MeepleKind::Normal))),
+                (GridPos(1, 0), 0, Some((2, MeepleKind::Normal))),
+            ]
+        );
+
+        // a builder can only follow the meeple already on the road
+        game.players[player_ident].builders = 1;
+        let moves: Vec<_> = game
+            .legal_moves(&STRAIGHT_ROAD, player_ident)
+            .into_iter()
+            .filter(|(pos, rotation, _)| *pos == GridPos(1, 0) && *rotation == 0)
+            .collect();
+        assert_eq!(
+            moves,
+            vec![
+                (GridPos(1, 0), 0, None),
+                (GridPos(1, 0), 0, Some((0, MeepleKind::Normal))),
+                (GridPos(1, 0), 0, Some((1, MeepleKind::Builder))),
+                (GridPos(1, 0), 0, Some((2, MeepleKind::Normal))),
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     pub fn test_river_outline_generation() -> GameResult {
         use crate::tile::SegmentType;
@@ -0,0 +1,440 @@
+use std::{
+    collections::VecDeque,
+    sync::mpsc::{channel, Receiver, TryRecvError},
+    thread,
+};
+
+use log::{debug, warn};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        rules::{GameState, RuleRng, TurnPhase},
+        MeepleKind, PlayerIdentifier,
+    },
+    multiplayer::message::{GameMessage, TilePose},
+};
+
+/// Value of an unplaced meeple, so that bots don't spend them on nothing.
+const MEEPLE_VALUE: f32 = 1.5;
+/// Fraction of an unfinished group's current score that a bot expects to collect from it.
+const PROJECTED_SCORE_WEIGHT: f32 = 0.6;
+
+const LOOKAHEAD_CANDIDATES: usize = 6;
+const LOOKAHEAD_ROLLOUTS: usize = 12;
+
+/// Seconds between each action a bot takes, so that people can follow along.
+const ACTION_DELAY: f32 = 0.75;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BotDifficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl BotDifficulty {
+    pub const ALL: [BotDifficulty; 3] = [
+        BotDifficulty::Easy,
+        BotDifficulty::Medium,
+        BotDifficulty::Hard,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BotDifficulty::Easy => "Easy",
+            BotDifficulty::Medium => "Medium",
+            BotDifficulty::Hard => "Hard",
+        }
+    }
+}
+
+/// A bot's turn, decided on a background thread and then played out one message at a time.
+struct BotTurn {
+    player: PlayerIdentifier,
+    plan: Receiver<Vec<GameMessage>>,
+    planned: bool,
+    /// Whether the plan fell through and was replaced with a random move, which is only
+    /// tried once so that a bot that can't move doesn't keep trying forever.
+    fallen_back: bool,
+    messages: VecDeque<GameMessage>,
+    next_action: f32,
+}
+
+/// Plays the turns of whichever seats are under bot control, without blocking the caller.
+#[derive(Default)]
+pub struct BotController {
+    turn: Option<BotTurn>,
+}
+
+impl BotController {
+    /// Abandons the turn being played, for when the game is rewound out from under it.
+    pub fn cancel(&mut self) {
+        self.turn = None;
+    }
+
+    /// Advances the current player's turn if it's controlled by a bot of the given
+    /// difficulty, returning the next message it wants to play once it's ready.
+    pub fn update(
+        &mut self,
+        state: &GameState,
+        difficulty: Option<BotDifficulty>,
+        now: f32,
+    ) -> Option<GameMessage> {
+        let Some(difficulty) = difficulty else {
+            self.turn = None;
+            return None;
+        };
+        if matches!(
+            state.turn_phase,
+            TurnPhase::Pregame { .. } | TurnPhase::EndGame
+        ) {
+            self.turn = None;
+            return None;
+        }
+
+        let current_player = state.current_player();
+        let turn = match &mut self.turn {
+            // the same player is up again after a finished plan when a builder has earned
+            // them a bonus turn, or when the dragon's flight comes back around to them
+            Some(turn)
+                if turn.player == current_player
+                    && !(turn.planned
+                        && turn.messages.is_empty()
+                        && matches!(
+                            state.turn_phase,
+                            TurnPhase::TilePlacement {
+                                bonus_turn: true,
+                                ..
+                            } | TurnPhase::DragonMovement { .. }
+                        )) =>
+            {
+                turn
+            }
+            turn => {
+                debug!("{} bot deciding its turn", difficulty.name());
+                let (plan_sender, plan) = channel();
+                let state = state.clone();
+                thread::spawn(move || {
+                    let _ =
+                        plan_sender.send(plan_turn(&state, difficulty, &mut rand::thread_rng()));
+                });
+                turn.insert(BotTurn {
+                    player: current_player,
+                    plan,
+                    planned: false,
+                    fallen_back: false,
+                    messages: VecDeque::new(),
+                    next_action: now + ACTION_DELAY,
+                })
+            }
+        };
+
+        if !turn.planned {
+            match turn.plan.try_recv() {
+                Ok(messages) => {
+                    turn.messages.extend(messages);
+                    turn.planned = true;
+                }
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => {
+                    warn!("bot failed to decide its turn");
+                    turn.planned = true;
+                }
+            }
+        }
+
+        if now < turn.next_action {
+            return None;
+        }
+        if turn.messages.is_empty() {
+            // the plan ran out without ending the turn, most likely because part of it
+            // was turned down; deciding again would only come up with the same plan
+            if turn.fallen_back {
+                return None;
+            }
+            warn!("bot failed to complete its turn, playing a random move instead");
+            turn.fallen_back = true;
+            turn.messages
+                .extend(fallback_turn(state, &mut rand::thread_rng()));
+        }
+        let message = turn.messages.pop_front()?;
+        turn.next_action = now + ACTION_DELAY;
+        Some(message)
+    }
+}
+
+/// Decides the current player's turn, returning the messages to send in order.
+/// Easy bots play a random legal move, medium bots take the move with the best
+/// immediate outcome, and hard bots play out the most promising moves a turn
+/// around the table before choosing.
+pub fn plan_turn(
+    state: &GameState,
+    difficulty: BotDifficulty,
+    rng: &mut impl Rng,
+) -> Vec<GameMessage> {
+    let player = state.current_player();
+    let mut outcomes: Vec<_> = candidate_turns(state)
+        .into_iter()
+        .filter_map(|messages| simulate(state, messages))
+        .collect();
+    outcomes.shuffle(rng);
+    if difficulty == BotDifficulty::Easy {
+        return outcomes
+            .into_iter()
+            .next()
+            .map(|(messages, _)| messages)
+            .unwrap_or_default();
+    }
+
+    let mut evaluated: Vec<_> = outcomes
+        .into_iter()
+        .map(|(messages, outcome)| (evaluate(&outcome, player), messages, outcome))
+        .collect();
+    evaluated.sort_by(|(a, _, _), (b, _, _)| b.total_cmp(a));
+    if difficulty == BotDifficulty::Hard {
+        evaluated.truncate(LOOKAHEAD_CANDIDATES);
+        for (value, _, outcome) in &mut evaluated {
+            *value = (0..LOOKAHEAD_ROLLOUTS)
+                .map(|_| rollout(outcome, player, rng))
+                .sum::<f32>()
+                / LOOKAHEAD_ROLLOUTS as f32;
+        }
+        evaluated.sort_by(|(a, _, _), (b, _, _)| b.total_cmp(a));
+    }
+    evaluated
+        .into_iter()
+        .next()
+        .map(|(_, messages, _)| messages)
+        .unwrap_or_default()
+}
+
+/// A random turn that can be played out from this point, for when the planned one
+/// couldn't. Meeples are skipped if nothing else can be played.
+fn fallback_turn(state: &GameState, rng: &mut impl Rng) -> Vec<GameMessage> {
+    let mut turns = candidate_turns(state);
+    turns.shuffle(rng);
+    turns
+        .into_iter()
+        .find_map(|messages| simulate(state, messages))
+        .map(|(messages, _)| messages)
+        .unwrap_or_else(|| vec![GameMessage::SkipMeeples])
+}
+
+/// Every way the current player could finish their turn from this point.
+fn candidate_turns(state: &GameState) -> Vec<Vec<GameMessage>> {
+    match &state.turn_phase {
+        TurnPhase::TilePlacement { tile, .. } => state
+            .game
+            .legal_moves(tile, state.current_player())
+            .into_iter()
+            .map(|(position, rotation, meeple)| {
+                vec![
+                    GameMessage::PlaceTile(TilePose { position, rotation }),
+                    match meeple {
+                        Some((seg_index, kind)) => GameMessage::PlaceMeeple {
+                            seg_ident: (position, seg_index),
+                            kind,
+                        },
+                        None => GameMessage::SkipMeeples,
+                    },
+                ]
+            })
+            .collect(),
+        TurnPhase::MeeplePlacement {
+            placed_position, ..
+        } => {
+            let tile = &state.game.placed_tiles[placed_position];
+            std::iter::once(vec![GameMessage::SkipMeeples])
+                .chain((0..tile.segments.len()).flat_map(|seg_index| {
+                    let seg_ident = (*placed_position, seg_index);
+                    MeepleKind::ALL.into_iter().filter_map(move |kind| {
+                        state
+                            .validate_meeple_placement(seg_ident, kind)
+                            .is_ok()
+                            .then(|| vec![GameMessage::PlaceMeeple { seg_ident, kind }])
+                    })
+                }))
+                .collect()
+        }
+        TurnPhase::DragonMovement { .. } => state
+            .dragon_moves()
+            .into_iter()
+            .map(|pos| vec![GameMessage::MoveDragon(pos)])
+            .collect(),
+        TurnPhase::Pregame { .. } | TurnPhase::EndGame => Vec::new(),
+    }
+}
+
+/// Applies a turn to a copy of the state. Messages left over once the turn has
+/// ended, such as a meeple placement after a tile with no free segments, are dropped.
+fn simulate(
+    state: &GameState,
+    messages: Vec<GameMessage>,
+) -> Option<(Vec<GameMessage>, GameState)> {
+    let player = state.current_player();
+    let mut outcome = state.clone();
+    let mut sent = Vec::new();
+    for message in messages {
+        outcome.apply(message.clone()).ok()?;
+        sent.push(message);
+        if outcome.is_endgame() || outcome.current_player() != player {
+            break;
+        }
+    }
+    Some((sent, outcome))
+}
+
+/// Plays random moves for a full round after the given state and evaluates the result.
+fn rollout(state: &GameState, player: PlayerIdentifier, rng: &mut impl Rng) -> f32 {
+    let mut state = state.clone();
+    // the bot doesn't know the order of the remaining tiles, or what's left to draw
+    state.game.library.shuffle(rng);
+    state.rng = RuleRng::new(rng.gen());
+    for _ in 0..state.turn_order.len() {
+        let Some(messages) = candidate_turns(&state).choose(rng).cloned() else {
+            break;
+        };
+        match simulate(&state, messages) {
+            Some((_, outcome)) => state = outcome,
+            None => break,
+        }
+    }
+    if state.is_endgame() {
+        state.score_endgame();
+    }
+    evaluate(&state, player)
+}
+
+/// How well the player is doing compared to the strongest of their opponents.
+fn evaluate(state: &GameState, player: PlayerIdentifier) -> f32 {
+    let game = &state.game;
+    let standing = |player_ident: PlayerIdentifier| {
+        let projected_score: usize = game
+            .groups
+            .values()
+            .filter(|group| {
+                group
+                    .compute_owners(&game.hills)
+                    .is_some_and(|(_, owners)| owners.contains(&player_ident))
+            })
+            .map(|group| game.compute_group_score(group))
+            .sum();
+        let player = &game.players[player_ident];
+        player.score as f32
+            + projected_score as f32 * PROJECTED_SCORE_WEIGHT
+            + player.meeples as f32 * MEEPLE_VALUE
+    };
+    let best_opponent = game
+        .players
+        .keys()
+        .filter(|player_ident| *player_ident != player)
+        .map(standing)
+        .fold(0.0, f32::max);
+    standing(player) - best_opponent
+}
+
+#[cfg(test)]
+mod test {
+    use ggez::graphics::Color;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{
+        game::{
+            player::{Player, PlayerType},
+            setup::{GameExpansions, LibraryConfiguration},
+            Game, MeepleKind, ScoringRules,
+        },
+        multiplayer::message::{GameMessage, TilePose},
+        pos::GridPos,
+        tile::tile_definitions::{STARTING_TILE, STRAIGHT_ROAD},
+    };
+
+    use super::{candidate_turns, fallback_turn, plan_turn, BotDifficulty, GameState};
+
+    fn bot_game(difficulties: &[BotDifficulty]) -> GameState {
+        let colors = [Color::RED, Color::BLUE, Color::GREEN];
+        GameState::new(
+            0,
+            difficulties
+                .iter()
+                .zip(colors)
+                .map(|(&difficulty, color)| {
+                    Player::new_inner(color, PlayerType::Bot { difficulty })
+                })
+                .collect(),
+            &GameExpansions::default(),
+            &LibraryConfiguration::default(),
+            &ScoringRules::default(),
+            PlayerType::Local,
+        )
+    }
+
+    fn play_bot_turn(state: &mut GameState, rng: &mut StdRng) {
+        let PlayerType::Bot { difficulty } = state.game.players[state.current_player()].ptype
+        else {
+            unreachable!();
+        };
+        let messages = plan_turn(state, difficulty, rng);
+        assert!(!messages.is_empty());
+        for message in messages {
+            state.apply(message).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_bots_play_full_game() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut state = bot_game(&[BotDifficulty::Easy, BotDifficulty::Medium]);
+        while !state.is_endgame() {
+            play_bot_turn(&mut state, &mut rng);
+        }
+    }
+
+    #[test]
+    fn test_bots_use_expansion_meeples() {
+        let mut game = Game::new_with_library(vec![STRAIGHT_ROAD.clone(); 3]);
+        let red = game.players.insert(Player::new(Color::RED));
+        game.players.insert(Player::new(Color::BLUE));
+        game.players[red].builders = 1;
+        game.place_tile(STARTING_TILE.clone(), GridPos(0, 0))
+            .unwrap();
+        game.place_meeple((GridPos(0, 0), 1), red).unwrap();
+        let mut state = GameState::from_game(game, 0, &GameExpansions::default()).unwrap();
+
+        let builder_on_road = GameMessage::PlaceMeeple {
+            seg_ident: (GridPos(1, 0), 1),
+            kind: MeepleKind::Builder,
+        };
+        let place_road = GameMessage::PlaceTile(TilePose {
+            position: GridPos(1, 0),
+            rotation: 0,
+        });
+        assert!(
+            candidate_turns(&state).contains(&vec![place_road.clone(), builder_on_road.clone()])
+        );
+        state.apply(place_road).unwrap();
+        assert!(candidate_turns(&state).contains(&vec![builder_on_road]));
+    }
+
+    #[test]
+    fn test_fallback_turn_is_playable() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut state = bot_game(&[BotDifficulty::Easy, BotDifficulty::Easy]);
+        let player = state.current_player();
+        for message in fallback_turn(&state, &mut rng) {
+            state.apply(message).unwrap();
+        }
+        assert_ne!(state.current_player(), player);
+    }
+
+    #[test]
+    fn test_lookahead_bot_turn() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut state = bot_game(&[BotDifficulty::Hard, BotDifficulty::Easy]);
+        let placed_tiles = state.game.placed_tiles.len();
+        play_bot_turn(&mut state, &mut rng);
+        assert_eq!(state.game.placed_tiles.len(), placed_tiles + 1);
+    }
+}
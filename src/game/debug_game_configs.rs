@@ -4,7 +4,8 @@ use ggez::{graphics::Color, GameError, GameResult};
 use ggez_no_re::checker_spiral::checker_spiral;
 
 use crate::{
-    game_client::{GameExpansions, PLAYER_COLORS},
+    game::setup::GameExpansions,
+    game_client::PLAYER_COLORS,
     pos::GridPos,
     tile::{
         tile_definitions::{
@@ -38,6 +39,14 @@ impl DebugGameConfiguration {
             RiverTest => river_test(),
         }
     }
+
+    pub fn expansions(&self) -> GameExpansions {
+        use DebugGameConfiguration::*;
+        match self {
+            RiverTest => river_test_expansions(),
+            _ => GameExpansions::default(),
+        }
+    }
 }
 pub fn meeple_locations() -> Result<Game, GameError> {
     let library: Vec<Tile> = Tile::default_library_tallies()
@@ -230,13 +239,16 @@ pub fn river_test() -> GameResult<Game> {
     let mut this = Game::new_with_library(vec![STARTING_TILE.clone()]);
     this.players.insert(Player::new(Color::BLUE));
 
-    for (pos, tile) in checker_spiral().zip(
-        GameExpansions { rivers_1: true }
-            .rivers()
-            .unwrap_or_default(),
-    ) {
+    for (pos, tile) in checker_spiral().zip(river_test_expansions().rivers().unwrap_or_default()) {
         this.place_tile(tile, pos.into())?;
     }
 
     Ok(this)
 }
+
+fn river_test_expansions() -> GameExpansions {
+    GameExpansions {
+        rivers_1: true,
+        ..Default::default()
+    }
+}
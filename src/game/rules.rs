@@ -0,0 +1,1808 @@
+use std::collections::{HashMap, VecDeque};
+
+use ggez_no_re::checker_spiral::checker_spiral;
+use rand::{seq::SliceRandom, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        player::{Player, PlayerType},
+        setup::{GameExpansions, LibraryConfiguration},
+        Game, GroupIdentifier, MeepleKind, PlayerIdentifier, ScoringResult, ScoringRules,
+        SegmentIdentifier, SheepToken,
+    },
+    multiplayer::message::{GameMessage, TilePose},
+    pos::GridPos,
+    tile::{
+        tile_definitions::STARTING_TILE, Opposite, Orientation, SegmentAttribute, SegmentType,
+        Tile, TradeGood,
+    },
+};
+
+/// Number of tiles the dragon flies each time it's summoned.
+const DRAGON_MOVES: usize = 6;
+/// Points the fairy's owner collects at the start of each of their turns.
+const FAIRY_TURN_SCORE: usize = 1;
+/// Points paid to another player to buy back a meeple captured by their tower.
+pub const RANSOM_COST: usize = 3;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuleError {
+    WrongPhase,
+    InvalidTilePlacement,
+    PositionOccupied,
+    NoTileHeld,
+    TileAlreadyHeld,
+    NoTileAtPosition,
+    RiverIncomplete,
+    InvalidSegment,
+    UnplaceableSegment,
+    GroupOccupied,
+    NoMeeplesRemaining,
+    NoFollowerInGroup,
+    NoAbbotPlaced,
+    DragonPresent,
+    InvalidDragonMove,
+    NotYourMeeple,
+    NoKnightToDismiss,
+    NoTowerPieces,
+    NoTowerFoundation,
+    OutOfTowerSight,
+    NoPrisonerHeld,
+    NotEnoughPoints,
+    NoFlockExtended,
+    SheepBagEmpty,
+    NoPlaceableTiles,
+    NotYourTurn,
+    UndoNotAllowed,
+    HostOnly,
+    Unsupported,
+}
+
+impl std::fmt::Display for RuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use RuleError::*;
+        let description = match self {
+            WrongPhase => "Action not allowed in the current turn phase",
+            InvalidTilePlacement => "Attempt to place invalid tile!",
+            PositionOccupied => "A tile is already placed at that position",
+            NoTileHeld => "No tile is currently held",
+            TileAlreadyHeld => "A tile is already held",
+            NoTileAtPosition => "There is no tile at that position",
+            RiverIncomplete => "The river has not been completed",
+            InvalidSegment => "That segment does not exist on the placed tile",
+            UnplaceableSegment => "Meeples can't be placed on that segment",
+            GroupOccupied => "That group is already occupied by a meeple",
+            NoMeeplesRemaining => "Player has no meeples remaining!",
+            NoFollowerInGroup => "Builders and pigs can only join a group you already occupy",
+            NoAbbotPlaced => "Player has no abbot on the board",
+            DragonPresent => "Meeples can't be placed where the dragon is",
+            InvalidDragonMove => "The dragon can't fly there",
+            NotYourMeeple => "The fairy can only join one of your own meeples",
+            NoKnightToDismiss => "There's no knight there for the princess to send away",
+            NoTowerPieces => "Player has no tower pieces remaining!",
+            NoTowerFoundation => "Towers can only be built on a tower foundation",
+            OutOfTowerSight => "That meeple is out of the tower's sight",
+            NoPrisonerHeld => "That player isn't holding any of your meeples prisoner",
+            NotEnoughPoints => "Player doesn't have enough points to pay the ransom",
+            NoFlockExtended => "The placed tile doesn't extend a farm with your shepherd",
+            SheepBagEmpty => "There are no sheep left in the bag",
+            NoPlaceableTiles => "No placeable tiles remain in the library",
+            NotYourTurn => "It isn't your turn",
+            UndoNotAllowed => "The undo policy doesn't allow that right now",
+            HostOnly => "Only the host can do that",
+            Unsupported => "Action is not handled by the rules engine",
+        };
+        write!(f, "{description}")
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+#[derive(Clone, Debug)]
+pub enum RuleEvent {
+    GameBegan,
+    TilePlaced {
+        player: PlayerIdentifier,
+        pose: TilePose,
+    },
+    MeeplePlaced {
+        player: PlayerIdentifier,
+        seg_ident: SegmentIdentifier,
+    },
+    MeeplesSkipped {
+        player: PlayerIdentifier,
+    },
+    AbbotRetrieved {
+        player: PlayerIdentifier,
+    },
+    FairyMoved {
+        player: PlayerIdentifier,
+        seg_ident: SegmentIdentifier,
+    },
+    KnightDismissed {
+        player: PlayerIdentifier,
+        seg_ident: SegmentIdentifier,
+    },
+    DragonMoved {
+        pos: GridPos,
+        eaten: usize,
+    },
+    FairyScored {
+        player: PlayerIdentifier,
+    },
+    TowerBuilt {
+        player: PlayerIdentifier,
+        pos: GridPos,
+    },
+    MeepleCaptured {
+        player: PlayerIdentifier,
+        seg_ident: SegmentIdentifier,
+    },
+    PrisonerRansomed {
+        player: PlayerIdentifier,
+        captor: PlayerIdentifier,
+    },
+    FlockExpanded {
+        player: PlayerIdentifier,
+        token: SheepToken,
+    },
+    FlockHerded {
+        results: Vec<ScoringResult>,
+    },
+    GroupScored {
+        group: GroupIdentifier,
+        results: Vec<ScoringResult>,
+    },
+    GoodsScored {
+        winners: Vec<(PlayerIdentifier, TradeGood)>,
+    },
+    BonusTurn {
+        player: PlayerIdentifier,
+    },
+    TurnEnded {
+        next_player: PlayerIdentifier,
+    },
+    GameEnded,
+}
+
+pub type Events = Vec<RuleEvent>;
+
+/// Source of chance for the rules themselves, such as shepherds drawing from the sheep bag.
+/// It's saved along with the rest of the game, so that every player in a multiplayer game,
+/// and every reload of a save, sees the same draws.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RuleRng {
+    state: u64,
+}
+
+impl RuleRng {
+    pub fn new(seed: u64) -> RuleRng {
+        RuleRng { state: seed }
+    }
+}
+
+// splitmix64, which is plenty for drawing tokens and simple enough to keep in the save file
+impl RngCore for RuleRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+pub fn recalculate_open_edges(tiles: &HashMap<GridPos, Tile>) -> Vec<(GridPos, Orientation)> {
+    tiles
+        .keys()
+        .cloned()
+        .flat_map(|pos| {
+            Orientation::iter_with_offsets().flat_map(move |(orientation, offset)| {
+                let tile = &tiles[&pos];
+                let middle_segment_id = tile.mounts.by_orientation(orientation)[1];
+                let is_river = tile.segments[middle_segment_id].stype == SegmentType::River;
+                if is_river && river_turns_back(|pos| tiles.get(&pos), pos, orientation) {
+                    return Some((pos, orientation));
+                }
+                if let Some(opposing_tile) = tiles.get(&(pos + offset)) {
+                    if tile.validate_mounting(opposing_tile, orientation).is_none() {
+                        return Some((pos, orientation));
+                    }
+                } else if is_river {
+                    return Some((pos, orientation));
+                }
+                None
+            })
+        })
+        .collect()
+}
+
+/// Draws a random token from the sheep bag, passing over the wolves unless they're allowed.
+fn draw_sheep(
+    bag: &mut Vec<SheepToken>,
+    rng: &mut RuleRng,
+    allow_wolf: bool,
+) -> Option<SheepToken> {
+    let candidates: Vec<usize> = (0..bag.len())
+        .filter(|index| allow_wolf || bag[*index] != SheepToken::Wolf)
+        .collect();
+    let index = *candidates.choose(rng)?;
+    Some(bag.swap_remove(index))
+}
+
+/// Whether the river leaving a bend through the given edge bends the same way again at the
+/// next bend it reaches, turning back on itself.
+pub fn river_turns_back<'a>(
+    tile_at: impl Fn(GridPos) -> Option<&'a Tile>,
+    pos: GridPos,
+    exit: Orientation,
+) -> bool {
+    // the edge the river leaves a tile through, given the edge it enters by
+    let course = |tile: &Tile, entry: Orientation| match tile.river_edges()[..] {
+        [a, b] if a == entry => Some(b),
+        [a, b] if b == entry => Some(a),
+        _ => None,
+    };
+    let Some(tile) = tile_at(pos) else {
+        return false;
+    };
+    let Some(bend) = course(tile, exit) else {
+        return false;
+    };
+    if bend == exit.opposite() {
+        return false;
+    }
+    // follow the river along any straight stretch until it next bends
+    let mut pos = pos;
+    loop {
+        pos = pos + exit.offset();
+        let Some(next) = tile_at(pos).and_then(|tile| course(tile, exit.opposite())) else {
+            return false;
+        };
+        if next != exit {
+            return next == bend;
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Serialize, Deserialize)]
+pub enum TurnPhase {
+    Pregame {
+        #[serde(with = "crate::save::map_as_pairs")]
+        tiles: HashMap<GridPos, Tile>,
+        held: Option<Tile>,
+        preview_location: Option<GridPos>,
+        open_edges: Vec<(GridPos, Orientation)>,
+    },
+    TilePlacement {
+        tile: Tile,
+        placeable_positions: Vec<GridPos>,
+        preview_location: Option<GridPos>,
+        /// Whether this is an extra turn granted by a builder, which can't grant another.
+        bonus_turn: bool,
+    },
+    MeeplePlacement {
+        placed_position: GridPos,
+        closed_groups: Vec<GroupIdentifier>,
+        /// Whether the placed tile extended one of the player's builders' groups.
+        earned_bonus_turn: bool,
+    },
+    /// The dragon's flight after a dragon tile is placed, with each player moving it one
+    /// tile in turn order, starting with the player who placed it.
+    DragonMovement {
+        visited: Vec<GridPos>,
+        moves_made: usize,
+        closed_groups: Vec<GroupIdentifier>,
+        earned_bonus_turn: bool,
+    },
+    EndGame,
+}
+
+/// Full rules state of a game in progress, independent of any rendering.
+/// All changes to a game are made by [`GameState::apply`]ing [`GameMessage`]s.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GameState {
+    pub game: Game,
+    pub turn_phase: TurnPhase,
+    pub turn_order: VecDeque<PlayerIdentifier>,
+    #[serde(default)]
+    pub goods_scored: bool,
+    /// Whether the fairy from the Princess & Dragon expansion is in play.
+    #[serde(default)]
+    pub fairy_in_play: bool,
+    #[serde(default)]
+    pub rng: RuleRng,
+    /// Names of the expansions the game was set up with, recorded in its save files.
+    #[serde(default)]
+    pub expansions: Vec<String>,
+}
+
+impl std::fmt::Debug for GameState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        #[allow(unused)]
+        #[derive(Debug)]
+        struct Game<'a> {
+            tiles: usize,
+            players: Vec<&'a Player>,
+        }
+        #[derive(Debug)]
+        enum TurnPhase {
+            Pregame,
+            MeeplePlacement,
+            TilePlacement,
+            DragonMovement,
+            EndGame,
+        }
+        f.debug_struct("GameState")
+            .field(
+                "game",
+                &Game {
+                    tiles: self.game.placed_tiles.len(),
+                    players: self
+                        .turn_order
+                        .iter()
+                        .map(|p| self.game.players.get(*p).unwrap())
+                        .collect(),
+                },
+            )
+            .field(
+                "turn_phase",
+                &match &self.turn_phase {
+                    self::TurnPhase::Pregame { .. } => TurnPhase::Pregame,
+                    self::TurnPhase::TilePlacement { .. } => TurnPhase::TilePlacement,
+                    self::TurnPhase::MeeplePlacement { .. } => TurnPhase::MeeplePlacement,
+                    self::TurnPhase::DragonMovement { .. } => TurnPhase::DragonMovement,
+                    self::TurnPhase::EndGame => TurnPhase::EndGame,
+                },
+            )
+            .finish()
+    }
+}
+
+impl GameState {
+    /// Sets up a new game; players take turns in the order they are given.
+    pub fn new(
+        seed: u64,
+        players: Vec<Player>,
+        expansions: &GameExpansions,
+        library: &LibraryConfiguration,
+        scoring_rules: &ScoringRules,
+        local_player: PlayerType,
+    ) -> GameState {
+        let mut library = library.library(expansions, seed);
+        // shuffled with the rules' own rng rather than one of rand's, whose output may change
+        // between versions, so every multiplayer client deals the same deck from the seed
+        let mut rng = RuleRng::new(seed);
+        library.shuffle(&mut rng);
+        let mut game = Game::new_inner(library, local_player);
+        game.scoring_rules = scoring_rules.clone();
+        // the tower pieces are shared out between however many players there are
+        let tower_pieces = match players.len() {
+            0..=2 => 10,
+            3 => 9,
+            4 => 7,
+            5 => 6,
+            _ => 5,
+        };
+        for mut player in players {
+            if expansions.inns_and_cathedrals {
+                player.large_meeples = 1;
+            }
+            if expansions.traders_and_builders {
+                player.builders = 1;
+                player.pigs = 1;
+            }
+            if expansions.abbots_and_gardens {
+                player.abbots = 1;
+            }
+            if expansions.tower {
+                player.tower_pieces = tower_pieces;
+            }
+            if expansions.hills_and_sheep {
+                player.shepherds = 1;
+            }
+            game.players.insert(player);
+        }
+        if expansions.hills_and_sheep {
+            game.sheep_bag = SheepToken::full_bag();
+        }
+        let turn_order = game.players.keys().collect();
+        let turn_phase = match expansions.rivers() {
+            Some(mut river_tiles) if !expansions.sequential_river => {
+                river_tiles.shuffle(&mut rng);
+                let tiles: HashMap<GridPos, Tile> = checker_spiral()
+                    .map(GridPos::from)
+                    .zip(river_tiles)
+                    .collect();
+                let open_edges = recalculate_open_edges(&tiles);
+                TurnPhase::Pregame {
+                    tiles,
+                    held: None,
+                    preview_location: None,
+                    open_edges,
+                }
+            }
+            rivers => {
+                let starting_tile = match rivers {
+                    Some(mut river_tiles) => {
+                        // the rest of the river is drawn before the main library, with the
+                        // tiles that end it held back until last
+                        let source = river_tiles.remove(0);
+                        let (mut ends, mut course): (Vec<_>, Vec<_>) = river_tiles
+                            .into_iter()
+                            .partition(|tile| tile.river_edges().len() == 1);
+                        ends.shuffle(&mut rng);
+                        course.shuffle(&mut rng);
+                        game.library.extend(ends);
+                        game.library.extend(course);
+                        source
+                    }
+                    None => STARTING_TILE.clone(),
+                };
+                game.place_tile(starting_tile, GridPos(0, 0)).unwrap();
+                let (tile, placeable_positions) = game.draw_placeable_tile().unwrap();
+                TurnPhase::TilePlacement {
+                    tile,
+                    placeable_positions,
+                    preview_location: None,
+                    bonus_turn: false,
+                }
+            }
+        };
+        GameState {
+            game,
+            turn_phase,
+            turn_order,
+            goods_scored: false,
+            fairy_in_play: expansions.princess_and_dragon,
+            rng,
+            expansions: expansions.names(),
+        }
+    }
+
+    /// Continues an already set up game from its first tile draw, with later draws made
+    /// from `seed` as in [`GameState::new`].
+    pub fn from_game(
+        mut game: Game,
+        seed: u64,
+        expansions: &GameExpansions,
+    ) -> Result<GameState, RuleError> {
+        let (tile, placeable_positions) = game
+            .draw_placeable_tile()
+            .ok_or(RuleError::NoPlaceableTiles)?;
+        Ok(GameState {
+            turn_phase: TurnPhase::TilePlacement {
+                tile,
+                placeable_positions,
+                preview_location: None,
+                bonus_turn: false,
+            },
+            turn_order: game.players.keys().collect(),
+            goods_scored: false,
+            fairy_in_play: expansions.princess_and_dragon,
+            rng: RuleRng::new(seed),
+            expansions: expansions.names(),
+            game,
+        })
+    }
+
+    /// The player who is expected to act next. While the dragon is flying, this moves
+    /// around the table with each step of its flight.
+    pub fn current_player(&self) -> PlayerIdentifier {
+        match &self.turn_phase {
+            TurnPhase::DragonMovement { moves_made, .. } => {
+                self.turn_order[moves_made % self.turn_order.len()]
+            }
+            _ => *self.turn_order.front().unwrap(),
+        }
+    }
+
+    pub fn is_endgame(&self) -> bool {
+        matches!(self.turn_phase, TurnPhase::EndGame)
+    }
+
+    pub fn held_tile(&self) -> Option<&Tile> {
+        match &self.turn_phase {
+            TurnPhase::TilePlacement { tile, .. } => Some(tile),
+            _ => None,
+        }
+    }
+
+    pub fn held_tile_mut(&mut self) -> Option<&mut Tile> {
+        match &mut self.turn_phase {
+            TurnPhase::TilePlacement { tile, .. } => Some(tile),
+            _ => None,
+        }
+    }
+
+    /// Applies an action on behalf of the current player. The state is left
+    /// untouched if the action is not legal.
+    pub fn apply(&mut self, message: GameMessage) -> Result<Events, RuleError> {
+        match message {
+            GameMessage::BeginGame => self.begin_game(),
+            GameMessage::PlaceTile(TilePose { position, rotation }) => {
+                self.place_tile(position, rotation)
+            }
+            GameMessage::PlaceMeeple { seg_ident, kind } => self.place_meeple(seg_ident, kind),
+            GameMessage::SkipMeeples => self.skip_meeples(),
+            GameMessage::RetrieveAbbot => self.retrieve_abbot(),
+            GameMessage::MoveFairy(seg_ident) => self.move_fairy(seg_ident),
+            GameMessage::DismissKnight(seg_ident) => self.dismiss_knight(seg_ident),
+            GameMessage::MoveDragon(pos) => self.move_dragon(pos),
+            GameMessage::BuildTower { pos, capture } => self.build_tower(pos, capture),
+            GameMessage::Ransom { captor } => self.ransom(captor),
+            GameMessage::ExpandFlock => self.expand_flock(),
+            GameMessage::HerdFlock => self.herd_flock(),
+            GameMessage::EndGame => self.end_game_immediately(),
+            GameMessage::PregamePickUp(pos) => self.pregame_pickup_tile(pos),
+            GameMessage::PregamePlace(TilePose { position, rotation }) => {
+                self.pregame_place_tile(position, rotation)
+            }
+            GameMessage::PreviewTile(tile_preview) => {
+                if let TurnPhase::TilePlacement {
+                    preview_location,
+                    tile,
+                    ..
+                } = &mut self.turn_phase
+                {
+                    *preview_location = tile_preview.map(|TilePose { position, rotation }| {
+                        tile.rotate_to(rotation);
+                        position
+                    });
+                }
+                Ok(Vec::new())
+            }
+            GameMessage::PregamePreview(tile_preview) => {
+                if let TurnPhase::Pregame {
+                    held,
+                    preview_location,
+                    ..
+                } = &mut self.turn_phase
+                {
+                    *preview_location = tile_preview.map(|TilePose { position, rotation }| {
+                        if let Some(held) = held {
+                            held.rotate_to(rotation);
+                        }
+                        position
+                    });
+                }
+                Ok(Vec::new())
+            }
+            GameMessage::Undo(_) | GameMessage::Redo(_) => Err(RuleError::Unsupported),
+        }
+    }
+
+    fn begin_game(&mut self) -> Result<Events, RuleError> {
+        let TurnPhase::Pregame {
+            tiles,
+            held,
+            open_edges,
+            ..
+        } = &self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        if !open_edges.is_empty() || held.is_some() {
+            return Err(RuleError::RiverIncomplete);
+        }
+        // the layout is laid down on a copy, so that the game is left as it was if it
+        // can't begin
+        let mut game = self.game.clone();
+        for (pos, tile) in tiles.iter() {
+            game.place_tile(tile.clone(), *pos)?;
+        }
+        let (tile, placeable_positions) = game
+            .draw_placeable_tile()
+            .ok_or(RuleError::NoPlaceableTiles)?;
+        self.game = game;
+        self.turn_phase = TurnPhase::TilePlacement {
+            tile,
+            placeable_positions,
+            preview_location: None,
+            bonus_turn: false,
+        };
+        Ok(vec![RuleEvent::GameBegan])
+    }
+
+    fn place_tile(&mut self, position: GridPos, rotation: usize) -> Result<Events, RuleError> {
+        let TurnPhase::TilePlacement {
+            tile,
+            placeable_positions,
+            bonus_turn,
+            ..
+        } = &self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        let bonus_turn = *bonus_turn;
+        if self.game.placed_tiles.contains_key(&position) {
+            return Err(RuleError::PositionOccupied);
+        }
+        let mut tile = tile.clone();
+        tile.rotate_to(rotation);
+        if !placeable_positions.contains(&position)
+            || !self.game.is_valid_tile_position(&tile, position)
+        {
+            return Err(RuleError::InvalidTilePlacement);
+        }
+
+        let is_volcano = tile.has_attribute(|a| matches!(a, SegmentAttribute::Volcano { .. }));
+        let closed_groups = self.game.place_tile(tile, position)?;
+        if is_volcano {
+            self.game.dragon = Some(position);
+        }
+        let player_ident = self.current_player();
+        let mut events = vec![RuleEvent::TilePlaced {
+            player: player_ident,
+            pose: TilePose { position, rotation },
+        }];
+
+        // a builder's extra turn can't earn another one
+        let earned_bonus_turn = !bonus_turn && self.game.extends_builder(position, player_ident);
+
+        self.turn_phase = TurnPhase::MeeplePlacement {
+            placed_position: position,
+            closed_groups: closed_groups.clone(),
+            earned_bonus_turn,
+        };
+        if !self.has_meeple_placement_options() {
+            events.extend(self.finish_meeple_placement(closed_groups, earned_bonus_turn));
+        }
+        Ok(events)
+    }
+
+    /// Whether the current player can do anything in the meeple placement phase besides
+    /// skipping it.
+    fn has_meeple_placement_options(&self) -> bool {
+        let TurnPhase::MeeplePlacement {
+            placed_position, ..
+        } = &self.turn_phase
+        else {
+            return false;
+        };
+        let player_ident = self.current_player();
+        let segments_at = |pos: GridPos| {
+            let segment_count = self.game.placed_tiles[&pos].segments.len();
+            (0..segment_count).map(move |seg_index| (pos, seg_index))
+        };
+        let placeable_positions: Vec<GridPos> = if self.placed_tile_has_portal() {
+            self.game.placed_tiles.keys().copied().collect()
+        } else {
+            vec![*placed_position]
+        };
+        let meeple_placeable =
+            placeable_positions
+                .into_iter()
+                .flat_map(segments_at)
+                .any(|seg_ident| {
+                    MeepleKind::ALL
+                        .iter()
+                        .any(|kind| self.validate_meeple_placement(seg_ident, *kind).is_ok())
+                });
+        let tower_buildable = self.game.players[player_ident].tower_pieces > 0
+            && self
+                .game
+                .placed_tiles
+                .keys()
+                .any(|pos| self.game.has_tower_foundation(*pos));
+        let figures_movable = self.game.placed_tiles.keys().any(|pos| {
+            segments_at(*pos).any(|seg_ident| {
+                self.validate_move_fairy(seg_ident).is_ok()
+                    || self.validate_dismiss_knight(seg_ident).is_ok()
+            })
+        });
+        meeple_placeable
+            || tower_buildable
+            || self.extended_flock().is_some()
+            || figures_movable
+            || self.game.abbot_group(player_ident).is_some()
+    }
+
+    fn placed_tile_has_portal(&self) -> bool {
+        let TurnPhase::MeeplePlacement {
+            placed_position, ..
+        } = &self.turn_phase
+        else {
+            return false;
+        };
+        self.game.placed_tiles[placed_position]
+            .has_attribute(|a| matches!(a, SegmentAttribute::Portal { .. }))
+    }
+
+    /// Checks whether the current player may place a meeple of the given kind on the given segment.
+    pub fn validate_meeple_placement(
+        &self,
+        seg_ident: SegmentIdentifier,
+        kind: MeepleKind,
+    ) -> Result<(), RuleError> {
+        let TurnPhase::MeeplePlacement {
+            placed_position, ..
+        } = &self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        let segment = self
+            .game
+            .segment_by_ident(seg_ident)
+            .ok_or(RuleError::InvalidSegment)?;
+        let (group, _) = self
+            .game
+            .group_and_key_by_seg_ident(seg_ident)
+            .ok_or(RuleError::InvalidSegment)?;
+        // a portal lets the meeple go to any group that isn't finished yet
+        if seg_ident.0 != *placed_position
+            && !(self.placed_tile_has_portal() && !self.game.is_group_complete(group))
+        {
+            return Err(RuleError::InvalidSegment);
+        }
+        if self.game.dragon == Some(seg_ident.0) {
+            return Err(RuleError::DragonPresent);
+        }
+        if !self.game.meeple_placeable(segment.stype) || !kind.placeable_on(segment.stype) {
+            return Err(RuleError::UnplaceableSegment);
+        }
+        kind.can_join(&group.meeples, self.current_player())?;
+        if self
+            .game
+            .players
+            .get(self.current_player())
+            .unwrap()
+            .supply(kind)
+            == 0
+        {
+            return Err(RuleError::NoMeeplesRemaining);
+        }
+        Ok(())
+    }
+
+    fn place_meeple(
+        &mut self,
+        seg_ident: SegmentIdentifier,
+        kind: MeepleKind,
+    ) -> Result<Events, RuleError> {
+        self.validate_meeple_placement(seg_ident, kind)?;
+        let TurnPhase::MeeplePlacement {
+            closed_groups,
+            earned_bonus_turn,
+            ..
+        } = &self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        let (closed_groups, earned_bonus_turn) = (closed_groups.clone(), *earned_bonus_turn);
+        let player_ident = self.current_player();
+        self.game
+            .place_meeple_of_kind(seg_ident, player_ident, kind)?;
+        let mut events = vec![RuleEvent::MeeplePlaced {
+            player: player_ident,
+            seg_ident,
+        }];
+        if kind == MeepleKind::Shepherd {
+            // a new flock never starts with a wolf
+            let flock = self.game.flocks.entry(seg_ident).or_default();
+            if let Some(token) = draw_sheep(&mut self.game.sheep_bag, &mut self.rng, false) {
+                flock.push(token);
+                events.push(RuleEvent::FlockExpanded {
+                    player: player_ident,
+                    token,
+                });
+            }
+        }
+        events.extend(self.finish_meeple_placement(closed_groups, earned_bonus_turn));
+        Ok(events)
+    }
+
+    /// The farm holding the current player's shepherd, if the tile they placed extends it.
+    pub fn extended_flock(&self) -> Option<(GroupIdentifier, SegmentIdentifier)> {
+        let TurnPhase::MeeplePlacement {
+            placed_position, ..
+        } = &self.turn_phase
+        else {
+            return None;
+        };
+        let segment_count = self.game.placed_tiles[placed_position].segments.len();
+        (0..segment_count).find_map(|seg_index| {
+            let (group, group_ident) = self
+                .game
+                .group_and_key_by_seg_ident((*placed_position, seg_index))?;
+            group
+                .meeples
+                .iter()
+                .find(|(_, owner, kind)| {
+                    *owner == self.current_player() && *kind == MeepleKind::Shepherd
+                })
+                .map(|(seg_ident, _, _)| (group_ident, *seg_ident))
+        })
+    }
+
+    /// Draws another token for the current player's flock in place of placing a meeple. A
+    /// wolf scatters every flock on the farm.
+    fn expand_flock(&mut self) -> Result<Events, RuleError> {
+        let (group_ident, shepherd) = self.extended_flock().ok_or(RuleError::NoFlockExtended)?;
+        let TurnPhase::MeeplePlacement {
+            closed_groups,
+            earned_bonus_turn,
+            ..
+        } = &self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        let (closed_groups, earned_bonus_turn) = (closed_groups.clone(), *earned_bonus_turn);
+        let token = draw_sheep(&mut self.game.sheep_bag, &mut self.rng, true)
+            .ok_or(RuleError::SheepBagEmpty)?;
+        match token {
+            SheepToken::Wolf => {
+                self.game.sheep_bag.push(token);
+                self.game.scatter_flocks(group_ident);
+            }
+            SheepToken::Sheep(_) => self.game.flocks.entry(shepherd).or_default().push(token),
+        }
+        let mut events = vec![RuleEvent::FlockExpanded {
+            player: self.current_player(),
+            token,
+        }];
+        events.extend(self.finish_meeple_placement(closed_groups, earned_bonus_turn));
+        Ok(events)
+    }
+
+    /// Herds in every flock on the current player's farm in place of placing a meeple.
+    fn herd_flock(&mut self) -> Result<Events, RuleError> {
+        let (group_ident, _) = self.extended_flock().ok_or(RuleError::NoFlockExtended)?;
+        let TurnPhase::MeeplePlacement {
+            closed_groups,
+            earned_bonus_turn,
+            ..
+        } = &self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        let (closed_groups, earned_bonus_turn) = (closed_groups.clone(), *earned_bonus_turn);
+        let results = self.game.herd_flocks(group_ident);
+        let mut events = vec![RuleEvent::FlockHerded { results }];
+        events.extend(self.finish_meeple_placement(closed_groups, earned_bonus_turn));
+        Ok(events)
+    }
+
+    fn skip_meeples(&mut self) -> Result<Events, RuleError> {
+        let TurnPhase::MeeplePlacement {
+            closed_groups,
+            earned_bonus_turn,
+            ..
+        } = &self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        let (closed_groups, earned_bonus_turn) = (closed_groups.clone(), *earned_bonus_turn);
+        let mut events = vec![RuleEvent::MeeplesSkipped {
+            player: self.current_player(),
+        }];
+        events.extend(self.finish_meeple_placement(closed_groups, earned_bonus_turn));
+        Ok(events)
+    }
+
+    /// Calls the current player's abbot back in place of placing a meeple this turn.
+    fn retrieve_abbot(&mut self) -> Result<Events, RuleError> {
+        let TurnPhase::MeeplePlacement {
+            closed_groups,
+            earned_bonus_turn,
+            ..
+        } = &self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        let (mut closed_groups, earned_bonus_turn) = (closed_groups.clone(), *earned_bonus_turn);
+        let player_ident = self.current_player();
+        let group_ident = self
+            .game
+            .abbot_group(player_ident)
+            .ok_or(RuleError::NoAbbotPlaced)?;
+        let results = self.game.retrieve_abbot(player_ident)?;
+        // the abbot's group has already been scored, even if this tile completed it
+        closed_groups.retain(|closed_group| *closed_group != group_ident);
+        let mut events = vec![
+            RuleEvent::AbbotRetrieved {
+                player: player_ident,
+            },
+            RuleEvent::GroupScored {
+                group: group_ident,
+                results,
+            },
+        ];
+        events.extend(self.finish_meeple_placement(closed_groups, earned_bonus_turn));
+        Ok(events)
+    }
+
+    /// Checks whether the current player may move the fairy to their meeple on the given segment.
+    pub fn validate_move_fairy(&self, seg_ident: SegmentIdentifier) -> Result<(), RuleError> {
+        if !self.fairy_in_play {
+            return Err(RuleError::Unsupported);
+        }
+        if !matches!(self.turn_phase, TurnPhase::MeeplePlacement { .. }) {
+            return Err(RuleError::WrongPhase);
+        }
+        let (group, _) = self
+            .game
+            .group_and_key_by_seg_ident(seg_ident)
+            .ok_or(RuleError::InvalidSegment)?;
+        if self.game.fairy == Some(seg_ident)
+            || !group.meeples.iter().any(|(meeple_seg_ident, owner, _)| {
+                *meeple_seg_ident == seg_ident && *owner == self.current_player()
+            })
+        {
+            return Err(RuleError::NotYourMeeple);
+        }
+        Ok(())
+    }
+
+    /// Moves the fairy to one of the current player's meeples in place of placing a meeple.
+    fn move_fairy(&mut self, seg_ident: SegmentIdentifier) -> Result<Events, RuleError> {
+        self.validate_move_fairy(seg_ident)?;
+        let TurnPhase::MeeplePlacement {
+            closed_groups,
+            earned_bonus_turn,
+            ..
+        } = &self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        let (closed_groups, earned_bonus_turn) = (closed_groups.clone(), *earned_bonus_turn);
+        self.game.fairy = Some(seg_ident);
+        let mut events = vec![RuleEvent::FairyMoved {
+            player: self.current_player(),
+            seg_ident,
+        }];
+        events.extend(self.finish_meeple_placement(closed_groups, earned_bonus_turn));
+        Ok(events)
+    }
+
+    /// Checks whether the princess on the placed tile can send away the knight on the given
+    /// segment, which has to be in the city she joined.
+    pub fn validate_dismiss_knight(&self, seg_ident: SegmentIdentifier) -> Result<(), RuleError> {
+        let TurnPhase::MeeplePlacement {
+            placed_position, ..
+        } = &self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        let tile = &self.game.placed_tiles[placed_position];
+        let (group, group_ident) = self
+            .game
+            .group_and_key_by_seg_ident(seg_ident)
+            .ok_or(RuleError::InvalidSegment)?;
+        let joins_princess = tile
+            .segments
+            .iter()
+            .enumerate()
+            .any(|(seg_index, segment)| {
+                segment
+                    .attributes
+                    .iter()
+                    .any(|a| matches!(a, SegmentAttribute::Princess { .. }))
+                    && self
+                        .game
+                        .group_and_key_by_seg_ident((*placed_position, seg_index))
+                        .is_some_and(|(_, princess_group)| princess_group == group_ident)
+            });
+        let is_knight = group.meeples.iter().any(|(meeple_seg_ident, _, kind)| {
+            *meeple_seg_ident == seg_ident && kind.strength() > 0
+        });
+        if !joins_princess || !is_knight {
+            return Err(RuleError::NoKnightToDismiss);
+        }
+        Ok(())
+    }
+
+    /// Has the princess send a knight home in place of placing a meeple.
+    fn dismiss_knight(&mut self, seg_ident: SegmentIdentifier) -> Result<Events, RuleError> {
+        self.validate_dismiss_knight(seg_ident)?;
+        let TurnPhase::MeeplePlacement {
+            closed_groups,
+            earned_bonus_turn,
+            ..
+        } = &self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        let (closed_groups, earned_bonus_turn) = (closed_groups.clone(), *earned_bonus_turn);
+        self.game.remove_meeple(seg_ident);
+        let mut events = vec![RuleEvent::KnightDismissed {
+            player: self.current_player(),
+            seg_ident,
+        }];
+        events.extend(self.finish_meeple_placement(closed_groups, earned_bonus_turn));
+        Ok(events)
+    }
+
+    /// Checks whether the current player may add a piece to the tower on the given tile, and
+    /// capture the meeple on the given segment with it.
+    pub fn validate_build_tower(
+        &self,
+        pos: GridPos,
+        capture: Option<SegmentIdentifier>,
+    ) -> Result<(), RuleError> {
+        if !matches!(self.turn_phase, TurnPhase::MeeplePlacement { .. }) {
+            return Err(RuleError::WrongPhase);
+        }
+        if self.game.players[self.current_player()].tower_pieces == 0 {
+            return Err(RuleError::NoTowerPieces);
+        }
+        if !self.game.has_tower_foundation(pos) {
+            return Err(RuleError::NoTowerFoundation);
+        }
+        if let Some(capture) = capture {
+            let height = self.game.towers.get(&pos).copied().unwrap_or(0) + 1;
+            if !self
+                .game
+                .tower_sight(pos, height)
+                .iter()
+                .any(|(seg_ident, _, _)| *seg_ident == capture)
+            {
+                return Err(RuleError::OutOfTowerSight);
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds a tower a piece higher in place of placing a meeple, capturing up to one meeple
+    /// in its sight.
+    fn build_tower(
+        &mut self,
+        pos: GridPos,
+        capture: Option<SegmentIdentifier>,
+    ) -> Result<Events, RuleError> {
+        self.validate_build_tower(pos, capture)?;
+        let TurnPhase::MeeplePlacement {
+            closed_groups,
+            earned_bonus_turn,
+            ..
+        } = &self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        let (closed_groups, earned_bonus_turn) = (closed_groups.clone(), *earned_bonus_turn);
+        let player_ident = self.current_player();
+        self.game.players[player_ident].tower_pieces -= 1;
+        *self.game.towers.entry(pos).or_default() += 1;
+        let mut events = vec![RuleEvent::TowerBuilt {
+            player: player_ident,
+            pos,
+        }];
+        if let Some(seg_ident) = capture {
+            self.game.capture_meeple(seg_ident, player_ident, pos);
+            events.push(RuleEvent::MeepleCaptured {
+                player: player_ident,
+                seg_ident,
+            });
+        }
+        events.extend(self.finish_meeple_placement(closed_groups, earned_bonus_turn));
+        Ok(events)
+    }
+
+    /// Checks whether the current player can buy back one of their meeples from the captor.
+    pub fn validate_ransom(&self, captor: PlayerIdentifier) -> Result<(), RuleError> {
+        if !matches!(
+            self.turn_phase,
+            TurnPhase::TilePlacement { .. } | TurnPhase::MeeplePlacement { .. }
+        ) {
+            return Err(RuleError::WrongPhase);
+        }
+        let player_ident = self.current_player();
+        if !self.game.players.get(captor).is_some_and(|captor| {
+            captor
+                .prisoners
+                .iter()
+                .any(|prisoner| prisoner.owner == player_ident)
+        }) {
+            return Err(RuleError::NoPrisonerHeld);
+        }
+        if self.game.players[player_ident].score < RANSOM_COST {
+            return Err(RuleError::NotEnoughPoints);
+        }
+        Ok(())
+    }
+
+    /// Pays another player to release one of the current player's captured meeples. This
+    /// doesn't use up the player's turn.
+    fn ransom(&mut self, captor: PlayerIdentifier) -> Result<Events, RuleError> {
+        self.validate_ransom(captor)?;
+        let player_ident = self.current_player();
+        self.game.ransom_prisoner(player_ident, captor, RANSOM_COST);
+        Ok(vec![RuleEvent::PrisonerRansomed {
+            player: player_ident,
+            captor,
+        }])
+    }
+
+    /// Sends the dragon on its flight if the placed tile summons it, and otherwise ends the turn.
+    fn finish_meeple_placement(
+        &mut self,
+        closed_groups: Vec<GroupIdentifier>,
+        earned_bonus_turn: bool,
+    ) -> Events {
+        let TurnPhase::MeeplePlacement {
+            placed_position, ..
+        } = &self.turn_phase
+        else {
+            return self.end_turn(closed_groups, earned_bonus_turn);
+        };
+        let summons_dragon = self.game.placed_tiles[placed_position]
+            .has_attribute(|a| matches!(a, SegmentAttribute::Dragon { .. }));
+        match self.game.dragon {
+            Some(dragon) if summons_dragon => {
+                self.turn_phase = TurnPhase::DragonMovement {
+                    visited: vec![dragon],
+                    moves_made: 0,
+                    closed_groups,
+                    earned_bonus_turn,
+                };
+                if self.dragon_moves().is_empty() {
+                    return self.end_dragon_movement();
+                }
+                Vec::new()
+            }
+            _ => self.end_turn(closed_groups, earned_bonus_turn),
+        }
+    }
+
+    /// Where the dragon can fly next: any adjacent tile it hasn't visited during this flight,
+    /// other than the one the fairy is on.
+    pub fn dragon_moves(&self) -> Vec<GridPos> {
+        let (TurnPhase::DragonMovement { visited, .. }, Some(dragon)) =
+            (&self.turn_phase, self.game.dragon)
+        else {
+            return Vec::new();
+        };
+        dragon
+            .adjacent()
+            .filter(|pos| {
+                self.game.placed_tiles.contains_key(pos)
+                    && !visited.contains(pos)
+                    && self.game.fairy.map(|(fairy_pos, _)| fairy_pos) != Some(*pos)
+            })
+            .collect()
+    }
+
+    fn move_dragon(&mut self, pos: GridPos) -> Result<Events, RuleError> {
+        if !matches!(self.turn_phase, TurnPhase::DragonMovement { .. }) {
+            return Err(RuleError::WrongPhase);
+        }
+        if !self.dragon_moves().contains(&pos) {
+            return Err(RuleError::InvalidDragonMove);
+        }
+        self.game.dragon = Some(pos);
+        let eaten = self.game.eat_meeples(pos);
+        let TurnPhase::DragonMovement {
+            visited,
+            moves_made,
+            ..
+        } = &mut self.turn_phase
+        else {
+            unreachable!();
+        };
+        visited.push(pos);
+        *moves_made += 1;
+        let flight_over = *moves_made == DRAGON_MOVES;
+        let mut events = vec![RuleEvent::DragonMoved { pos, eaten }];
+        if flight_over || self.dragon_moves().is_empty() {
+            events.extend(self.end_dragon_movement());
+        }
+        Ok(events)
+    }
+
+    fn end_dragon_movement(&mut self) -> Events {
+        let TurnPhase::DragonMovement {
+            closed_groups,
+            earned_bonus_turn,
+            ..
+        } = &self.turn_phase
+        else {
+            return Vec::new();
+        };
+        let (closed_groups, earned_bonus_turn) = (closed_groups.clone(), *earned_bonus_turn);
+        self.end_turn(closed_groups, earned_bonus_turn)
+    }
+
+    fn end_turn(&mut self, groups_to_close: Vec<GroupIdentifier>, bonus_turn: bool) -> Events {
+        let mut events = Vec::new();
+        let player_ident = *self.turn_order.front().unwrap();
+        for group_ident in groups_to_close {
+            use SegmentType::*;
+            let gtype = self.game.groups.get(group_ident).unwrap().gtype;
+            if gtype == City {
+                self.game.collect_goods(group_ident, player_ident);
+            }
+            match gtype {
+                City | Road | Monastary | Garden => {
+                    let results = self.game.score_group(group_ident);
+                    events.push(RuleEvent::GroupScored {
+                        group: group_ident,
+                        results,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        // a builder lets the same player go again
+        if !bonus_turn {
+            let player_ident = self.turn_order.pop_front().unwrap();
+            self.turn_order.push_back(player_ident);
+        }
+
+        // the fairy pays its owner a point at the start of each of their turns
+        let next_player = *self.turn_order.front().unwrap();
+        if !bonus_turn && self.game.fairy_owner() == Some(next_player) {
+            self.game.players.get_mut(next_player).unwrap().score += FAIRY_TURN_SCORE;
+            events.push(RuleEvent::FairyScored {
+                player: next_player,
+            });
+        }
+
+        match self.game.draw_placeable_tile() {
+            Some((tile, placeable_positions)) => {
+                self.turn_phase = TurnPhase::TilePlacement {
+                    tile,
+                    placeable_positions,
+                    preview_location: None,
+                    bonus_turn,
+                };
+                events.push(if bonus_turn {
+                    RuleEvent::BonusTurn {
+                        player: self.current_player(),
+                    }
+                } else {
+                    RuleEvent::TurnEnded {
+                        next_player: self.current_player(),
+                    }
+                });
+            }
+            None => {
+                self.turn_phase = TurnPhase::EndGame;
+                events.push(RuleEvent::GameEnded);
+            }
+        }
+
+        events
+    }
+
+    fn end_game_immediately(&mut self) -> Result<Events, RuleError> {
+        if self.is_endgame() {
+            return Err(RuleError::WrongPhase);
+        }
+        self.turn_phase = TurnPhase::EndGame;
+        Ok(vec![RuleEvent::GameEnded])
+    }
+
+    /// Scores one of the groups still holding meeples once the game has ended,
+    /// followed by the goods. Returns `None` when end-game scoring is complete.
+    pub fn score_next_endgame_group(&mut self) -> Option<RuleEvent> {
+        if !self.is_endgame() {
+            return None;
+        }
+        // flocks still out in the fields are herded in before the farms are scored
+        if let Some(shepherd) = self.game.flocks.keys().next().copied() {
+            let group_ident = self.game.group_associations[&shepherd];
+            let results = self.game.herd_flocks(group_ident);
+            self.game.flocks.remove(&shepherd);
+            return Some(RuleEvent::FlockHerded { results });
+        }
+        if let Some((group_ident, _)) = self
+            .game
+            .groups
+            .iter()
+            .find(|(_, group)| !group.meeples.is_empty())
+        {
+            return Some(RuleEvent::GroupScored {
+                group: group_ident,
+                results: self.game.score_group(group_ident),
+            });
+        }
+        if self.goods_scored {
+            return None;
+        }
+        self.goods_scored = true;
+        let winners = self.game.score_goods();
+        (!winners.is_empty()).then_some(RuleEvent::GoodsScored { winners })
+    }
+
+    /// Performs all remaining end-game scoring at once.
+    pub fn score_endgame(&mut self) -> Events {
+        let mut events = Vec::new();
+        while let Some(event) = self.score_next_endgame_group() {
+            events.push(event);
+        }
+        events
+    }
+
+    fn pregame_place_tile(&mut self, pos: GridPos, rotation: usize) -> Result<Events, RuleError> {
+        let TurnPhase::Pregame {
+            tiles,
+            held,
+            open_edges,
+            ..
+        } = &mut self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        if tiles.contains_key(&pos) {
+            return Err(RuleError::PositionOccupied);
+        }
+        let mut tile = held.take().ok_or(RuleError::NoTileHeld)?;
+        tile.rotate_to(rotation);
+        tiles.insert(pos, tile);
+        *open_edges = recalculate_open_edges(tiles);
+        Ok(Vec::new())
+    }
+
+    fn pregame_pickup_tile(&mut self, pos: GridPos) -> Result<Events, RuleError> {
+        let TurnPhase::Pregame {
+            tiles,
+            held,
+            open_edges,
+            ..
+        } = &mut self.turn_phase
+        else {
+            return Err(RuleError::WrongPhase);
+        };
+        if held.is_some() {
+            return Err(RuleError::TileAlreadyHeld);
+        }
+        let tile = tiles.remove(&pos).ok_or(RuleError::NoTileAtPosition)?;
+        *held = Some(tile);
+        *open_edges = recalculate_open_edges(tiles);
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ggez::graphics::Color;
+
+    use crate::{
+        game::{
+            player::{Player, PlayerType},
+            setup::{GameExpansions, LibraryConfiguration},
+            Game, MeepleKind, ScoringRules, SheepToken,
+        },
+        multiplayer::message::{GameMessage, TilePose},
+        pos::GridPos,
+        tile::{
+            tile_definitions::{
+                princess_and_dragon::DRAGON_STRAIGHT_ROAD, rivers_1::RIVER_CORNER,
+                tower::TOWER_STRAIGHT_ROAD, STARTING_TILE, STRAIGHT_ROAD,
+            },
+            Orientation, Tile,
+        },
+    };
+
+    use super::{
+        recalculate_open_edges, GameState, HashMap, RuleError, RuleEvent, RuleRng, TurnPhase,
+        RANSOM_COST,
+    };
+
+    #[test]
+    fn test_headless_game_to_completion() {
+        let mut state = GameState::new(
+            0,
+            vec![Player::new(Color::RED), Player::new(Color::BLUE)],
+            &GameExpansions::default(),
+            &LibraryConfiguration::default(),
+            &ScoringRules::default(),
+            PlayerType::Local,
+        );
+        while !state.is_endgame() {
+            let message = match &state.turn_phase {
+                TurnPhase::TilePlacement {
+                    tile,
+                    placeable_positions,
+                    ..
+                } => placeable_positions
+                    .iter()
+                    .find_map(|position| {
+                        (0..4).find_map(|rotation| {
+                            let mut tile = tile.clone();
+                            tile.rotate_to(rotation);
+                            state
+                                .game
+                                .is_valid_tile_position(&tile, *position)
+                                .then_some(TilePose {
+                                    position: *position,
+                                    rotation,
+                                })
+                        })
+                    })
+                    .map(GameMessage::PlaceTile)
+                    .unwrap(),
+                TurnPhase::MeeplePlacement { .. } => GameMessage::SkipMeeples,
+                _ => panic!("unexpected turn phase"),
+            };
+            state.apply(message).unwrap();
+        }
+        state.score_endgame();
+        assert!(state.game.placed_tiles.len() > 1);
+        assert_eq!(
+            state.apply(GameMessage::SkipMeeples).unwrap_err(),
+            RuleError::WrongPhase
+        );
+    }
+
+    #[test]
+    fn test_builder_bonus_turn() {
+        let mut game = Game::new_with_library(vec![STRAIGHT_ROAD.clone(); 3]);
+        let red = game.players.insert(Player::new(Color::RED));
+        let blue = game.players.insert(Player::new(Color::BLUE));
+        game.players[red].builders = 1;
+        game.place_tile(STARTING_TILE.clone(), GridPos(0, 0))
+            .unwrap();
+        game.place_meeple((GridPos(0, 0), 1), red).unwrap();
+        game.place_meeple_of_kind((GridPos(0, 0), 1), red, MeepleKind::Builder)
+            .unwrap();
+        let mut state = GameState::from_game(game, 0, &GameExpansions::default()).unwrap();
+        assert_eq!(state.current_player(), red);
+
+        let mut take_turn = |position| {
+            state
+                .apply(GameMessage::PlaceTile(TilePose {
+                    position,
+                    rotation: 0,
+                }))
+                .unwrap();
+            let events = state.apply(GameMessage::SkipMeeples).unwrap();
+            (events, state.current_player())
+        };
+
+        // extending the builder's road lets red go again, but only once
+        let (events, player) = take_turn(GridPos(1, 0));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, RuleEvent::BonusTurn { .. })));
+        assert_eq!(player, red);
+        let (_, player) = take_turn(GridPos(-1, 0));
+        assert_eq!(player, blue);
+    }
+
+    #[test]
+    fn test_river_turning_back() {
+        let bend = RIVER_CORNER.clone();
+        let u_turn: HashMap<_, _> = [
+            (GridPos(0, 0), bend.clone()),
+            (GridPos(0, 1), bend.clone().rotated()),
+        ]
+        .into();
+        let open_edges = recalculate_open_edges(&u_turn);
+        assert!(open_edges.contains(&(GridPos(0, 0), Orientation::South)));
+        assert!(open_edges.contains(&(GridPos(0, 1), Orientation::North)));
+
+        let zigzag: HashMap<_, _> = [
+            (GridPos(0, 0), bend.clone()),
+            (GridPos(0, 1), bend.rotated().rotated()),
+        ]
+        .into();
+        let open_edges = recalculate_open_edges(&zigzag);
+        assert!(!open_edges.contains(&(GridPos(0, 0), Orientation::South)));
+        assert!(!open_edges.contains(&(GridPos(0, 1), Orientation::North)));
+    }
+
+    #[test]
+    fn test_sequential_river() {
+        let mut state = GameState::new(
+            0,
+            vec![Player::new(Color::RED), Player::new(Color::BLUE)],
+            &GameExpansions {
+                rivers_1: true,
+                sequential_river: true,
+                ..Default::default()
+            },
+            &LibraryConfiguration::default(),
+            &ScoringRules::default(),
+            PlayerType::Local,
+        );
+        let river_length = GameExpansions {
+            rivers_1: true,
+            ..Default::default()
+        }
+        .rivers()
+        .unwrap()
+        .len();
+        assert_eq!(state.game.placed_tiles.len(), 1);
+        for _ in 1..river_length {
+            let TurnPhase::TilePlacement {
+                tile,
+                placeable_positions,
+                ..
+            } = &state.turn_phase
+            else {
+                panic!("unexpected turn phase");
+            };
+            assert!(!tile.river_edges().is_empty());
+            let pose = placeable_positions
+                .iter()
+                .find_map(|position| {
+                    (0..4).find_map(|rotation| {
+                        let mut tile = tile.clone();
+                        tile.rotate_to(rotation);
+                        state
+                            .game
+                            .is_valid_tile_position(&tile, *position)
+                            .then_some(TilePose {
+                                position: *position,
+                                rotation,
+                            })
+                    })
+                })
+                .unwrap();
+            state.apply(GameMessage::PlaceTile(pose)).unwrap();
+            if matches!(state.turn_phase, TurnPhase::MeeplePlacement { .. }) {
+                state.apply(GameMessage::SkipMeeples).unwrap();
+            }
+        }
+        assert!(state.held_tile().unwrap().river_edges().is_empty());
+    }
+
+    #[test]
+    fn test_custom_library() {
+        let mut library = LibraryConfiguration {
+            counts: vec![0; Tile::default_library_tallies().len()],
+            decks: 2,
+            generated_tiles: 0,
+        };
+        let straight_road = Tile::default_library_tallies()
+            .iter()
+            .position(|(tile, _)| std::ptr::eq(*tile, &*STRAIGHT_ROAD))
+            .unwrap();
+        library.counts[straight_road] = 2;
+        let state = GameState::new(
+            0,
+            vec![Player::new(Color::RED), Player::new(Color::BLUE)],
+            &GameExpansions::default(),
+            &library,
+            &ScoringRules::default(),
+            PlayerType::Local,
+        );
+        assert_eq!(state.game.library.len(), 3);
+    }
+
+    #[test]
+    fn test_deal_is_stable() {
+        use rand::seq::SliceRandom;
+
+        // every multiplayer client deals the deck from the same seed, so a given seed has to
+        // shuffle the same way on every build
+        let mut order: Vec<usize> = (0..10).collect();
+        order.shuffle(&mut RuleRng::new(7));
+        assert_eq!(order, [6, 2, 7, 3, 4, 5, 9, 1, 8, 0]);
+    }
+
+    #[test]
+    fn test_dragon_flight() {
+        let mut game =
+            Game::new_with_library(vec![STRAIGHT_ROAD.clone(), DRAGON_STRAIGHT_ROAD.clone()]);
+        let red = game.players.insert(Player::new(Color::RED));
+        let blue = game.players.insert(Player::new(Color::BLUE));
+        game.place_tile(STARTING_TILE.clone(), GridPos(0, 0))
+            .unwrap();
+        game.place_tile(STRAIGHT_ROAD.clone(), GridPos(-1, 0))
+            .unwrap();
+        game.place_meeple((GridPos(0, 0), 1), blue).unwrap();
+        game.dragon = Some(GridPos(-1, 0));
+        let blue_meeples = game.players[blue].meeples;
+        let mut state = GameState::from_game(game, 0, &GameExpansions::default()).unwrap();
+
+        state
+            .apply(GameMessage::PlaceTile(TilePose {
+                position: GridPos(1, 0),
+                rotation: 0,
+            }))
+            .unwrap();
+        state.apply(GameMessage::SkipMeeples).unwrap();
+        assert!(matches!(state.turn_phase, TurnPhase::DragonMovement { .. }));
+        assert_eq!(state.current_player(), red);
+        assert_eq!(
+            state
+                .apply(GameMessage::MoveDragon(GridPos(-2, 0)))
+                .unwrap_err(),
+            RuleError::InvalidDragonMove
+        );
+
+        // the dragon eats blue's meeple, then blue moves it on to the last unvisited tile
+        let events = state.apply(GameMessage::MoveDragon(GridPos(0, 0))).unwrap();
+        assert!(matches!(
+            events[..],
+            [RuleEvent::DragonMoved { eaten: 1, .. }]
+        ));
+        assert_eq!(state.game.players[blue].meeples, blue_meeples + 1);
+        assert_eq!(state.current_player(), blue);
+        state.apply(GameMessage::MoveDragon(GridPos(1, 0))).unwrap();
+        assert!(matches!(state.turn_phase, TurnPhase::TilePlacement { .. }));
+        assert_eq!(state.game.dragon, Some(GridPos(1, 0)));
+        assert_eq!(state.current_player(), blue);
+    }
+
+    #[test]
+    fn test_tower_capture_and_ransom() {
+        let mut game =
+            Game::new_with_library(vec![STRAIGHT_ROAD.clone(), TOWER_STRAIGHT_ROAD.clone()]);
+        let red = game.players.insert(Player::new(Color::RED));
+        let blue = game.players.insert(Player::new(Color::BLUE));
+        game.players[red].tower_pieces = 1;
+        game.place_tile(STARTING_TILE.clone(), GridPos(0, 0))
+            .unwrap();
+        game.place_meeple((GridPos(0, 0), 1), blue).unwrap();
+        let blue_meeples = game.players[blue].meeples;
+        let mut state = GameState::from_game(game, 0, &GameExpansions::default()).unwrap();
+
+        state
+            .apply(GameMessage::PlaceTile(TilePose {
+                position: GridPos(1, 0),
+                rotation: 0,
+            }))
+            .unwrap();
+        assert_eq!(
+            state
+                .apply(GameMessage::BuildTower {
+                    pos: GridPos(1, 0),
+                    capture: Some((GridPos(3, 0), 0)),
+                })
+                .unwrap_err(),
+            RuleError::OutOfTowerSight
+        );
+        state
+            .apply(GameMessage::BuildTower {
+                pos: GridPos(1, 0),
+                capture: Some((GridPos(0, 0), 1)),
+            })
+            .unwrap();
+        assert_eq!(state.game.towers[&GridPos(1, 0)], 1);
+        assert_eq!(state.game.players[red].prisoners.len(), 1);
+        assert_eq!(state.game.players[blue].meeples, blue_meeples);
+
+        // blue can only buy their meeple back once they can afford it
+        assert_eq!(state.current_player(), blue);
+        assert_eq!(
+            state
+                .apply(GameMessage::Ransom { captor: red })
+                .unwrap_err(),
+            RuleError::NotEnoughPoints
+        );
+        state.game.players[blue].score = RANSOM_COST;
+        state.apply(GameMessage::Ransom { captor: red }).unwrap();
+        assert!(state.game.players[red].prisoners.is_empty());
+        assert_eq!(state.game.players[red].score, RANSOM_COST);
+        assert_eq!(state.game.players[blue].meeples, blue_meeples + 1);
+    }
+
+    #[test]
+    fn test_failed_begin_keeps_pregame() {
+        let mut game = Game::new_with_library(vec![STRAIGHT_ROAD.clone()]);
+        game.players.insert(Player::new(Color::RED));
+        game.place_tile(STARTING_TILE.clone(), GridPos(0, 0))
+            .unwrap();
+        // drawing the first tile empties the library
+        let mut state = GameState::from_game(game, 0, &GameExpansions::default()).unwrap();
+        state.turn_phase = TurnPhase::Pregame {
+            tiles: HashMap::from([(GridPos(1, 0), STRAIGHT_ROAD.clone())]),
+            held: None,
+            preview_location: None,
+            open_edges: Vec::new(),
+        };
+
+        assert_eq!(
+            state.apply(GameMessage::BeginGame).unwrap_err(),
+            RuleError::NoPlaceableTiles
+        );
+        assert!(!state.game.placed_tiles.contains_key(&GridPos(1, 0)));
+        let TurnPhase::Pregame { tiles, .. } = &state.turn_phase else {
+            panic!("expected the game to still be in pregame");
+        };
+        assert_eq!(tiles.len(), 1);
+    }
+
+    #[test]
+    fn test_illegal_moves_leave_game_untouched() {
+        let mut game = Game::new_with_library(vec![STRAIGHT_ROAD.clone(); 2]);
+        let red = game.players.insert(Player::new(Color::RED));
+        let blue = game.players.insert(Player::new(Color::BLUE));
+        game.place_tile(STARTING_TILE.clone(), GridPos(0, 0))
+            .unwrap();
+        game.place_meeple((GridPos(0, 0), 1), blue).unwrap();
+        let mut state = GameState::from_game(game, 0, &GameExpansions::default()).unwrap();
+        let red_meeples = state.game.players[red].meeples;
+
+        let place_tile = GameMessage::PlaceTile(TilePose {
+            position: GridPos(1, 0),
+            rotation: 0,
+        });
+        assert_eq!(
+            state.apply(GameMessage::SkipMeeples).unwrap_err(),
+            RuleError::WrongPhase
+        );
+        assert!(matches!(state.turn_phase, TurnPhase::TilePlacement { .. }));
+        state.apply(place_tile).unwrap();
+
+        // blue's meeple already holds the road being extended
+        assert_eq!(
+            state
+                .apply(GameMessage::PlaceMeeple {
+                    seg_ident: (GridPos(1, 0), 1),
+                    kind: MeepleKind::Normal,
+                })
+                .unwrap_err(),
+            RuleError::GroupOccupied
+        );
+        assert!(matches!(
+            state.turn_phase,
+            TurnPhase::MeeplePlacement { .. }
+        ));
+        assert_eq!(state.game.players[red].meeples, red_meeples);
+        let road = state.game.group_associations[&(GridPos(1, 0), 1)];
+        assert_eq!(state.game.groups[road].meeples.len(), 1);
+    }
+
+    #[test]
+    fn test_shepherd_flock() {
+        let mut game = Game::new_with_library(vec![STRAIGHT_ROAD.clone(); 4]);
+        let red = game.players.insert(Player::new(Color::RED));
+        game.players.insert(Player::new(Color::BLUE));
+        game.players[red].shepherds = 1;
+        game.place_tile(STARTING_TILE.clone(), GridPos(0, 0))
+            .unwrap();
+        let mut state = GameState::from_game(game, 0, &GameExpansions::default()).unwrap();
+        state.game.sheep_bag = vec![SheepToken::Sheep(3), SheepToken::Wolf];
+
+        // a shepherd's first draw is never a wolf
+        state
+            .apply(GameMessage::PlaceTile(TilePose {
+                position: GridPos(1, 0),
+                rotation: 0,
+            }))
+            .unwrap();
+        state
+            .apply(GameMessage::PlaceMeeple {
+                seg_ident: (GridPos(1, 0), 2),
+                kind: MeepleKind::Shepherd,
+            })
+            .unwrap();
+        assert_eq!(
+            state.game.flocks[&(GridPos(1, 0), 2)],
+            vec![SheepToken::Sheep(3)]
+        );
+
+        state
+            .apply(GameMessage::PlaceTile(TilePose {
+                position: GridPos(-1, 0),
+                rotation: 0,
+            }))
+            .unwrap();
+        state.apply(GameMessage::SkipMeeples).unwrap();
+
+        // extending the farm again draws the wolf, which scatters the flock
+        state
+            .apply(GameMessage::PlaceTile(TilePose {
+                position: GridPos(2, 0),
+                rotation: 0,
+            }))
+            .unwrap();
+        state.apply(GameMessage::ExpandFlock).unwrap();
+        assert_eq!(state.game.players[red].shepherds, 1);
+        assert!(state.game.flocks.is_empty());
+        assert_eq!(state.game.sheep_bag.len(), 2);
+    }
+}
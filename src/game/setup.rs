@@ -0,0 +1,229 @@
+use serde::{Deserialize, Serialize};
+
+use crate::tile::{
+    procedural,
+    tile_definitions::{
+        rivers_1::{
+            CITY_RIVER_CROSSING, CORNER_CITY_RIVER, CORNER_ROAD_CORNER_RIVER,
+            CORNER_ROAD_WATERFALL, CURVY_STRAIGHT_RIVER, MONASTARY_POND, RIVER_CORNER,
+            RIVER_CROSSING, RIVER_MONASTARY_BRIDGE, STRAIGHT_RIVER, STRAIGHT_RIVER_DUELING_CITIES,
+        },
+        rivers_2::{
+            RIVER_CITY_LAKE, RIVER_EDGE_CITY, RIVER_FORK, RIVER_INN_CROSSING, RIVER_LAKE,
+            RIVER_SPRING,
+        },
+    },
+    tileset::Tileset,
+    Tile,
+};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GameExpansions {
+    pub rivers_1: bool,
+    pub rivers_2: bool,
+    /// Lay the river out a tile per turn from its source, rather than arranging it all
+    /// before the game.
+    pub sequential_river: bool,
+    pub inns_and_cathedrals: bool,
+    pub traders_and_builders: bool,
+    pub abbots_and_gardens: bool,
+    pub princess_and_dragon: bool,
+    pub tower: bool,
+    pub hills_and_sheep: bool,
+    /// Custom tilesets loaded from the tilesets directory, carried along in full so that
+    /// joining players don't need a copy of their own.
+    #[serde(default)]
+    pub tilesets: Vec<Tileset>,
+}
+
+impl GameExpansions {
+    /// Names of the selected expansions and tilesets.
+    pub fn names(&self) -> Vec<String> {
+        [
+            (self.rivers_1, "River Expansion 1"),
+            (self.rivers_2, "River Expansion 2"),
+            (self.inns_and_cathedrals, "Inns & Cathedrals"),
+            (self.traders_and_builders, "Traders & Builders"),
+            (self.abbots_and_gardens, "Abbots & Gardens"),
+            (self.princess_and_dragon, "Princess & Dragon"),
+            (self.tower, "The Tower"),
+            (self.hills_and_sheep, "Hills & Sheep"),
+        ]
+        .into_iter()
+        .filter_map(|(selected, name)| selected.then(|| name.to_string()))
+        .chain(
+            self.tilesets
+                .iter()
+                .map(|tileset| tileset.expansion.clone()),
+        )
+        .collect()
+    }
+
+    /// Checks that the tiles of every tileset can be built, since tilesets sent by the
+    /// host haven't been through [`Tileset::load`] here.
+    pub fn check(&self) -> Result<(), String> {
+        self.tilesets.iter().try_for_each(Tileset::check)
+    }
+
+    /// Tiles added to the main library by the selected expansions.
+    pub fn library_tiles(&self) -> Vec<Tile> {
+        let mut tallies = Vec::new();
+        if self.inns_and_cathedrals {
+            tallies.extend(Tile::inns_and_cathedrals_library_tallies());
+        }
+        if self.traders_and_builders {
+            tallies.extend(Tile::traders_and_builders_library_tallies());
+        }
+        if self.abbots_and_gardens {
+            tallies.extend(Tile::abbots_and_gardens_library_tallies());
+        }
+        if self.princess_and_dragon {
+            tallies.extend(Tile::princess_and_dragon_library_tallies());
+        }
+        if self.tower {
+            tallies.extend(Tile::tower_library_tallies());
+        }
+        if self.hills_and_sheep {
+            tallies.extend(Tile::hills_and_sheep_library_tallies());
+        }
+        tallies
+            .into_iter()
+            .flat_map(|(tile, count)| (0..count).map(|_| tile.clone()))
+            .chain(self.tilesets.iter().flat_map(Tileset::library))
+            .collect()
+    }
+
+    /// The river tiles of the selected river expansions, with the river's source first.
+    /// When both are combined, the river flows from River II's spring, and River I's
+    /// waterfall ends one branch of the fork in place of River II's plain lake.
+    pub fn rivers(&self) -> Option<Vec<Tile>> {
+        let mut rivers = Vec::new();
+        if self.rivers_2 {
+            rivers.extend([
+                RIVER_SPRING.clone(),
+                RIVER_FORK.clone(),
+                RIVER_INN_CROSSING.clone(),
+                RIVER_EDGE_CITY.clone(),
+                RIVER_EDGE_CITY.clone().rotated().rotated(),
+                RIVER_CITY_LAKE.clone(),
+            ]);
+            if !self.rivers_1 {
+                rivers.push(RIVER_LAKE.clone());
+            }
+        }
+        if self.rivers_1 {
+            if !self.rivers_2 {
+                rivers.push(MONASTARY_POND.clone());
+            }
+            rivers.extend([
+                RIVER_CROSSING.clone(),
+                RIVER_CORNER.clone(),
+                CORNER_CITY_RIVER.clone(),
+                RIVER_MONASTARY_BRIDGE.clone(),
+                CURVY_STRAIGHT_RIVER.clone(),
+                CORNER_ROAD_CORNER_RIVER.clone(),
+                STRAIGHT_RIVER.clone(),
+                RIVER_CORNER.clone().rotated(),
+                STRAIGHT_RIVER_DUELING_CITIES.clone(),
+                CITY_RIVER_CROSSING.clone(),
+                CORNER_ROAD_WATERFALL.clone(),
+            ]);
+        }
+        (!rivers.is_empty()).then_some(rivers)
+    }
+}
+
+/// How many of each base game tile go into the library, and how many times over the
+/// whole deck is used.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LibraryConfiguration {
+    /// Copies of each tile, in the order of [`Tile::default_library_tallies`]. Checked by
+    /// [`LibraryConfiguration::check`] wherever a configuration is read back in.
+    pub counts: Vec<usize>,
+    /// Copies of the whole deck, expansion tiles included, for longer games.
+    pub decks: usize,
+    /// Randomly generated tiles to add to the deck, built from the game's seed.
+    #[serde(default)]
+    pub generated_tiles: usize,
+}
+
+impl Default for LibraryConfiguration {
+    fn default() -> Self {
+        LibraryConfiguration {
+            counts: Tile::default_library_tallies()
+                .into_iter()
+                .map(|(_, count)| count)
+                .collect(),
+            decks: 1,
+            generated_tiles: 0,
+        }
+    }
+}
+
+impl LibraryConfiguration {
+    /// Makes sure there's a count for each base game tile. A preset or replay saved while
+    /// the base game had a different set of tiles would otherwise give its counts to the
+    /// wrong tiles.
+    pub fn check(&self) -> Result<(), String> {
+        let tiles = Tile::default_library_tallies().len();
+        if self.counts.len() == tiles {
+            Ok(())
+        } else {
+            Err(format!(
+                "the library has counts for {} base game tiles rather than {tiles}",
+                self.counts.len()
+            ))
+        }
+    }
+
+    /// The base game tiles in the library, before the deck is copied.
+    pub fn base_tiles(&self) -> Vec<Tile> {
+        debug_assert_eq!(self.check(), Ok(()));
+        Tile::default_library_tallies()
+            .into_iter()
+            .zip(self.counts.iter())
+            .flat_map(|((tile, _), count)| (0..*count).map(|_| tile.clone()))
+            .collect()
+    }
+
+    /// The full library for a game with the given expansions and seed, excluding the river.
+    pub fn library(&self, expansions: &GameExpansions, seed: u64) -> Vec<Tile> {
+        let mut deck = self.base_tiles();
+        deck.extend(expansions.library_tiles());
+        deck.extend(procedural::generate_library(seed, self.generated_tiles));
+        (0..self.decks.max(1)).flat_map(|_| deck.clone()).collect()
+    }
+}
+
+/// A library configuration saved under a name so it can be picked again later.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LibraryPreset {
+    pub name: String,
+    pub library: LibraryConfiguration,
+}
+
+impl LibraryPreset {
+    pub fn builtin() -> Vec<LibraryPreset> {
+        vec![
+            LibraryPreset {
+                name: "Standard".to_string(),
+                library: LibraryConfiguration::default(),
+            },
+            LibraryPreset {
+                name: "Long Game".to_string(),
+                library: LibraryConfiguration {
+                    decks: 2,
+                    ..Default::default()
+                },
+            },
+            LibraryPreset {
+                name: "Procedural".to_string(),
+                library: LibraryConfiguration {
+                    counts: vec![0; Tile::default_library_tallies().len()],
+                    decks: 1,
+                    generated_tiles: Tile::default_library().len(),
+                },
+            },
+        ]
+    }
+}
@@ -1,55 +1,54 @@
 use std::cell::RefCell;
-use std::collections::{HashMap, VecDeque};
-use std::fs::{create_dir_all, File};
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::time::SystemTime;
 
+use crate::board_export::BoardImage;
 use crate::colors::PANEL_COLOR;
+use crate::game::bot::BotController;
 use crate::game::player::{ConnectionState, PlayerType};
-use crate::game::ShapeDetails;
+use crate::game::rules::{Events, GameState, RuleError, RuleEvent, TurnPhase, RANSOM_COST};
 use crate::game::{
     player::Player, Game, GroupIdentifier, PlayerIdentifier, ScoringResult, SegmentIdentifier,
+    SheepToken,
 };
+use crate::game::{MeepleKind, ScoringRules, ShapeDetails};
 use crate::main_client::MainEvent;
-use crate::multiplayer::message::server::User;
+use crate::multiplayer::message::server::{RewindVote, ServerMessage, UndoPolicy, User};
 use crate::multiplayer::message::{GameMessage, TilePose};
 use crate::pos::GridPos;
+use crate::replay::{load_game, Replay};
+use crate::save::SaveFile;
 use crate::shared::Keybinds;
-use crate::tile::tile_definitions::rivers_1::{
-    CITY_RIVER_CROSSING, CORNER_CITY_RIVER, CORNER_ROAD_CORNER_RIVER, CORNER_ROAD_WATERFALL,
-    CURVY_STRAIGHT_RIVER, MONASTARY_POND, RIVER_CORNER, RIVER_CROSSING, RIVER_MONASTARY_BRIDGE,
-    STRAIGHT_RIVER, STRAIGHT_RIVER_DUELING_CITIES,
-};
-use crate::tile::{tile_definitions::STARTING_TILE, Tile};
-use crate::tile::{Orientation, SegmentType};
-use crate::{game_client, Shared};
-use ggez_no_re::checker_spiral::checker_spiral;
+use crate::tile::Tile;
+use crate::tile::{Orientation, SegmentAttribute, TradeGood};
+use crate::Shared;
 use ggez_no_re::line::LineExt;
 use ggez_no_re::sub_event_handler::SubEventHandler;
 use ggez_no_re::ui_manager::set_cursor_icon;
 use ggez_no_re::ui_manager::{button::Button, Bounds, UIElement, UIElementState, UIManager};
 use ggez_no_re::util::{
     point_in_polygon, refit_to_rect, AnchorPoint, ContextExt, DrawableWihParamsExt, MinByF32Key,
-    RectExt, ResultExt, ResultExtToGameError, SystemTimeExt, TextExt,
+    RectExt, ResultExt, SystemTimeExt, TextExt,
 };
 
-use ggez::input::mouse::CursorIcon;
+use ggez::input::mouse::{CursorIcon, MouseButton};
 use ggez::{
     glam::{vec2, Vec2, Vec2Swizzles},
     graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text},
     Context, GameError, GameResult,
 };
-use log::{debug, trace};
+use history::{color_name, describe, describe_rewind, History};
+use log::{debug, info, trace, warn};
 use pause_screen_subclient::PauseScreenSubclient;
-use rand::rngs::StdRng;
-use rand::seq::SliceRandom;
-use rand::SeedableRng;
-use serde::{Deserialize, Serialize};
 
+mod history;
 mod pause_screen_subclient;
 
+pub use crate::game::setup::{GameExpansions, LibraryConfiguration, LibraryPreset};
+pub use history::HistoryRecord;
+
 const ZOOM_SPEED: f32 = 1.1;
 const MOVE_SPEED: f32 = 45.0;
 const MOVE_ACCEL: f32 = 0.1;
@@ -65,80 +64,81 @@ const SCORE_EFFECT_DECCEL: f32 = 15.0;
 const END_GAME_SCORE_DELAY: f32 = 3.0;
 const END_GAME_SCORE_INTERVAL: f32 = 1.75;
 
-const MEEPLE_SIZE: f32 = 0.001;
+const HISTORY_PANEL_WIDTH: f32 = 440.0;
+const HISTORY_PANEL_ROWS: usize = 16;
+const HISTORY_ROW_HEIGHT: f32 = 22.0;
+
+const NOTICE_DURATION: f32 = 4.0;
+
+pub const MEEPLE_SIZE: f32 = 0.001;
+
+impl From<RuleError> for GameError {
+    fn from(err: RuleError) -> Self {
+        GameError::CustomError(err.to_string())
+    }
+}
+
+/// The outline of a meeple's body, in a unit square, drawn around `MEEPLE_CENTER`.
+pub const MEEPLE_POINTS: [Vec2; 13] = [
+    vec2(0.025, 1.0),
+    vec2(0.425, 1.0),
+    vec2(0.5, 0.85),
+    vec2(0.575, 1.0),
+    vec2(0.975, 1.0),
+    vec2(0.75, 0.575),
+    vec2(1.0, 0.475),
+    vec2(1.0, 0.35),
+    vec2(0.675, 0.3),
+    vec2(0.325, 0.3),
+    vec2(0.0, 0.35),
+    vec2(0.0, 0.475),
+    vec2(0.25, 0.575),
+];
+pub const MEEPLE_CENTER: Vec2 = vec2(0.5, 0.6);
+pub const MEEPLE_HEAD_POINT: Vec2 = vec2(0.5, 0.3);
+pub const MEEPLE_HEAD_RADIUS: f32 = 0.175;
 
-pub const NUM_PLAYERS: usize = 5;
+pub const NUM_PLAYERS: usize = 6;
 pub const PLAYER_COLORS: [Color; NUM_PLAYERS] = [
     Color::RED,
     Color::YELLOW,
     Color::BLUE,
     Color::GREEN,
     Color::BLACK,
+    Color::new(0.5, 0.5, 0.5, 1.0),
 ];
 
-fn recalculate_open_edges(tiles: &HashMap<GridPos, Tile>) -> Vec<(GridPos, Orientation)> {
-    tiles
-        .keys()
-        .cloned()
-        .flat_map(|pos| {
-            Orientation::iter_with_offsets().flat_map(move |(orientation, offset)| {
-                let tile = &tiles[&pos];
-                let middle_segment_id = tile.mounts.by_orientation(orientation)[1];
-                let is_river = tile.segments[middle_segment_id].stype == SegmentType::River;
-                if let Some(opposing_tile) = tiles.get(&(pos + offset)) {
-                    if tile.validate_mounting(opposing_tile, orientation).is_none() {
-                        return Some((pos, orientation));
-                    }
-                } else if is_river {
-                    return Some((pos, orientation));
-                }
-                None
-            })
-        })
-        .collect()
-}
-
 #[derive(Debug, Clone)]
 enum GameEvent {
     MainEvent(MainEvent),
     BeginGame,
     SkipMeeples,
+    CycleMeepleKind,
+    RetrieveAbbot,
+    ToggleBuildTower,
+    Ransom,
+    ExpandFlock,
+    HerdFlock,
     ClosePauseMenu,
     EndGame,
     ResetCamera,
+    ExportBoard,
     Undo,
+    Redo,
+    ToggleHistory,
+    VoteOnRewind(bool),
     ReturnToLobby,
 }
 
 #[derive(Clone, Debug)]
 pub enum GameAction {
     Message(GameMessage),
+    /// Asks the host to carry out an `Undo` or `Redo`, or put it to a vote.
+    RequestRewind(GameMessage),
+    VoteOnRewind(bool),
     ReturnToLobby,
 }
 
-#[allow(clippy::large_enum_variant)]
-#[derive(Clone, Serialize, Deserialize)]
-enum TurnPhase {
-    Pregame {
-        tiles: HashMap<GridPos, Tile>,
-        held: Option<Tile>,
-        preview_location: Option<GridPos>,
-        open_edges: Vec<(GridPos, Orientation)>,
-    },
-    TilePlacement {
-        tile: Tile,
-        placeable_positions: Vec<GridPos>,
-        preview_location: Option<GridPos>,
-    },
-    MeeplePlacement {
-        placed_position: GridPos,
-        closed_groups: Vec<GroupIdentifier>,
-    },
-    EndGame {
-        next_tick: Option<f32>,
-    },
-}
-
 #[derive(Debug)]
 struct ScoringEffect {
     position: Vec2,
@@ -162,53 +162,6 @@ struct GroupInspection {
     selected_group: Option<GroupIdentifier>,
 }
 
-#[derive(Clone, Serialize, Deserialize)]
-pub struct GameState {
-    pub game: Game,
-    turn_phase: TurnPhase,
-    turn_order: VecDeque<PlayerIdentifier>,
-}
-
-impl std::fmt::Debug for GameState {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        #[allow(unused)]
-        #[derive(Debug)]
-        struct Game<'a> {
-            tiles: usize,
-            players: Vec<&'a Player>,
-        }
-        #[derive(Debug)]
-        enum TurnPhase {
-            Pregame,
-            MeeplePlacement,
-            TilePlacement,
-            EndGame,
-        }
-        f.debug_struct("GameState")
-            .field(
-                "game",
-                &Game {
-                    tiles: self.game.placed_tiles.len(),
-                    players: self
-                        .turn_order
-                        .iter()
-                        .map(|p| self.game.players.get(*p).unwrap())
-                        .collect(),
-                },
-            )
-            .field(
-                "turn_phase",
-                &match &self.turn_phase {
-                    game_client::TurnPhase::Pregame { .. } => TurnPhase::Pregame,
-                    game_client::TurnPhase::TilePlacement { .. } => TurnPhase::TilePlacement,
-                    game_client::TurnPhase::MeeplePlacement { .. } => TurnPhase::MeeplePlacement,
-                    game_client::TurnPhase::EndGame { .. } => TurnPhase::EndGame,
-                },
-            )
-            .finish()
-    }
-}
-
 #[derive(PartialEq, Eq, Debug)]
 enum PlacementValidity {
     Invalid,
@@ -216,6 +169,14 @@ enum PlacementValidity {
     Valid,
 }
 
+/// A line of the history panel.
+struct HistoryRow {
+    text: String,
+    /// The undo or redo that clicking the line asks for, to get back to just after its move.
+    rewind: Option<GameMessage>,
+    undone: bool,
+}
+
 struct GridSelectionInfo {
     gameboard_pos: Vec2,
     focused_pos: GridPos,
@@ -227,45 +188,19 @@ pub struct GameClientConfiguration {
     pub seed: u64,
     pub players: PlayerConfiguration,
     pub expansions: GameExpansions,
+    pub library: LibraryConfiguration,
+    pub scoring_rules: ScoringRules,
 }
 
 #[derive(Clone, Debug)]
 pub enum PlayerConfiguration {
-    Local(Vec<Color>),
+    Local(Vec<(Color, PlayerType)>),
     Multiplayer {
         local_player: PlayerType,
         players: Vec<(Color, PlayerType)>,
     },
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
-pub struct GameExpansions {
-    pub rivers_1: bool,
-}
-
-impl GameExpansions {
-    pub fn rivers(&self) -> Option<Vec<Tile>> {
-        let mut rivers = Vec::new();
-        if self.rivers_1 {
-            rivers.extend([
-                MONASTARY_POND.clone(),
-                RIVER_CROSSING.clone(),
-                RIVER_CORNER.clone(),
-                CORNER_CITY_RIVER.clone(),
-                RIVER_MONASTARY_BRIDGE.clone(),
-                CURVY_STRAIGHT_RIVER.clone(),
-                CORNER_ROAD_CORNER_RIVER.clone(),
-                STRAIGHT_RIVER.clone(),
-                RIVER_CORNER.clone().rotated(),
-                STRAIGHT_RIVER_DUELING_CITIES.clone(),
-                CITY_RIVER_CROSSING.clone(),
-                CORNER_ROAD_WATERFALL.clone(),
-            ]);
-        }
-        (!rivers.is_empty()).then_some(rivers)
-    }
-}
-
 pub struct GameClient {
     parent_channel: Sender<MainEvent>,
     action_channel: Option<Sender<GameAction>>,
@@ -278,10 +213,42 @@ pub struct GameClient {
     offset: Vec2,
     scale: f32,
     scoring_effects: Vec<ScoringEffect>,
-    history: Vec<GameState>,
+    history: History,
+    history_open: bool,
+    /// Who can undo and redo, in multiplayer games.
+    pub undo_policy: UndoPolicy,
+    /// An undo or redo waiting on the players to agree to it.
+    pub rewind_vote: Option<RewindVote>,
+    /// Something that happened in a multiplayer game worth telling the player about, such
+    /// as the host turning down a move made here, until the notice is hidden again.
+    notice: Option<(String, f32)>,
+    /// The moves made so far, for games that were set up from scratch rather than
+    /// continued from an existing game.
+    replay: Option<Replay>,
+    /// Whether the game is only being watched, as in the replay viewer, so that nobody
+    /// can make moves in it.
+    spectating: bool,
+    /// The player whose card is shown while spectating, instead of the current player's.
+    pub perspective: Option<PlayerIdentifier>,
+    end_game_next_tick: Option<f32>,
+    bot_controller: BotController,
     skip_meeples_button: Rc<RefCell<Button<GameEvent>>>,
+    meeple_kind_button: Rc<RefCell<Button<GameEvent>>>,
+    retrieve_abbot_button: Rc<RefCell<Button<GameEvent>>>,
+    build_tower_button: Rc<RefCell<Button<GameEvent>>>,
+    ransom_button: Rc<RefCell<Button<GameEvent>>>,
+    expand_flock_button: Rc<RefCell<Button<GameEvent>>>,
+    herd_flock_button: Rc<RefCell<Button<GameEvent>>>,
+    meeple_kind: MeepleKind,
+    /// Whether the player is choosing a tower to build instead of placing a meeple.
+    building_tower: bool,
+    /// The tower being built, while the player chooses a meeple for it to capture.
+    tower_capture: Option<GridPos>,
     return_to_main_menu_button: Rc<RefCell<Button<GameEvent>>>,
     begin_game_button: Rc<RefCell<Button<GameEvent>>>,
+    history_button: Rc<RefCell<Button<GameEvent>>>,
+    agree_button: Rc<RefCell<Button<GameEvent>>>,
+    refuse_button: Rc<RefCell<Button<GameEvent>>>,
     pub state: GameState,
     inspecting_groups: Option<GroupInspection>,
     ui: UIManager<GameEvent, GameEvent>,
@@ -300,97 +267,73 @@ impl GameClient {
         action_channel: Option<Sender<GameAction>>,
         config: GameClientConfiguration,
     ) -> Self {
-        let mut library = Tile::default_library();
-        let mut rng = StdRng::seed_from_u64(config.seed);
-        library.shuffle(&mut rng);
-        let (mut game, turn_order) = match config.players {
-            PlayerConfiguration::Local(colors) => {
-                let mut game = Game::new_with_library(library);
-                for color in colors {
-                    game.players.insert(Player::new(color));
+        let (players, local_player) = match config.players {
+            PlayerConfiguration::Local(players) => {
+                let mut players: Vec<_> = players
+                    .into_iter()
+                    .map(|(color, ptype)| Player::new_inner(color, ptype))
+                    .collect();
+                // a person needs to go first to lay out the river
+                if let Some(first_local) = players
+                    .iter()
+                    .position(|player| player.ptype == PlayerType::Local)
+                {
+                    players.rotate_left(first_local);
                 }
-                let turn_order = game.players.keys().collect();
-                (game, turn_order)
+                (players, PlayerType::Local)
             }
             PlayerConfiguration::Multiplayer {
                 local_player,
                 players,
             } => {
-                let mut game = Game::new_inner(library, local_player);
-                let mut host_id = None;
-                for (color, ptype) in players {
-                    if matches!(ptype, PlayerType::MultiplayerHost { .. }) {
-                        host_id = Some(game.players.insert(Player::new_inner(color, ptype)));
-                    } else {
-                        game.players.insert(Player::new_inner(color, ptype));
-                    }
-                }
-                let mut turn_order: VecDeque<_> = game.players.keys().collect();
-                while turn_order.front() != host_id.as_ref() {
-                    turn_order.rotate_right(turn_order.len() - 1);
+                let mut players: Vec<_> = players
+                    .into_iter()
+                    .map(|(color, ptype)| Player::new_inner(color, ptype))
+                    .collect();
+                if let Some(host_index) = players
+                    .iter()
+                    .position(|player| matches!(player.ptype, PlayerType::MultiplayerHost { .. }))
+                {
+                    players.rotate_left(host_index);
                 }
-                (game, turn_order)
+                (players, local_player)
             }
         };
-        let turn_phase = if let Some(mut river_tiles) = config.expansions.rivers() {
-            river_tiles.shuffle(&mut rng);
-            let tiles: HashMap<GridPos, Tile> = checker_spiral()
-                .map(GridPos::from)
-                .zip(river_tiles)
-                .collect();
-            let open_edges = recalculate_open_edges(&tiles);
-            TurnPhase::Pregame {
-                tiles,
-                held: None,
-                preview_location: None,
-                open_edges,
-            }
-        } else {
-            game.place_tile(STARTING_TILE.clone(), GridPos(0, 0))
-                .unwrap();
-            let (tile, placeable_positions) = game.draw_placeable_tile().unwrap();
-            TurnPhase::TilePlacement {
-                tile,
-                placeable_positions,
-                preview_location: None,
-            }
-        };
-        GameClient::new_from_state(
+        let replay = Replay::new(
+            config.seed,
+            &players,
+            local_player,
+            config.expansions,
+            config.library,
+            config.scoring_rules,
+        );
+        let mut this = GameClient::new_from_state(
             ctx,
             shared,
-            GameState {
-                turn_phase,
-                turn_order,
-                game,
-            },
+            replay.initial_state(),
             parent_channel,
             action_channel,
-        )
+        );
+        this.replay = Some(replay);
+        this
     }
 
     pub fn new_with_game(
         ctx: &Context,
         shared: Shared,
-        mut game: Game,
+        game: Game,
+        seed: u64,
+        expansions: &GameExpansions,
         parent_channel: Sender<MainEvent>,
         action_channel: Option<Sender<GameAction>>,
-    ) -> Self {
-        let (first_tile, placeable_positions) = game.draw_placeable_tile().unwrap();
-        GameClient::new_from_state(
+    ) -> GameResult<Self> {
+        Ok(GameClient::new_from_state(
             ctx,
             shared,
-            GameState {
-                turn_phase: TurnPhase::TilePlacement {
-                    tile: first_tile,
-                    placeable_positions,
-                    preview_location: None,
-                },
-                turn_order: game.players.keys().collect(),
-                game,
-            },
+            GameState::from_game(game, seed, expansions)?,
             parent_channel,
             action_channel,
-        )
+        ))
     }
 
     pub fn new_from_state(
@@ -406,7 +349,7 @@ impl GameClient {
         let ui_sender = event_sender.clone();
         let (
             ui,
-            [UIElement::Button(skip_meeples_button), UIElement::Button(return_to_main_menu_button), UIElement::Button(begin_game_button)],
+            [UIElement::Button(skip_meeples_button), UIElement::Button(meeple_kind_button), UIElement::Button(retrieve_abbot_button), UIElement::Button(build_tower_button), UIElement::Button(ransom_button), UIElement::Button(expand_flock_button), UIElement::Button(herd_flock_button), UIElement::Button(return_to_main_menu_button), UIElement::Button(begin_game_button), UIElement::Button(history_button), UIElement::Button(agree_button), UIElement::Button(refuse_button)],
         ) = UIManager::new_and_rc_elements(
             ui_sender,
             [
@@ -420,6 +363,54 @@ impl GameClient {
                     Color::from_rgb(0, 128, 192),
                     GameEvent::SkipMeeples,
                 )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Rect::new(1.0, 0.0, 0.0, 0.0),
+                        absolute: Rect::new(-220.0, 70.0, 200.0, 40.0),
+                    },
+                    Text::new(format!("Placing: {}", MeepleKind::Normal.name())),
+                    GameEvent::CycleMeepleKind,
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Rect::new(1.0, 0.0, 0.0, 0.0),
+                        absolute: Rect::new(-220.0, 120.0, 200.0, 40.0),
+                    },
+                    Text::new("Retrieve abbot"),
+                    GameEvent::RetrieveAbbot,
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Rect::new(1.0, 0.0, 0.0, 0.0),
+                        absolute: Rect::new(-220.0, 170.0, 200.0, 40.0),
+                    },
+                    Text::new("Build tower"),
+                    GameEvent::ToggleBuildTower,
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Rect::new(1.0, 0.0, 0.0, 0.0),
+                        absolute: Rect::new(-220.0, 220.0, 200.0, 40.0),
+                    },
+                    Text::new(format!("Ransom ({RANSOM_COST} points)")),
+                    GameEvent::Ransom,
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Rect::new(1.0, 0.0, 0.0, 0.0),
+                        absolute: Rect::new(-220.0, 270.0, 200.0, 40.0),
+                    },
+                    Text::new("Expand flock"),
+                    GameEvent::ExpandFlock,
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Rect::new(1.0, 0.0, 0.0, 0.0),
+                        absolute: Rect::new(-220.0, 320.0, 200.0, 40.0),
+                    },
+                    Text::new("Herd flock"),
+                    GameEvent::HerdFlock,
+                )),
                 UIElement::Button(Button::new(
                     Bounds {
                         relative: Rect::new(1.0, 0.0, 0.0, 0.0),
@@ -444,6 +435,30 @@ impl GameClient {
                     Text::new("Begin game"),
                     GameEvent::BeginGame,
                 )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Rect::new(1.0, 1.0, 0.0, 0.0),
+                        absolute: Rect::new(-220.0, -60.0, 200.0, 40.0),
+                    },
+                    Text::new("Show History"),
+                    GameEvent::ToggleHistory,
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Rect::new(0.5, 0.0, 0.0, 0.0),
+                        absolute: Rect::new(-125.0, 70.0, 120.0, 40.0),
+                    },
+                    Text::new("Agree"),
+                    GameEvent::VoteOnRewind(true),
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Rect::new(0.5, 0.0, 0.0, 0.0),
+                        absolute: Rect::new(5.0, 70.0, 120.0, 40.0),
+                    },
+                    Text::new("Refuse"),
+                    GameEvent::VoteOnRewind(false),
+                )),
             ],
         )
         else {
@@ -462,12 +477,35 @@ impl GameClient {
             offset: Vec2::ZERO,
             scale: 1.0,
             scoring_effects: Vec::new(),
-            history: Vec::new(),
+            history: History::default(),
+            history_open: false,
+            undo_policy: UndoPolicy::default(),
+            rewind_vote: None,
+            notice: None,
+            replay: None,
+            spectating: false,
+            perspective: None,
+            end_game_next_tick: state
+                .is_endgame()
+                .then(|| ctx.time.time_since_start().as_secs_f32() + END_GAME_SCORE_DELAY),
+            bot_controller: BotController::default(),
             state,
             inspecting_groups: None,
             skip_meeples_button,
+            meeple_kind_button,
+            meeple_kind: MeepleKind::Normal,
+            retrieve_abbot_button,
+            build_tower_button,
+            ransom_button,
+            expand_flock_button,
+            herd_flock_button,
+            building_tower: false,
+            tower_capture: None,
             return_to_main_menu_button,
             begin_game_button,
+            history_button,
+            agree_button,
+            refuse_button,
             ui,
             camera_movement: Vec2::ZERO,
             camera_zoom: 0.0,
@@ -490,6 +528,20 @@ impl GameClient {
         }
     }
 
+    /// A client for watching a game without taking part in it.
+    pub fn new_spectator(
+        ctx: &Context,
+        shared: Shared,
+        state: GameState,
+        parent_channel: Sender<MainEvent>,
+    ) -> Self {
+        let mut this = GameClient::new_from_state(ctx, shared, state, parent_channel, None);
+        this.spectating = true;
+        this
+    }
+
+    /// Continues a saved game locally. Seats that were played over the network are
+    /// handed to local players; use the Load Game screen to host it again instead.
     pub fn load(
         ctx: &Context,
         args: Shared,
@@ -497,43 +549,306 @@ impl GameClient {
         action_channel: Option<Sender<GameAction>>,
         path: PathBuf,
     ) -> GameResult<Self> {
-        let file = File::open(path)?;
-        let mut state: GameState = bincode::deserialize_from(file).to_gameerror()?;
+        let (mut state, mut replay) = load_game(path)?;
         for (_, player) in &mut state.game.players {
-            player.ptype = PlayerType::Local;
+            if !matches!(player.ptype, PlayerType::Bot { .. }) {
+                if !matches!(player.ptype, PlayerType::Local) {
+                    info!("{:?} will be played locally", player.ptype);
+                }
+                player.ptype = PlayerType::Local;
+            }
         }
         state.game.local_player = PlayerType::Local;
-        Ok(Self::new_from_state(
-            ctx,
-            args,
-            state,
-            parent_channel,
-            action_channel,
-        ))
+        if let Some(replay) = &mut replay {
+            for (_, ptype) in &mut replay.players {
+                if !matches!(ptype, PlayerType::Bot { .. }) {
+                    *ptype = PlayerType::Local;
+                }
+            }
+            replay.local_player = PlayerType::Local;
+        }
+        let mut this = Self::new_from_state(ctx, args, state, parent_channel, action_channel);
+        this.replay = replay;
+        Ok(this)
+    }
+
+    /// Writes the game to the save directory, if saving is enabled. Each game keeps one
+    /// file that's rewritten as it goes: a replay if the game was set up from scratch,
+    /// or otherwise a save of its current state.
+    fn save(&self) -> GameResult<()> {
+        let Some(Some(base_path)) = &self.shared.args.save_games else {
+            return Ok(());
+        };
+        let format = self.shared.args.save_format;
+        let name = self.creation_time.strftime("%Y-%m-%d_%H-%M-%S");
+        match &self.replay {
+            Some(replay) => {
+                let path = base_path.join(format!("{name}.{}", format.replay_extension()));
+                debug!("saving replay to {}", path.display());
+                replay.write(path, format)?;
+            }
+            None => {
+                let path = base_path.join(format!("{name}.{}", format.extension()));
+                debug!("saving game state to {}", path.display());
+                SaveFile::write(path, &self.state, format)?;
+            }
+        }
+        Ok(())
     }
 
-    fn save(&self, mut path: PathBuf) -> GameResult<()> {
-        path.push(self.creation_time.strftime("%Y-%m-%d_%H-%M-%S"));
-        let _ = create_dir_all(&path);
-        path.push(SystemTime::now().strftime("%Y-%m-%d_%H-%M-%S%.3f.save"));
-        debug!("saving game state to {}", path.display());
-        let mut file = File::create(path)?;
-        bincode::serialize_into(&mut file, &self.state).to_gameerror()
+    /// Writes the board out as both an SVG and a PNG to the export directory.
+    fn export_board(&mut self) {
+        let path = self
+            .shared
+            .args
+            .export_dir
+            .join(SystemTime::now().strftime("%Y-%m-%d_%H-%M-%S"));
+        let image = BoardImage::new(&mut self.state.game);
+        for extension in ["svg", "png"] {
+            let path = path.with_extension(extension);
+            match image.save(&path, self.shared.args.export_tile_size) {
+                Ok(()) => info!("exported board to {}", path.display()),
+                Err(err) => warn!("failed to export board to {}: {err}", path.display()),
+            }
+        }
     }
 
-    fn push_history(&mut self) -> GameResult<()> {
-        if let Some(Some(base_path)) = &self.shared.args.save_games {
-            self.save(base_path.clone())?;
+    /// Takes back, or makes again, the moves an `Undo` or `Redo` asks for.
+    fn rewind(&mut self, ctx: &Context, message: GameMessage) -> GameResult<()> {
+        debug!("rewinding with {message:?}");
+        match message {
+            GameMessage::Undo(steps) => {
+                for _ in 0..steps {
+                    if self.history.undo(&mut self.state).is_none() {
+                        break;
+                    }
+                    if let Some(replay) = &mut self.replay {
+                        replay.undo();
+                    }
+                }
+            }
+            GameMessage::Redo(steps) => {
+                for _ in 0..steps {
+                    let Some(entry) = self.history.redo(&mut self.state) else {
+                        break;
+                    };
+                    if let Some(replay) = &mut self.replay {
+                        replay.record(&entry.message);
+                    }
+                }
+            }
+            _ => return Ok(()),
         }
-        self.history.push(self.state.clone());
-        Ok(())
+        self.rewind_vote = None;
+        self.state_replaced(ctx);
+        self.save()
+    }
+
+    /// Resets whatever was in progress on the old game after it's replaced outright.
+    fn state_replaced(&mut self, ctx: &Context) {
+        self.bot_controller.cancel();
+        self.scoring_effects.clear();
+        self.end_game_next_tick = self
+            .state
+            .is_endgame()
+            .then(|| ctx.time.time_since_start().as_secs_f32() + END_GAME_SCORE_DELAY);
+        self.set_building_tower(false);
+        self.set_meeple_kind(MeepleKind::Normal);
+        self.selected_segment_and_group = None;
+        self.reevaluate_selected_square();
+        self.update_discord_presence();
+    }
+
+    /// Handles the host turning down a move made here. Moves are applied here before
+    /// the host sees them, so the move is taken back, ahead of the resync that follows.
+    pub fn reject(&mut self, ctx: &Context, message: GameMessage, reason: String) {
+        warn!("the host rejected {message:?}: {reason}");
+        self.history
+            .take_back(&mut self.state, self.replay.as_mut(), &message);
+        self.notify(ctx, format!("The host turned down your move: {reason}"));
+    }
+
+    /// Shows the notice over the game for a few seconds.
+    pub fn notify(&mut self, ctx: &Context, notice: String) {
+        self.notice = Some((
+            notice,
+            ctx.time.time_since_start().as_secs_f32() + NOTICE_DURATION,
+        ));
+    }
+
+    /// What a client that fell out of step with this game needs to take it up: the moves
+    /// that can still be rewound, and the replay if there is one.
+    pub fn resync_message(&self) -> ServerMessage {
+        ServerMessage::Resync {
+            history: self.history.record(&self.state).into(),
+            replay: self.replay.clone(),
+        }
+    }
+
+    /// Takes up the host's copy of the game, after this one fell out of step with it. The
+    /// history is rebuilt from the host's, so that it can be rewound as far as the host's.
+    pub fn resync(
+        &mut self,
+        ctx: &Context,
+        mut history: HistoryRecord,
+        mut replay: Option<Replay>,
+    ) -> GameResult<()> {
+        debug!("resyncing with the host");
+        let local_player = self.state.game.local_player.clone();
+        history.start.game.local_player = local_player.clone();
+        let (history, state) = History::rebuild(history).map_err(|err| {
+            GameError::CustomError(format!("The host's game couldn't be rebuilt: {err}"))
+        })?;
+        if let Some(replay) = &mut replay {
+            replay.local_player = local_player;
+        }
+        self.state = state;
+        self.history = history;
+        self.replay = replay;
+        self.state_replaced(ctx);
+        self.save()
     }
 
-    fn pop_history(&mut self) {
-        if let Some(state) = self.history.pop() {
-            debug!("pop history");
-            self.state = state;
+    /// The seat of whoever is using this client, in multiplayer games.
+    fn local_seat(&self) -> Option<PlayerIdentifier> {
+        if matches!(self.state.game.local_player, PlayerType::Local) {
+            return None;
         }
+        self.seat_of(&self.state.game.local_player)
+    }
+
+    pub fn seat_of(&self, ptype: &PlayerType) -> Option<PlayerIdentifier> {
+        self.state
+            .game
+            .players
+            .iter()
+            .find(|(_, player)| player.ptype == *ptype)
+            .map(|(player_ident, _)| player_ident)
+    }
+
+    /// Whether `player` may have the moves an `Undo` or `Redo` asks for taken back or
+    /// made again, under the undo policy.
+    pub fn may_rewind(&self, player: PlayerIdentifier, message: &GameMessage) -> bool {
+        self.history.allows(
+            message,
+            player,
+            self.state.current_player(),
+            self.undo_policy,
+        )
+    }
+
+    /// Whether whoever is using this client can ask for an `Undo` or `Redo`. Local games
+    /// have no undo policy, since everyone is at the same table.
+    fn can_request_rewind(&self, message: &GameMessage) -> bool {
+        if self.spectating {
+            return false;
+        }
+        match self.local_seat() {
+            Some(seat) => self.rewind_vote.is_none() && self.may_rewind(seat, message),
+            None => {
+                let current_player = self.state.current_player();
+                self.history
+                    .allows(message, current_player, current_player, UndoPolicy::Allowed)
+            }
+        }
+    }
+
+    /// The lines of the history panel, oldest first, cut down to the most recent that fit.
+    fn history_rows(&self) -> Vec<HistoryRow> {
+        let done = self.history.done();
+        let done_moves = done.iter().enumerate().map(|(i, entry)| {
+            let rewind = (i + 1 < done.len()).then(|| GameMessage::Undo(done.len() - 1 - i));
+            (entry, rewind, false)
+        });
+        let undone_moves = self
+            .history
+            .undone()
+            .enumerate()
+            .map(|(i, entry)| (entry, Some(GameMessage::Redo(i + 1)), true));
+        let rows: Vec<_> = done_moves
+            .chain(undone_moves)
+            .flat_map(|(entry, rewind, undone)| {
+                entry
+                    .description
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, line)| HistoryRow {
+                        // what a move led to is indented under the move itself
+                        text: if i == 0 {
+                            line.clone()
+                        } else {
+                            format!("    {line}")
+                        },
+                        rewind: rewind.clone(),
+                        undone,
+                    })
+            })
+            .collect();
+        let skip = rows.len().saturating_sub(HISTORY_PANEL_ROWS);
+        rows.into_iter().skip(skip).collect()
+    }
+
+    fn history_panel_rect(ctx: &Context, rows: usize) -> Rect {
+        let res = ctx.res();
+        let height = rows.max(1) as f32 * HISTORY_ROW_HEIGHT + 20.0;
+        Rect::new(
+            res.x - 20.0 - HISTORY_PANEL_WIDTH,
+            res.y - 70.0 - height,
+            HISTORY_PANEL_WIDTH,
+            height,
+        )
+    }
+
+    fn history_row_rect(panel: Rect, i: usize) -> Rect {
+        Rect::new(
+            panel.x + 10.0,
+            panel.y + 10.0 + HISTORY_ROW_HEIGHT * i as f32,
+            panel.w - 20.0,
+            HISTORY_ROW_HEIGHT,
+        )
+    }
+
+    /// Rewinds the game to the move clicked in the history panel. Returns whether the
+    /// cursor is over the panel, so that clicks on it don't also go to the board.
+    fn history_panel_update(&mut self, ctx: &Context, on_clickable: &mut bool) -> GameResult<bool> {
+        if !self.history_open {
+            return Ok(false);
+        }
+        let rows = self.history_rows();
+        let panel = Self::history_panel_rect(ctx, rows.len());
+        let mouse = ctx.mouse.position();
+        if !panel.contains(mouse) {
+            return Ok(false);
+        }
+        let rewind = rows
+            .into_iter()
+            .enumerate()
+            .find(|(i, _)| Self::history_row_rect(panel, *i).contains(mouse))
+            .and_then(|(_, row)| row.rewind)
+            .filter(|rewind| self.can_request_rewind(rewind));
+        if let Some(rewind) = rewind {
+            *on_clickable = true;
+            if ctx.mouse.button_just_pressed(MouseButton::Left) {
+                self.request_rewind(ctx, rewind)?;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Asks for moves to be taken back or made again. In multiplayer games it's up to the
+    /// host to carry it out, or to put it to a vote.
+    fn request_rewind(&mut self, ctx: &Context, message: GameMessage) -> GameResult<()> {
+        if !self.can_request_rewind(&message) {
+            return Ok(());
+        }
+        self.pause_menu = None;
+        match &self.action_channel {
+            Some(action_channel) => {
+                let _ = action_channel.send(GameAction::RequestRewind(message));
+            }
+            None => self.rewind(ctx, message)?,
+        }
+        Ok(())
     }
 
     fn reset_camera(&mut self, ctx: &Context) {
@@ -586,9 +901,12 @@ impl GameClient {
                 if placement_validity != ValidWithDifferentRotation {
                     break;
                 } else if clockwise {
-                    self.get_held_tile_mut().unwrap().rotate_clockwise();
+                    self.state.held_tile_mut().unwrap().rotate_clockwise();
                 } else {
-                    self.get_held_tile_mut().unwrap().rotate_counterclockwise();
+                    self.state
+                        .held_tile_mut()
+                        .unwrap()
+                        .rotate_counterclockwise();
                 }
                 placement_validity = self.is_placement_valid(selected_square);
             }
@@ -626,48 +944,99 @@ impl GameClient {
         scale: f32,
     ) -> GameResult<()> {
         const MEEPLE_SIZE: f32 = 200.0;
-        const MEEPLE_CENTER: Vec2 = vec2(0.5, 0.6);
-        const MEEPLE_POINTS: [Vec2; 13] = [
-            vec2(0.025, 1.0),
-            vec2(0.425, 1.0),
-            vec2(0.5, 0.85),
-            vec2(0.575, 1.0),
-            vec2(0.975, 1.0),
-            vec2(0.75, 0.575),
-            vec2(1.0, 0.475),
-            vec2(1.0, 0.35),
-            vec2(0.675, 0.3),
-            vec2(0.325, 0.3),
-            vec2(0.0, 0.35),
-            vec2(0.0, 0.475),
-            vec2(0.25, 0.575),
-        ];
-        const HEAD_POINT: Vec2 = vec2(0.5, 0.3);
         let scale = scale * MEEPLE_SIZE;
         let meeple_points = MEEPLE_POINTS.map(|p| (p - MEEPLE_CENTER) * scale + pos);
-        let head_point = (HEAD_POINT - MEEPLE_CENTER) * scale + pos;
+        let head_point = (MEEPLE_HEAD_POINT - MEEPLE_CENTER) * scale + pos;
         Mesh::new_polygon(ctx, DrawMode::fill(), &meeple_points, color)?.draw(canvas);
-        Mesh::new_circle(ctx, DrawMode::fill(), head_point, scale * 0.175, 1.0, color)?
-            .draw(canvas);
+        Mesh::new_circle(
+            ctx,
+            DrawMode::fill(),
+            head_point,
+            scale * MEEPLE_HEAD_RADIUS,
+            1.0,
+            color,
+        )?
+        .draw(canvas);
         Ok(())
     }
 
-    fn get_held_tile_mut(&mut self) -> Option<&mut Tile> {
-        match &mut self.state.turn_phase {
-            TurnPhase::TilePlacement { tile, .. } => Some(tile),
-            _ => None,
+    pub fn draw_meeple_of_kind(
+        ctx: &Context,
+        canvas: &mut Canvas,
+        pos: Vec2,
+        color: Color,
+        scale: f32,
+        kind: MeepleKind,
+    ) -> GameResult<()> {
+        match kind {
+            MeepleKind::Normal => GameClient::draw_meeple(ctx, canvas, pos, color, scale),
+            MeepleKind::Large => GameClient::draw_meeple(ctx, canvas, pos, color, scale * 1.5),
+            MeepleKind::Builder => {
+                GameClient::draw_meeple(ctx, canvas, pos, color, scale * 0.8)?;
+                Mesh::new_circle(ctx, DrawMode::fill(), pos, scale * 20.0, 1.0, Color::WHITE)?
+                    .draw(canvas);
+                Ok(())
+            }
+            MeepleKind::Pig => {
+                let size = scale * 200.0;
+                Mesh::new_ellipse(
+                    ctx,
+                    DrawMode::fill(),
+                    pos,
+                    size * 0.45,
+                    size * 0.3,
+                    1.0,
+                    color,
+                )?
+                .draw(canvas);
+                Mesh::new_circle(
+                    ctx,
+                    DrawMode::fill(),
+                    pos + vec2(size * 0.45, -size * 0.1),
+                    size * 0.15,
+                    1.0,
+                    color,
+                )?
+                .draw(canvas);
+                Ok(())
+            }
+            MeepleKind::Abbot => {
+                GameClient::draw_meeple(ctx, canvas, pos, color, scale)?;
+                Mesh::new_rectangle(
+                    ctx,
+                    DrawMode::fill(),
+                    Rect::new(pos.x - scale * 50.0, pos.y, scale * 100.0, scale * 15.0),
+                    Color::WHITE,
+                )?
+                .draw(canvas);
+                Ok(())
+            }
+            MeepleKind::Shepherd => {
+                GameClient::draw_meeple(ctx, canvas, pos, color, scale)?;
+                // the shepherd's crook
+                Mesh::new_line(
+                    ctx,
+                    &[
+                        pos + vec2(scale * 110.0, scale * 80.0),
+                        pos + vec2(scale * 110.0, -scale * 110.0),
+                        pos + vec2(scale * 80.0, -scale * 130.0),
+                    ],
+                    scale * 12.0,
+                    Color::from_rgb(120, 80, 40),
+                )?
+                .draw(canvas);
+                Ok(())
+            }
         }
     }
 
-    fn is_endgame(&self) -> bool {
-        matches!(self.state.turn_phase, TurnPhase::EndGame { .. })
-    }
-
     pub fn can_play(&self) -> bool {
-        self.get_current_player_type() == &self.state.game.local_player && !self.is_endgame()
+        !self.spectating
+            && self.get_current_player_type() == &self.state.game.local_player
+            && !self.state.is_endgame()
     }
 
-    fn draw_player_card(
+    pub fn draw_player_card(
         &self,
         ctx: &Context,
         canvas: &mut Canvas,
@@ -679,7 +1048,12 @@ impl GameClient {
         let mut card_rect = Rect {
             x: pos.x,
             y: pos.y,
-            w: 160.0,
+            w: 160.0
+                + 30.0
+                    * MeepleKind::ALL[1..]
+                        .iter()
+                        .map(|kind| player.supply(*kind))
+                        .sum::<usize>() as f32,
             h: 60.0,
         };
         let mut content_origin = vec2(10.0, 10.0);
@@ -687,6 +1061,7 @@ impl GameClient {
             PlayerType::Local => None,
             PlayerType::MultiplayerHost { username }
             | PlayerType::MultiplayerClient { username, .. } => Some(username.clone()),
+            PlayerType::Bot { difficulty } => Some(format!("{} Bot", difficulty.name())),
         };
         if display_name.is_some() {
             card_rect.h += 20.0;
@@ -743,6 +1118,14 @@ impl GameClient {
             .pos(pos + content_origin)
             .color(Color::BLACK)
             .draw(canvas);
+        for (i, good) in TradeGood::ALL.iter().enumerate() {
+            if let Some(count) = player.goods.get(good).filter(|count| **count > 0) {
+                Text::new(format!("{count}"))
+                    .pos(pos + content_origin + vec2(90.0 + 20.0 * i as f32, 0.0))
+                    .color(good.color())
+                    .draw(canvas);
+            }
+        }
         for i in 0..player.meeples {
             GameClient::draw_meeple(
                 ctx,
@@ -752,6 +1135,22 @@ impl GameClient {
                 0.1,
             )?;
         }
+        let special_meeples = MeepleKind::ALL[1..]
+            .iter()
+            .flat_map(|kind| (0..player.supply(*kind)).map(move |_| *kind));
+        for (i, kind) in special_meeples.enumerate() {
+            GameClient::draw_meeple_of_kind(
+                ctx,
+                canvas,
+                pos + content_origin
+                    + vec2(15.0, 30.0)
+                    + vec2(20.0, 0.0) * player.meeples as f32
+                    + vec2(30.0, 0.0) * i as f32,
+                player.color,
+                0.1,
+                kind,
+            )?;
+        }
         if highlighted {
             Mesh::new_rounded_rectangle(ctx, DrawMode::stroke(4.0), card_rect, 5.0, player.color)?
                 .draw(canvas);
@@ -760,84 +1159,144 @@ impl GameClient {
         Ok(card_rect)
     }
 
-    fn end_turn(&mut self, ctx: &Context, groups_to_close: Vec<GroupIdentifier>) {
-        for group_ident in groups_to_close {
-            use crate::tile::SegmentType::*;
-            let group = self.state.game.groups.get(group_ident).unwrap();
-            match group.gtype {
-                City | Road | Monastary => {
-                    let scored_meeples = self.state.game.score_group(group_ident);
+    /// Applies a message to the game state, recording history and triggering
+    /// effects for the resulting events. Illegal messages leave the game untouched, and
+    /// return the rule they broke.
+    fn apply(&mut self, ctx: &Context, message: GameMessage) -> GameResult<Result<(), RuleError>> {
+        let previous_state = message.is_undoable().then(|| self.state.clone());
+        let player = self.state.current_player();
+        match self.state.apply(message.clone()) {
+            Ok(events) => {
+                if let Some(previous_state) = previous_state {
+                    let description = describe(&self.state, player, &events);
+                    self.history
+                        .push(previous_state, message.clone(), player, description);
+                }
+                if !message.is_preview() {
+                    // whatever was being voted on no longer applies to the game
+                    self.rewind_vote = None;
+                    if let Some(replay) = &mut self.replay {
+                        replay.record(&message);
+                    }
+                    self.save()?;
+                }
+                self.handle_rule_events(ctx, events);
+                Ok(Ok(()))
+            }
+            Err(err) => {
+                warn!("rejected {message:?}: {err}");
+                Ok(Err(err))
+            }
+        }
+    }
+
+    fn handle_rule_events(&mut self, ctx: &Context, events: Events) {
+        for event in events {
+            trace!("rule event = {event:?}");
+            match event {
+                RuleEvent::TilePlaced { .. } => self.reevaluate_selected_square(),
+                RuleEvent::MeeplePlaced { .. }
+                | RuleEvent::AbbotRetrieved { .. }
+                | RuleEvent::FairyMoved { .. }
+                | RuleEvent::KnightDismissed { .. } => {
+                    self.selected_segment_and_group = None;
+                    self.set_meeple_kind(MeepleKind::Normal);
+                }
+                RuleEvent::TowerBuilt { .. } => {
+                    self.selected_segment_and_group = None;
+                    self.set_building_tower(false);
+                }
+                RuleEvent::GroupScored { results, .. } | RuleEvent::FlockHerded { results } => {
                     self.scoring_effects.extend(
-                        scored_meeples.into_iter().map(|score_result| {
+                        results.into_iter().map(|score_result| {
                             ScoringEffect::from_scoring_result(ctx, score_result)
                         }),
-                    );
+                    )
+                }
+                RuleEvent::TurnEnded { .. } => {
+                    self.set_building_tower(false);
+                    self.update_discord_presence();
                 }
-                _ => {}
+                RuleEvent::GameEnded => {
+                    self.end_game_next_tick =
+                        Some(ctx.time.time_since_start().as_secs_f32() + END_GAME_SCORE_DELAY);
+                    self.update_discord_presence();
+                }
+                RuleEvent::GameBegan
+                | RuleEvent::MeeplesSkipped { .. }
+                | RuleEvent::GoodsScored { .. }
+                | RuleEvent::DragonMoved { .. }
+                | RuleEvent::FairyScored { .. }
+                | RuleEvent::MeepleCaptured { .. }
+                | RuleEvent::PrisonerRansomed { .. }
+                | RuleEvent::FlockExpanded { .. } => {}
+                RuleEvent::BonusTurn { .. } => self.set_building_tower(false),
             }
         }
+    }
 
-        let player_ident = self.state.turn_order.pop_front().unwrap();
-        self.state.turn_order.push_back(player_ident);
+    fn set_meeple_kind(&mut self, meeple_kind: MeepleKind) {
+        self.meeple_kind = meeple_kind;
+        self.meeple_kind_button.borrow_mut().text =
+            Text::new(format!("Placing: {}", meeple_kind.name()));
+    }
 
-        match self.state.game.draw_placeable_tile() {
-            Some((tile, placeable_positions)) => {
-                self.state.turn_phase = TurnPhase::TilePlacement {
-                    tile,
-                    placeable_positions,
-                    preview_location: None,
-                }
-            }
-            None => self.end_game(ctx),
-        }
+    fn set_building_tower(&mut self, building_tower: bool) {
+        self.building_tower = building_tower;
+        self.tower_capture = None;
+        self.build_tower_button.borrow_mut().text = Text::new(if building_tower {
+            "Cancel tower"
+        } else {
+            "Build tower"
+        });
+    }
 
-        self.update_discord_presence();
+    /// The first player holding one of the current player's meeples prisoner.
+    fn ransom_captor(&self) -> Option<PlayerIdentifier> {
+        let player_ident = self.state.current_player();
+        self.state.turn_order.iter().copied().find(|captor| {
+            self.state.game.players[*captor]
+                .prisoners
+                .iter()
+                .any(|prisoner| prisoner.owner == player_ident)
+        })
     }
 
-    fn end_game(&mut self, ctx: &Context) {
-        self.state.turn_phase = TurnPhase::EndGame {
-            next_tick: Some(ctx.time.time_since_start().as_secs_f32() + END_GAME_SCORE_DELAY),
-        };
+    /// Switches to the next kind of meeple the current player has left to place.
+    fn cycle_meeple_kind(&mut self) {
+        let player = &self.state.game.players[self.state.current_player()];
+        let index = MeepleKind::ALL
+            .iter()
+            .position(|kind| *kind == self.meeple_kind)
+            .unwrap();
+        let next_kind = (1..=MeepleKind::ALL.len())
+            .map(|offset| MeepleKind::ALL[(index + offset) % MeepleKind::ALL.len()])
+            .find(|kind| player.supply(*kind) > 0)
+            .unwrap_or_default();
+        self.set_meeple_kind(next_kind);
     }
 
-    fn skip_meeples(&mut self, ctx: &Context) -> GameResult<()> {
-        debug!("skipping meeple placement");
-        if let TurnPhase::MeeplePlacement { closed_groups, .. } = &self.state.turn_phase {
-            let groups_to_close = closed_groups.clone();
-            self.push_history()?;
-            self.end_turn(ctx, groups_to_close);
-        }
+    /// Plays a move while spectating, showing its effects without recording it.
+    pub fn show_move(&mut self, ctx: &Context, message: GameMessage) -> GameResult<()> {
+        let events = self.state.apply(message)?;
+        self.handle_rule_events(ctx, events);
         Ok(())
     }
 
-    fn end_game_immediately(&mut self, ctx: &Context) -> GameResult<()> {
-        self.push_history()?;
-        self.end_game(ctx);
-        Ok(())
+    /// Jumps to another point in the game while spectating.
+    pub fn show_state(&mut self, ctx: &Context, state: GameState) {
+        self.scoring_effects.clear();
+        self.end_game_next_tick = state
+            .is_endgame()
+            .then(|| ctx.time.time_since_start().as_secs_f32() + END_GAME_SCORE_DELAY);
+        self.state = state;
+        self.selected_square = None;
     }
 
-    fn begin_game(&mut self, broadcast: bool) -> GameResult<()> {
-        if let TurnPhase::Pregame {
-            tiles,
-            held,
-            open_edges,
-            ..
-        } = &mut self.state.turn_phase
-        {
-            if open_edges.is_empty() && held.is_none() {
-                for (pos, tile) in tiles.drain() {
-                    self.state.game.place_tile(tile, pos)?;
-                }
-                let (tile, placeable_positions) = self.state.game.draw_placeable_tile().unwrap();
-                self.state.turn_phase = TurnPhase::TilePlacement {
-                    tile,
-                    placeable_positions,
-                    preview_location: None,
-                };
-                if broadcast {
-                    self.broadcast_action(GameMessage::BeginGame);
-                }
-            }
+    /// Applies a message made locally, and forwards it to other players if it was legal.
+    fn apply_local(&mut self, ctx: &Context, message: GameMessage) -> GameResult<()> {
+        if self.apply(ctx, message.clone())?.is_ok() {
+            self.broadcast_action(message);
         }
         Ok(())
     }
@@ -847,25 +1306,55 @@ impl GameClient {
         match event {
             GameEvent::MainEvent(event) => self.parent_channel.send(event).unwrap(),
             GameEvent::SkipMeeples => {
-                self.skip_meeples(ctx)?;
-                self.broadcast_action(GameMessage::SkipMeeples);
+                debug!("skipping meeple placement");
+                self.apply_local(ctx, GameMessage::SkipMeeples)?;
+            }
+            GameEvent::CycleMeepleKind => self.cycle_meeple_kind(),
+            GameEvent::RetrieveAbbot => {
+                debug!("retrieving abbot");
+                self.apply_local(ctx, GameMessage::RetrieveAbbot)?;
+            }
+            GameEvent::ToggleBuildTower => self.set_building_tower(!self.building_tower),
+            GameEvent::ExpandFlock => {
+                debug!("expanding flock");
+                self.apply_local(ctx, GameMessage::ExpandFlock)?;
+            }
+            GameEvent::HerdFlock => {
+                debug!("herding flock");
+                self.apply_local(ctx, GameMessage::HerdFlock)?;
+            }
+            GameEvent::Ransom => {
+                if let Some(captor) = self.ransom_captor() {
+                    debug!("paying ransom to {captor:?}");
+                    self.apply_local(ctx, GameMessage::Ransom { captor })?;
+                }
             }
             GameEvent::ClosePauseMenu => self.pause_menu = None,
             GameEvent::EndGame => {
                 self.pause_menu = None;
-                self.end_game_immediately(ctx)?;
-                self.broadcast_action(GameMessage::EndGame);
+                self.apply_local(ctx, GameMessage::EndGame)?;
             }
             GameEvent::ResetCamera => {
                 self.pause_menu = None;
                 self.reset_camera(ctx)
             }
-            GameEvent::Undo => {
-                if self.can_play() {
-                    self.pause_menu = None;
-                    self.pop_history();
-                    self.reevaluate_selected_square();
-                    self.broadcast_action(GameMessage::Undo);
+            GameEvent::ExportBoard => {
+                self.pause_menu = None;
+                self.export_board();
+            }
+            GameEvent::Undo => self.request_rewind(ctx, GameMessage::Undo(1))?,
+            GameEvent::Redo => self.request_rewind(ctx, GameMessage::Redo(1))?,
+            GameEvent::ToggleHistory => {
+                self.history_open = !self.history_open;
+                self.history_button.borrow_mut().text = Text::new(if self.history_open {
+                    "Hide History"
+                } else {
+                    "Show History"
+                });
+            }
+            GameEvent::VoteOnRewind(agree) => {
+                if let Some(action_channel) = &self.action_channel {
+                    let _ = action_channel.send(GameAction::VoteOnRewind(agree));
                 }
             }
             GameEvent::ReturnToLobby => {
@@ -874,86 +1363,26 @@ impl GameClient {
                 }
             }
             GameEvent::BeginGame => {
-                self.begin_game(true)?;
+                self.apply_local(ctx, GameMessage::BeginGame)?;
             }
         }
         Ok(())
     }
 
-    pub fn handle_message(&mut self, ctx: &mut Context, message: GameMessage) -> GameResult<()> {
+    /// Applies a message from another player. Returns the rule it broke, if it wasn't legal.
+    pub fn handle_message(
+        &mut self,
+        ctx: &mut Context,
+        message: GameMessage,
+    ) -> GameResult<Result<(), RuleError>> {
         trace!("received {message:?}");
         match message {
-            GameMessage::PlaceTile(TilePose { position, rotation }) => {
-                if let TurnPhase::TilePlacement { tile, .. } = &mut self.state.turn_phase {
-                    tile.rotate_to(rotation);
-                    self.place_tile(ctx, position)?;
-                }
-            }
-            GameMessage::PlaceMeeple { seg_ident } => {
-                if let TurnPhase::MeeplePlacement { closed_groups, .. } = &mut self.state.turn_phase
-                {
-                    let closed_groups = closed_groups.clone();
-                    let player_ident = self.state.turn_order.front().unwrap();
-                    self.place_meeple(ctx, closed_groups, seg_ident, *player_ident)?;
-                }
-            }
-            GameMessage::SkipMeeples => self.skip_meeples(ctx)?,
-            GameMessage::EndGame => self.end_game_immediately(ctx)?,
-            GameMessage::Undo => {
-                self.pop_history();
-                self.reevaluate_selected_square();
-            }
-            GameMessage::PreviewTile(tile_preview) => {
-                if let TurnPhase::TilePlacement {
-                    preview_location,
-                    tile,
-                    ..
-                } = &mut self.state.turn_phase
-                {
-                    if let Some(TilePose {
-                        position: selected_square,
-                        rotation,
-                    }) = tile_preview
-                    {
-                        *preview_location = Some(selected_square);
-                        tile.rotate_to(rotation);
-                    } else {
-                        *preview_location = None;
-                    }
-                }
-            }
-            GameMessage::PregamePickUp(pos) => {
-                self.pregame_pickup_tile(pos);
-            }
-            GameMessage::PregamePlace(TilePose { position, rotation }) => {
-                self.pregame_place_tile(position, rotation);
-            }
-            GameMessage::PregamePreview(tile_preview) => {
-                if let TurnPhase::Pregame {
-                    held,
-                    preview_location,
-                    ..
-                } = &mut self.state.turn_phase
-                {
-                    if let Some(TilePose {
-                        position: selected_square,
-                        rotation,
-                    }) = tile_preview
-                    {
-                        *preview_location = Some(selected_square);
-                        if let Some(held) = held {
-                            held.rotate_to(rotation);
-                        }
-                    } else {
-                        *preview_location = None;
-                    }
-                }
-            }
-            GameMessage::BeginGame => {
-                self.begin_game(false)?;
+            message if message.is_rewind() => {
+                self.rewind(ctx, message)?;
+                Ok(Ok(()))
             }
+            message => self.apply(ctx, message),
         }
-        Ok(())
     }
 
     pub fn update_pings(&mut self, users: Vec<User>) -> GameResult<()> {
@@ -986,55 +1415,6 @@ impl GameClient {
         Ok(())
     }
 
-    fn place_tile(&mut self, ctx: &mut Context, focused_pos: GridPos) -> Result<(), GameError> {
-        debug!("place_tile at {focused_pos:?}");
-        self.push_history()?;
-
-        let tile = self.get_held_tile_mut().unwrap().clone();
-        let closed_groups = self.state.game.place_tile(tile, focused_pos)?;
-        self.reevaluate_selected_square();
-
-        let tile = self.state.game.placed_tiles.get(&focused_pos).unwrap();
-        let player_ident = *self.state.turn_order.front().unwrap();
-        let player = self.state.game.players.get(player_ident).unwrap();
-
-        if player.meeples == 0
-            || (0..tile.segments.len())
-                .filter_map(|i| {
-                    let (group, _) = self
-                        .state
-                        .game
-                        .group_and_key_by_seg_ident((focused_pos, i))?;
-                    Some(!group.meeples.is_empty())
-                })
-                .all(|x| x)
-        {
-            self.end_turn(ctx, closed_groups.clone());
-        } else {
-            self.state.turn_phase = TurnPhase::MeeplePlacement {
-                placed_position: focused_pos,
-                closed_groups,
-            };
-        }
-        Ok(())
-    }
-
-    fn place_meeple(
-        &mut self,
-        ctx: &Context,
-        closed_groups: Vec<GroupIdentifier>,
-        seg_ident: SegmentIdentifier,
-        player_ident: PlayerIdentifier,
-    ) -> Result<(), GameError> {
-        debug!("player {player_ident:?} placing meeple at {seg_ident:?}");
-        self.push_history()?;
-
-        self.state.game.place_meeple(seg_ident, player_ident)?;
-        self.selected_segment_and_group = None;
-        self.end_turn(ctx, closed_groups);
-        Ok(())
-    }
-
     fn broadcast_action(&mut self, message: GameMessage) {
         trace!("sending {message:?}");
         if let Some(action_channel) = &mut self.action_channel {
@@ -1042,40 +1422,37 @@ impl GameClient {
         }
     }
 
+    /// The segment under the cursor, if the current player can do anything with it.
     fn get_selected_segment(
         &self,
         focused_pos: GridPos,
-        placed_position: &GridPos,
         subgrid_pos: Vec2,
+        selectable: impl Fn(SegmentIdentifier) -> bool,
     ) -> Option<(SegmentIdentifier, GroupIdentifier)> {
         if let Some(tile) = self.state.game.placed_tiles.get(&focused_pos) {
             for (i, _) in tile.segments.iter().enumerate() {
-                let (group, group_ident) = self
+                let seg_ident = (focused_pos, i);
+                let (_, group_ident) = self
                     .state
                     .game
-                    .group_and_key_by_seg_ident((*placed_position, i))
+                    .group_and_key_by_seg_ident(seg_ident)
                     .unwrap();
-                if group.gtype.placeable()
-                    && group.meeples.is_empty()
+                if selectable(seg_ident)
                     && point_in_polygon(subgrid_pos, &tile.segment_polygon(i).collect::<Vec<_>>())
                 {
-                    return Some(((focused_pos, i), group_ident));
+                    return Some((seg_ident, group_ident));
                 }
             }
         }
         None
     }
 
-    fn get_current_player(&self) -> PlayerIdentifier {
-        *self.state.turn_order.front().unwrap()
-    }
-
     pub fn get_current_player_type(&self) -> &PlayerType {
         &self
             .state
             .game
             .players
-            .get(self.get_current_player())
+            .get(self.state.current_player())
             .unwrap()
             .ptype
     }
@@ -1252,7 +1629,7 @@ impl GameClient {
                 ..
             } => {
                 for (pos, tile) in tiles {
-                    tile.render(ctx, canvas, self.grid_pos_rect(pos, ctx))?;
+                    self.render_tile(ctx, canvas, tile, self.grid_pos_rect(pos, ctx))?;
                 }
                 for (pos, orientation) in open_edges {
                     use Orientation::*;
@@ -1276,7 +1653,7 @@ impl GameClient {
                         Color::CYAN
                     };
                     let rect = self.grid_pos_rect(&pos, ctx);
-                    tile.render(ctx, canvas, rect)?;
+                    self.render_tile(ctx, canvas, tile, rect)?;
                     Mesh::new_rectangle(ctx, DrawMode::stroke(2.0), rect, cursor_color)?
                         .draw(canvas);
                 }
@@ -1296,6 +1673,14 @@ impl GameClient {
                 let sin_time = time.sin() * 0.1 + 1.0;
                 Mesh::new_rectangle(ctx, DrawMode::stroke(2.0), rect, Color::CYAN)?.draw(canvas);
 
+                if self.building_tower {
+                    if let Some(pos) = self.tower_capture.or(self.selected_square) {
+                        let rect = self.grid_pos_rect(&pos, ctx);
+                        Mesh::new_rectangle(ctx, DrawMode::stroke(2.0), rect, Color::WHITE)?
+                            .draw(canvas);
+                    }
+                }
+
                 if self.ui.cursor_override.is_none() {
                     'draw_outline: {
                         if let Some((_, group_ident)) = self.selected_segment_and_group {
@@ -1326,11 +1711,45 @@ impl GameClient {
                     }
                 }
             }
-            TurnPhase::EndGame { .. } => {}
+            TurnPhase::DragonMovement { .. } => {
+                for pos in self.state.dragon_moves() {
+                    let color = if self.selected_square == Some(pos) {
+                        Color::RED
+                    } else {
+                        Color::from_rgb(200, 120, 120)
+                    };
+                    Mesh::new_rectangle(
+                        ctx,
+                        DrawMode::stroke(2.0),
+                        self.grid_pos_rect(&pos, ctx),
+                        color,
+                    )?
+                    .draw(canvas);
+                }
+            }
+            TurnPhase::EndGame => {}
         }
         Ok(())
     }
 
+    /// Draws a tile with the selected texture pack, showing the segment polygons over the
+    /// art while the detailed view is held.
+    fn render_tile(
+        &self,
+        ctx: &Context,
+        canvas: &mut Canvas,
+        tile: &Tile,
+        rect: Rect,
+    ) -> GameResult<()> {
+        tile.render_textured(
+            ctx,
+            canvas,
+            rect,
+            self.shared.texture_pack.borrow().as_ref(),
+            self.keybinds.detailed_view.pressed(ctx),
+        )
+    }
+
     fn draw_held_tile_at_pos(
         &mut self,
         ctx: &mut Context,
@@ -1347,7 +1766,7 @@ impl GameClient {
             Color::GREEN
         };
         if !self.state.game.placed_tiles.contains_key(&pos) {
-            tile.render(ctx, canvas, rect)?;
+            self.render_tile(ctx, canvas, tile, rect)?;
         }
         Mesh::new_rectangle(ctx, DrawMode::stroke(2.0), rect, cursor_color)?.draw(canvas);
         Ok(())
@@ -1358,8 +1777,8 @@ impl GameClient {
         ctx: &mut Context,
         canvas: &mut Canvas,
     ) -> Result<(), GameError> {
-        let is_endgame = matches!(self.state.turn_phase, TurnPhase::EndGame { .. });
-        let current_player_ident = self.get_current_player();
+        let is_endgame = matches!(self.state.turn_phase, TurnPhase::EndGame);
+        let current_player_ident = self.state.current_player();
         if self.keybinds.detailed_view.pressed(ctx) || is_endgame {
             // draw player cards
             let mut card_location = vec2(20.0, 20.0);
@@ -1431,14 +1850,17 @@ impl GameClient {
                     .draw(canvas);
             }
         } else {
-            // draw card of current player
-            self.draw_player_card(ctx, canvas, current_player_ident, vec2(20.0, 20.0), false)?;
+            // draw card of current player, or of the player being followed
+            let player_ident = self.perspective.unwrap_or(current_player_ident);
+            let highlighted =
+                self.perspective.is_some() && player_ident == current_player_ident && !is_endgame;
+            self.draw_player_card(ctx, canvas, player_ident, vec2(20.0, 20.0), highlighted)?;
         }
         Ok(())
     }
 
     fn draw_meeples(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), GameError> {
-        for &(seg_ident, player) in self
+        for &(seg_ident, player, kind) in self
             .state
             .game
             .groups
@@ -1452,11 +1874,145 @@ impl GameClient {
             let segment_meeple_spot = refit_to_rect(tile.segments[seg_index].meeple_spot, rect);
             let norm = self.norm(ctx);
             let meeple_scale = MEEPLE_SIZE / norm.x.max(norm.y);
-            GameClient::draw_meeple(ctx, canvas, segment_meeple_spot, color, meeple_scale)?;
+            GameClient::draw_meeple_of_kind(
+                ctx,
+                canvas,
+                segment_meeple_spot,
+                color,
+                meeple_scale,
+                kind,
+            )?;
+        }
+
+        let norm = self.norm(ctx);
+        let figure_scale = MEEPLE_SIZE / norm.x.max(norm.y);
+        if let Some((pos, seg_index)) = self.state.game.fairy {
+            let tile = self.state.game.placed_tiles.get(&pos).unwrap();
+            let rect = self.grid_pos_rect(&pos, ctx);
+            let fairy_spot = refit_to_rect(tile.segments[seg_index].meeple_spot, rect)
+                + vec2(figure_scale * 80.0, -figure_scale * 80.0);
+            Mesh::new_circle(
+                ctx,
+                DrawMode::fill(),
+                fairy_spot,
+                figure_scale * 40.0,
+                1.0,
+                Color::from_rgb(230, 160, 230),
+            )?
+            .draw(canvas);
+        }
+        if let Some(pos) = self.state.game.dragon {
+            let center = self.grid_pos_rect(&pos, ctx).center().into();
+            Mesh::new_circle(
+                ctx,
+                DrawMode::fill(),
+                center,
+                figure_scale * 150.0,
+                1.0,
+                Color::from_rgb(160, 20, 20),
+            )?
+            .draw(canvas);
+            Text::new("Dragon")
+                .size(figure_scale * 70.0)
+                .centered_on(ctx, center)?
+                .color(Color::WHITE)
+                .draw(canvas);
+        }
+
+        for (seg_ident, flock) in &self.state.game.flocks {
+            let (pos, seg_index) = *seg_ident;
+            let tile = self.state.game.placed_tiles.get(&pos).unwrap();
+            let rect = self.grid_pos_rect(&pos, ctx);
+            let flock_spot = refit_to_rect(tile.segments[seg_index].meeple_spot, rect)
+                + vec2(-figure_scale * 110.0, figure_scale * 50.0);
+            Mesh::new_ellipse(
+                ctx,
+                DrawMode::fill(),
+                flock_spot,
+                figure_scale * 60.0,
+                figure_scale * 40.0,
+                1.0,
+                Color::WHITE,
+            )?
+            .draw(canvas);
+            let sheep: usize = flock.iter().map(SheepToken::sheep).sum();
+            Text::new(sheep.to_string())
+                .size(figure_scale * 60.0)
+                .centered_on(ctx, flock_spot)?
+                .color(Color::BLACK)
+                .draw(canvas);
+        }
+
+        for (pos, height) in &self.state.game.towers {
+            let tower_spot = self.tower_spot(pos, ctx);
+            for level in 0..*height {
+                let level_spot = tower_spot - vec2(0.0, level as f32 * figure_scale * 30.0);
+                Mesh::new_rectangle(
+                    ctx,
+                    DrawMode::fill(),
+                    Rect::new(
+                        level_spot.x - figure_scale * 50.0,
+                        level_spot.y - figure_scale * 15.0,
+                        figure_scale * 100.0,
+                        figure_scale * 28.0,
+                    ),
+                    Color::from_rgb(110, 110, 120),
+                )?
+                .draw(canvas);
+            }
+        }
+
+        // prisoners are shown behind bars beside the tower that captured them
+        for player in self.state.game.players.values() {
+            for (i, prisoner) in player.prisoners.iter().enumerate() {
+                let color = self.state.game.players[prisoner.owner].color;
+                let prisoner_spot = self.tower_spot(&prisoner.tower, ctx)
+                    + vec2((i + 1) as f32 * figure_scale * 90.0, 0.0);
+                GameClient::draw_meeple_of_kind(
+                    ctx,
+                    canvas,
+                    prisoner_spot,
+                    color,
+                    figure_scale * 0.6,
+                    prisoner.kind,
+                )?;
+                for bar in [-1.0, 0.0, 1.0] {
+                    let bar_x = prisoner_spot.x + bar * figure_scale * 25.0;
+                    Mesh::new_line(
+                        ctx,
+                        &[
+                            vec2(bar_x, prisoner_spot.y - figure_scale * 70.0),
+                            vec2(bar_x, prisoner_spot.y + figure_scale * 50.0),
+                        ],
+                        figure_scale * 8.0,
+                        player.color,
+                    )?
+                    .draw(canvas);
+                }
+            }
         }
         Ok(())
     }
 
+    /// Where the tower on the given tile is drawn.
+    fn tower_spot(&self, pos: &GridPos, ctx: &Context) -> Vec2 {
+        let rect = self.grid_pos_rect(pos, ctx);
+        let location = self
+            .state
+            .game
+            .placed_tiles
+            .get(pos)
+            .into_iter()
+            .flat_map(|tile| &tile.segments)
+            .flat_map(|segment| &segment.attributes)
+            .find_map(|attribute| match attribute {
+                SegmentAttribute::TowerFoundation { tower_location } => Some(*tower_location),
+                _ => None,
+            })
+            .unwrap_or(vec2(0.5, 0.5));
+        refit_to_rect(location, rect)
+    }
+
     fn draw_scoring_effects(
         &mut self,
         ctx: &mut Context,
@@ -1530,43 +2086,13 @@ impl GameClient {
             self.pause_menu = Some(PauseScreenSubclient::new(
                 self.shared.clone(),
                 self.event_sender.clone(),
-                self.is_endgame(),
-                !self.history.is_empty() && self.can_play(),
+                self.state.is_endgame(),
+                self.can_request_rewind(&GameMessage::Undo(1)),
+                self.can_request_rewind(&GameMessage::Redo(1)),
             ));
         }
     }
 
-    fn pregame_place_tile(&mut self, pos: GridPos, rotation: usize) {
-        if let TurnPhase::Pregame {
-            tiles,
-            held,
-            open_edges,
-            ..
-        } = &mut self.state.turn_phase
-        {
-            if let Some(mut tile) = held.take() {
-                tile.rotate_to(rotation);
-                tiles.insert(pos, tile);
-                *open_edges = recalculate_open_edges(tiles);
-            }
-        }
-    }
-
-    fn pregame_pickup_tile(&mut self, pos: GridPos) {
-        if let TurnPhase::Pregame {
-            tiles,
-            held: held @ None,
-            open_edges,
-            ..
-        } = &mut self.state.turn_phase
-        {
-            if let Some(tile) = tiles.remove(&pos) {
-                *held = Some(tile);
-                *open_edges = recalculate_open_edges(tiles);
-            }
-        }
-    }
-
     fn turn_phase_update(&mut self, ctx: &mut Context, on_clickable: &mut bool) -> GameResult<()> {
         let can_play = self.can_play();
         let GridSelectionInfo {
@@ -1608,16 +2134,14 @@ impl GameClient {
                                     position: pos,
                                     rotation,
                                 };
-                                self.pregame_place_tile(pos, rotation);
-                                self.broadcast_action(GameMessage::PregamePlace(pose));
+                                self.apply_local(ctx, GameMessage::PregamePlace(pose))?;
                                 preview_update = true;
                             }
                         }
                     } else {
                         if self.keybinds.place_tile.just_pressed(ctx) {
                             if tiles.contains_key(&pos) {
-                                self.pregame_pickup_tile(pos);
-                                self.broadcast_action(GameMessage::PregamePickUp(pos));
+                                self.apply_local(ctx, GameMessage::PregamePickUp(pos))?;
                                 preview_update = true;
                             }
                         }
@@ -1651,14 +2175,17 @@ impl GameClient {
 
                 // rotate tile
                 if self.keybinds.rotate_clockwise.just_pressed(ctx) {
-                    self.get_held_tile_mut().unwrap().rotate_clockwise();
+                    self.state.held_tile_mut().unwrap().rotate_clockwise();
                     self.reevaluate_selected_square();
                     self.update_preview();
                 }
 
                 // rotate tile counterclockwise (dont tell anyone it's actually just three clockwise rotations)
                 if self.keybinds.rotate_counterclockwise.just_pressed(ctx) {
-                    self.get_held_tile_mut().unwrap().rotate_counterclockwise();
+                    self.state
+                        .held_tile_mut()
+                        .unwrap()
+                        .rotate_counterclockwise();
                     self.reevaluate_selected_square_counterclockwise();
                     self.update_preview();
                 }
@@ -1666,85 +2193,168 @@ impl GameClient {
                 // place tile
                 if self.keybinds.place_tile.just_pressed(ctx) && self.placement_is_valid {
                     if let Some(selected_square) = self.selected_square {
-                        let rotation = self.get_held_tile_mut().unwrap().rotation;
-                        self.place_tile(ctx, selected_square)?;
-                        self.broadcast_action(GameMessage::PlaceTile(TilePose {
-                            position: selected_square,
-                            rotation,
-                        }));
+                        let rotation = self.state.held_tile().unwrap().rotation;
+                        debug!("place_tile at {selected_square:?}");
+                        self.apply_local(
+                            ctx,
+                            GameMessage::PlaceTile(TilePose {
+                                position: selected_square,
+                                rotation,
+                            }),
+                        )?;
                     }
                 }
 
                 *on_clickable = self.placement_is_valid;
             }
-            TurnPhase::MeeplePlacement {
-                placed_position,
-                closed_groups,
-            } => {
+            TurnPhase::MeeplePlacement { .. } if self.building_tower => {
                 self.selected_segment_and_group = None;
 
                 if !can_play {
+                    self.set_building_tower(false);
                     return Ok(());
                 }
 
-                if *placed_position == focused_pos {
-                    self.selected_segment_and_group =
-                        self.get_selected_segment(focused_pos, placed_position, subgrid_pos);
-
-                    *on_clickable = self.selected_segment_and_group.is_some();
+                match self.tower_capture {
+                    Some(tower) => {
+                        let height = self.state.game.towers.get(&tower).copied().unwrap_or(0) + 1;
+                        let sighted = self.state.game.tower_sight(tower, height);
+                        self.selected_segment_and_group =
+                            self.get_selected_segment(focused_pos, subgrid_pos, |seg_ident| {
+                                sighted.iter().any(|(sighted_seg_ident, _, _)| {
+                                    *sighted_seg_ident == seg_ident
+                                })
+                            });
+                        *on_clickable = self.selected_segment_and_group.is_some();
 
-                    if self.keybinds.place_meeple.just_pressed(ctx) {
-                        if let Some((seg_ident, Some(group))) =
+                        let capture = if self.keybinds.place_meeple.just_pressed(ctx) {
                             self.selected_segment_and_group
-                                .map(|(seg_ident, group_ident)| {
-                                    (seg_ident, self.state.game.groups.get(group_ident))
-                                })
-                        {
-                            let player_ident = *self.state.turn_order.front().unwrap();
-                            let player = self.state.game.players.get(player_ident).unwrap();
-                            if group.meeples.is_empty() && player.meeples > 0 {
-                                self.place_meeple(
+                                .map(|(seg_ident, _)| Some(seg_ident))
+                        } else if self.keybinds.skip_meeples.just_pressed(ctx) {
+                            Some(None)
+                        } else {
+                            None
+                        };
+                        if let Some(capture) = capture {
+                            debug!("building tower at {tower:?}, capturing {capture:?}");
+                            self.apply_local(
+                                ctx,
+                                GameMessage::BuildTower {
+                                    pos: tower,
+                                    capture,
+                                },
+                            )?;
+                        }
+                    }
+                    None => {
+                        let buildable = self.state.validate_build_tower(focused_pos, None).is_ok();
+                        self.set_selected_square(buildable.then_some(focused_pos));
+                        *on_clickable = buildable;
+
+                        if buildable && self.keybinds.place_meeple.just_pressed(ctx) {
+                            let height = self
+                                .state
+                                .game
+                                .towers
+                                .get(&focused_pos)
+                                .copied()
+                                .unwrap_or(0)
+                                + 1;
+                            if self.state.game.tower_sight(focused_pos, height).is_empty() {
+                                debug!("building tower at {focused_pos:?}");
+                                self.apply_local(
                                     ctx,
-                                    closed_groups.clone(),
-                                    seg_ident,
-                                    player_ident,
+                                    GameMessage::BuildTower {
+                                        pos: focused_pos,
+                                        capture: None,
+                                    },
                                 )?;
-                                self.broadcast_action(GameMessage::PlaceMeeple { seg_ident });
+                            } else {
+                                self.tower_capture = Some(focused_pos);
                             }
                         }
                     }
                 }
+            }
+            TurnPhase::MeeplePlacement { .. } => {
+                self.selected_segment_and_group = None;
+
+                if !can_play {
+                    return Ok(());
+                }
+
+                let kind = self.meeple_kind;
+                // usually only the placed tile, but a portal opens up the rest of the board
+                self.selected_segment_and_group =
+                    self.get_selected_segment(focused_pos, subgrid_pos, |seg_ident| {
+                        self.state
+                            .validate_meeple_placement(seg_ident, kind)
+                            .is_ok()
+                            || self.state.validate_dismiss_knight(seg_ident).is_ok()
+                            || self.state.validate_move_fairy(seg_ident).is_ok()
+                    });
+
+                *on_clickable = self.selected_segment_and_group.is_some();
+
+                if self.keybinds.place_meeple.just_pressed(ctx) {
+                    if let Some((seg_ident, _)) = self.selected_segment_and_group {
+                        if self
+                            .state
+                            .validate_meeple_placement(seg_ident, kind)
+                            .is_ok()
+                        {
+                            debug!(
+                                "player {:?} placing {kind:?} meeple at {seg_ident:?}",
+                                self.state.current_player()
+                            );
+                            self.apply_local(ctx, GameMessage::PlaceMeeple { seg_ident, kind })?;
+                        } else if self.state.validate_dismiss_knight(seg_ident).is_ok() {
+                            debug!("princess dismissing knight at {seg_ident:?}");
+                            self.apply_local(ctx, GameMessage::DismissKnight(seg_ident))?;
+                        } else if self.state.validate_move_fairy(seg_ident).is_ok() {
+                            debug!("moving fairy to {seg_ident:?}");
+                            self.apply_local(ctx, GameMessage::MoveFairy(seg_ident))?;
+                        }
+                    }
+                }
 
                 if self.keybinds.skip_meeples.just_pressed(ctx) {
-                    self.skip_meeples(ctx)?;
-                    self.broadcast_action(GameMessage::SkipMeeples);
+                    debug!("skipping meeple placement");
+                    self.apply_local(ctx, GameMessage::SkipMeeples)?;
                 }
             }
-            TurnPhase::EndGame { next_tick } => {
-                if let Some(next_tick) = next_tick {
-                    if ctx.time.time_since_start().as_secs_f32() > *next_tick {
-                        let next_tick = 'group_score: {
-                            let Some((group_ident, _)) = self
-                                .state
-                                .game
-                                .groups
-                                .iter()
-                                .find(|(_, group)| !group.meeples.is_empty())
-                            else {
-                                break 'group_score None;
-                            };
-                            let scored_meeples = self.state.game.score_group(group_ident);
-                            self.scoring_effects.extend(scored_meeples.into_iter().map(
-                                |score_result| {
-                                    ScoringEffect::from_scoring_result(ctx, score_result)
-                                },
-                            ));
+            TurnPhase::DragonMovement { .. } => {
+                if !can_play {
+                    self.set_selected_square(None);
+                    return Ok(());
+                }
 
-                            Some(
-                                ctx.time.time_since_start().as_secs_f32() + END_GAME_SCORE_INTERVAL,
-                            )
+                let dragon_moves = self.state.dragon_moves();
+                self.set_selected_square(
+                    dragon_moves.contains(&focused_pos).then_some(focused_pos),
+                );
+                *on_clickable = self.selected_square.is_some();
+
+                if self.keybinds.place_tile.just_pressed(ctx) {
+                    if let Some(pos) = self.selected_square {
+                        debug!("moving dragon to {pos:?}");
+                        self.apply_local(ctx, GameMessage::MoveDragon(pos))?;
+                    }
+                }
+            }
+            TurnPhase::EndGame => {
+                if let Some(next_tick) = self.end_game_next_tick {
+                    if ctx.time.time_since_start().as_secs_f32() > next_tick {
+                        self.end_game_next_tick = match self.state.score_next_endgame_group() {
+                            Some(event) => {
+                                self.handle_rule_events(ctx, vec![event]);
+                                Some(
+                                    ctx.time.time_since_start().as_secs_f32()
+                                        + END_GAME_SCORE_INTERVAL,
+                                )
+                            }
+                            None => None,
                         };
-                        self.state.turn_phase = TurnPhase::EndGame { next_tick };
                     }
                 }
             }
@@ -1752,6 +2362,23 @@ impl GameClient {
         Ok(())
     }
 
+    /// Whether this client decides the moves of bot players.
+    fn runs_bots(&self) -> bool {
+        !self.spectating && matches!(self.state.game.local_player, PlayerType::Local)
+    }
+
+    fn bot_update(&mut self, ctx: &Context) -> GameResult<()> {
+        let difficulty = match self.get_current_player_type() {
+            PlayerType::Bot { difficulty } if self.runs_bots() => Some(*difficulty),
+            _ => None,
+        };
+        let now = ctx.time.time_since_start().as_secs_f32();
+        if let Some(message) = self.bot_controller.update(&self.state, difficulty, now) {
+            self.apply_local(ctx, message)?;
+        }
+        Ok(())
+    }
+
     fn group_inspection_update(&mut self, ctx: &mut Context) {
         let GridSelectionInfo {
             focused_pos,
@@ -1759,25 +2386,130 @@ impl GameClient {
             ..
         } = self.grid_selection_info(ctx);
 
-        self.inspecting_groups = (self.keybinds.detailed_view.pressed(ctx) || self.is_endgame())
-            .then(|| GroupInspection {
-                selected_group: self
-                    .state
-                    .game
-                    .placed_tiles
-                    .get(&focused_pos)
-                    .and_then(|tile| {
-                        (0..tile.segments.len()).find(|seg_index| {
-                            tile.segments[*seg_index].stype.placeable() && {
-                                let segment_poly: Vec<_> =
-                                    tile.segment_polygon(*seg_index).collect();
-                                point_in_polygon(subgrid_pos, &segment_poly)
-                            }
-                        })
+        self.inspecting_groups = (self.keybinds.detailed_view.pressed(ctx)
+            || self.state.is_endgame())
+        .then(|| GroupInspection {
+            selected_group: self
+                .state
+                .game
+                .placed_tiles
+                .get(&focused_pos)
+                .and_then(|tile| {
+                    (0..tile.segments.len()).find(|seg_index| {
+                        tile.segments[*seg_index].stype.placeable() && {
+                            let segment_poly: Vec<_> = tile.segment_polygon(*seg_index).collect();
+                            point_in_polygon(subgrid_pos, &segment_poly)
+                        }
                     })
-                    .and_then(|i| self.state.game.group_associations.get(&(focused_pos, i)))
-                    .cloned(),
-            });
+                })
+                .and_then(|i| self.state.game.group_associations.get(&(focused_pos, i)))
+                .cloned(),
+        });
+    }
+
+    fn draw_history_panel(&self, ctx: &Context, canvas: &mut Canvas) -> GameResult<()> {
+        if !self.history_open {
+            return Ok(());
+        }
+        let rows = self.history_rows();
+        let panel = Self::history_panel_rect(ctx, rows.len());
+        Mesh::new_rounded_rectangle(ctx, DrawMode::fill(), panel, 5.0, PANEL_COLOR)?.draw(canvas);
+        if rows.is_empty() {
+            let rect = Self::history_row_rect(panel, 0);
+            Text::new("No moves yet")
+                .size(16.0)
+                .anchored_by(
+                    ctx,
+                    vec2(rect.x + 4.0, rect.y + rect.h / 2.0),
+                    AnchorPoint::CenterWest,
+                )?
+                .color(Color::from_rgb(160, 160, 160))
+                .draw(canvas);
+        }
+        let mouse = ctx.mouse.position();
+        for (i, row) in rows.iter().enumerate() {
+            let rect = Self::history_row_rect(panel, i);
+            let clickable = row
+                .rewind
+                .as_ref()
+                .is_some_and(|rewind| self.can_request_rewind(rewind));
+            if clickable && rect.contains(mouse) {
+                Mesh::new_rectangle(
+                    ctx,
+                    DrawMode::fill(),
+                    rect,
+                    Color::from_rgba(255, 255, 255, 40),
+                )?
+                .draw(canvas);
+            }
+            Text::new(&row.text)
+                .size(16.0)
+                .anchored_by(
+                    ctx,
+                    vec2(rect.x + 4.0, rect.y + rect.h / 2.0),
+                    AnchorPoint::CenterWest,
+                )?
+                .color(if row.undone {
+                    Color::from_rgb(140, 140, 140)
+                } else {
+                    Color::WHITE
+                })
+                .draw(canvas);
+        }
+        Ok(())
+    }
+
+    fn draw_rewind_vote(&self, ctx: &Context, canvas: &mut Canvas) -> GameResult<()> {
+        let Some(vote) = &self.rewind_vote else {
+            return Ok(());
+        };
+        let rewind = describe_rewind(&vote.message);
+        let local_seat = self.local_seat();
+        let text = if local_seat == Some(vote.proposer) {
+            format!("Waiting for everyone to agree to {rewind}")
+        } else {
+            let proposer = color_name(self.state.game.players[vote.proposer].color);
+            if local_seat.is_some_and(|seat| vote.agreed.contains(&seat)) {
+                format!("{proposer} wants to {rewind}; waiting for everyone to agree")
+            } else {
+                format!("{proposer} wants to {rewind}")
+            }
+        };
+        let text = Text::new(text).size(24.0);
+        let size: Vec2 = text.measure(ctx)?.into();
+        let center = vec2(ctx.res().x / 2.0, 40.0);
+        Mesh::new_rounded_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(
+                center.x - size.x / 2.0 - 10.0,
+                center.y - size.y / 2.0 - 10.0,
+                size.x + 20.0,
+                size.y + 20.0,
+            ),
+            5.0,
+            PANEL_COLOR,
+        )?
+        .draw(canvas);
+        text.centered_on(ctx, center)?
+            .color(Color::WHITE)
+            .draw(canvas);
+        Ok(())
+    }
+
+    fn draw_notice(&self, ctx: &Context, canvas: &mut Canvas) -> GameResult<()> {
+        let Some((notice, until)) = &self.notice else {
+            return Ok(());
+        };
+        if ctx.time.time_since_start().as_secs_f32() > *until {
+            return Ok(());
+        }
+        Text::new(notice.as_str())
+            .size(24.0)
+            .centered_on(ctx, vec2(ctx.res().x / 2.0, 140.0))?
+            .color(Color::from_rgb(200, 40, 40))
+            .draw(canvas);
+        Ok(())
     }
 
     fn draw_player_color_outline(
@@ -1785,9 +2517,10 @@ impl GameClient {
         ctx: &mut Context,
         canvas: &mut Canvas,
     ) -> Result<(), GameError> {
-        let current_player_ident = self.get_current_player();
+        let current_player_ident = self.state.current_player();
         let res = ctx.res();
-        if self.can_play() {
+        let followed = self.perspective == Some(current_player_ident) && !self.state.is_endgame();
+        if self.can_play() || followed {
             Mesh::new_rectangle(
                 ctx,
                 DrawMode::stroke(8.0),
@@ -1805,13 +2538,18 @@ impl GameClient {
     }
 
     fn pause_menu_update(&mut self, ctx: &mut Context) -> Result<bool, GameError> {
-        let is_endgame = self.is_endgame();
-        let can_play = self.can_play();
+        // only the host can end a multiplayer game early
+        let is_endgame = self.state.is_endgame()
+            || matches!(
+                self.state.game.local_player,
+                PlayerType::MultiplayerClient { .. }
+            );
+        let can_undo = self.can_request_rewind(&GameMessage::Undo(1));
+        let can_redo = self.can_request_rewind(&GameMessage::Redo(1));
         let pause_menu_open = if let Some(pause_menu) = &mut self.pause_menu {
             pause_menu.can_end_game.set(is_endgame);
-            pause_menu
-                .can_undo
-                .set(can_play && !self.history.is_empty());
+            pause_menu.can_undo.set(can_undo);
+            pause_menu.can_redo.set(can_redo);
             pause_menu.update(ctx)?;
             self.set_selected_square(None);
             true
@@ -1849,11 +2587,50 @@ impl GameClient {
         self.skip_meeples_button.borrow_mut().state = UIElementState::invisible_if(
             !matches!(self.state.turn_phase, TurnPhase::MeeplePlacement { .. }) || !self.can_play(),
         );
-        self.return_to_main_menu_button.borrow_mut().state =
-            UIElementState::invisible_if(!matches!(
-                self.state.turn_phase,
-                TurnPhase::EndGame { next_tick: None }
-            ));
+        let player = &self.state.game.players[self.state.current_player()];
+        let has_special_meeples = MeepleKind::ALL
+            .iter()
+            .any(|kind| *kind != MeepleKind::Normal && player.supply(*kind) > 0);
+        self.meeple_kind_button.borrow_mut().state = UIElementState::invisible_if(
+            !matches!(self.state.turn_phase, TurnPhase::MeeplePlacement { .. })
+                || !self.can_play()
+                || !has_special_meeples,
+        );
+        self.retrieve_abbot_button.borrow_mut().state = UIElementState::invisible_if(
+            !matches!(self.state.turn_phase, TurnPhase::MeeplePlacement { .. })
+                || !self.can_play()
+                || self
+                    .state
+                    .game
+                    .abbot_group(self.state.current_player())
+                    .is_none(),
+        );
+        self.build_tower_button.borrow_mut().state = UIElementState::invisible_if(
+            !matches!(self.state.turn_phase, TurnPhase::MeeplePlacement { .. })
+                || !self.can_play()
+                || player.tower_pieces == 0,
+        );
+        let flock_extended = matches!(self.state.turn_phase, TurnPhase::MeeplePlacement { .. })
+            && self.can_play()
+            && self.state.extended_flock().is_some();
+        self.expand_flock_button.borrow_mut().state = UIElementState::invisible_if(!flock_extended);
+        self.herd_flock_button.borrow_mut().state = UIElementState::invisible_if(!flock_extended);
+        self.ransom_button.borrow_mut().state = match self.ransom_captor() {
+            Some(captor) if self.can_play() => {
+                UIElementState::disabled_if(self.state.validate_ransom(captor).is_err())
+            }
+            _ => UIElementState::Invisible,
+        };
+        self.return_to_main_menu_button.borrow_mut().state = UIElementState::invisible_if(
+            !self.state.is_endgame() || self.end_game_next_tick.is_some() || self.spectating,
+        );
+        self.history_button.borrow_mut().state = UIElementState::invisible_if(self.spectating);
+        let can_vote = self.rewind_vote.as_ref().is_some_and(|vote| {
+            self.local_seat()
+                .is_some_and(|seat| !vote.agreed.contains(&seat))
+        });
+        self.agree_button.borrow_mut().state = UIElementState::invisible_if(!can_vote);
+        self.refuse_button.borrow_mut().state = UIElementState::invisible_if(!can_vote);
         self.begin_game_button.borrow_mut().state = match &self.state.turn_phase {
             TurnPhase::Pregame {
                 open_edges, held, ..
@@ -1886,7 +2663,9 @@ impl SubEventHandler for GameClient {
 
         self.board_movement_update(ctx)?;
 
-        self.pause_menu_activation_update(ctx);
+        if !self.spectating {
+            self.pause_menu_activation_update(ctx);
+        }
 
         // update scoring effects
         self.scoring_effects.retain(|effect| {
@@ -1895,7 +2674,11 @@ impl SubEventHandler for GameClient {
 
         self.group_inspection_update(ctx);
 
-        if self.inspecting_groups.is_none() || self.is_endgame() {
+        self.bot_update(ctx)?;
+
+        let over_history = self.history_panel_update(ctx, &mut on_clickable)?;
+
+        if (self.inspecting_groups.is_none() || self.state.is_endgame()) && !over_history {
             self.turn_phase_update(ctx, &mut on_clickable)?;
         } else {
             self.set_selected_square(None);
@@ -1910,7 +2693,7 @@ impl SubEventHandler for GameClient {
 
     fn draw(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
         for (pos, tile) in &self.state.game.placed_tiles {
-            tile.render(ctx, canvas, self.grid_pos_rect(pos, ctx))?;
+            self.render_tile(ctx, canvas, tile, self.grid_pos_rect(pos, ctx))?;
         }
 
         self.draw_meeples(ctx, canvas)?;
@@ -1927,6 +2710,12 @@ impl SubEventHandler for GameClient {
 
         self.draw_game_details(ctx, canvas)?;
 
+        self.draw_history_panel(ctx, canvas)?;
+
+        self.draw_rewind_vote(ctx, canvas)?;
+
+        self.draw_notice(ctx, canvas)?;
+
         self.draw_player_color_outline(ctx, canvas)?;
 
         if let Some(pause_menu) = &mut self.pause_menu {
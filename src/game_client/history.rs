@@ -0,0 +1,541 @@
+use ggez::graphics::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        rules::{Events, GameState, RuleError, RuleEvent},
+        PlayerIdentifier, SegmentIdentifier, SheepToken,
+    },
+    multiplayer::message::{server::UndoPolicy, GameMessage},
+    pos::GridPos,
+    replay::Replay,
+    tile::{SegmentType, Tile},
+};
+
+use super::{NUM_PLAYERS, PLAYER_COLORS};
+
+const PLAYER_COLOR_NAMES: [&str; NUM_PLAYERS] = ["Red", "Yellow", "Blue", "Green", "Black", "Gray"];
+
+/// A move that can be taken back.
+pub struct HistoryEntry {
+    /// The game on the other side of the move: as it was before the move while the move
+    /// stands, and as it was after the move once it's been undone.
+    state: GameState,
+    pub message: GameMessage,
+    /// Who made the move.
+    pub player: PlayerIdentifier,
+    /// What happened in the move, for the history panel.
+    pub description: Vec<String>,
+}
+
+/// The moves made in a game that can be taken back, along with the moves taken back
+/// since the last new move, so that they can be made again.
+#[derive(Default)]
+pub struct History {
+    done: Vec<HistoryEntry>,
+    /// Most recently undone last.
+    undone: Vec<HistoryEntry>,
+}
+
+/// The moves of a [`History`] without the games between them, for a client to rebuild
+/// the history from when it takes up the host's game.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    /// The game before the first move, which is the game as it stands if none were made.
+    pub start: GameState,
+    pub done: Vec<GameMessage>,
+    /// In the order they were originally made.
+    pub undone: Vec<GameMessage>,
+}
+
+impl History {
+    /// Records a move made from `before`. Making a new move gives up on redoing the moves
+    /// that were undone.
+    pub fn push(
+        &mut self,
+        before: GameState,
+        message: GameMessage,
+        player: PlayerIdentifier,
+        description: Vec<String>,
+    ) {
+        self.undone.clear();
+        self.done.push(HistoryEntry {
+            state: before,
+            message,
+            player,
+            description,
+        });
+    }
+
+    /// The moves that stand, oldest first.
+    pub fn done(&self) -> &[HistoryEntry] {
+        &self.done
+    }
+
+    /// The moves that were undone, in the order they were originally made.
+    pub fn undone(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.undone.iter().rev()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Takes back the last move, returning the game to how it was before it.
+    pub fn undo(&mut self, state: &mut GameState) -> Option<&HistoryEntry> {
+        let mut entry = self.done.pop()?;
+        std::mem::swap(state, &mut entry.state);
+        self.undone.push(entry);
+        self.undone.last()
+    }
+
+    /// Makes the last undone move again, returning the game to how it was after it.
+    pub fn redo(&mut self, state: &mut GameState) -> Option<&HistoryEntry> {
+        let mut entry = self.undone.pop()?;
+        std::mem::swap(state, &mut entry.state);
+        self.done.push(entry);
+        self.done.last()
+    }
+
+    /// Takes back the last move for good, as if it had never been made.
+    pub fn discard(&mut self, state: &mut GameState) -> Option<HistoryEntry> {
+        let mut entry = self.done.pop()?;
+        std::mem::swap(state, &mut entry.state);
+        Some(entry)
+    }
+
+    /// Takes a move the host turned down back out of the game and its replay, if it's the
+    /// last move made. Any made after it are left for the resync that follows.
+    pub fn take_back(
+        &mut self,
+        state: &mut GameState,
+        replay: Option<&mut Replay>,
+        message: &GameMessage,
+    ) {
+        if self
+            .done
+            .last()
+            .is_some_and(|entry| entry.message == *message)
+        {
+            self.discard(state);
+            if let Some(replay) = replay {
+                replay.undo();
+            }
+        }
+    }
+
+    /// The moves of the history, starting from `state` if none of them stand.
+    pub fn record(&self, state: &GameState) -> HistoryRecord {
+        HistoryRecord {
+            start: self
+                .done
+                .first()
+                .map_or_else(|| state.clone(), |entry| entry.state.clone()),
+            done: self
+                .done
+                .iter()
+                .map(|entry| entry.message.clone())
+                .collect(),
+            undone: self.undone().map(|entry| entry.message.clone()).collect(),
+        }
+    }
+
+    /// Makes the moves of a record again, returning the history they make up and the
+    /// game as it stands at the end of it.
+    pub fn rebuild(record: HistoryRecord) -> Result<(History, GameState), RuleError> {
+        let HistoryRecord {
+            start: mut state,
+            done,
+            undone,
+        } = record;
+        let mut history = History::default();
+        for message in done.into_iter().chain(undone.iter().cloned()) {
+            let before = state.clone();
+            let player = state.current_player();
+            let events = state.apply(message.clone())?;
+            let description = describe(&state, player, &events);
+            history.push(before, message, player, description);
+        }
+        for _ in &undone {
+            history.undo(&mut state);
+        }
+        Ok((history, state))
+    }
+
+    /// Whether `player` may have the moves `message` asks for taken back or made again,
+    /// under the undo policy. Under the voting policy anyone may ask, since the other
+    /// players get their say before anything happens.
+    pub fn allows(
+        &self,
+        message: &GameMessage,
+        player: PlayerIdentifier,
+        current_player: PlayerIdentifier,
+        policy: UndoPolicy,
+    ) -> bool {
+        let (steps, moves): (_, Vec<_>) = match message {
+            GameMessage::Undo(steps) => (*steps, self.done.iter().rev().take(*steps).collect()),
+            GameMessage::Redo(steps) => (*steps, self.undone.iter().rev().take(*steps).collect()),
+            _ => return false,
+        };
+        if steps == 0 || moves.len() < steps {
+            return false;
+        }
+        match policy {
+            UndoPolicy::Allowed | UndoPolicy::Vote => true,
+            UndoPolicy::OwnTurn => {
+                player == current_player && moves.iter().all(|entry| entry.player == player)
+            }
+        }
+    }
+}
+
+/// The name of a player's color, for describing what they did.
+pub fn color_name(color: Color) -> &'static str {
+    PLAYER_COLORS
+        .iter()
+        .position(|player_color| *player_color == color)
+        .map_or("Someone", |i| PLAYER_COLOR_NAMES[i])
+}
+
+/// Describes a tile by what's on it, such as "City and Road".
+fn tile_name(tile: &Tile) -> String {
+    use SegmentType::*;
+    let features: Vec<_> = [Monastary, Garden, City, Village, Road, River]
+        .into_iter()
+        .filter(|stype| tile.segments.iter().any(|segment| segment.stype == *stype))
+        .map(|stype| stype.name())
+        .collect();
+    match features.as_slice() {
+        [] => Farm.name().to_string(),
+        [feature] => feature.to_string(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+    }
+}
+
+fn format_pos(GridPos(x, y): GridPos) -> String {
+    format!("({x},{y})")
+}
+
+/// Describes what happened in a move, from the game after it and the events it caused.
+pub fn describe(state: &GameState, player: PlayerIdentifier, events: &Events) -> Vec<String> {
+    let name = |player: PlayerIdentifier| color_name(state.game.players[player].color);
+    let segment_type = |(pos, i): SegmentIdentifier| {
+        state
+            .game
+            .placed_tiles
+            .get(&pos)
+            .map_or(SegmentType::Farm, |tile| tile.segments[i].stype)
+            .name()
+            .to_lowercase()
+    };
+    let mut lines = Vec::new();
+    for event in events {
+        match event {
+            RuleEvent::TilePlaced { player, pose } => {
+                if let Some(tile) = state.game.placed_tiles.get(&pose.position) {
+                    lines.push(format!(
+                        "{} placed {} at {}",
+                        name(*player),
+                        tile_name(tile),
+                        format_pos(pose.position)
+                    ));
+                }
+            }
+            RuleEvent::MeeplePlaced { player, seg_ident } => lines.push(format!(
+                "{} placed a meeple on a {}",
+                name(*player),
+                segment_type(*seg_ident)
+            )),
+            RuleEvent::MeeplesSkipped { player } => {
+                lines.push(format!("{} placed no meeple", name(*player)))
+            }
+            RuleEvent::AbbotRetrieved { player } => {
+                lines.push(format!("{} retrieved their abbot", name(*player)))
+            }
+            RuleEvent::FairyMoved { player, .. } => {
+                lines.push(format!("{} moved the fairy", name(*player)))
+            }
+            RuleEvent::KnightDismissed { player, .. } => lines.push(format!(
+                "{} had the princess dismiss a knight",
+                name(*player)
+            )),
+            RuleEvent::DragonMoved { pos, eaten } => lines.push(match eaten {
+                0 => format!("The dragon moved to {}", format_pos(*pos)),
+                eaten => format!(
+                    "The dragon moved to {} and ate {eaten} meeple(s)",
+                    format_pos(*pos)
+                ),
+            }),
+            RuleEvent::FairyScored { player } => {
+                lines.push(format!("{} scored for the fairy", name(*player)))
+            }
+            RuleEvent::TowerBuilt { player, pos } => lines.push(format!(
+                "{} built a tower at {}",
+                name(*player),
+                format_pos(*pos)
+            )),
+            RuleEvent::MeepleCaptured { player, .. } => {
+                lines.push(format!("{} captured a meeple", name(*player)))
+            }
+            RuleEvent::PrisonerRansomed { player, captor } => lines.push(format!(
+                "{} ransomed a meeple from {}",
+                name(*player),
+                name(*captor)
+            )),
+            RuleEvent::FlockExpanded { player, token } => lines.push(match token {
+                SheepToken::Sheep(count) => {
+                    format!("{}'s flock grew by {count} sheep", name(*player))
+                }
+                SheepToken::Wolf => format!("A wolf scattered {}'s flock", name(*player)),
+            }),
+            RuleEvent::FlockHerded { results } => lines.extend(results.iter().map(|result| {
+                format!(
+                    "{} herded their flock for {}",
+                    color_name(result.meeple_color),
+                    result.score
+                )
+            })),
+            RuleEvent::GroupScored { group, results } => {
+                let gtype = state
+                    .game
+                    .groups
+                    .get(*group)
+                    .map_or("group".to_string(), |group| {
+                        group.gtype.name().to_lowercase()
+                    });
+                lines.extend(results.iter().map(|result| {
+                    format!(
+                        "{} scored {gtype} for {}",
+                        color_name(result.meeple_color),
+                        result.score
+                    )
+                }))
+            }
+            RuleEvent::GoodsScored { winners } => {
+                lines.extend(winners.iter().map(|(player, good)| {
+                    format!("{} won the most {}", name(*player), good.name())
+                }))
+            }
+            RuleEvent::BonusTurn { player } => {
+                lines.push(format!("{} earned a bonus turn", name(*player)))
+            }
+            RuleEvent::GameEnded => lines.push("The game ended".to_string()),
+            RuleEvent::GameBegan | RuleEvent::TurnEnded { .. } => {}
+        }
+    }
+    if lines.is_empty() {
+        lines.push(format!("{} made a move", name(player)));
+    }
+    lines
+}
+
+/// Describes an undo or redo, such as "undo the last 2 moves".
+pub fn describe_rewind(message: &GameMessage) -> String {
+    let (action, steps) = match message {
+        GameMessage::Undo(steps) => ("undo", *steps),
+        GameMessage::Redo(steps) => ("redo", *steps),
+        _ => return String::new(),
+    };
+    match steps {
+        1 => format!("{action} the last move"),
+        steps => format!("{action} the last {steps} moves"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ggez::graphics::Color;
+
+    use crate::{
+        game::{
+            player::{Player, PlayerType},
+            rules::{GameState, TurnPhase},
+            setup::{GameExpansions, LibraryConfiguration},
+            ScoringRules,
+        },
+        multiplayer::message::{server::UndoPolicy, GameMessage, TilePose},
+        replay::Replay,
+    };
+
+    use super::{describe, History};
+
+    /// The first spot the held tile fits in.
+    fn first_fit(state: &GameState) -> TilePose {
+        let TurnPhase::TilePlacement {
+            tile,
+            placeable_positions,
+            ..
+        } = &state.turn_phase
+        else {
+            panic!("expected a tile to place");
+        };
+        placeable_positions
+            .iter()
+            .find_map(|position| {
+                (0..4).find_map(|rotation| {
+                    let mut tile = tile.clone();
+                    tile.rotate_to(rotation);
+                    state
+                        .game
+                        .is_valid_tile_position(&tile, *position)
+                        .then_some(TilePose {
+                            position: *position,
+                            rotation,
+                        })
+                })
+            })
+            .unwrap()
+    }
+
+    /// Makes a move the way the game client does, recording it in the history and replay.
+    fn make_move(
+        state: &mut GameState,
+        history: &mut History,
+        replay: Option<&mut Replay>,
+        message: GameMessage,
+    ) {
+        let before = state.clone();
+        let player = state.current_player();
+        let events = state.apply(message.clone()).unwrap();
+        if let Some(replay) = replay {
+            replay.record(&message);
+        }
+        history.push(before, message, player, describe(state, player, &events));
+    }
+
+    /// Places the held tile in the first spot it fits, then skips placing a meeple.
+    fn play_turn(state: &mut GameState, history: &mut History) {
+        let pose = first_fit(state);
+        for message in [GameMessage::PlaceTile(pose), GameMessage::SkipMeeples] {
+            make_move(state, history, None, message);
+        }
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut state = GameState::new(
+            5,
+            vec![Player::new(Color::RED), Player::new(Color::BLUE)],
+            &GameExpansions::default(),
+            &LibraryConfiguration::default(),
+            &ScoringRules::default(),
+            PlayerType::Local,
+        );
+        let mut history = History::default();
+        let start = state.game.placed_tiles.len();
+        play_turn(&mut state, &mut history);
+        play_turn(&mut state, &mut history);
+        assert!(history.done()[0].description[0].starts_with("Red placed"));
+        assert_eq!(history.done()[1].description, vec!["Red placed no meeple"]);
+
+        let red = history.done()[0].player;
+        history.undo(&mut state).unwrap();
+        let blue = state.current_player();
+        assert_ne!(red, blue);
+        // blue's tile is still down, but it's their turn to place a meeple
+        assert_eq!(state.game.placed_tiles.len(), start + 2);
+        assert!(history.allows(&GameMessage::Undo(1), blue, blue, UndoPolicy::OwnTurn));
+        assert!(!history.allows(&GameMessage::Undo(2), blue, blue, UndoPolicy::OwnTurn));
+        assert!(!history.allows(&GameMessage::Undo(1), red, blue, UndoPolicy::OwnTurn));
+        assert!(history.allows(&GameMessage::Undo(3), red, blue, UndoPolicy::Allowed));
+        assert!(!history.allows(&GameMessage::Undo(4), red, blue, UndoPolicy::Allowed));
+
+        history.undo(&mut state).unwrap();
+        assert_eq!(state.game.placed_tiles.len(), start + 1);
+        assert!(history.allows(&GameMessage::Redo(2), red, blue, UndoPolicy::Vote));
+        history.redo(&mut state).unwrap();
+        history.redo(&mut state).unwrap();
+        assert!(history.redo(&mut state).is_none());
+        assert_eq!(state.game.placed_tiles.len(), start + 2);
+        assert_eq!(history.done().len(), 4);
+
+        // a new move can't be followed by redoing the ones undone before it
+        history.undo(&mut state).unwrap();
+        history.undo(&mut state).unwrap();
+        play_turn(&mut state, &mut history);
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn rejected_move_leaves_no_trace() {
+        let mut replay = Some(Replay::new(
+            5,
+            &[Player::new(Color::RED), Player::new(Color::BLUE)],
+            PlayerType::Local,
+            GameExpansions::default(),
+            LibraryConfiguration::default(),
+            ScoringRules::default(),
+        ));
+        let mut state = replay.as_ref().unwrap().initial_state();
+        let mut history = History::default();
+        let start = state.game.placed_tiles.len();
+
+        // turned down before anything else was done
+        let place_tile = GameMessage::PlaceTile(first_fit(&state));
+        make_move(
+            &mut state,
+            &mut history,
+            replay.as_mut(),
+            place_tile.clone(),
+        );
+        history.take_back(&mut state, replay.as_mut(), &place_tile);
+        assert!(history.is_empty());
+        assert!(replay.as_ref().unwrap().messages.is_empty());
+        assert_eq!(state.game.placed_tiles.len(), start);
+
+        // turned down after the meeple was skipped too, which the resync clears up
+        for message in [place_tile.clone(), GameMessage::SkipMeeples] {
+            make_move(&mut state, &mut history, replay.as_mut(), message);
+        }
+        history.take_back(&mut state, replay.as_mut(), &place_tile);
+        assert_eq!(history.done().len(), 2);
+    }
+
+    #[test]
+    fn rebuild_from_record() {
+        let mut host_state = GameState::new(
+            5,
+            vec![Player::new(Color::RED), Player::new(Color::BLUE)],
+            &GameExpansions::default(),
+            &LibraryConfiguration::default(),
+            &ScoringRules::default(),
+            PlayerType::Local,
+        );
+        let mut host_history = History::default();
+        play_turn(&mut host_state, &mut host_history);
+        play_turn(&mut host_state, &mut host_history);
+        host_history.undo(&mut host_state).unwrap();
+
+        let (mut history, mut state) = History::rebuild(host_history.record(&host_state)).unwrap();
+        assert_eq!(history.done().len(), 3);
+        assert_eq!(
+            history.done()[0].description,
+            host_history.done()[0].description
+        );
+        assert!(history.can_redo());
+        assert_eq!(state.current_player(), host_state.current_player());
+        assert_eq!(
+            state.game.placed_tiles.len(),
+            host_state.game.placed_tiles.len()
+        );
+
+        // every move the host can take back can be taken back here too
+        for _ in 0..3 {
+            host_history.undo(&mut host_state).unwrap();
+            history.undo(&mut state).unwrap();
+        }
+        assert_eq!(
+            state.game.placed_tiles.len(),
+            host_state.game.placed_tiles.len()
+        );
+        history.redo(&mut state).unwrap();
+        assert!(matches!(
+            state.turn_phase,
+            TurnPhase::MeeplePlacement { .. }
+        ));
+    }
+}
@@ -45,6 +45,7 @@ pub struct PauseScreenSubclient {
     shared: Shared,
     pub can_end_game: Rc<Cell<bool>>,
     pub can_undo: Rc<Cell<bool>>,
+    pub can_redo: Rc<Cell<bool>>,
 }
 
 impl PauseScreenSubclient {
@@ -53,11 +54,13 @@ impl PauseScreenSubclient {
         parent_channel: Sender<GameEvent>,
         can_end_game: bool,
         can_undo: bool,
+        can_redo: bool,
     ) -> PauseScreenSubclient {
         let (event_sender, event_receiver) = channel();
         let ui_sender = event_sender.clone();
         let can_end_game = Rc::new(Cell::new(can_end_game));
         let can_undo = Rc::new(Cell::new(can_undo));
+        let can_redo = Rc::new(Cell::new(can_redo));
         PauseScreenSubclient {
             parent_channel,
             scene: Box::new(MainPauseMenuSubclient::new(
@@ -65,12 +68,14 @@ impl PauseScreenSubclient {
                 event_sender.clone(),
                 can_end_game.clone(),
                 can_undo.clone(),
+                can_redo.clone(),
             )),
             event_sender,
             event_receiver,
             shared,
             can_end_game,
             can_undo,
+            can_redo,
             ui: UIManager::new(
                 ui_sender,
                 [UIElement::Button(Button::new(
@@ -97,6 +102,7 @@ impl PauseScreenSubclient {
                     self.event_sender.clone(),
                     self.can_end_game.clone(),
                     self.can_undo.clone(),
+                    self.can_redo.clone(),
                 ))
             }
             Controls => {
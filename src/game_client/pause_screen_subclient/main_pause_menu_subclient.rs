@@ -9,7 +9,9 @@ use std::{
     sync::mpsc::{channel, Receiver, Sender},
 };
 
-use crate::{game_client::GameEvent, main_client::MainEvent, shared::Shared};
+use crate::{
+    game_client::GameEvent, main_client::MainEvent, shared::Shared, tile::texture_pack::TexturePack,
+};
 
 use ggez_no_re::{
     sub_event_handler::SubEventHandler,
@@ -21,6 +23,7 @@ use super::PauseScreenEvent;
 #[derive(Debug, Clone)]
 pub enum MainPauseMenuEvent {
     PauseScreenEvent(PauseScreenEvent),
+    CycleTexturePack,
 }
 
 impl MainPauseMenuEvent {
@@ -41,8 +44,11 @@ pub struct MainPauseMenuSubclient {
     ui: UIManager<MainPauseMenuEvent, MainPauseMenuEvent>,
     can_end_game: Rc<Cell<bool>>,
     can_undo: Rc<Cell<bool>>,
+    can_redo: Rc<Cell<bool>>,
     end_game_button: Rc<RefCell<Button<MainPauseMenuEvent>>>,
     undo_button: Rc<RefCell<Button<MainPauseMenuEvent>>>,
+    redo_button: Rc<RefCell<Button<MainPauseMenuEvent>>>,
+    texture_pack_button: Rc<RefCell<Button<MainPauseMenuEvent>>>,
 }
 
 impl MainPauseMenuSubclient {
@@ -51,69 +57,98 @@ impl MainPauseMenuSubclient {
         parent_channel: Sender<PauseScreenEvent>,
         can_end_game: Rc<Cell<bool>>,
         can_undo: Rc<Cell<bool>>,
+        can_redo: Rc<Cell<bool>>,
     ) -> MainPauseMenuSubclient {
         let (event_sender, event_receiver) = channel();
         let ui_sender = event_sender.clone();
         let button_center = Rect::new(0.5, 0.2, 0.0, 0.0);
-        let (ui, [UIElement::Button(end_game_button), _, _, UIElement::Button(undo_button), ..]) =
-            UIManager::new_and_rc_elements(
-                ui_sender,
-                [
-                    UIElement::Button(Button::new(
-                        Bounds {
-                            relative: button_center,
-                            absolute: Rect::new(-250.0, 0.0, 240.0, 40.0),
-                        },
-                        Text::new("End Game"),
-                        MainPauseMenuEvent::game_event(GameEvent::EndGame),
-                    )),
-                    UIElement::Button(Button::new(
-                        Bounds {
-                            relative: button_center,
-                            absolute: Rect::new(10.0, 0.0, 240.0, 40.0),
-                        },
-                        Text::new("Reset Camera"),
-                        MainPauseMenuEvent::game_event(GameEvent::ResetCamera),
-                    )),
-                    UIElement::Button(Button::new(
-                        Bounds {
-                            relative: button_center,
-                            absolute: Rect::new(-250.0, 60.0, 240.0, 40.0),
-                        },
-                        Text::new("Return to Main Menu"),
-                        MainPauseMenuEvent::main_event(MainEvent::MainMenu),
-                    )),
-                    UIElement::Button(Button::new(
-                        Bounds {
-                            relative: button_center,
-                            absolute: Rect::new(10.0, 60.0, 240.0, 40.0),
-                        },
-                        Text::new("Undo Last Move"),
-                        MainPauseMenuEvent::game_event(GameEvent::Undo),
-                    )),
-                    UIElement::Button(Button::new(
-                        Bounds {
-                            relative: button_center,
-                            absolute: Rect::new(-250.0, 120.0, 240.0, 40.0),
-                        },
-                        Text::new("Controls"),
-                        MainPauseMenuEvent::PauseScreenEvent(PauseScreenEvent::Controls),
-                    )),
-                    UIElement::Button(Button::new(
-                        Bounds {
-                            relative: button_center,
-                            absolute: Rect::new(10.0, 120.0, 240.0, 40.0),
-                        },
-                        Text::new("Rules"),
-                        MainPauseMenuEvent::PauseScreenEvent(PauseScreenEvent::Rules),
-                    )),
-                ],
-            )
+        let (
+            ui,
+            [UIElement::Button(end_game_button), _, _, UIElement::Button(undo_button), UIElement::Button(redo_button), _, _, UIElement::Button(texture_pack_button), _],
+        ) = UIManager::new_and_rc_elements(
+            ui_sender,
+            [
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: button_center,
+                        absolute: Rect::new(-250.0, 0.0, 240.0, 40.0),
+                    },
+                    Text::new("End Game"),
+                    MainPauseMenuEvent::game_event(GameEvent::EndGame),
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: button_center,
+                        absolute: Rect::new(10.0, 0.0, 240.0, 40.0),
+                    },
+                    Text::new("Reset Camera"),
+                    MainPauseMenuEvent::game_event(GameEvent::ResetCamera),
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: button_center,
+                        absolute: Rect::new(-250.0, 60.0, 240.0, 40.0),
+                    },
+                    Text::new("Return to Main Menu"),
+                    MainPauseMenuEvent::main_event(MainEvent::MainMenu),
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: button_center,
+                        absolute: Rect::new(10.0, 60.0, 115.0, 40.0),
+                    },
+                    Text::new("Undo"),
+                    MainPauseMenuEvent::game_event(GameEvent::Undo),
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: button_center,
+                        absolute: Rect::new(135.0, 60.0, 115.0, 40.0),
+                    },
+                    Text::new("Redo"),
+                    MainPauseMenuEvent::game_event(GameEvent::Redo),
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: button_center,
+                        absolute: Rect::new(-250.0, 120.0, 240.0, 40.0),
+                    },
+                    Text::new("Controls"),
+                    MainPauseMenuEvent::PauseScreenEvent(PauseScreenEvent::Controls),
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: button_center,
+                        absolute: Rect::new(10.0, 120.0, 240.0, 40.0),
+                    },
+                    Text::new("Rules"),
+                    MainPauseMenuEvent::PauseScreenEvent(PauseScreenEvent::Rules),
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: button_center,
+                        absolute: Rect::new(-250.0, 180.0, 500.0, 40.0),
+                    },
+                    Text::new(""),
+                    MainPauseMenuEvent::CycleTexturePack,
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: button_center,
+                        absolute: Rect::new(-250.0, 240.0, 500.0, 40.0),
+                    },
+                    Text::new("Export Board"),
+                    MainPauseMenuEvent::game_event(GameEvent::ExportBoard),
+                )),
+            ],
+        )
         else {
             panic!()
         };
         end_game_button.borrow_mut().state = UIElementState::disabled_if(can_end_game.get());
         undo_button.borrow_mut().state = UIElementState::disabled_if(!can_undo.get());
+        redo_button.borrow_mut().state = UIElementState::disabled_if(!can_redo.get());
+        texture_pack_button.borrow_mut().text = texture_pack_label(&shared);
         MainPauseMenuSubclient {
             shared,
             parent_channel,
@@ -121,9 +156,12 @@ impl MainPauseMenuSubclient {
             event_receiver,
             ui,
             can_undo,
+            can_redo,
             can_end_game,
             end_game_button,
             undo_button,
+            redo_button,
+            texture_pack_button,
         }
     }
 
@@ -136,16 +174,40 @@ impl MainPauseMenuSubclient {
         use MainPauseMenuEvent::*;
         match event {
             PauseScreenEvent(event) => self.parent_channel.send(event).unwrap(),
+            CycleTexturePack => {
+                // cycles through each pack found on disk, then back to plain tiles
+                let packs = TexturePack::list(&self.shared.args.texture_packs);
+                let current = self.shared.persistent.borrow().texture_pack.clone();
+                let next =
+                    match current.and_then(|name| packs.iter().position(|pack| *pack == name)) {
+                        Some(i) => packs.get(i + 1).cloned(),
+                        None => packs.first().cloned(),
+                    };
+                self.shared.select_texture_pack(next);
+                self.texture_pack_button.borrow_mut().text = texture_pack_label(&self.shared);
+            }
         }
         Ok(())
     }
 }
 
+fn texture_pack_label(shared: &Shared) -> Text {
+    Text::new(format!(
+        "Texture Pack: {}",
+        shared
+            .texture_pack
+            .borrow()
+            .as_ref()
+            .map_or("None", |pack| pack.name.as_str())
+    ))
+}
+
 impl SubEventHandler for MainPauseMenuSubclient {
     fn update(&mut self, ctx: &mut Context) -> Result<(), GameError> {
         self.end_game_button.borrow_mut().state =
             UIElementState::disabled_if(self.can_end_game.get());
         self.undo_button.borrow_mut().state = UIElementState::disabled_if(!self.can_undo.get());
+        self.redo_button.borrow_mut().state = UIElementState::disabled_if(!self.can_redo.get());
         self.ui.update(ctx)?;
 
         if self
@@ -8,44 +8,140 @@ use ggez_no_re::{
     util::AnchorPoint,
 };
 
-use crate::game_client::GameExpansions;
+use crate::{game_client::GameExpansions, tile::tileset::Tileset};
 
 pub struct GameExpansionsSelector {
     _root: Bounds,
     ui: UIManager,
     rivers_1_checkbox: Rc<RefCell<Checkbox>>,
+    inns_and_cathedrals_checkbox: Rc<RefCell<Checkbox>>,
+    traders_and_builders_checkbox: Rc<RefCell<Checkbox>>,
+    abbots_and_gardens_checkbox: Rc<RefCell<Checkbox>>,
+    rivers_2_checkbox: Rc<RefCell<Checkbox>>,
+    sequential_river_checkbox: Rc<RefCell<Checkbox>>,
+    princess_and_dragon_checkbox: Rc<RefCell<Checkbox>>,
+    tower_checkbox: Rc<RefCell<Checkbox>>,
+    hills_and_sheep_checkbox: Rc<RefCell<Checkbox>>,
+    tileset_checkboxes: Vec<(Tileset, UIManager, Rc<RefCell<Checkbox>>)>,
 }
 
 impl GameExpansionsSelector {
-    pub fn new(root: Bounds) -> Self {
+    pub fn new(root: Bounds, tilesets: Vec<Tileset>) -> Self {
         let Bounds { relative, absolute } = root;
         let (_sender, _) = channel(); // dont like this :(
-        let (ui, [UIElement::Checkbox(rivers_1_checkbox)]) = UIManager::new_and_rc_elements(
+        let (
+            ui,
+            [UIElement::Checkbox(rivers_1_checkbox), UIElement::Checkbox(inns_and_cathedrals_checkbox), UIElement::Checkbox(traders_and_builders_checkbox), UIElement::Checkbox(abbots_and_gardens_checkbox), UIElement::Checkbox(rivers_2_checkbox), UIElement::Checkbox(sequential_river_checkbox), UIElement::Checkbox(princess_and_dragon_checkbox), UIElement::Checkbox(tower_checkbox), UIElement::Checkbox(hills_and_sheep_checkbox)],
+        ) = UIManager::new_and_rc_elements(
             _sender,
-            [UIElement::Checkbox(Checkbox::new(Bounds {
-                relative,
-                absolute: Rect::new(absolute.x + 10.0, absolute.y + 10.0, 20.0, 20.0),
-            }))],
-        ) else {
+            [
+                UIElement::Checkbox(Checkbox::new(Bounds {
+                    relative,
+                    absolute: Rect::new(absolute.x + 10.0, absolute.y + 10.0, 20.0, 20.0),
+                })),
+                UIElement::Checkbox(Checkbox::new(Bounds {
+                    relative,
+                    absolute: Rect::new(absolute.x + 10.0, absolute.y + 40.0, 20.0, 20.0),
+                })),
+                UIElement::Checkbox(Checkbox::new(Bounds {
+                    relative,
+                    absolute: Rect::new(absolute.x + 10.0, absolute.y + 70.0, 20.0, 20.0),
+                })),
+                UIElement::Checkbox(Checkbox::new(Bounds {
+                    relative,
+                    absolute: Rect::new(absolute.x + 10.0, absolute.y + 100.0, 20.0, 20.0),
+                })),
+                UIElement::Checkbox(Checkbox::new(Bounds {
+                    relative,
+                    absolute: Rect::new(absolute.x + 10.0, absolute.y + 130.0, 20.0, 20.0),
+                })),
+                UIElement::Checkbox(Checkbox::new(Bounds {
+                    relative,
+                    absolute: Rect::new(absolute.x + 10.0, absolute.y + 160.0, 20.0, 20.0),
+                })),
+                UIElement::Checkbox(Checkbox::new(Bounds {
+                    relative,
+                    absolute: Rect::new(absolute.x + 10.0, absolute.y + 190.0, 20.0, 20.0),
+                })),
+                UIElement::Checkbox(Checkbox::new(Bounds {
+                    relative,
+                    absolute: Rect::new(absolute.x + 10.0, absolute.y + 220.0, 20.0, 20.0),
+                })),
+                UIElement::Checkbox(Checkbox::new(Bounds {
+                    relative,
+                    absolute: Rect::new(absolute.x + 10.0, absolute.y + 250.0, 20.0, 20.0),
+                })),
+            ],
+        )
+        else {
             panic!()
         };
+        // custom tilesets get a column of their own beside the built in expansions
+        let tileset_checkboxes = tilesets
+            .into_iter()
+            .enumerate()
+            .map(|(i, tileset)| {
+                let (ui, [UIElement::Checkbox(checkbox)]) = UIManager::new_and_rc_elements(
+                    _sender.clone(),
+                    [UIElement::Checkbox(Checkbox::new(Bounds {
+                        relative,
+                        absolute: Rect::new(
+                            absolute.x + 260.0,
+                            absolute.y + 10.0 + i as f32 * 30.0,
+                            20.0,
+                            20.0,
+                        ),
+                    }))],
+                ) else {
+                    panic!()
+                };
+                (tileset, ui, checkbox)
+            })
+            .collect();
         GameExpansionsSelector {
             _root: root,
             ui,
             rivers_1_checkbox,
+            inns_and_cathedrals_checkbox,
+            traders_and_builders_checkbox,
+            abbots_and_gardens_checkbox,
+            rivers_2_checkbox,
+            sequential_river_checkbox,
+            princess_and_dragon_checkbox,
+            tower_checkbox,
+            hills_and_sheep_checkbox,
+            tileset_checkboxes,
         }
     }
 
     pub fn get_selected_expansions(&self) -> GameExpansions {
         GameExpansions {
             rivers_1: self.rivers_1_checkbox.borrow().checked,
+            rivers_2: self.rivers_2_checkbox.borrow().checked,
+            sequential_river: self.sequential_river_checkbox.borrow().checked,
+            inns_and_cathedrals: self.inns_and_cathedrals_checkbox.borrow().checked,
+            traders_and_builders: self.traders_and_builders_checkbox.borrow().checked,
+            abbots_and_gardens: self.abbots_and_gardens_checkbox.borrow().checked,
+            princess_and_dragon: self.princess_and_dragon_checkbox.borrow().checked,
+            tower: self.tower_checkbox.borrow().checked,
+            hills_and_sheep: self.hills_and_sheep_checkbox.borrow().checked,
+            tilesets: self
+                .tileset_checkboxes
+                .iter()
+                .filter(|(_, _, checkbox)| checkbox.borrow().checked)
+                .map(|(tileset, _, _)| tileset.clone())
+                .collect(),
         }
     }
 }
 
 impl SubEventHandler for GameExpansionsSelector {
     fn update(&mut self, ctx: &mut ggez::Context) -> Result<(), ggez::GameError> {
-        self.ui.update(ctx)
+        self.ui.update(ctx)?;
+        for (_, ui, _) in &mut self.tileset_checkboxes {
+            ui.update(ctx)?;
+        }
+        Ok(())
     }
 
     fn draw(
@@ -61,6 +157,64 @@ impl SubEventHandler for GameExpansionsSelector {
             &Text::new(" River Expansion 1"),
             AnchorPoint::CenterEast,
         )?;
+        self.inns_and_cathedrals_checkbox.borrow().render_label(
+            ctx,
+            canvas,
+            &Text::new(" Inns & Cathedrals"),
+            AnchorPoint::CenterEast,
+        )?;
+        self.traders_and_builders_checkbox.borrow().render_label(
+            ctx,
+            canvas,
+            &Text::new(" Traders & Builders"),
+            AnchorPoint::CenterEast,
+        )?;
+        self.abbots_and_gardens_checkbox.borrow().render_label(
+            ctx,
+            canvas,
+            &Text::new(" Abbots & Gardens"),
+            AnchorPoint::CenterEast,
+        )?;
+        self.rivers_2_checkbox.borrow().render_label(
+            ctx,
+            canvas,
+            &Text::new(" River Expansion 2"),
+            AnchorPoint::CenterEast,
+        )?;
+        self.sequential_river_checkbox.borrow().render_label(
+            ctx,
+            canvas,
+            &Text::new(" Draw River Turn by Turn"),
+            AnchorPoint::CenterEast,
+        )?;
+        self.princess_and_dragon_checkbox.borrow().render_label(
+            ctx,
+            canvas,
+            &Text::new(" Princess & Dragon"),
+            AnchorPoint::CenterEast,
+        )?;
+        self.tower_checkbox.borrow().render_label(
+            ctx,
+            canvas,
+            &Text::new(" The Tower"),
+            AnchorPoint::CenterEast,
+        )?;
+        self.hills_and_sheep_checkbox.borrow().render_label(
+            ctx,
+            canvas,
+            &Text::new(" Hills & Sheep"),
+            AnchorPoint::CenterEast,
+        )?;
+
+        for (tileset, ui, checkbox) in &mut self.tileset_checkboxes {
+            ui.draw(ctx, canvas)?;
+            checkbox.borrow().render_label(
+                ctx,
+                canvas,
+                &Text::new(format!(" {}", tileset.expansion)),
+                AnchorPoint::CenterEast,
+            )?;
+        }
 
         Ok(())
     }
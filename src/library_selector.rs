@@ -0,0 +1,398 @@
+use core::panic;
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::mpsc::{channel, Receiver},
+    time::{Duration, Instant},
+};
+
+use ggez::{
+    glam::{vec2, Vec2},
+    graphics::{Canvas, Color, DrawMode, Mesh, Rect, Text},
+    Context, GameError,
+};
+use ggez_no_re::{
+    sub_event_handler::SubEventHandler,
+    ui_manager::{
+        button::Button, text_input::TextInput, Bounds, UIElement, UIElementRenderable,
+        UIElementState, UIManager,
+    },
+};
+
+use crate::{
+    colors::PANEL_COLOR,
+    game_client::{LibraryConfiguration, LibraryPreset},
+    tile::Tile,
+    util::{AnchorPoint, ContextExt, DrawableWihParamsExt, TextExt},
+    Shared,
+};
+
+const MESSAGE_DISPLAY_PERIOD: Duration = Duration::from_secs(10);
+const COLUMNS: usize = 6;
+const CELL_SIZE: Vec2 = Vec2::new(145.0, 115.0);
+const TILE_SIZE: f32 = 60.0;
+/// How many generated tiles each click adds or removes.
+const GENERATED_TILES_STEP: usize = 4;
+
+#[derive(Clone, Debug)]
+enum LibraryEvent {
+    Open,
+    Close,
+    AdjustCount(usize, isize),
+    RemoveTile(usize),
+    AdjustGeneratedTiles(isize),
+    ToggleDoubleDeck,
+    Reset,
+    CyclePreset,
+    LoadPreset,
+    SavePreset,
+}
+
+/// A button that opens an overlay for choosing how many of each base game tile go into
+/// the library. While the overlay is open, the parent should stop updating its own ui.
+pub struct LibrarySelector {
+    shared: Shared,
+    event_receiver: Receiver<LibraryEvent>,
+    ui: UIManager<LibraryEvent, LibraryEvent>,
+    panel_ui: UIManager<LibraryEvent, LibraryEvent>,
+    tile_uis: Vec<UIManager<LibraryEvent, LibraryEvent>>,
+    double_deck_button: Rc<RefCell<Button<LibraryEvent>>>,
+    done_button: Rc<RefCell<Button<LibraryEvent>>>,
+    preset_button: Rc<RefCell<Button<LibraryEvent>>>,
+    preset_name_input: Rc<RefCell<TextInput>>,
+    tiles: Vec<Tile>,
+    library: LibraryConfiguration,
+    preset_index: usize,
+    open: bool,
+    message: Option<(String, Instant)>,
+}
+
+impl LibrarySelector {
+    const PANEL_CENTER: Rect = Rect::new(0.5, 0.5, 0.0, 0.0);
+    const PANEL: Rect = Rect::new(-450.0, -320.0, 900.0, 640.0);
+
+    pub fn new(root: Bounds, shared: Shared) -> Self {
+        let Bounds { relative, absolute } = root;
+        let (event_sender, event_receiver) = channel();
+        let (ui, _) = UIManager::new_and_rc_elements(
+            event_sender.clone(),
+            [UIElement::Button(Button::new(
+                Bounds {
+                    relative,
+                    absolute: Rect::new(absolute.x, absolute.y, 220.0, 30.0),
+                },
+                Text::new("Customize Library"),
+                LibraryEvent::Open,
+            ))],
+        );
+        let panel_bounds = |x: f32, y: f32, w: f32, h: f32| Bounds {
+            relative: Self::PANEL_CENTER,
+            absolute: Rect::new(Self::PANEL.x + x, Self::PANEL.y + y, w, h),
+        };
+        let (
+            panel_ui,
+            [UIElement::Button(double_deck_button), _, UIElement::Button(done_button), UIElement::Button(preset_button), _, UIElement::TextInput(preset_name_input), _, _, _],
+        ) = UIManager::new_and_rc_elements(
+            event_sender.clone(),
+            [
+                UIElement::Button(Button::new(
+                    panel_bounds(20.0, 540.0, 200.0, 40.0),
+                    Text::new(""),
+                    LibraryEvent::ToggleDoubleDeck,
+                )),
+                UIElement::Button(Button::new(
+                    panel_bounds(230.0, 540.0, 120.0, 40.0),
+                    Text::new("Reset"),
+                    LibraryEvent::Reset,
+                )),
+                UIElement::Button(Button::new(
+                    panel_bounds(760.0, 540.0, 120.0, 40.0),
+                    Text::new("Done"),
+                    LibraryEvent::Close,
+                )),
+                UIElement::Button(Button::new(
+                    panel_bounds(20.0, 590.0, 200.0, 40.0),
+                    Text::new(""),
+                    LibraryEvent::CyclePreset,
+                )),
+                UIElement::Button(Button::new(
+                    panel_bounds(230.0, 590.0, 140.0, 40.0),
+                    Text::new("Load Preset"),
+                    LibraryEvent::LoadPreset,
+                )),
+                UIElement::TextInput(TextInput::new(panel_bounds(490.0, 598.0, 220.0, 24.0))),
+                UIElement::Button(Button::new(
+                    panel_bounds(720.0, 590.0, 160.0, 40.0),
+                    Text::new("Save Preset"),
+                    LibraryEvent::SavePreset,
+                )),
+                UIElement::Button(Button::new(
+                    panel_bounds(590.0, 540.0, 30.0, 40.0),
+                    Text::new("-"),
+                    LibraryEvent::AdjustGeneratedTiles(-(GENERATED_TILES_STEP as isize)),
+                )),
+                UIElement::Button(Button::new(
+                    panel_bounds(625.0, 540.0, 30.0, 40.0),
+                    Text::new("+"),
+                    LibraryEvent::AdjustGeneratedTiles(GENERATED_TILES_STEP as isize),
+                )),
+            ],
+        )
+        else {
+            panic!()
+        };
+        let tiles: Vec<Tile> = Tile::default_library_tallies()
+            .into_iter()
+            .map(|(tile, _)| tile.clone())
+            .collect();
+        let tile_uis = (0..tiles.len())
+            .map(|i| {
+                let cell = Self::cell_position(i);
+                let (ui, _) = UIManager::new_and_rc_elements(
+                    event_sender.clone(),
+                    [
+                        ("-", 0.0, LibraryEvent::AdjustCount(i, -1)),
+                        ("+", 35.0, LibraryEvent::AdjustCount(i, 1)),
+                        ("x", 70.0, LibraryEvent::RemoveTile(i)),
+                    ]
+                    .map(|(text, x, event)| {
+                        UIElement::Button(Button::new(
+                            panel_bounds(cell.x + x, cell.y + TILE_SIZE + 25.0, 30.0, 24.0),
+                            Text::new(text),
+                            event,
+                        ))
+                    }),
+                );
+                ui
+            })
+            .collect();
+        let mut this = LibrarySelector {
+            shared,
+            event_receiver,
+            ui,
+            panel_ui,
+            tile_uis,
+            double_deck_button,
+            done_button,
+            preset_button,
+            preset_name_input,
+            tiles,
+            library: LibraryConfiguration::default(),
+            preset_index: 0,
+            open: false,
+            message: None,
+        };
+        this.update_buttons();
+        this
+    }
+
+    /// Top left corner of a tile's cell in the grid, relative to the panel.
+    fn cell_position(index: usize) -> Vec2 {
+        vec2(
+            20.0 + (index % COLUMNS) as f32 * CELL_SIZE.x,
+            60.0 + (index / COLUMNS) as f32 * CELL_SIZE.y,
+        )
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn get_selected_library(&self) -> LibraryConfiguration {
+        self.library.clone()
+    }
+
+    fn presets(&self) -> Vec<LibraryPreset> {
+        let mut presets = LibraryPreset::builtin();
+        presets.extend(self.shared.persistent.borrow().library_presets.clone());
+        presets
+    }
+
+    fn total_tiles(&self) -> usize {
+        (self.library.counts.iter().sum::<usize>() + self.library.generated_tiles)
+            * self.library.decks.max(1)
+    }
+
+    fn update_buttons(&mut self) {
+        self.double_deck_button.borrow_mut().text = Text::new(if self.library.decks > 1 {
+            "Double Deck: On"
+        } else {
+            "Double Deck: Off"
+        });
+        self.done_button.borrow_mut().state = UIElementState::disabled_if(self.total_tiles() == 0);
+        let presets = self.presets();
+        self.preset_index %= presets.len();
+        self.preset_button.borrow_mut().text =
+            Text::new(format!("Preset: {}", presets[self.preset_index].name));
+    }
+
+    fn handle_event(&mut self, event: LibraryEvent) -> Result<(), String> {
+        match event {
+            LibraryEvent::Open => self.open = true,
+            LibraryEvent::Close => self.open = false,
+            LibraryEvent::AdjustCount(index, change) => {
+                let count = &mut self.library.counts[index];
+                *count = count.saturating_add_signed(change);
+            }
+            LibraryEvent::RemoveTile(index) => self.library.counts[index] = 0,
+            LibraryEvent::AdjustGeneratedTiles(change) => {
+                let generated_tiles = &mut self.library.generated_tiles;
+                *generated_tiles = generated_tiles.saturating_add_signed(change);
+            }
+            LibraryEvent::ToggleDoubleDeck => {
+                self.library.decks = if self.library.decks > 1 { 1 } else { 2 };
+            }
+            LibraryEvent::Reset => self.library = LibraryConfiguration::default(),
+            LibraryEvent::CyclePreset => self.preset_index += 1,
+            LibraryEvent::LoadPreset => {
+                let preset = self.presets().swap_remove(self.preset_index);
+                preset
+                    .library
+                    .check()
+                    .map_err(|err| format!("Can't load '{}': {err}", preset.name))?;
+                self.library = preset.library;
+                self.preset_name_input.borrow_mut().text = preset.name;
+            }
+            LibraryEvent::SavePreset => {
+                let name = self.preset_name_input.borrow().text.trim().to_string();
+                if name.is_empty() {
+                    return Err("Enter a name for the preset".to_string());
+                }
+                if LibraryPreset::builtin()
+                    .iter()
+                    .any(|preset| preset.name == name)
+                {
+                    return Err(format!("'{name}' is a built in preset"));
+                }
+                let mut persistent = self.shared.persistent.borrow_mut();
+                let preset = LibraryPreset {
+                    name: name.clone(),
+                    library: self.library.clone(),
+                };
+                match persistent
+                    .library_presets
+                    .iter_mut()
+                    .find(|preset| preset.name == name)
+                {
+                    Some(existing) => *existing = preset,
+                    None => persistent.library_presets.push(preset),
+                }
+                drop(persistent);
+                self.preset_index = self
+                    .presets()
+                    .iter()
+                    .position(|preset| preset.name == name)
+                    .unwrap_or(0);
+                self.message = Some((format!("Saved preset '{name}'"), Instant::now()));
+            }
+        }
+        self.update_buttons();
+        Ok(())
+    }
+
+    fn draw_panel(&mut self, ctx: &Context, canvas: &mut Canvas) -> Result<(), GameError> {
+        let res = ctx.res();
+        let origin = res * Vec2::from(Self::PANEL_CENTER.point()) + Vec2::from(Self::PANEL.point());
+        let panel = Rect::new(origin.x, origin.y, Self::PANEL.w, Self::PANEL.h);
+        Mesh::new_rectangle(ctx, DrawMode::fill(), panel, PANEL_COLOR)?.draw(canvas);
+
+        Text::new(format!("Library: {} tiles", self.total_tiles()))
+            .size(32.0)
+            .anchored_by(ctx, origin + vec2(20.0, 15.0), AnchorPoint::NorthWest)?
+            .color(Color::BLACK)
+            .draw(canvas);
+        Text::new("Expansion tiles are added on top")
+            .anchored_by(
+                ctx,
+                origin + vec2(Self::PANEL.w - 20.0, 25.0),
+                AnchorPoint::NorthEast,
+            )?
+            .color(Color::BLACK)
+            .draw(canvas);
+
+        let textures = self.shared.texture_pack.borrow();
+        for (i, (tile, count)) in self.tiles.iter().zip(&self.library.counts).enumerate() {
+            let cell = origin + Self::cell_position(i);
+            tile.render_textured(
+                ctx,
+                canvas,
+                Rect::new(cell.x, cell.y, TILE_SIZE, TILE_SIZE),
+                textures.as_ref(),
+                false,
+            )?;
+            Text::new(format!("x{count}"))
+                .size(24.0)
+                .anchored_by(
+                    ctx,
+                    cell + vec2(TILE_SIZE + 10.0, TILE_SIZE / 2.0),
+                    AnchorPoint::CenterWest,
+                )?
+                .color(if *count == 0 {
+                    Color::from_rgb(96, 0, 0)
+                } else {
+                    Color::BLACK
+                })
+                .draw(canvas);
+        }
+        for ui in &mut self.tile_uis {
+            ui.draw(ctx, canvas)?;
+        }
+
+        self.panel_ui.draw(ctx, canvas)?;
+        Text::new(format!("Generated tiles: {}", self.library.generated_tiles))
+            .anchored_by(ctx, origin + vec2(370.0, 560.0), AnchorPoint::CenterWest)?
+            .color(Color::BLACK)
+            .draw(canvas);
+        self.preset_name_input.borrow().render_label(
+            ctx,
+            canvas,
+            &Text::new("Name:"),
+            AnchorPoint::CenterWest,
+        )?;
+
+        if let Some((message, _)) = &self.message {
+            Text::new(message)
+                .anchored_by(
+                    ctx,
+                    origin + vec2(Self::PANEL.w / 2.0, Self::PANEL.h + 10.0),
+                    AnchorPoint::NorthCenter,
+                )?
+                .color(Color::BLACK)
+                .draw(canvas);
+        }
+        Ok(())
+    }
+}
+
+impl SubEventHandler for LibrarySelector {
+    fn update(&mut self, ctx: &mut Context) -> Result<(), GameError> {
+        if self.open {
+            self.panel_ui.update(ctx)?;
+            for ui in &mut self.tile_uis {
+                ui.update(ctx)?;
+            }
+        } else {
+            self.ui.update(ctx)?;
+        }
+        while let Ok(event) = self.event_receiver.try_recv() {
+            if let Err(message) = self.handle_event(event) {
+                self.message = Some((message, Instant::now()));
+            }
+        }
+
+        if let Some((_, shown)) = self.message {
+            if Instant::now() - shown > MESSAGE_DISPLAY_PERIOD {
+                self.message = None;
+            }
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), GameError> {
+        if self.open {
+            self.draw_panel(ctx, canvas)
+        } else {
+            self.ui.draw(ctx, canvas)
+        }
+    }
+}
@@ -0,0 +1,464 @@
+use std::{
+    fs::read_dir,
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, Receiver, Sender},
+    thread,
+    time::SystemTime,
+};
+
+use ggez::{
+    glam::{vec2, Vec2},
+    graphics::{Canvas, Color, DrawMode, DrawParam, Image, ImageFormat, Mesh, Rect, Text},
+    Context, GameError, GameResult,
+};
+use image::RgbaImage;
+use log::{trace, warn};
+
+use crate::{
+    board_export::BoardImage,
+    colors::PANEL_COLOR,
+    game::player::PlayerType,
+    game_client::GameClient,
+    main_client::MainEvent,
+    replay::load_game,
+    util::{AnchorPoint, ContextExt, DrawableWihParamsExt, SystemTimeExt, TextExt},
+    Shared,
+};
+
+use ggez_no_re::{
+    sub_event_handler::SubEventHandler,
+    ui_manager::{button::Button, Bounds, UIElement, UIElementState, UIManager},
+};
+
+const PAGE_SIZE: usize = 5;
+const ENTRY_WIDTH: f32 = 900.0;
+const ENTRY_HEIGHT: f32 = 130.0;
+const ENTRY_SPACING: f32 = 15.0;
+const THUMBNAIL_SIZE: u32 = 120;
+
+#[derive(Clone, Debug)]
+enum LoadGameEvent {
+    MainEvent(MainEvent),
+    Host(PathBuf),
+    NewerPage,
+    OlderPage,
+}
+
+/// What a save shows about its game in the list.
+struct SaveSummary {
+    replay: bool,
+    /// Colors and scores, in turn order.
+    players: Vec<(Color, usize)>,
+    tiles_remaining: usize,
+    finished: bool,
+    /// Whether any of the seats were played over the network.
+    multiplayer: bool,
+    thumbnail: Option<RgbaImage>,
+}
+
+struct SaveEntry {
+    path: PathBuf,
+    saved_at: String,
+    /// Filled in once the save has been read in the background.
+    summary: Option<Result<SaveSummary, String>>,
+    thumbnail: Option<Image>,
+    ui: UIManager<LoadGameEvent, LoadGameEvent>,
+}
+
+/// Lists the games in the save directory, newest first, to resume one locally or host it
+/// as a multiplayer game.
+pub struct LoadGameClient {
+    parent_channel: Sender<MainEvent>,
+    event_sender: Sender<LoadGameEvent>,
+    event_receiver: Receiver<LoadGameEvent>,
+    shared: Shared,
+    ui: UIManager<LoadGameEvent, LoadGameEvent>,
+    save_dir: PathBuf,
+    saves: Vec<(PathBuf, SystemTime)>,
+    page: usize,
+    entries: Vec<SaveEntry>,
+    summaries: Receiver<(usize, Result<SaveSummary, String>)>,
+}
+
+impl LoadGameClient {
+    pub fn new(ctx: &Context, parent_channel: Sender<MainEvent>, shared: Shared) -> Self {
+        let (event_sender, event_receiver) = channel();
+        let save_dir = shared
+            .args
+            .save_games
+            .clone()
+            .flatten()
+            .unwrap_or_else(|| PathBuf::from("saves/"));
+        let ui = UIManager::new(
+            event_sender.clone(),
+            [
+                UIElement::Button(Button::new(
+                    Bounds::absolute(Rect::new(30.0, 30.0, 120.0, 40.0)),
+                    Text::new("Back"),
+                    LoadGameEvent::MainEvent(MainEvent::MainMenu),
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Rect::new(0.5, 1.0, 0.0, 0.0),
+                        absolute: Rect::new(-250.0, -70.0, 240.0, 40.0),
+                    },
+                    Text::new("Newer"),
+                    LoadGameEvent::NewerPage,
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Rect::new(0.5, 1.0, 0.0, 0.0),
+                        absolute: Rect::new(10.0, -70.0, 240.0, 40.0),
+                    },
+                    Text::new("Older"),
+                    LoadGameEvent::OlderPage,
+                )),
+            ],
+        );
+        let mut this = LoadGameClient {
+            parent_channel,
+            event_sender,
+            event_receiver,
+            shared,
+            ui,
+            saves: Self::find_saves(&save_dir),
+            save_dir,
+            page: 0,
+            entries: Vec::new(),
+            summaries: channel().1,
+        };
+        this.load_page();
+        this
+    }
+
+    fn is_save(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                [".save", ".save.json", ".replay", ".replay.json"]
+                    .iter()
+                    .any(|extension| name.ends_with(extension))
+            })
+    }
+
+    /// Finds the saves in the directory, newest first. Older versions of the game wrote
+    /// a folder of snapshots for each game, so every snapshot in those folders is
+    /// included too.
+    fn find_saves(dir: &Path) -> Vec<(PathBuf, SystemTime)> {
+        let Ok(entries) = read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut saves: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .flat_map(|path| {
+                if path.is_dir() {
+                    read_dir(&path)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                        .filter(|path| Self::is_save(path))
+                        .collect()
+                } else {
+                    Self::is_save(&path).then_some(path).into_iter().collect()
+                }
+            })
+            .filter_map(|path| {
+                let modified = path.metadata().and_then(|meta| meta.modified()).ok()?;
+                Some((path, modified))
+            })
+            .collect();
+        saves.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+        saves
+    }
+
+    fn summarize(path: &Path) -> GameResult<SaveSummary> {
+        let (mut state, replay) = load_game(path)?;
+        let thumbnail = match BoardImage::new(&mut state.game).thumbnail(THUMBNAIL_SIZE) {
+            Ok(image) => Some(image),
+            Err(err) => {
+                warn!("couldn't draw thumbnail for {}: {err}", path.display());
+                None
+            }
+        };
+        Ok(SaveSummary {
+            replay: replay.is_some(),
+            players: state
+                .turn_order
+                .iter()
+                .map(|player| {
+                    let player = &state.game.players[*player];
+                    (player.color, player.score)
+                })
+                .collect(),
+            tiles_remaining: state.game.library.len(),
+            finished: state.is_endgame(),
+            multiplayer: state.game.players.values().any(|player| {
+                matches!(
+                    player.ptype,
+                    PlayerType::MultiplayerHost { .. } | PlayerType::MultiplayerClient { .. }
+                )
+            }),
+            thumbnail,
+        })
+    }
+
+    fn entry_bounds(i: usize) -> Bounds {
+        Bounds {
+            relative: Rect::new(0.5, 0.0, 0.0, 0.0),
+            absolute: Rect::new(
+                -ENTRY_WIDTH / 2.0,
+                160.0 + (ENTRY_HEIGHT + ENTRY_SPACING) * i as f32,
+                ENTRY_WIDTH,
+                ENTRY_HEIGHT,
+            ),
+        }
+    }
+
+    /// Reads the saves on the current page in the background. Only one page is read at a
+    /// time, since replays have to be played through to see how their game ended up.
+    fn load_page(&mut self) {
+        let saves: Vec<_> = self
+            .saves
+            .iter()
+            .skip(self.page * PAGE_SIZE)
+            .take(PAGE_SIZE)
+            .cloned()
+            .collect();
+        self.entries = saves
+            .iter()
+            .enumerate()
+            .map(|(i, (path, modified))| SaveEntry {
+                path: path.clone(),
+                saved_at: modified.strftime("%Y-%m-%d %H:%M"),
+                summary: None,
+                thumbnail: None,
+                ui: self.entry_ui(i, path, false),
+            })
+            .collect();
+        // summaries still coming in for the previous page are dropped along with its receiver
+        let (summary_sender, summaries) = channel();
+        self.summaries = summaries;
+        thread::spawn(move || {
+            for (i, (path, _)) in saves.into_iter().enumerate() {
+                let summary = Self::summarize(&path).map_err(|err| err.to_string());
+                if summary_sender.send((i, summary)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    fn entry_ui(
+        &self,
+        i: usize,
+        path: &Path,
+        enabled: bool,
+    ) -> UIManager<LoadGameEvent, LoadGameEvent> {
+        let bounds = Self::entry_bounds(i).absolute;
+        let button = |y: f32, text: &str, event: LoadGameEvent| {
+            let mut button = Button::new(
+                Bounds {
+                    relative: Rect::new(0.5, 0.0, 0.0, 0.0),
+                    absolute: Rect::new(bounds.right() - 160.0, bounds.y + y, 140.0, 40.0),
+                },
+                Text::new(text),
+                event,
+            );
+            button.state = UIElementState::disabled_if(!enabled);
+            UIElement::Button(button)
+        };
+        UIManager::new(
+            self.event_sender.clone(),
+            [
+                button(
+                    20.0,
+                    "Resume",
+                    LoadGameEvent::MainEvent(MainEvent::LoadGame(path.to_path_buf())),
+                ),
+                button(70.0, "Host", LoadGameEvent::Host(path.to_path_buf())),
+            ],
+        )
+    }
+
+    fn receive_summaries(&mut self, ctx: &Context) {
+        while let Ok((i, summary)) = self.summaries.try_recv() {
+            let ui = self.entry_ui(i, &self.entries[i].path, summary.is_ok());
+            let entry = &mut self.entries[i];
+            entry.thumbnail = summary
+                .as_ref()
+                .ok()
+                .and_then(|summary| summary.thumbnail.as_ref())
+                .map(|image| {
+                    Image::from_pixels(
+                        ctx,
+                        image.as_raw(),
+                        ImageFormat::Rgba8UnormSrgb,
+                        image.width(),
+                        image.height(),
+                    )
+                });
+            entry.summary = Some(summary);
+            entry.ui = ui;
+        }
+    }
+
+    fn page_count(&self) -> usize {
+        self.saves.len().div_ceil(PAGE_SIZE).max(1)
+    }
+
+    fn handle_event(&mut self, event: LoadGameEvent) {
+        trace!("event = {event:?}");
+        match event {
+            LoadGameEvent::MainEvent(event) => self.parent_channel.send(event).unwrap(),
+            LoadGameEvent::Host(path) => {
+                let persistent = self.shared.persistent.borrow();
+                let username = match persistent.username.trim() {
+                    "" => "Host".to_string(),
+                    username => username.to_string(),
+                };
+                self.parent_channel
+                    .send(MainEvent::MultiplayerHost {
+                        username,
+                        port: persistent.host_port,
+                        saved_game: Some(path),
+                    })
+                    .unwrap();
+            }
+            LoadGameEvent::NewerPage => {
+                if self.page > 0 {
+                    self.page -= 1;
+                    self.load_page();
+                }
+            }
+            LoadGameEvent::OlderPage => {
+                if self.page + 1 < self.page_count() {
+                    self.page += 1;
+                    self.load_page();
+                }
+            }
+        }
+    }
+
+    fn draw_entry(
+        &self,
+        ctx: &Context,
+        canvas: &mut Canvas,
+        i: usize,
+        entry: &SaveEntry,
+    ) -> GameResult<()> {
+        let bounds = Self::entry_bounds(i).corrected_bounds(ctx.res());
+        Mesh::new_rounded_rectangle(ctx, DrawMode::fill(), bounds, 8.0, PANEL_COLOR)?.draw(canvas);
+        let origin = Vec2::from(bounds.point());
+        let text_origin = origin + vec2(ENTRY_HEIGHT + 10.0, 10.0);
+        Text::new(&entry.saved_at)
+            .size(24.0)
+            .pos(text_origin)
+            .color(Color::WHITE)
+            .draw(canvas);
+        let summary = match &entry.summary {
+            Some(Ok(summary)) => summary,
+            None => {
+                Text::new("Reading save...")
+                    .pos(text_origin + vec2(0.0, 40.0))
+                    .color(Color::WHITE)
+                    .draw(canvas);
+                return Ok(());
+            }
+            Some(Err(err)) => {
+                Text::new(format!("Couldn't read this save: {err}"))
+                    .pos(text_origin + vec2(0.0, 40.0))
+                    .color(Color::WHITE)
+                    .draw(canvas);
+                return Ok(());
+            }
+        };
+
+        let thumbnail_bounds = Rect::new(
+            origin.x + 5.0,
+            origin.y + 5.0,
+            ENTRY_HEIGHT - 10.0,
+            ENTRY_HEIGHT - 10.0,
+        );
+        if let Some(thumbnail) = &entry.thumbnail {
+            let center: Vec2 = thumbnail_bounds.center().into();
+            canvas.draw(
+                thumbnail,
+                DrawParam::default()
+                    .dest(center - vec2(thumbnail.width() as f32, thumbnail.height() as f32) / 2.0),
+            );
+        }
+
+        let status = if summary.finished {
+            "Finished".to_string()
+        } else {
+            format!("{} tiles remaining", summary.tiles_remaining)
+        };
+        let kind = match (summary.replay, summary.multiplayer) {
+            (true, true) => "Multiplayer replay",
+            (true, false) => "Replay",
+            (false, true) => "Multiplayer save",
+            (false, false) => "Save",
+        };
+        Text::new(format!("{kind}  -  {status}"))
+            .pos(text_origin + vec2(0.0, 34.0))
+            .color(Color::WHITE)
+            .draw(canvas);
+        for (j, (color, score)) in summary.players.iter().enumerate() {
+            let pos = text_origin + vec2(15.0 + 90.0 * j as f32, 80.0);
+            GameClient::draw_meeple(ctx, canvas, pos, *color, 0.12)?;
+            Text::new(format!("{score}"))
+                .anchored_by(ctx, pos + vec2(20.0, 0.0), AnchorPoint::CenterWest)?
+                .color(Color::WHITE)
+                .draw(canvas);
+        }
+        Ok(())
+    }
+}
+
+impl SubEventHandler for LoadGameClient {
+    fn update(&mut self, ctx: &mut Context) -> Result<(), GameError> {
+        self.ui.update(ctx)?;
+        for entry in &mut self.entries {
+            entry.ui.update(ctx)?;
+        }
+        self.receive_summaries(ctx);
+        while let Ok(event) = self.event_receiver.try_recv() {
+            self.handle_event(event);
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), GameError> {
+        let res = ctx.res();
+        Text::new("Load Game")
+            .size(64.0)
+            .centered_on(ctx, res * vec2(0.5, 0.0) + vec2(0.0, 90.0))?
+            .color(Color::BLACK)
+            .draw(canvas);
+        if self.entries.is_empty() {
+            Text::new(format!(
+                "No saved games found in {}; play with --save-games to save them",
+                self.save_dir.display()
+            ))
+            .centered_on(ctx, res * vec2(0.5, 0.0) + vec2(0.0, 220.0))?
+            .color(Color::BLACK)
+            .draw(canvas);
+        }
+        for (i, entry) in self.entries.iter().enumerate() {
+            self.draw_entry(ctx, canvas, i, entry)?;
+        }
+        for entry in &mut self.entries {
+            entry.ui.draw(ctx, canvas)?;
+        }
+        Text::new(format!("Page {} of {}", self.page + 1, self.page_count()))
+            .anchored_by(
+                ctx,
+                res * vec2(0.5, 1.0) + vec2(0.0, -90.0),
+                AnchorPoint::SouthCenter,
+            )?
+            .color(Color::BLACK)
+            .draw(canvas);
+        self.ui.draw(ctx, canvas)?;
+        Ok(())
+    }
+}
@@ -5,13 +5,17 @@
 #![feature(lazy_get)]
 #![feature(generic_const_exprs)]
 
-use std::{path::PathBuf, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
+use board_export::BoardImage;
 use clap::{crate_authors, crate_name, ArgAction, Parser};
 use game::debug_game_configs::DebugGameConfiguration;
 use ggez::{
     conf::{FullscreenType, WindowMode},
-    event, ContextBuilder, GameResult,
+    event, ContextBuilder, GameError, GameResult,
 };
 use ggez_no_re::{
     logger::{LogLevel, LoggerBuilder},
@@ -19,18 +23,30 @@ use ggez_no_re::{
 };
 use log::debug;
 use main_client::MainClient;
+use replay::load_game;
+use save::SaveFormat;
 use shared::Shared;
+use tile::{tileset::Tileset, validation};
 
-mod game_expansions_selector;
+mod board_export;
 mod colors;
 mod game;
 mod game_client;
+mod game_expansions_selector;
+mod library_selector;
+mod load_game_client;
 mod main_client;
 mod main_menu_client;
 mod multiplayer;
 mod pos;
+mod replay;
+mod replay_client;
+mod replay_menu_client;
+mod save;
+mod scoring_rules_selector;
 mod shared;
 mod tile;
+mod tile_editor_client;
 
 const LATEST_RELEASE_LINK: &str = "https://github.com/Maurdekye/carcassonne/releases/latest";
 
@@ -84,13 +100,17 @@ struct Args {
     #[arg(short = 'e', long, default_value = "info")]
     log_level: LogLevel,
 
-    /// Load a save file
+    /// Format to write saves and replays in when saving game progress
+    #[arg(long, value_enum, default_value_t = SaveFormat::Binary)]
+    save_format: SaveFormat,
+
+    /// Load a save file or replay
     #[arg(short, long)]
     load: Option<PathBuf>,
 
-    /// Load this save file when starting a multiplayer game
-    #[arg(short, long)]
-    multiplayer_load: Option<PathBuf>,
+    /// Open this replay in the replay viewer
+    #[arg(long)]
+    replay: Option<PathBuf>,
 
     /// Enables debug mode: increases log level to 'trace',
     /// enables saving log files, and enables saving game state
@@ -100,6 +120,59 @@ struct Args {
     /// Path to save persistent data to.
     #[arg(short, long, default_value = "data.json")]
     save_path: PathBuf,
+
+    /// Directory to load custom tilesets from.
+    #[arg(short, long, default_value = "tilesets/")]
+    tilesets: PathBuf,
+
+    /// Directory to load tile texture packs from; each subdirectory is a pack.
+    #[arg(long, default_value = "texture_packs/")]
+    texture_packs: PathBuf,
+
+    /// Check every tile definition for mistakes, in every rotation, then exit
+    #[arg(long, action = ArgAction::SetTrue)]
+    validate_tiles: bool,
+
+    /// Export the board from the save file given by --load to this .svg or .png file, then exit
+    #[arg(long, requires = "load")]
+    export: Option<PathBuf>,
+
+    /// Directory that boards exported from the pause menu are saved to.
+    #[arg(long, default_value = "exports/")]
+    export_dir: PathBuf,
+
+    /// Size of each tile in exported boards, in pixels.
+    #[arg(long, default_value_t = 128)]
+    export_tile_size: u32,
+}
+
+fn validate_tiles(args: &Args) -> GameResult {
+    let tilesets = Tileset::read_dir(&args.tilesets);
+    let tile_count = validation::all_tiles(&[]).len()
+        + tilesets
+            .iter()
+            .map(|tileset| tileset.tiles.len())
+            .sum::<usize>();
+    let defects = validation::validate_all_tiles(&tilesets);
+    for (label, rotation, defect) in &defects {
+        println!("{label} (rotation {rotation}): {defect}");
+    }
+    if defects.is_empty() {
+        println!("All {tile_count} tiles are valid");
+        Ok(())
+    } else {
+        Err(GameError::CustomError(format!(
+            "Found {} defects across {tile_count} tiles",
+            defects.len()
+        )))
+    }
+}
+
+fn export_board(save: &Path, output: &Path, tile_size: u32) -> GameResult {
+    let (mut state, _) = load_game(save)?;
+    BoardImage::new(&mut state.game).save(output, tile_size)?;
+    println!("Exported {} to {}", save.display(), output.display());
+    Ok(())
 }
 
 fn main() -> GameResult {
@@ -121,6 +194,14 @@ fn main() -> GameResult {
     debug!("Logger initialized");
     debug!("Arguments: {args:#?}");
 
+    if args.validate_tiles {
+        return validate_tiles(&args);
+    }
+
+    if let (Some(save), Some(output)) = (&args.load, &args.export) {
+        return export_board(save, output, args.export_tile_size);
+    }
+
     let shared = Shared::new(args);
 
     let window_mode = if let Some(fullscreen_res) = shared.args.fullscreen {
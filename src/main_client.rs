@@ -14,10 +14,15 @@ use log::{info, trace};
 use crate::{
     game::debug_game_configs::DebugGameConfiguration,
     game_client::{GameClient, GameClientConfiguration},
+    load_game_client::LoadGameClient,
     main_menu_client::MainMenuClient,
     multiplayer::{
         host_client::HostClient, join_client::JoinClient, multiplayer_menu::MultiplayerMenuClient,
     },
+    replay::load_game,
+    replay_client::ReplayClient,
+    replay_menu_client::ReplayMenuClient,
+    tile_editor_client::TileEditorClient,
     Shared,
 };
 
@@ -26,14 +31,20 @@ use ggez_no_re::{sub_event_handler::SubEventHandler, ui_manager};
 #[derive(Clone, Debug)]
 pub enum MainEvent {
     StartGame(GameClientConfiguration),
+    LoadGameMenu,
     LoadGame(PathBuf),
     StartDebugGame(DebugGameConfiguration),
     MainMenu,
     MultiplayerHost {
         username: String,
         port: u16,
+        /// A save or replay to continue, instead of starting a new game.
+        saved_game: Option<PathBuf>,
     },
     MultiplayerMenu,
+    TileEditor,
+    ReplayMenu,
+    WatchReplay(PathBuf),
     MultiplayerJoin {
         username: String,
         socket: SocketAddr,
@@ -57,6 +68,10 @@ impl MainClient {
             event_sender
                 .send(MainEvent::LoadGame(load_path.clone()))
                 .unwrap();
+        } else if let Some(replay_path) = &shared.args.replay {
+            event_sender
+                .send(MainEvent::WatchReplay(replay_path.clone()))
+                .unwrap();
         } else if let Some(debug_config) = &shared.args.debug_game {
             event_sender
                 .send(MainEvent::StartDebugGame(debug_config.clone()))
@@ -93,6 +108,13 @@ impl MainClient {
                 self.quitting = true;
                 ctx.request_quit();
             }
+            MainEvent::LoadGameMenu => {
+                self.scene = Box::new(LoadGameClient::new(
+                    ctx,
+                    self.event_sender.clone(),
+                    self.shared.clone(),
+                ));
+            }
             MainEvent::LoadGame(path) => {
                 self.scene = Box::new(GameClient::load(
                     ctx,
@@ -107,16 +129,26 @@ impl MainClient {
                     ctx,
                     self.shared.clone(),
                     config.get_game()?,
+                    rand::random(),
+                    &config.expansions(),
                     self.event_sender.clone(),
                     None,
-                ));
+                )?);
             }
-            MainEvent::MultiplayerHost { username, port } => {
+            MainEvent::MultiplayerHost {
+                username,
+                port,
+                saved_game,
+            } => {
+                let saved_game = saved_game
+                    .map(|path| load_game(path).map(|(state, _)| state))
+                    .transpose()?;
                 self.scene = Box::new(HostClient::new(
                     self.event_sender.clone(),
                     self.shared.clone(),
                     username,
                     port,
+                    saved_game,
                 ));
             }
             MainEvent::MultiplayerJoin {
@@ -138,6 +170,26 @@ impl MainClient {
                     self.shared.clone(),
                 ));
             }
+            MainEvent::TileEditor => {
+                self.scene = Box::new(TileEditorClient::new(
+                    self.event_sender.clone(),
+                    self.shared.clone(),
+                ));
+            }
+            MainEvent::ReplayMenu => {
+                self.scene = Box::new(ReplayMenuClient::new(
+                    self.event_sender.clone(),
+                    self.shared.clone(),
+                ));
+            }
+            MainEvent::WatchReplay(path) => {
+                self.scene = Box::new(ReplayClient::load(
+                    ctx,
+                    self.shared.clone(),
+                    self.event_sender.clone(),
+                    path,
+                )?);
+            }
         }
         Ok(())
     }
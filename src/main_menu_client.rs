@@ -13,11 +13,14 @@ use ggez::{
 use log::{trace, warn};
 
 use crate::{
+    game::{bot::BotDifficulty, player::PlayerType},
     game_client::{
         GameClient, GameClientConfiguration, PlayerConfiguration, NUM_PLAYERS, PLAYER_COLORS,
     },
     game_expansions_selector::GameExpansionsSelector,
+    library_selector::LibrarySelector,
     main_client::MainEvent,
+    scoring_rules_selector::ScoringRulesSelector,
     util::{AnchorPoint, ContextExt, DrawableWihParamsExt, TextExt},
     Shared,
 };
@@ -31,6 +34,7 @@ use ggez_no_re::{
 enum MainMenuEvent {
     MainEvent(MainEvent),
     SelectColor(Color),
+    CyclePlayerType(usize),
     StartGame,
 }
 
@@ -47,8 +51,13 @@ pub struct MainMenuClient {
     _args: Shared,
     ui: UIManager<MainMenuEvent, MainMenuEvent>,
     expansion_selector: GameExpansionsSelector,
+    scoring_rules_selector: ScoringRulesSelector,
+    library_selector: LibrarySelector,
     color_selection_ui: UIManager<Color, MainMenuEvent>,
     color_selection_buttons: [Rc<RefCell<Button<Color>>>; NUM_PLAYERS],
+    player_type_ui: UIManager<MainMenuEvent, MainMenuEvent>,
+    player_type_buttons: [Rc<RefCell<Button<MainMenuEvent>>>; NUM_PLAYERS],
+    bot_difficulties: [Option<BotDifficulty>; NUM_PLAYERS],
     selected_colors: Vec<Color>,
     start_game_button: Rc<RefCell<Button<MainMenuEvent>>>,
 }
@@ -57,6 +66,7 @@ impl MainMenuClient {
     const BUTTONS_CENTER: Rect = Rect::new(0.5, 0.4, 0.0, 0.0);
     const BUTTON_SIZE: f32 = 40.0;
     const BUTTON_SPACING: f32 = 10.0;
+    const PLAYER_TYPE_BUTTON_HEIGHT: f32 = 24.0;
 
     const SELECTED_COLOR: Color = Color {
         r: 0.5,
@@ -82,7 +92,7 @@ impl MainMenuClient {
                             40.0,
                         ),
                     },
-                    Text::new("Start Local 0 Player Game"),
+                    Text::new("Start Local Game"),
                     MainMenuEvent::StartGame,
                 )),
                 UIElement::Button(Button::new(
@@ -98,6 +108,45 @@ impl MainMenuClient {
                     Text::new("Multiplayer"),
                     MainMenuEvent::MainEvent(MainEvent::MultiplayerMenu),
                 )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Self::BUTTONS_CENTER,
+                        absolute: Rect::new(
+                            -120.0,
+                            Self::BUTTON_SIZE * 2.0 + Self::BUTTON_SPACING + 120.0,
+                            240.0,
+                            40.0,
+                        ),
+                    },
+                    Text::new("Tile Editor"),
+                    MainMenuEvent::MainEvent(MainEvent::TileEditor),
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Self::BUTTONS_CENTER,
+                        absolute: Rect::new(
+                            -120.0,
+                            Self::BUTTON_SIZE * 2.0 + Self::BUTTON_SPACING + 180.0,
+                            240.0,
+                            40.0,
+                        ),
+                    },
+                    Text::new("Watch Replay"),
+                    MainMenuEvent::MainEvent(MainEvent::ReplayMenu),
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: Self::BUTTONS_CENTER,
+                        absolute: Rect::new(
+                            -120.0,
+                            Self::BUTTON_SIZE * 2.0 + Self::BUTTON_SPACING + 240.0,
+                            240.0,
+                            40.0,
+                        ),
+                    },
+                    Text::new("Load Game"),
+                    MainMenuEvent::MainEvent(MainEvent::LoadGameMenu),
+                )),
                 UIElement::Button(Button::new(
                     Bounds {
                         relative: Rect::new(0.5, 1.0, 0.0, 0.0),
@@ -138,8 +187,48 @@ impl MainMenuClient {
                 }),
             )
         };
-        let expansion_selector =
-            GameExpansionsSelector::new(Bounds::relative(Rect::new(0.05, 0.5, 0.0, 0.0)));
+        let (player_type_ui, player_type_buttons) = {
+            let full_width = (Self::BUTTON_SIZE * NUM_PLAYERS as f32)
+                + (Self::BUTTON_SPACING * (NUM_PLAYERS - 1) as f32);
+            let ui_sender = event_sender.clone();
+            UIManager::new_and_rc_elements(
+                ui_sender,
+                std::array::from_fn(|i| {
+                    UIElement::Button(Button::new(
+                        Bounds {
+                            relative: Self::BUTTONS_CENTER,
+                            absolute: Rect::new(
+                                (Self::BUTTON_SIZE + Self::BUTTON_SPACING) * i as f32
+                                    - full_width / 2.0,
+                                -Self::BUTTON_SIZE / 2.0
+                                    - Self::BUTTON_SPACING
+                                    - Self::PLAYER_TYPE_BUTTON_HEIGHT,
+                                Self::BUTTON_SIZE,
+                                Self::PLAYER_TYPE_BUTTON_HEIGHT,
+                            ),
+                        },
+                        Self::player_type_text(None),
+                        MainMenuEvent::CyclePlayerType(i),
+                    ))
+                }),
+            )
+        };
+        let player_type_buttons = player_type_buttons.map(UIElement::unwrap_button);
+        let expansion_selector = GameExpansionsSelector::new(
+            Bounds::relative(Rect::new(0.05, 0.5, 0.0, 0.0)),
+            args.tilesets.borrow().clone(),
+        );
+        let scoring_rules_selector = ScoringRulesSelector::new(Bounds {
+            relative: Rect::new(0.05, 0.5, 0.0, 0.0),
+            absolute: Rect::new(0.0, 280.0, 0.0, 0.0),
+        });
+        let library_selector = LibrarySelector::new(
+            Bounds {
+                relative: Rect::new(0.05, 0.5, 0.0, 0.0),
+                absolute: Rect::new(0.0, 350.0, 0.0, 0.0),
+            },
+            args.clone(),
+        );
         let color_selection_buttons = color_selection_buttons.map(UIElement::unwrap_button);
         MainMenuClient {
             parent_channel,
@@ -148,13 +237,48 @@ impl MainMenuClient {
             _args: args,
             color_selection_ui,
             color_selection_buttons,
+            player_type_ui,
+            player_type_buttons,
+            bot_difficulties: [None; NUM_PLAYERS],
             selected_colors: Vec::new(),
             expansion_selector,
+            scoring_rules_selector,
+            library_selector,
             start_game_button,
             ui,
         }
     }
 
+    fn player_type_text(difficulty: Option<BotDifficulty>) -> Text {
+        let mut text = Text::new(difficulty.map_or("Human", |difficulty| difficulty.name()));
+        text.set_scale(11.0);
+        text
+    }
+
+    fn player_type(&self, color: Color) -> PlayerType {
+        let index = PLAYER_COLORS.iter().position(|c| *c == color).unwrap();
+        match self.bot_difficulties[index] {
+            Some(difficulty) => PlayerType::Bot { difficulty },
+            None => PlayerType::Local,
+        }
+    }
+
+    fn has_human_player(&self) -> bool {
+        self.selected_colors
+            .iter()
+            .any(|color| self.player_type(*color) == PlayerType::Local)
+    }
+
+    fn update_start_game_button(&mut self) {
+        let can_start = self.selected_colors.len() >= 2 && self.has_human_player();
+        let mut start_game_button = self.start_game_button.borrow_mut();
+        start_game_button.state = UIElementState::disabled_if(!can_start);
+        start_game_button.text = Text::new(format!(
+            "Start Local {} Player Game",
+            self.selected_colors.len()
+        ));
+    }
+
     fn handle_event(&mut self, event: MainMenuEvent) -> Result<(), GameError> {
         trace!("event = {event:?}");
         match event {
@@ -174,23 +298,41 @@ impl MainMenuClient {
                     self.selected_colors.push(color);
                     button.color = Self::SELECTED_COLOR;
                 }
-                let mut start_game_button = self.start_game_button.borrow_mut();
-                start_game_button.state =
-                    UIElementState::disabled_if(self.selected_colors.len() < 2);
-                start_game_button.text = Text::new(format!(
-                    "Start Local {} Player Game",
-                    self.selected_colors.len()
-                ));
+                drop(button);
+                self.update_start_game_button();
+            }
+            MainMenuEvent::CyclePlayerType(index) => {
+                let difficulty = &mut self.bot_difficulties[index];
+                *difficulty = match *difficulty {
+                    None => Some(BotDifficulty::ALL[0]),
+                    Some(current) => BotDifficulty::ALL
+                        .iter()
+                        .position(|difficulty| *difficulty == current)
+                        .and_then(|i| BotDifficulty::ALL.get(i + 1))
+                        .cloned(),
+                };
+                self.player_type_buttons[index].borrow_mut().text =
+                    Self::player_type_text(*difficulty);
+                self.update_start_game_button();
             }
             MainMenuEvent::StartGame => {
                 if self.selected_colors.len() < 2 {
                     warn!("Can't start a game with less than two players!");
+                } else if !self.has_human_player() {
+                    warn!("Can't start a game without any human players!");
                 } else {
+                    let players = self
+                        .selected_colors
+                        .iter()
+                        .map(|color| (*color, self.player_type(*color)))
+                        .collect();
                     self.parent_channel
                         .send(MainEvent::StartGame(GameClientConfiguration {
                             seed: rand::random(),
-                            players: PlayerConfiguration::Local(self.selected_colors.clone()),
+                            players: PlayerConfiguration::Local(players),
                             expansions: self.expansion_selector.get_selected_expansions(),
+                            library: self.library_selector.get_selected_library(),
+                            scoring_rules: self.scoring_rules_selector.get_selected_rules(),
                         }))
                         .unwrap()
                 }
@@ -202,9 +344,16 @@ impl MainMenuClient {
 
 impl SubEventHandler for MainMenuClient {
     fn update(&mut self, ctx: &mut ggez::Context) -> Result<(), GameError> {
-        self.ui.update(ctx)?;
-        self.expansion_selector.update(ctx)?;
-        self.color_selection_ui.update(ctx)?;
+        // the library overlay covers the rest of the menu while it's open
+        let library_open = self.library_selector.is_open();
+        self.library_selector.update(ctx)?;
+        if !library_open {
+            self.ui.update(ctx)?;
+            self.expansion_selector.update(ctx)?;
+            self.scoring_rules_selector.update(ctx)?;
+            self.color_selection_ui.update(ctx)?;
+            self.player_type_ui.update(ctx)?;
+        }
         while let Ok(event) = self.event_receiver.try_recv() {
             self.handle_event(event)?;
         }
@@ -236,9 +385,11 @@ impl SubEventHandler for MainMenuClient {
         // render ui
         self.ui.draw(ctx, canvas)?;
         self.expansion_selector.draw(ctx, canvas)?;
+        self.scoring_rules_selector.draw(ctx, canvas)?;
 
         // render player choice buttons
         self.color_selection_ui.draw(ctx, canvas)?;
+        self.player_type_ui.draw(ctx, canvas)?;
 
         for (button, color) in self.color_selection_buttons.iter().zip(PLAYER_COLORS) {
             let button = button.borrow();
@@ -264,6 +415,8 @@ impl SubEventHandler for MainMenuClient {
             GameClient::draw_meeple(ctx, canvas, center, *color, 0.1)?;
         }
 
+        self.library_selector.draw(ctx, canvas)?;
+
         Ok(())
     }
 }
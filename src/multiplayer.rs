@@ -5,12 +5,12 @@ use std::{
 
 use ggez::{graphics::Canvas, Context, GameError};
 use lobby_client::{LobbyClient, LobbyEvent};
-use message::server::User;
+use message::server::{LobbyBot, User};
 
 use crate::{
-    game::player::PlayerType,
+    game::{player::PlayerType, rules::GameState, ScoringRules},
     game_client::{
-        GameAction, GameClient, GameClientConfiguration, GameExpansions, GameState,
+        GameAction, GameClient, GameClientConfiguration, GameExpansions, LibraryConfiguration,
         PlayerConfiguration,
     },
     main_client::MainEvent,
@@ -38,10 +38,13 @@ impl<T> MultiplayerPhase<T> {
         shared: Shared,
         parent_channel: Sender<MainEvent>,
         users: Vec<User>,
+        bots: Vec<LobbyBot>,
         seed: u64,
         local_user: Option<IpAddr>,
         local_username: String,
         expansions: GameExpansions,
+        library: LibraryConfiguration,
+        scoring_rules: ScoringRules,
     ) -> MultiplayerPhase<T> {
         let (action_sender, action_channel) = channel();
         let local_player = PlayerType::from_details(local_username, local_user);
@@ -54,6 +57,14 @@ impl<T> MultiplayerPhase<T> {
                     PlayerType::from_details(user.username.clone(), address),
                 )
             })
+            .chain(bots.iter().map(|bot| {
+                (
+                    bot.color,
+                    PlayerType::Bot {
+                        difficulty: bot.difficulty,
+                    },
+                )
+            }))
             .collect();
         MultiplayerPhase::Game {
             game: GameClient::new(
@@ -64,6 +75,8 @@ impl<T> MultiplayerPhase<T> {
                 GameClientConfiguration {
                     seed,
                     expansions,
+                    library,
+                    scoring_rules,
                     players: PlayerConfiguration::Multiplayer {
                         local_player,
                         players,
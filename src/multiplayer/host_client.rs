@@ -1,8 +1,6 @@
 use std::{
     cell::RefCell,
     collections::HashMap,
-    error::Error,
-    fs::File,
     net::IpAddr,
     rc::Rc,
     sync::mpsc::{channel, Receiver, Sender},
@@ -14,22 +12,31 @@ use ggez::{
     graphics::{Canvas, Color, Rect, Text},
     Context, GameError, GameResult,
 };
-use log::{debug, info, trace};
+use log::{debug, info, trace, warn};
 
 use crate::{
-    game::player::PlayerType,
-    game_client::{GameAction, GameState},
+    game::{
+        bot::{BotController, BotDifficulty},
+        player::{ConnectionState, PlayerType},
+        rules::{GameState, RuleError},
+        PlayerIdentifier,
+    },
+    game_client::{GameAction, GameClient, PLAYER_COLORS},
     game_expansions_selector::GameExpansionsSelector,
+    library_selector::LibrarySelector,
     main_client::MainEvent,
     multiplayer::{
         lobby_client::{LobbyClient, LobbyEvent},
         message::{
             client::{self, ClientMessage},
-            server::{self, ClientInfo, LobbyState, ServerMessage, User},
+            server::{
+                self, ClientInfo, LobbyBot, LobbyState, RewindVote, ServerMessage, UndoPolicy, User,
+            },
         },
         MultiplayerPhase,
     },
-    util::{AnchorPoint, ContextExt, TextExt},
+    scoring_rules_selector::ScoringRulesSelector,
+    util::{AnchorPoint, ContextExt, DrawableWihParamsExt, TextExt},
     Shared,
 };
 
@@ -39,12 +46,17 @@ use ggez_no_re::{
     ui_manager::{Bounds, button::Button, UIElement, UIElementState, UIManager},
 };
 
-use super::message::Message;
+use super::message::{GameMessage, Message};
 
 #[derive(Clone, Debug)]
 enum UIEvent {
     MainEvent(MainEvent),
     StartGame,
+    AddBot,
+    RemoveBot,
+    CycleBotDifficulty,
+    ToggleDropoutBots,
+    CycleUndoPolicy,
 }
 
 #[allow(clippy::enum_variant_names)]
@@ -56,6 +68,7 @@ enum HostEvent {
         event: ServerNetworkEvent<Message>,
     },
     LobbyEvent(LobbyEvent),
+    BotMessage(GameMessage),
 }
 
 impl From<(IpAddr, ServerNetworkEvent<Message>)> for HostEvent {
@@ -98,13 +111,27 @@ pub struct HostClient {
     event_sender: Sender<HostEvent>,
     event_receiver: Receiver<HostEvent>,
     ui: UIManager<UIEvent, HostEvent>,
+    bot_ui: UIManager<UIEvent, HostEvent>,
     expansions_selector: GameExpansionsSelector,
+    scoring_rules_selector: ScoringRulesSelector,
+    library_selector: LibrarySelector,
     _message_server: MessageServer,
     users: HashMap<IpOrHost, HostUser>,
     phase: MultiplayerPhase<HostEvent>,
     start_game_button: Rc<RefCell<Button<UIEvent>>>,
+    bot_difficulty_button: Rc<RefCell<Button<UIEvent>>>,
+    dropout_bots_button: Rc<RefCell<Button<UIEvent>>>,
+    undo_policy_button: Rc<RefCell<Button<UIEvent>>>,
+    bots: Vec<LobbyBot>,
+    bot_difficulty: BotDifficulty,
+    dropout_bots: bool,
+    undo_policy: UndoPolicy,
+    bot_controller: BotController,
     port: u16,
     username: String,
+    /// A saved game to continue instead of starting a new one. Each of its seats goes to
+    /// whoever picks that seat's color in the lobby.
+    saved_game: Option<GameState>,
 }
 
 impl HostClient {
@@ -113,39 +140,111 @@ impl HostClient {
         shared: Shared,
         username: String,
         port: u16,
+        saved_game: Option<GameState>,
     ) -> HostClient {
         let (event_sender, event_receiver) = channel();
         let ui_sender = event_sender.clone();
-        let (ui, [_, UIElement::Button(start_game_button)]) = UIManager::new_and_rc_elements(
+        let (ui, [_, UIElement::Button(start_game_button), UIElement::Button(undo_policy_button)]) =
+            UIManager::new_and_rc_elements(
+                ui_sender.clone(),
+                [
+                    UIElement::Button(Button::new(
+                        Bounds::absolute(Rect::new(30.0, 30.0, 120.0, 40.0)),
+                        Text::new("Back"),
+                        UIEvent::MainEvent(MainEvent::MultiplayerMenu),
+                    )),
+                    UIElement::Button(Button::new(
+                        Bounds {
+                            relative: Rect::new(1.0, 1.0, 0.0, 0.0),
+                            absolute: Rect::new(-260.0, -60.0, 240.0, 40.0),
+                        },
+                        Text::new("Start Game").size(32.0),
+                        UIEvent::StartGame,
+                    )),
+                    UIElement::Button(Button::new(
+                        Bounds {
+                            relative: Rect::new(0.6, 0.3, 0.0, 0.0),
+                            absolute: Rect::new(390.0, 390.0, 240.0, 40.0),
+                        },
+                        Text::new(format!("Undo: {}", UndoPolicy::default().name())),
+                        UIEvent::CycleUndoPolicy,
+                    )),
+                ],
+            )
+        else {
+            panic!()
+        };
+        let bot_controls_pos = Rect::new(0.6, 0.3, 0.0, 0.0);
+        let (
+            bot_ui,
+            [_, _, UIElement::Button(bot_difficulty_button), UIElement::Button(dropout_bots_button)],
+        ) = UIManager::new_and_rc_elements(
             ui_sender,
             [
                 UIElement::Button(Button::new(
-                    Bounds::absolute(Rect::new(30.0, 30.0, 120.0, 40.0)),
-                    Text::new("Back"),
-                    UIEvent::MainEvent(MainEvent::MultiplayerMenu),
+                    Bounds {
+                        relative: bot_controls_pos,
+                        absolute: Rect::new(0.0, 390.0, 120.0, 40.0),
+                    },
+                    Text::new("Add Bot"),
+                    UIEvent::AddBot,
                 )),
                 UIElement::Button(Button::new(
                     Bounds {
-                        relative: Rect::new(1.0, 1.0, 0.0, 0.0),
-                        absolute: Rect::new(-260.0, -60.0, 240.0, 40.0),
+                        relative: bot_controls_pos,
+                        absolute: Rect::new(130.0, 390.0, 120.0, 40.0),
                     },
-                    Text::new("Start Game").size(32.0),
-                    UIEvent::StartGame,
+                    Text::new("Remove Bot"),
+                    UIEvent::RemoveBot,
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: bot_controls_pos,
+                        absolute: Rect::new(260.0, 390.0, 120.0, 40.0),
+                    },
+                    Text::new(""),
+                    UIEvent::CycleBotDifficulty,
+                )),
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative: bot_controls_pos,
+                        absolute: Rect::new(0.0, 440.0, 380.0, 40.0),
+                    },
+                    Text::new(""),
+                    UIEvent::ToggleDropoutBots,
                 )),
             ],
-        ) else {
+        )
+        else {
             panic!()
         };
-        let expansions_selector = GameExpansionsSelector::new(Bounds {
+        let expansions_selector = GameExpansionsSelector::new(
+            Bounds {
+                relative: Rect::new(0.6, 0.3, 0.0, 0.0),
+                absolute: Rect::new(0.0, 100.0, 0.0, 0.0),
+            },
+            shared.tilesets.borrow().clone(),
+        );
+        let scoring_rules_selector = ScoringRulesSelector::new(Bounds {
             relative: Rect::new(0.6, 0.3, 0.0, 0.0),
-            absolute: Rect::new(0.0, 100.0, 0.0, 0.0),
+            absolute: Rect::new(0.0, 490.0, 0.0, 0.0),
         });
+        let library_selector = LibrarySelector::new(
+            Bounds {
+                relative: Rect::new(0.6, 0.3, 0.0, 0.0),
+                absolute: Rect::new(0.0, 560.0, 0.0, 0.0),
+            },
+            shared.clone(),
+        );
         start_game_button.borrow_mut().state = UIElementState::Disabled;
         let message_server = MessageServer::start::<Message>(event_sender.clone(), port);
         let mut this = HostClient {
             parent_channel,
             ui,
+            bot_ui,
             expansions_selector,
+            scoring_rules_selector,
+            library_selector,
             _message_server: message_server,
             users: HashMap::from([(
                 IpOrHost::Host,
@@ -168,9 +267,19 @@ impl HostClient {
             event_sender,
             event_receiver,
             start_game_button,
+            bot_difficulty_button,
+            dropout_bots_button,
+            undo_policy_button,
+            bots: Vec::new(),
+            bot_difficulty: BotDifficulty::Medium,
+            dropout_bots: true,
+            undo_policy: UndoPolicy::default(),
+            bot_controller: BotController::default(),
             port,
             username,
+            saved_game,
         };
+        this.update_bot_buttons();
         this.update_lobby_clients();
         this
     }
@@ -200,12 +309,29 @@ impl HostClient {
         let users: Vec<_> = self.users.values().map(|user| user.user.clone()).collect();
         let message = server::LobbyMessage::LobbyState(LobbyState {
             users: users.clone(),
+            bots: self.bots.clone(),
+            undo_policy: self.undo_policy,
         });
         match &mut self.phase {
             MultiplayerPhase::Lobby(lobby) => {
-                self.start_game_button.borrow_mut().state = UIElementState::disabled_if(
-                    users.len() < 2 || users.iter().any(|user| user.color.is_none()),
-                );
+                let colors: Vec<_> = users
+                    .iter()
+                    .map(|user| user.color)
+                    .chain(self.bots.iter().map(|bot| Some(bot.color)))
+                    .collect();
+                let can_start = match &self.saved_game {
+                    Some(state) => colors.iter().all(|color| {
+                        color.is_some_and(|color| {
+                            state
+                                .game
+                                .players
+                                .values()
+                                .any(|player| player.color == color)
+                        })
+                    }),
+                    None => colors.len() >= 2 && colors.iter().all(Option::is_some),
+                };
+                self.start_game_button.borrow_mut().state = UIElementState::disabled_if(!can_start);
                 let _ = lobby.handle_message(message.clone());
             }
             MultiplayerPhase::Game { game, .. } => {
@@ -215,6 +341,51 @@ impl HostClient {
         self.broadcast(ServerMessage::Lobby(message.clone()));
     }
 
+    fn update_bot_buttons(&mut self) {
+        self.bot_difficulty_button.borrow_mut().text =
+            Text::new(format!("{} Bots", self.bot_difficulty.name()));
+        self.dropout_bots_button.borrow_mut().text = Text::new(format!(
+            "Bots Replace Dropped Players: {}",
+            if self.dropout_bots { "On" } else { "Off" }
+        ));
+    }
+
+    /// Gives an empty seat to a bot, taking the first color nobody has chosen.
+    fn add_bot(&mut self) {
+        let taken_colors: Vec<_> = self
+            .users
+            .values()
+            .filter_map(|user| user.user.color)
+            .chain(self.bots.iter().map(|bot| bot.color))
+            .collect();
+        if let Some(color) = PLAYER_COLORS
+            .into_iter()
+            .find(|color| !taken_colors.contains(color))
+        {
+            self.bots.push(LobbyBot {
+                color,
+                difficulty: self.bot_difficulty,
+            });
+            self.update_lobby_clients();
+        }
+    }
+
+    /// The difficulty of the bot playing the current turn, if the seat is bot controlled.
+    /// Bots stand in for disconnected players until they reconnect from the same address.
+    fn current_bot(&self) -> Option<BotDifficulty> {
+        let MultiplayerPhase::Game { game, .. } = &self.phase else {
+            return None;
+        };
+        match game.get_current_player_type() {
+            PlayerType::Bot { difficulty } => Some(*difficulty),
+            PlayerType::MultiplayerClient {
+                connection_state: ConnectionState::Disconnected,
+                ..
+            } if self.dropout_bots => Some(self.bot_difficulty),
+            _ => None,
+        }
+    }
+
     fn add_client(&mut self, mut transport: ServersideTransport, client_info: ClientInfo) {
         if let MultiplayerPhase::Game { game, .. } = &self.phase {
             transport.blind_send::<Message>(ServerMessage::GameState(game.state.clone().into()));
@@ -273,14 +444,23 @@ impl HostClient {
                                 self.update_lobby_clients();
                             }
                         }
+                        ClientMessage::Game(message) if message.is_rewind() => {
+                            self.request_rewind(ctx, Some(src_addr), message)?;
+                        }
+                        ClientMessage::VoteOnRewind(agree) => {
+                            self.vote_on_rewind(ctx, Some(src_addr), agree)?;
+                        }
                         ClientMessage::Game(message) => {
-                            if let MultiplayerPhase::Game { game, .. } = &mut self.phase {
-                                if game
-                                    .get_current_player_type()
-                                    .matches_address(Some(src_addr))
-                                {
-                                    let username = host_client.user.username.clone();
-                                    game.handle_message(ctx, message.clone())?;
+                            let username = host_client.user.username.clone();
+                            let MultiplayerPhase::Game { game, .. } = &mut self.phase else {
+                                return Ok(());
+                            };
+                            let outcome = match Self::check_move(&game.state, src_addr, &message) {
+                                Ok(()) => game.handle_message(ctx, message.clone())?,
+                                Err(reason) => Err(reason),
+                            };
+                            match outcome {
+                                Ok(()) => {
                                     self.broadcast_filter(
                                         ServerMessage::Game {
                                             message,
@@ -292,18 +472,34 @@ impl HostClient {
                                         |ip| ip != src_addr,
                                     );
                                 }
+                                // previews can arrive just after the turn has passed on,
+                                // and don't change anything, so they're dropped quietly
+                                Err(_) if message.is_preview() => {}
+                                Err(reason) => self.reject(src_addr, message, reason),
                             }
                         }
                         ClientMessage::Username(username) => {
                             host_client.user.username = username;
                             self.update_lobby_clients();
                         }
+                        ClientMessage::CantStartGame(reason) => {
+                            let username = host_client.user.username.clone();
+                            warn!("{username} can't start the game: {reason}");
+                            if let MultiplayerPhase::Game { game, .. } = &mut self.phase {
+                                game.notify(
+                                    ctx,
+                                    format!("{username} couldn't start the game: {reason}"),
+                                );
+                            }
+                        }
                     }
                 }
                 NetworkEvent::Disconnect => {
                     debug!("[{src_addr:?}] disconnected");
                     self.users.remove(&IpOrHost::Ip(src_addr));
                     self.update_lobby_clients();
+                    // the vote may have only been waiting on them
+                    self.resolve_rewind_vote(ctx)?;
                 }
             },
             HostEvent::UIEvent(uievent) => match uievent {
@@ -313,6 +509,35 @@ impl HostClient {
                         self.start_game(ctx);
                     }
                 }
+                UIEvent::AddBot => self.add_bot(),
+                UIEvent::RemoveBot => {
+                    if self.bots.pop().is_some() {
+                        self.update_lobby_clients();
+                    }
+                }
+                UIEvent::CycleBotDifficulty => {
+                    let index = BotDifficulty::ALL
+                        .iter()
+                        .position(|difficulty| *difficulty == self.bot_difficulty)
+                        .unwrap();
+                    self.bot_difficulty =
+                        BotDifficulty::ALL[(index + 1) % BotDifficulty::ALL.len()];
+                    self.update_bot_buttons();
+                }
+                UIEvent::ToggleDropoutBots => {
+                    self.dropout_bots = !self.dropout_bots;
+                    self.update_bot_buttons();
+                }
+                UIEvent::CycleUndoPolicy => {
+                    let index = UndoPolicy::ALL
+                        .iter()
+                        .position(|policy| *policy == self.undo_policy)
+                        .unwrap();
+                    self.undo_policy = UndoPolicy::ALL[(index + 1) % UndoPolicy::ALL.len()];
+                    self.undo_policy_button.borrow_mut().text =
+                        Text::new(format!("Undo: {}", self.undo_policy.name()));
+                    self.update_lobby_clients();
+                }
             },
             HostEvent::LobbyEvent(LobbyEvent::ChooseColor(color)) => {
                 let me = self
@@ -323,22 +548,232 @@ impl HostClient {
                 me.user.color = color;
                 self.update_lobby_clients();
             }
+            HostEvent::BotMessage(message) => {
+                // the seat may have been reclaimed since the bot decided its move
+                if self.current_bot().is_some() {
+                    if let MultiplayerPhase::Game { game, .. } = &mut self.phase {
+                        let user = game.get_current_player_type().clone();
+                        if game.handle_message(ctx, message.clone())?.is_ok() {
+                            self.broadcast(ServerMessage::Game { message, user });
+                        }
+                    }
+                }
+            }
         }
         Ok(())
     }
 
-    fn start_game(&mut self, ctx: &Context) {
-        info!("Game Start!");
-        let result: Result<Option<GameState>, Box<dyn Error>> = try {
-            if let Some(path) = &self.shared.args.multiplayer_load {
-                let file = File::open(path)?;
-                Some(bincode::deserialize_from(file)?)
-            } else {
-                None
+    /// Checks that a move sent by a client is theirs to make. The rules are checked as
+    /// it's applied, which leaves the game untouched if it breaks them. Undos and redos
+    /// are checked against the undo policy instead, by `request_rewind`. Ending the game
+    /// early ends it for everyone, so only the host can do it.
+    fn check_move(
+        state: &GameState,
+        src_addr: IpAddr,
+        message: &GameMessage,
+    ) -> Result<(), RuleError> {
+        if *message == GameMessage::EndGame {
+            Err(RuleError::HostOnly)
+        } else if state.game.players[state.current_player()]
+            .ptype
+            .matches_address(Some(src_addr))
+        {
+            Ok(())
+        } else {
+            Err(RuleError::NotYourTurn)
+        }
+    }
+
+    /// Turns down a move from a client, and sends them the game as it stands so they
+    /// can get back in step with it. Nobody else hears about the move.
+    fn reject(&mut self, src_addr: IpAddr, message: GameMessage, reason: RuleError) {
+        info!("rejected {message:?} from {src_addr}: {reason}");
+        let MultiplayerPhase::Game { game, .. } = &self.phase else {
+            return;
+        };
+        let resync = game.resync_message();
+        if let Some(client_info) = self
+            .users
+            .get_mut(&IpOrHost::Ip(src_addr))
+            .and_then(|user| user.client_info.as_mut())
+        {
+            client_info
+                .transport
+                .blind_send::<Message>(ServerMessage::Rejected {
+                    message,
+                    reason: reason.to_string(),
+                });
+            client_info.transport.blind_send::<Message>(resync);
+        }
+    }
+
+    /// The seat played from an address, with `None` being the host.
+    fn seat_at(game: &GameClient, address: Option<IpAddr>) -> Option<PlayerIdentifier> {
+        game.state
+            .game
+            .players
+            .iter()
+            .find(|(_, player)| player.ptype.matches_address(address))
+            .map(|(player_ident, _)| player_ident)
+    }
+
+    /// The seats that get a say in a vote on undoing or redoing: everyone still connected.
+    fn rewind_voters(&self, game: &GameClient) -> Vec<PlayerIdentifier> {
+        game.state
+            .game
+            .players
+            .iter()
+            .filter(|(_, player)| match &player.ptype {
+                PlayerType::MultiplayerHost { .. } => true,
+                PlayerType::MultiplayerClient { address, .. } => {
+                    self.users.contains_key(&IpOrHost::Ip(*address))
+                }
+                _ => false,
+            })
+            .map(|(player_ident, _)| player_ident)
+            .collect()
+    }
+
+    /// Handles an `Undo` or `Redo` asked for from an address, with `None` being the host.
+    /// It's carried out if the undo policy allows it, or put to a vote under the voting
+    /// policy.
+    fn request_rewind(
+        &mut self,
+        ctx: &mut Context,
+        src_addr: Option<IpAddr>,
+        message: GameMessage,
+    ) -> GameResult<()> {
+        let MultiplayerPhase::Game { game, .. } = &mut self.phase else {
+            return Ok(());
+        };
+        let Some(proposer) = Self::seat_at(game, src_addr) else {
+            return Ok(());
+        };
+        if game.rewind_vote.is_some() || !game.may_rewind(proposer, &message) {
+            debug!("refusing {message:?} from {src_addr:?}");
+            if let Some(src_addr) = src_addr {
+                self.reject(src_addr, message, RuleError::UndoNotAllowed);
             }
+            return Ok(());
+        }
+        if game.undo_policy == UndoPolicy::Vote {
+            game.rewind_vote = Some(RewindVote {
+                proposer,
+                message,
+                agreed: vec![proposer],
+            });
+            self.resolve_rewind_vote(ctx)
+        } else {
+            self.rewind(ctx, message)
+        }
+    }
+
+    fn vote_on_rewind(
+        &mut self,
+        ctx: &mut Context,
+        src_addr: Option<IpAddr>,
+        agree: bool,
+    ) -> GameResult<()> {
+        let MultiplayerPhase::Game { game, .. } = &mut self.phase else {
+            return Ok(());
+        };
+        let Some(voter) = Self::seat_at(game, src_addr) else {
+            return Ok(());
+        };
+        let Some(vote) = &mut game.rewind_vote else {
+            return Ok(());
+        };
+        if agree {
+            if !vote.agreed.contains(&voter) {
+                vote.agreed.push(voter);
+            }
+            self.resolve_rewind_vote(ctx)
+        } else {
+            debug!("{src_addr:?} refused {:?}", vote.message);
+            self.set_rewind_vote(None);
+            Ok(())
+        }
+    }
+
+    /// Carries out the undo or redo being voted on once everyone has agreed to it, and
+    /// otherwise lets everyone know who has agreed so far.
+    fn resolve_rewind_vote(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let MultiplayerPhase::Game { game, .. } = &self.phase else {
+            return Ok(());
+        };
+        let Some(vote) = game.rewind_vote.clone() else {
+            return Ok(());
+        };
+        if self
+            .rewind_voters(game)
+            .iter()
+            .all(|voter| vote.agreed.contains(voter))
+        {
+            self.set_rewind_vote(None);
+            self.rewind(ctx, vote.message)
+        } else {
+            self.set_rewind_vote(Some(vote));
+            Ok(())
+        }
+    }
+
+    fn set_rewind_vote(&mut self, vote: Option<RewindVote>) {
+        if let MultiplayerPhase::Game { game, .. } = &mut self.phase {
+            game.rewind_vote = vote.clone();
+        }
+        self.broadcast(ServerMessage::RewindVote(vote));
+    }
+
+    /// Undoes or redoes moves for everyone.
+    fn rewind(&mut self, ctx: &mut Context, message: GameMessage) -> GameResult<()> {
+        let MultiplayerPhase::Game { game, .. } = &mut self.phase else {
+            return Ok(());
         };
-        match result {
-            Ok(Some(state)) => {
+        // undos and redos have already been checked against the undo policy
+        let _ = game.handle_message(ctx, message.clone())?;
+        // the move a bot was working out may have just been taken back
+        self.bot_controller.cancel();
+        self.broadcast(ServerMessage::Game {
+            message,
+            user: PlayerType::MultiplayerHost {
+                username: self.username.clone(),
+            },
+        });
+        Ok(())
+    }
+
+    /// Hands the seats of a saved game to the players in the lobby, by color. Seats
+    /// nobody picked are played by bots.
+    fn seat_players(&self, state: &mut GameState) {
+        for player in state.game.players.values_mut() {
+            let user = self
+                .users
+                .values()
+                .find(|user| user.user.color == Some(player.color));
+            let bot = self.bots.iter().find(|bot| bot.color == player.color);
+            player.ptype = match (user, bot) {
+                (Some(user), _) => PlayerType::from_details(
+                    user.user.username.clone(),
+                    user.user.client_info.as_ref().map(|info| info.ip),
+                ),
+                (None, Some(bot)) => PlayerType::Bot {
+                    difficulty: bot.difficulty,
+                },
+                (None, None) => match player.ptype {
+                    PlayerType::Bot { difficulty } => PlayerType::Bot { difficulty },
+                    _ => PlayerType::Bot {
+                        difficulty: self.bot_difficulty,
+                    },
+                },
+            };
+        }
+    }
+
+    fn start_game(&mut self, ctx: &Context) {
+        info!("Game Start!");
+        match self.saved_game.take() {
+            Some(mut state) => {
+                self.seat_players(&mut state);
                 self.broadcast(ServerMessage::GameState(state.clone().into()));
                 self.phase = MultiplayerPhase::new_from_state(
                     ctx,
@@ -347,19 +782,22 @@ impl HostClient {
                     state,
                     None,
                     self.username.clone(),
-                )
+                );
             }
-            result => {
-                if let Err(err) = result {
-                    log::error!("Error loading multiplayer game: {err}");
-                }
+            None => {
                 let game_seed = rand::random();
                 let expansions = self.expansions_selector.get_selected_expansions();
+                let library = self.library_selector.get_selected_library();
+                let scoring_rules = self.scoring_rules_selector.get_selected_rules();
                 {
                     let expansions = expansions.clone();
+                    let library = library.clone();
+                    let scoring_rules = scoring_rules.clone();
                     self.broadcast(ServerMessage::StartGame {
                         game_seed,
                         expansions,
+                        library,
+                        scoring_rules,
                     });
                 }
                 self.phase = MultiplayerPhase::new_game(
@@ -367,13 +805,55 @@ impl HostClient {
                     self.shared.clone(),
                     self.parent_channel.clone(),
                     self.users.values().map(|user| user.user.clone()).collect(),
+                    self.bots.clone(),
                     game_seed,
                     None,
                     self.username.clone(),
                     expansions,
+                    library,
+                    scoring_rules,
                 );
             }
         }
+        if let MultiplayerPhase::Game { game, .. } = &mut self.phase {
+            game.undo_policy = self.undo_policy;
+        }
+    }
+
+    /// Shows the seats of the saved game in place of the new game settings.
+    fn draw_saved_game(
+        &self,
+        ctx: &Context,
+        canvas: &mut Canvas,
+        state: &GameState,
+    ) -> GameResult<()> {
+        let origin = ctx.res() * vec2(0.6, 0.3) + vec2(0.0, 100.0);
+        for (i, line) in [
+            format!(
+                "Continuing a saved game with {} tiles left.",
+                state.game.library.len()
+            ),
+            "Pick a seat's color to take it; bots play the rest.".to_string(),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            Text::new(line)
+                .size(18.0)
+                .pos(origin + vec2(0.0, 22.0 * i as f32))
+                .color(Color::BLACK)
+                .draw(canvas);
+        }
+        for (i, player_ident) in state.turn_order.iter().enumerate() {
+            let player = &state.game.players[*player_ident];
+            let pos = origin + vec2(20.0 + 110.0 * i as f32, 60.0);
+            GameClient::draw_meeple(ctx, canvas, pos, player.color, 0.15)?;
+            Text::new(format!("{}", player.score))
+                .anchored_by(ctx, pos + vec2(25.0, 0.0), AnchorPoint::CenterWest)?
+                .color(Color::BLACK)
+                .draw(canvas);
+        }
+        Ok(())
     }
 
     fn ping_clients(&mut self) {
@@ -402,16 +882,31 @@ impl SubEventHandler for HostClient {
     }
 
     fn update(&mut self, ctx: &mut Context) -> Result<(), GameError> {
+        // the library overlay covers the rest of the lobby while it's open
+        let library_open = self.library_selector.is_open();
         if let MultiplayerPhase::Lobby(_) = &self.phase {
-            self.ui.update(ctx)?;
-            self.expansions_selector.update(ctx)?;
+            // a saved game already has its rules decided
+            let new_game = self.saved_game.is_none();
+            if new_game {
+                self.library_selector.update(ctx)?;
+            }
+            if !library_open {
+                self.ui.update(ctx)?;
+                self.bot_ui.update(ctx)?;
+                if new_game {
+                    self.expansions_selector.update(ctx)?;
+                    self.scoring_rules_selector.update(ctx)?;
+                }
+            }
         }
 
         while let Ok(event) = self.event_receiver.try_recv() {
             self.handle_event(ctx, event)?;
         }
 
-        self.phase.update(ctx)?;
+        if !(library_open && matches!(self.phase, MultiplayerPhase::Lobby(_))) {
+            self.phase.update(ctx)?;
+        }
         let actions = if let MultiplayerPhase::Game { action_channel, .. } = &mut self.phase {
             action_channel.try_iter().collect()
         } else {
@@ -427,6 +922,8 @@ impl SubEventHandler for HostClient {
                         },
                     });
                 }
+                GameAction::RequestRewind(message) => self.request_rewind(ctx, None, message)?,
+                GameAction::VoteOnRewind(agree) => self.vote_on_rewind(ctx, None, agree)?,
                 GameAction::ReturnToLobby => {
                     self.phase = MultiplayerPhase::Lobby(LobbyClient::new(
                         Vec::new(),
@@ -440,6 +937,16 @@ impl SubEventHandler for HostClient {
             }
         }
 
+        if let MultiplayerPhase::Game { game, .. } = &self.phase {
+            let difficulty = self.current_bot();
+            let now = ctx.time.time_since_start().as_secs_f32();
+            if let Some(message) = self.bot_controller.update(&game.state, difficulty, now) {
+                self.event_sender
+                    .send(HostEvent::BotMessage(message))
+                    .unwrap();
+            }
+        }
+
         self.ping_clients();
 
         Ok(())
@@ -462,9 +969,81 @@ impl SubEventHandler for HostClient {
 
         if let MultiplayerPhase::Lobby(_) = &self.phase {
             self.ui.draw(ctx, canvas)?;
-            self.expansions_selector.draw(ctx, canvas)?;
+            self.bot_ui.draw(ctx, canvas)?;
+            match &self.saved_game {
+                Some(state) => self.draw_saved_game(ctx, canvas, state)?,
+                None => {
+                    self.expansions_selector.draw(ctx, canvas)?;
+                    self.scoring_rules_selector.draw(ctx, canvas)?;
+                    self.library_selector.draw(ctx, canvas)?;
+                }
+            }
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use ggez::graphics::Color;
+
+    use crate::{
+        game::{
+            player::{Player, PlayerType},
+            rules::{GameState, RuleError},
+            setup::{GameExpansions, LibraryConfiguration},
+            ScoringRules,
+        },
+        multiplayer::message::GameMessage,
+    };
+
+    use super::HostClient;
+
+    #[test]
+    fn clients_cannot_end_the_game() {
+        let addresses = [1, 2].map(|i| IpAddr::V4(Ipv4Addr::new(10, 0, 0, i)));
+        let state = GameState::new(
+            5,
+            [Color::RED, Color::BLUE]
+                .into_iter()
+                .zip(addresses)
+                .map(|(color, address)| {
+                    Player::new_inner(
+                        color,
+                        PlayerType::from_details(address.to_string(), Some(address)),
+                    )
+                })
+                .collect(),
+            &GameExpansions::default(),
+            &LibraryConfiguration::default(),
+            &ScoringRules::default(),
+            PlayerType::from_details("host".to_string(), None),
+        );
+        let PlayerType::MultiplayerClient { address, .. } =
+            state.game.players[state.current_player()].ptype
+        else {
+            panic!("expected a client to start");
+        };
+        let other = addresses
+            .into_iter()
+            .find(|other| *other != address)
+            .unwrap();
+
+        assert_eq!(
+            HostClient::check_move(&state, address, &GameMessage::SkipMeeples),
+            Ok(())
+        );
+        assert_eq!(
+            HostClient::check_move(&state, other, &GameMessage::SkipMeeples),
+            Err(RuleError::NotYourTurn)
+        );
+        // not even on their own turn
+        assert_eq!(
+            HostClient::check_move(&state, address, &GameMessage::EndGame),
+            Err(RuleError::HostOnly)
+        );
+    }
+}
@@ -11,16 +11,17 @@ use ggez::{
     graphics::{Canvas, Color, Rect, Text},
     Context, GameError, GameResult,
 };
-use log::{debug, info, trace};
+use log::{debug, info, trace, warn};
 
 use crate::{
-    game_client::{GameAction, GameExpansions},
+    game::ScoringRules,
+    game_client::{GameAction, GameExpansions, LibraryConfiguration},
     main_client::MainEvent,
     multiplayer::{
         lobby_client::{LobbyClient, LobbyEvent},
         message::{
             client::{self, ClientMessage},
-            server::{self, LobbyState, ServerMessage, User},
+            server::{self, LobbyBot, LobbyState, ServerMessage, UndoPolicy, User},
         },
         MultiplayerPhase,
     },
@@ -81,8 +82,12 @@ pub struct JoinClient {
     _socket: SocketAddr,
     back_button: Rc<RefCell<Button<UIEvent>>>,
     users: Option<Vec<User>>,
+    bots: Vec<LobbyBot>,
+    undo_policy: UndoPolicy,
     username: String,
     destination_name: String,
+    /// Why the game the host started couldn't be set up here, after leaving it.
+    error: Option<String>,
 }
 
 impl JoinClient {
@@ -119,8 +124,11 @@ impl JoinClient {
             _socket: socket,
             back_button,
             users: None,
+            bots: Vec::new(),
+            undo_policy: UndoPolicy::default(),
             username,
             destination_name,
+            error: None,
         }
     }
 
@@ -130,6 +138,8 @@ impl JoinClient {
         users: Vec<User>,
         seed: u64,
         expansions: GameExpansions,
+        library: LibraryConfiguration,
+        scoring_rules: ScoringRules,
     ) {
         info!("Game start!");
         self.phase = Some(MultiplayerPhase::new_game(
@@ -137,11 +147,33 @@ impl JoinClient {
             self.shared.clone(),
             self.parent_channel.clone(),
             users,
+            self.bots.clone(),
             seed,
             Some(self.connection.as_ref().unwrap().1),
             self.username.clone(),
             expansions,
+            library,
+            scoring_rules,
         ));
+        self.apply_undo_policy();
+    }
+
+    /// Disconnects from the host, leaving the reason up until the player backs out.
+    fn leave(&mut self, error: String) {
+        if let Some((mut connection, _)) = self.connection.take() {
+            let _ = connection.shutdown();
+        }
+        self.latency = None;
+        self.phase = None;
+        self.users = None;
+        self.bots.clear();
+        self.error = Some(error);
+    }
+
+    fn apply_undo_policy(&mut self) {
+        if let Some(MultiplayerPhase::Game { game, .. }) = &mut self.phase {
+            game.undo_policy = self.undo_policy;
+        }
     }
 
     fn handle_event(&mut self, ctx: &mut Context, event: JoinEvent) -> GameResult<()> {
@@ -176,6 +208,9 @@ impl JoinClient {
                             match &lobby_message {
                                 server::LobbyMessage::LobbyState(lobby_state) => {
                                     self.users = Some(lobby_state.users.clone());
+                                    self.bots = lobby_state.bots.clone();
+                                    self.undo_policy = lobby_state.undo_policy;
+                                    self.apply_undo_policy();
                                 }
                             }
                             match (&mut self.phase, lobby_message) {
@@ -184,7 +219,7 @@ impl JoinClient {
                                 }
                                 (
                                     Some(MultiplayerPhase::Game { game, .. }),
-                                    server::LobbyMessage::LobbyState(LobbyState { users }),
+                                    server::LobbyMessage::LobbyState(LobbyState { users, .. }),
                                 ) => {
                                     game.update_pings(users)?;
                                 }
@@ -194,18 +229,52 @@ impl JoinClient {
                         ServerMessage::StartGame {
                             game_seed,
                             expansions,
+                            library,
+                            scoring_rules,
                         } => {
-                            if let Some(users) = &self.users {
-                                self.start_game(ctx, users.clone(), game_seed, expansions);
+                            if let Err(err) = expansions.check().and_then(|_| library.check()) {
+                                warn!("can't start the host's game: {err}");
+                                LazyCell::force_mut(&mut server).blind_send::<Message>(
+                                    ClientMessage::CantStartGame(err.clone()),
+                                );
+                                self.leave(err);
+                            } else if let Some(users) = &self.users {
+                                self.start_game(
+                                    ctx,
+                                    users.clone(),
+                                    game_seed,
+                                    expansions,
+                                    library,
+                                    scoring_rules,
+                                );
                             }
                         }
                         ServerMessage::Game { message, user } => {
                             if let Some(MultiplayerPhase::Game { game, .. }) = &mut self.phase {
-                                if game.get_current_player_type() == &user {
-                                    game.handle_message(ctx, message)?;
+                                // undos and redos are checked by the host, and can be asked
+                                // for by anyone
+                                if message.is_rewind() || game.get_current_player_type() == &user {
+                                    // the host only passes on legal moves, so there's
+                                    // nothing more to do about one failing than log it
+                                    let _ = game.handle_message(ctx, message)?;
                                 }
                             }
                         }
+                        ServerMessage::Rejected { message, reason } => {
+                            if let Some(MultiplayerPhase::Game { game, .. }) = &mut self.phase {
+                                game.reject(ctx, message, reason);
+                            }
+                        }
+                        ServerMessage::Resync { history, replay } => {
+                            if let Some(MultiplayerPhase::Game { game, .. }) = &mut self.phase {
+                                game.resync(ctx, *history, replay)?;
+                            }
+                        }
+                        ServerMessage::RewindVote(vote) => {
+                            if let Some(MultiplayerPhase::Game { game, .. }) = &mut self.phase {
+                                game.rewind_vote = vote;
+                            }
+                        }
                         ServerMessage::GameState(state) => {
                             self.phase = Some(MultiplayerPhase::new_from_state(
                                 ctx,
@@ -215,6 +284,7 @@ impl JoinClient {
                                 Some(self.connection.as_ref().unwrap().1),
                                 self.username.clone(),
                             ));
+                            self.apply_undo_policy();
                         }
                     }
                 }
@@ -224,6 +294,7 @@ impl JoinClient {
                     self.latency = None;
                     self.phase = None;
                     self.users = None;
+                    self.bots.clear();
                 }
             },
             JoinEvent::UIEvent(ui_event) => match ui_event {
@@ -258,6 +329,7 @@ impl SubEventHandler for JoinClient {
         {
             let mut back_button = self.back_button.borrow_mut();
             match self.phase {
+                None if self.error.is_some() => back_button.text = Text::new("Back"),
                 None => back_button.text = Text::new("Cancel"),
                 Some(MultiplayerPhase::Lobby(_)) => back_button.text = Text::new("Leave"),
                 _ => {}
@@ -279,6 +351,13 @@ impl SubEventHandler for JoinClient {
                             debug!("sending {message:?}");
                             connection.blind_send::<Message>(ClientMessage::Game(message));
                         }
+                        GameAction::RequestRewind(message) => {
+                            debug!("asking for {message:?}");
+                            connection.blind_send::<Message>(ClientMessage::Game(message));
+                        }
+                        GameAction::VoteOnRewind(agree) => {
+                            connection.blind_send::<Message>(ClientMessage::VoteOnRewind(agree));
+                        }
                         GameAction::ReturnToLobby => {
                             self.phase = Some(MultiplayerPhase::Lobby(LobbyClient::new(
                                 self.users.clone().unwrap_or_default(),
@@ -307,7 +386,11 @@ impl SubEventHandler for JoinClient {
     fn draw(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), GameError> {
         match &mut self.phase {
             None => {
-                Text::new(format!("Connecting to {}...", self.destination_name))
+                let status = match &self.error {
+                    Some(error) => format!("Couldn't start the host's game: {error}"),
+                    None => format!("Connecting to {}...", self.destination_name),
+                };
+                Text::new(status)
                     .size(36.0)
                     .anchored_by(
                         ctx,
@@ -20,7 +20,7 @@ use ggez_no_re::{
     util::ResultExt,
 };
 
-use super::message::server::{LobbyMessage, User};
+use super::message::server::{LobbyBot, LobbyMessage, UndoPolicy, User};
 
 #[derive(Clone, Debug)]
 pub enum LobbyEvent {
@@ -29,6 +29,8 @@ pub enum LobbyEvent {
 
 pub struct LobbyClient<T> {
     pub users: Vec<User>,
+    bots: Vec<LobbyBot>,
+    undo_policy: UndoPolicy,
     me: Option<IpAddr>,
     shared: Shared,
     _parent_channel: Sender<T>,
@@ -83,6 +85,8 @@ where
             me,
             shared,
             users,
+            bots: Vec::new(),
+            undo_policy: UndoPolicy::default(),
             _parent_channel: parent_channel,
             color_choice_ui,
             color_choice_buttons,
@@ -121,8 +125,14 @@ where
             LobbyMessage::LobbyState(state) => {
                 let changed_user_count = self.users.len() != state.users.len();
                 self.users = state.users;
-                let selected_colors: Vec<_> =
-                    self.users.iter().filter_map(|user| user.color).collect();
+                self.bots = state.bots;
+                self.undo_policy = state.undo_policy;
+                let selected_colors: Vec<_> = self
+                    .users
+                    .iter()
+                    .filter_map(|user| user.color)
+                    .chain(self.bots.iter().map(|bot| bot.color))
+                    .collect();
                 for (color, button) in PLAYER_COLORS.iter().zip(self.color_choice_buttons.iter()) {
                     button.borrow_mut().state =
                         UIElementState::disabled_if(selected_colors.contains(color));
@@ -197,6 +207,36 @@ where
             }
         }
 
+        for (i, bot) in self.bots.iter().enumerate() {
+            let bot_row_position =
+                player_list_pos + vec2(0.0, 32.0) * (self.users.len() + i + 1) as f32;
+            Text::new(format!("{} Bot", bot.difficulty.name()))
+                .size(32.0)
+                .anchored_by(ctx, bot_row_position, AnchorPoint::NorthWest)?
+                .color(Color::BLACK)
+                .draw(canvas);
+            GameClient::draw_meeple(
+                ctx,
+                canvas,
+                bot_row_position + vec2(-16.0, 16.0),
+                bot.color,
+                0.1,
+            )?;
+        }
+
+        // the host changes the policy from its own controls
+        if self.me.is_some() {
+            Text::new(format!("Undo: {}", self.undo_policy.name()))
+                .size(24.0)
+                .anchored_by(
+                    ctx,
+                    ctx.res() * vec2(0.6, 0.3) + vec2(0.0, 100.0),
+                    AnchorPoint::NorthWest,
+                )?
+                .color(Color::BLACK)
+                .draw(canvas);
+        }
+
         self.color_choice_ui.draw(ctx, canvas)?;
         for (color, button) in PLAYER_COLORS.iter().zip(self.color_choice_buttons.iter()) {
             let meeple_pos = button.borrow().corrected_bounds(ctx.res()).center().into();
@@ -3,7 +3,7 @@ use ggez_no_re::transport::ClientServerMessage;
 use serde::{Deserialize, Serialize};
 use server::ServerMessage;
 
-use crate::game::SegmentIdentifier;
+use crate::game::{MeepleKind, PlayerIdentifier, SegmentIdentifier};
 use crate::pos::GridPos;
 
 pub mod client;
@@ -54,7 +54,7 @@ impl TryFrom<Message> for ServerMessage {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameMessage {
     BeginGame,
     PlaceTile(TilePose),
@@ -64,13 +64,66 @@ pub enum GameMessage {
     PregamePickUp(GridPos),
     PlaceMeeple {
         seg_ident: SegmentIdentifier,
+        kind: MeepleKind,
     },
     SkipMeeples,
+    RetrieveAbbot,
+    MoveFairy(SegmentIdentifier),
+    DismissKnight(SegmentIdentifier),
+    MoveDragon(GridPos),
+    BuildTower {
+        pos: GridPos,
+        capture: Option<SegmentIdentifier>,
+    },
+    Ransom {
+        captor: PlayerIdentifier,
+    },
+    ExpandFlock,
+    HerdFlock,
     EndGame,
-    Undo,
+    /// Takes back this many of the most recent moves.
+    Undo(usize),
+    /// Makes this many of the most recently undone moves again.
+    Redo(usize),
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+impl GameMessage {
+    /// Whether the message is a move that can be taken back with `Undo`.
+    pub fn is_undoable(&self) -> bool {
+        matches!(
+            self,
+            GameMessage::PlaceTile(_)
+                | GameMessage::PlaceMeeple { .. }
+                | GameMessage::SkipMeeples
+                | GameMessage::RetrieveAbbot
+                | GameMessage::MoveFairy(_)
+                | GameMessage::DismissKnight(_)
+                | GameMessage::MoveDragon(_)
+                | GameMessage::BuildTower { .. }
+                | GameMessage::Ransom { .. }
+                | GameMessage::ExpandFlock
+                | GameMessage::HerdFlock
+                | GameMessage::EndGame
+        )
+    }
+
+    /// Whether the message moves back or forward through the game's history, rather
+    /// than making a move.
+    pub fn is_rewind(&self) -> bool {
+        matches!(self, GameMessage::Undo(_) | GameMessage::Redo(_))
+    }
+
+    /// Whether the message only shows where a player is thinking of placing a tile,
+    /// without changing the game.
+    pub fn is_preview(&self) -> bool {
+        matches!(
+            self,
+            GameMessage::PreviewTile(_) | GameMessage::PregamePreview(_)
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TilePose {
     pub position: GridPos,
     pub rotation: usize,
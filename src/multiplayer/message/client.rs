@@ -10,6 +10,10 @@ pub enum ClientMessage {
     Lobby(LobbyMessage),
     Game(GameMessage),
     Username(String),
+    /// Agrees to, or refuses, the undo or redo being voted on.
+    VoteOnRewind(bool),
+    /// Says why the game the host started can't be set up here, just before leaving it.
+    CantStartGame(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -5,8 +5,13 @@ use std::time::Duration;
 
 use std::net::IpAddr;
 
+use crate::game::bot::BotDifficulty;
 use crate::game::player::PlayerType;
-use crate::game_client::{GameExpansions, GameState};
+use crate::game::rules::GameState;
+use crate::game::setup::{GameExpansions, LibraryConfiguration};
+use crate::game::{PlayerIdentifier, ScoringRules};
+use crate::game_client::HistoryRecord;
+use crate::replay::Replay;
 
 use super::GameMessage;
 
@@ -22,8 +27,23 @@ pub enum ServerMessage {
     StartGame {
         game_seed: u64,
         expansions: GameExpansions,
+        library: LibraryConfiguration,
+        scoring_rules: ScoringRules,
     },
     GameState(Box<GameState>),
+    /// Turns down a move from the client that the rules don't allow.
+    Rejected {
+        message: GameMessage,
+        reason: String,
+    },
+    /// The game as the host has it, for a client that fell out of step to take up, as
+    /// the moves that can still be rewound and the replay of the game if it has one.
+    Resync {
+        history: Box<HistoryRecord>,
+        replay: Option<Replay>,
+    },
+    /// The undo or redo waiting on players to agree to it, if there is one.
+    RewindVote(Option<RewindVote>),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -34,6 +54,49 @@ pub enum LobbyMessage {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LobbyState {
     pub users: Vec<User>,
+    pub bots: Vec<LobbyBot>,
+    pub undo_policy: UndoPolicy,
+}
+
+/// Who can take back moves in a multiplayer game, chosen by the host.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UndoPolicy {
+    /// Anyone can undo or redo at any time.
+    #[default]
+    Allowed,
+    /// Players can only take back moves they made during their current turn.
+    OwnTurn,
+    /// Every player has to agree before anything is undone or redone.
+    Vote,
+}
+
+impl UndoPolicy {
+    pub const ALL: [UndoPolicy; 3] = [UndoPolicy::Allowed, UndoPolicy::OwnTurn, UndoPolicy::Vote];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            UndoPolicy::Allowed => "Allowed",
+            UndoPolicy::OwnTurn => "Own Turn Only",
+            UndoPolicy::Vote => "Unanimous Vote",
+        }
+    }
+}
+
+/// An undo or redo that a player has asked for, under the voting undo policy.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RewindVote {
+    pub proposer: PlayerIdentifier,
+    /// The `Undo` or `Redo` being voted on.
+    pub message: GameMessage,
+    /// The players who have agreed so far, starting with the proposer.
+    pub agreed: Vec<PlayerIdentifier>,
+}
+
+/// A seat the host has filled with a bot.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LobbyBot {
+    pub color: Color,
+    pub difficulty: BotDifficulty,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
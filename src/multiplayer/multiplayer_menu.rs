@@ -186,8 +186,11 @@ impl MultiplayerMenuClient {
                     let mut persistent = self.shared.persistent.borrow_mut();
                     persistent.username = username.clone();
                     persistent.host_port = port;
-                    self.parent_channel
-                        .send(MainEvent::MultiplayerHost { username, port })
+                    self.parent_channel.send(MainEvent::MultiplayerHost {
+                        username,
+                        port,
+                        saved_game: None,
+                    })
                 };
                 if let Err(errmsg) = result {
                     self.error_message = Some((errmsg, Instant::now()))
@@ -0,0 +1,375 @@
+use std::{
+    fs::{create_dir_all, read, write},
+    path::Path,
+};
+
+use ggez::{graphics::Color, GameError, GameResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    game::{
+        player::{Player, PlayerType},
+        rules::{GameState, TurnPhase},
+        setup::{GameExpansions, LibraryConfiguration},
+        ScoringRules,
+    },
+    multiplayer::message::GameMessage,
+    save::{SaveError, SaveFile, SaveFormat, SaveHeader, FORMAT_VERSION},
+};
+
+/// Marks the start of a binary replay file.
+const MAGIC: &[u8; 8] = b"CARCRPLY";
+/// Marks a JSON replay file, in its `format` field.
+const JSON_FORMAT: &str = "carcassonne-replay";
+
+/// A game recorded as how it was set up and the moves made in it. Setting a game up
+/// is deterministic given its seed, so any point in the game can be rebuilt by
+/// replaying its moves from the start.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    /// In turn order.
+    pub players: Vec<(Color, PlayerType)>,
+    pub local_player: PlayerType,
+    pub expansions: GameExpansions,
+    pub library: LibraryConfiguration,
+    pub scoring_rules: ScoringRules,
+    /// Every move made in the game that wasn't undone, in order.
+    pub messages: Vec<GameMessage>,
+}
+
+#[derive(Serialize)]
+struct JsonReplay<'a> {
+    format: &'a str,
+    header: SaveHeader,
+    replay: &'a Replay,
+}
+
+#[derive(Deserialize)]
+struct JsonReplayContents {
+    format: String,
+    header: SaveHeader,
+    replay: Value,
+}
+
+impl Replay {
+    pub fn new(
+        seed: u64,
+        players: &[Player],
+        local_player: PlayerType,
+        expansions: GameExpansions,
+        library: LibraryConfiguration,
+        scoring_rules: ScoringRules,
+    ) -> Replay {
+        Replay {
+            seed,
+            players: players
+                .iter()
+                .map(|player| (player.color, player.ptype.clone()))
+                .collect(),
+            local_player,
+            expansions,
+            library,
+            scoring_rules,
+            messages: Vec::new(),
+        }
+    }
+
+    /// The game as it was before any moves were made.
+    pub fn initial_state(&self) -> GameState {
+        GameState::new(
+            self.seed,
+            self.players
+                .iter()
+                .map(|(color, ptype)| Player::new_inner(*color, ptype.clone()))
+                .collect(),
+            &self.expansions,
+            &self.library,
+            &self.scoring_rules,
+            self.local_player.clone(),
+        )
+    }
+
+    /// Rebuilds the game as it was after the first `moves` moves.
+    pub fn state_at(&self, moves: usize) -> GameResult<GameState> {
+        let mut state = self.initial_state();
+        for (i, message) in self.messages.iter().take(moves).enumerate() {
+            state.apply(message.clone()).map_err(|err| {
+                GameError::CustomError(format!(
+                    "Move {} of the replay ({message:?}) couldn't be made: {err}",
+                    i + 1
+                ))
+            })?;
+        }
+        Ok(state)
+    }
+
+    /// Rebuilds the game as it was after its last move.
+    pub fn final_state(&self) -> GameResult<GameState> {
+        self.state_at(self.messages.len())
+    }
+
+    /// The game at the start of each turn, along with how many moves had been made by
+    /// then. The last entry is always the game after its last move, even if that move
+    /// didn't finish a turn.
+    pub fn turns(&self) -> GameResult<Vec<(usize, GameState)>> {
+        let mut state = self.initial_state();
+        let mut turns = vec![(0, state.clone())];
+        for (i, message) in self.messages.iter().enumerate() {
+            state.apply(message.clone()).map_err(|err| {
+                GameError::CustomError(format!(
+                    "Move {} of the replay ({message:?}) couldn't be made: {err}",
+                    i + 1
+                ))
+            })?;
+            if matches!(
+                state.turn_phase,
+                TurnPhase::TilePlacement { .. } | TurnPhase::EndGame
+            ) {
+                turns.push((i + 1, state.clone()));
+            }
+        }
+        if turns.last().unwrap().0 != self.messages.len() {
+            turns.push((self.messages.len(), state));
+        }
+        Ok(turns)
+    }
+
+    /// Records a move that was legally made. Previews don't change the game, so
+    /// they're left out, as are undos and redos, which are recorded by their effect.
+    pub fn record(&mut self, message: &GameMessage) {
+        if !message.is_preview() && !message.is_rewind() {
+            self.messages.push(message.clone());
+        }
+    }
+
+    /// Takes back the last undoable move, along with everything recorded after it.
+    pub fn undo(&mut self) {
+        if let Some(last) = self.messages.iter().rposition(GameMessage::is_undoable) {
+            self.messages.truncate(last);
+        }
+    }
+
+    fn header(&self) -> SaveHeader {
+        SaveHeader {
+            format_version: FORMAT_VERSION,
+            game_version: env!("CARGO_PKG_VERSION").to_string(),
+            expansions: self.expansions.names(),
+        }
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>, format: SaveFormat) -> Result<(), SaveError> {
+        if let Some(parent) = path.as_ref().parent() {
+            create_dir_all(parent)?;
+        }
+        let header = self.header();
+        let bytes = match format {
+            SaveFormat::Binary => {
+                let mut bytes = MAGIC.to_vec();
+                bincode::serialize_into(&mut bytes, &header)
+                    .and_then(|_| bincode::serialize_into(&mut bytes, self))
+                    .map_err(|err| SaveError::Encode(err.to_string()))?;
+                bytes
+            }
+            SaveFormat::Json => serde_json::to_vec_pretty(&JsonReplay {
+                format: JSON_FORMAT,
+                header,
+                replay: self,
+            })
+            .map_err(|err| SaveError::Encode(err.to_string()))?,
+        };
+        Ok(write(path, bytes)?)
+    }
+
+    /// Reads a replay file. Files that aren't replays, such as saves, are rejected with
+    /// `SaveError::NotASave`.
+    pub fn read(path: impl AsRef<Path>) -> Result<Replay, SaveError> {
+        let bytes = read(path)?;
+        let (header, replay) = if let Some(mut payload) = bytes.strip_prefix(MAGIC) {
+            let header: SaveHeader = bincode::deserialize_from(&mut payload)
+                .map_err(|err| SaveError::NotASave(format!("damaged header: {err}")))?;
+            if header.format_version > FORMAT_VERSION {
+                return Err(SaveError::TooNew(header));
+            }
+            let replay = bincode::deserialize(payload).map_err(|err| err.to_string());
+            (header, replay)
+        } else {
+            let contents: JsonReplayContents = serde_json::from_slice(&bytes)
+                .map_err(|_| SaveError::NotASave("not a replay".to_string()))?;
+            if contents.format != JSON_FORMAT {
+                return Err(SaveError::NotASave(format!(
+                    "its format is '{}'",
+                    contents.format
+                )));
+            }
+            if contents.header.format_version > FORMAT_VERSION {
+                return Err(SaveError::TooNew(contents.header));
+            }
+            let replay = serde_json::from_value(contents.replay).map_err(|err| err.to_string());
+            (contents.header, replay)
+        };
+        // the layout of replays hasn't changed since they were introduced in format 1
+        replay
+            .and_then(|replay: Replay| replay.library.check().map(|()| replay))
+            .map_err(|reason| SaveError::Unreadable { header, reason })
+    }
+}
+
+/// Reads a game written out by `--save-games`: either a replay, played through to its
+/// end, or a save from any version of the save format.
+pub fn load_game(path: impl AsRef<Path>) -> GameResult<(GameState, Option<Replay>)> {
+    match Replay::read(&path) {
+        Ok(replay) => Ok((replay.final_state()?, Some(replay))),
+        Err(SaveError::NotASave(_)) => Ok((SaveFile::read(path)?.state, None)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env::temp_dir, path::PathBuf, process};
+
+    use ggez::graphics::Color;
+
+    use crate::{
+        game::{
+            player::{Player, PlayerType},
+            rules::{GameState, TurnPhase},
+            setup::{GameExpansions, LibraryConfiguration},
+            ScoringRules,
+        },
+        multiplayer::message::{GameMessage, TilePose},
+        save::{SaveError, SaveFile, SaveFormat},
+    };
+
+    use super::Replay;
+
+    /// A path in the temp directory that no other test, or test run, writes to.
+    fn temp_path(name: &str) -> PathBuf {
+        temp_dir().join(format!("carcassonne_{}_{name}", process::id()))
+    }
+
+    fn test_replay() -> Replay {
+        Replay::new(
+            3,
+            &[Player::new(Color::RED), Player::new(Color::BLUE)],
+            PlayerType::Local,
+            GameExpansions {
+                traders_and_builders: true,
+                ..Default::default()
+            },
+            LibraryConfiguration::default(),
+            ScoringRules::default(),
+        )
+    }
+
+    /// Places the first tile it finds a spot for, and never places meeples.
+    fn next_move(state: &GameState) -> GameMessage {
+        match &state.turn_phase {
+            TurnPhase::TilePlacement {
+                tile,
+                placeable_positions,
+                ..
+            } => placeable_positions
+                .iter()
+                .find_map(|position| {
+                    (0..4).find_map(|rotation| {
+                        let mut tile = tile.clone();
+                        tile.rotate_to(rotation);
+                        state
+                            .game
+                            .is_valid_tile_position(&tile, *position)
+                            .then_some(TilePose {
+                                position: *position,
+                                rotation,
+                            })
+                    })
+                })
+                .map(GameMessage::PlaceTile)
+                .unwrap(),
+            _ => GameMessage::SkipMeeples,
+        }
+    }
+
+    #[test]
+    fn rebuild_from_replay() {
+        let mut replay = test_replay();
+        let mut state = replay.initial_state();
+        let mut history = Vec::new();
+        for _ in 0..20 {
+            let message = next_move(&state);
+            history.push(state.clone());
+            state.apply(message.clone()).unwrap();
+            replay.record(&message);
+            replay.record(&GameMessage::PreviewTile(None));
+        }
+        assert_eq!(replay.messages.len(), 20);
+        replay.undo();
+        state = history.pop().unwrap();
+
+        let rebuilt = replay.final_state().unwrap();
+        assert_eq!(
+            rebuilt.game.placed_tiles.len(),
+            state.game.placed_tiles.len()
+        );
+        assert_eq!(rebuilt.game.library.len(), state.game.library.len());
+        assert_eq!(
+            replay.state_at(0).unwrap().game.placed_tiles.len(),
+            replay.initial_state().game.placed_tiles.len()
+        );
+
+        // every tile placement starts a new turn
+        let turns = replay.turns().unwrap();
+        assert_eq!(turns.last().unwrap().0, replay.messages.len());
+        for (moves, turn) in &turns {
+            assert_eq!(
+                turn.game.placed_tiles.len(),
+                replay.state_at(*moves).unwrap().game.placed_tiles.len()
+            );
+        }
+        assert_eq!(
+            turns.len(),
+            replay
+                .messages
+                .iter()
+                .filter(|message| matches!(message, GameMessage::PlaceTile(_)))
+                .count()
+                + 1
+        );
+    }
+
+    #[test]
+    fn replay_round_trip() {
+        let mut replay = test_replay();
+        let state = replay.initial_state();
+        replay.record(&next_move(&state));
+        for format in [SaveFormat::Binary, SaveFormat::Json] {
+            let path = temp_path(&format!("replay_round_trip.{}", format.replay_extension()));
+            replay.write(&path, format).unwrap();
+            let read = Replay::read(&path).unwrap();
+            assert_eq!(read.seed, replay.seed);
+            assert_eq!(read.messages.len(), 1);
+
+            // replays and saves are told apart by their contents
+            let save_path = temp_path(&format!("replay_round_trip_save.{}", format.extension()));
+            SaveFile::write(&save_path, &state, format).unwrap();
+            assert!(matches!(
+                Replay::read(&save_path),
+                Err(SaveError::NotASave(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn reject_mismatched_library() {
+        let mut replay = test_replay();
+        replay.library.counts.pop();
+        let path = temp_path("reject_mismatched_library.replay.json");
+        replay.write(&path, SaveFormat::Json).unwrap();
+        assert!(matches!(
+            Replay::read(&path),
+            Err(SaveError::Unreadable { .. })
+        ));
+    }
+}
@@ -0,0 +1,375 @@
+use std::{
+    cell::RefCell,
+    path::PathBuf,
+    rc::Rc,
+    sync::mpsc::{channel, Receiver, Sender},
+};
+
+use ggez::{
+    glam::{vec2, Vec2},
+    graphics::{Canvas, Color, DrawMode, Mesh, Rect, Text},
+    input::mouse::MouseButton,
+    Context, GameError, GameResult,
+};
+use log::{debug, trace};
+
+use crate::{
+    colors::PANEL_COLOR,
+    game::{rules::GameState, PlayerIdentifier},
+    game_client::GameClient,
+    main_client::MainEvent,
+    replay::Replay,
+    util::{AnchorPoint, ContextExt, DrawableWihParamsExt, TextExt},
+    Shared,
+};
+
+use ggez_no_re::{
+    sub_event_handler::SubEventHandler,
+    ui_manager::{button::Button, Bounds, UIElement, UIManager},
+};
+
+/// Autoplay speeds to choose from, in turns per second.
+const SPEEDS: [f32; 6] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
+const DEFAULT_SPEED: usize = 2;
+
+const CONTROLS_CENTER: Rect = Rect::new(0.5, 1.0, 0.0, 0.0);
+const TIMELINE: Rect = Rect::new(-300.0, -110.0, 600.0, 12.0);
+
+#[derive(Clone, Debug)]
+enum ReplayEvent {
+    MainEvent(MainEvent),
+    First,
+    Previous,
+    TogglePlaying,
+    Next,
+    Last,
+    Slower,
+    Faster,
+    CyclePerspective,
+}
+
+/// Plays back a recorded game a turn at a time. Watching uses the same board as
+/// playing, with moves made by the replay instead of the players.
+pub struct ReplayClient {
+    parent_channel: Sender<MainEvent>,
+    _event_sender: Sender<ReplayEvent>,
+    event_receiver: Receiver<ReplayEvent>,
+    ui: UIManager<ReplayEvent, ReplayEvent>,
+    play_button: Rc<RefCell<Button<ReplayEvent>>>,
+    perspective_button: Rc<RefCell<Button<ReplayEvent>>>,
+    game: GameClient,
+    replay: Replay,
+    /// The game at the start of each turn, along with how many moves had been made by
+    /// then, so that jumping around the timeline doesn't replay from the beginning.
+    turns: Vec<(usize, GameState)>,
+    turn: usize,
+    playing: bool,
+    speed: usize,
+    next_step_at: f32,
+    /// Whether the timeline is being dragged.
+    scrubbing: bool,
+    /// The players in turn order, for choosing whose perspective to watch from.
+    players: Vec<PlayerIdentifier>,
+}
+
+impl ReplayClient {
+    pub fn load(
+        ctx: &Context,
+        shared: Shared,
+        parent_channel: Sender<MainEvent>,
+        path: PathBuf,
+    ) -> GameResult<ReplayClient> {
+        debug!("watching replay {}", path.display());
+        let replay = Replay::read(&path)?;
+        let turns = replay.turns()?;
+        let initial_state = turns[0].1.clone();
+        let players = initial_state.turn_order.iter().copied().collect();
+        let game = GameClient::new_spectator(ctx, shared, initial_state, parent_channel.clone());
+
+        let (event_sender, event_receiver) = channel();
+        let button = |x: f32, w: f32, text: &str, event: ReplayEvent| {
+            UIElement::Button(Button::new(
+                Bounds {
+                    relative: CONTROLS_CENTER,
+                    absolute: Rect::new(x, -80.0, w, 40.0),
+                },
+                Text::new(text),
+                event,
+            ))
+        };
+        let (ui, [UIElement::Button(play_button), UIElement::Button(perspective_button), ..]) =
+            UIManager::new_and_rc_elements(
+                event_sender.clone(),
+                [
+                    button(-180.0, 110.0, "Play", ReplayEvent::TogglePlaying),
+                    UIElement::Button(Button::new(
+                        Bounds {
+                            relative: Rect::new(1.0, 0.0, 0.0, 0.0),
+                            absolute: Rect::new(-260.0, 80.0, 240.0, 40.0),
+                        },
+                        Self::perspective_text(None),
+                        ReplayEvent::CyclePerspective,
+                    )),
+                    button(-300.0, 50.0, "|<", ReplayEvent::First),
+                    button(-240.0, 50.0, "<", ReplayEvent::Previous),
+                    button(-60.0, 50.0, ">", ReplayEvent::Next),
+                    button(0.0, 50.0, ">|", ReplayEvent::Last),
+                    button(70.0, 40.0, "-", ReplayEvent::Slower),
+                    button(260.0, 40.0, "+", ReplayEvent::Faster),
+                    UIElement::Button(Button::new(
+                        Bounds {
+                            relative: Rect::new(1.0, 0.0, 0.0, 0.0),
+                            absolute: Rect::new(-260.0, 20.0, 240.0, 40.0),
+                        },
+                        Text::new("Main Menu"),
+                        ReplayEvent::MainEvent(MainEvent::MainMenu),
+                    )),
+                ],
+            )
+        else {
+            panic!()
+        };
+
+        Ok(ReplayClient {
+            parent_channel,
+            _event_sender: event_sender,
+            event_receiver,
+            ui,
+            play_button,
+            perspective_button,
+            game,
+            replay,
+            turns,
+            turn: 0,
+            playing: false,
+            speed: DEFAULT_SPEED,
+            next_step_at: 0.0,
+            scrubbing: false,
+            players,
+        })
+    }
+
+    fn perspective_text(player: Option<usize>) -> Text {
+        Text::new(match player {
+            Some(i) => format!("Following Player {}", i + 1),
+            None => "Following Current Player".to_string(),
+        })
+    }
+
+    fn last_turn(&self) -> usize {
+        self.turns.len() - 1
+    }
+
+    /// Plays the moves of the current turn, showing their effects as they happen.
+    fn step_forward(&mut self, ctx: &Context) -> GameResult<()> {
+        if self.turn == self.last_turn() {
+            return Ok(());
+        }
+        let (start, _) = self.turns[self.turn];
+        let (end, _) = self.turns[self.turn + 1];
+        for message in &self.replay.messages[start..end] {
+            self.game.show_move(ctx, message.clone())?;
+        }
+        self.turn += 1;
+        Ok(())
+    }
+
+    fn jump_to(&mut self, ctx: &Context, turn: usize) {
+        let turn = turn.min(self.last_turn());
+        if turn != self.turn {
+            self.turn = turn;
+            self.game.show_state(ctx, self.turns[turn].1.clone());
+        }
+    }
+
+    fn set_playing(&mut self, ctx: &Context, playing: bool) {
+        self.playing = playing;
+        self.next_step_at = ctx.time.time_since_start().as_secs_f32();
+        self.play_button.borrow_mut().text = Text::new(if playing { "Pause" } else { "Play" });
+    }
+
+    fn handle_event(&mut self, ctx: &Context, event: ReplayEvent) -> GameResult<()> {
+        trace!("event = {event:?}");
+        match event {
+            ReplayEvent::MainEvent(event) => self.parent_channel.send(event).unwrap(),
+            ReplayEvent::First => self.jump_to(ctx, 0),
+            ReplayEvent::Previous => self.jump_to(ctx, self.turn.saturating_sub(1)),
+            ReplayEvent::Next => self.step_forward(ctx)?,
+            ReplayEvent::Last => self.jump_to(ctx, self.last_turn()),
+            ReplayEvent::TogglePlaying => {
+                if !self.playing && self.turn == self.last_turn() {
+                    self.jump_to(ctx, 0);
+                }
+                self.set_playing(ctx, !self.playing);
+            }
+            ReplayEvent::Slower => self.speed = self.speed.saturating_sub(1),
+            ReplayEvent::Faster => self.speed = (self.speed + 1).min(SPEEDS.len() - 1),
+            ReplayEvent::CyclePerspective => {
+                let next = match self.game.perspective {
+                    None => Some(0),
+                    Some(current) => self
+                        .players
+                        .iter()
+                        .position(|player| *player == current)
+                        .map(|i| i + 1)
+                        .filter(|i| *i < self.players.len()),
+                };
+                self.game.perspective = next.map(|i| self.players[i]);
+                self.perspective_button.borrow_mut().text = Self::perspective_text(next);
+            }
+        }
+        Ok(())
+    }
+
+    fn timeline_bounds(ctx: &Context) -> Rect {
+        Bounds {
+            relative: CONTROLS_CENTER,
+            absolute: TIMELINE,
+        }
+        .corrected_bounds(ctx.res())
+    }
+
+    fn timeline_update(&mut self, ctx: &Context) {
+        let bounds = Self::timeline_bounds(ctx);
+        let mouse: Vec2 = ctx.mouse.position().into();
+        if ctx.mouse.button_just_pressed(MouseButton::Left) {
+            // a little leeway above and below, since the bar is thin
+            let grab_bounds = Rect::new(bounds.x, bounds.y - 8.0, bounds.w, bounds.h + 16.0);
+            self.scrubbing = grab_bounds.contains(mouse);
+        }
+        if !ctx.mouse.button_pressed(MouseButton::Left) {
+            self.scrubbing = false;
+        }
+        if self.scrubbing {
+            let progress = ((mouse.x - bounds.x) / bounds.w).clamp(0.0, 1.0);
+            let turn = (progress * self.last_turn() as f32).round() as usize;
+            self.jump_to(ctx, turn);
+        }
+    }
+
+    fn autoplay_update(&mut self, ctx: &Context) -> GameResult<()> {
+        let now = ctx.time.time_since_start().as_secs_f32();
+        if self.playing && now >= self.next_step_at {
+            self.step_forward(ctx)?;
+            self.next_step_at = now + 1.0 / SPEEDS[self.speed];
+            if self.turn == self.last_turn() {
+                self.set_playing(ctx, false);
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_scoreboard(&self, ctx: &Context, canvas: &mut Canvas) -> GameResult<()> {
+        let res = ctx.res();
+        let state = &self.game.state;
+        let row_height = 30.0;
+        let panel = Rect::new(
+            res.x - 260.0,
+            140.0,
+            240.0,
+            row_height * state.turn_order.len() as f32 + 10.0,
+        );
+        Mesh::new_rounded_rectangle(ctx, DrawMode::fill(), panel, 5.0, PANEL_COLOR)?.draw(canvas);
+        for (i, player_ident) in self.players.iter().enumerate() {
+            let player = &state.game.players[*player_ident];
+            let row = vec2(panel.x + 10.0, panel.y + 5.0 + row_height * i as f32);
+            if self.game.perspective == Some(*player_ident) {
+                Mesh::new_rounded_rectangle(
+                    ctx,
+                    DrawMode::fill(),
+                    Rect::new(row.x - 5.0, row.y, panel.w - 10.0, row_height),
+                    5.0,
+                    Color::from_rgb(160, 160, 160),
+                )?
+                .draw(canvas);
+            }
+            GameClient::draw_meeple(ctx, canvas, row + vec2(15.0, 15.0), player.color, 0.1)?;
+            Text::new(format!("Player {}", i + 1))
+                .anchored_by(ctx, row + vec2(40.0, 15.0), AnchorPoint::CenterWest)?
+                .color(Color::WHITE)
+                .draw(canvas);
+            Text::new(format!("{}", player.score))
+                .anchored_by(
+                    ctx,
+                    row + vec2(panel.w - 30.0, 15.0),
+                    AnchorPoint::CenterEast,
+                )?
+                .color(Color::WHITE)
+                .draw(canvas);
+        }
+        Ok(())
+    }
+
+    fn draw_controls(&self, ctx: &Context, canvas: &mut Canvas) -> GameResult<()> {
+        let res = ctx.res();
+        let origin = Vec2::from(CONTROLS_CENTER.point()) * res;
+        Mesh::new_rounded_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(origin.x - 320.0, origin.y - 150.0, 640.0, 140.0),
+            8.0,
+            PANEL_COLOR,
+        )?
+        .draw(canvas);
+
+        // timeline
+        let bounds = Self::timeline_bounds(ctx);
+        Mesh::new_rounded_rectangle(
+            ctx,
+            DrawMode::fill(),
+            bounds,
+            bounds.h / 2.0,
+            Color::from_rgb(96, 96, 96),
+        )?
+        .draw(canvas);
+        let progress = if self.last_turn() == 0 {
+            1.0
+        } else {
+            self.turn as f32 / self.last_turn() as f32
+        };
+        let handle = vec2(bounds.x + bounds.w * progress, bounds.y + bounds.h / 2.0);
+        Mesh::new_rounded_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(bounds.x, bounds.y, handle.x - bounds.x, bounds.h),
+            bounds.h / 2.0,
+            Color::from_rgb(200, 200, 200),
+        )?
+        .draw(canvas);
+        Mesh::new_circle(ctx, DrawMode::fill(), handle, 10.0, 0.5, Color::WHITE)?.draw(canvas);
+
+        Text::new(format!("Turn {} of {}", self.turn, self.last_turn()))
+            .centered_on(ctx, origin + vec2(0.0, -133.0))?
+            .color(Color::WHITE)
+            .draw(canvas);
+        Text::new(format!("{} turns/s", SPEEDS[self.speed]))
+            .centered_on(ctx, origin + vec2(185.0, -60.0))?
+            .color(Color::WHITE)
+            .draw(canvas);
+        Ok(())
+    }
+}
+
+impl SubEventHandler for ReplayClient {
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) -> Result<(), GameError> {
+        self.game.mouse_wheel_event(ctx, x, y)
+    }
+
+    fn update(&mut self, ctx: &mut Context) -> Result<(), GameError> {
+        self.ui.update(ctx)?;
+        while let Ok(event) = self.event_receiver.try_recv() {
+            self.handle_event(ctx, event)?;
+        }
+        self.timeline_update(ctx);
+        self.autoplay_update(ctx)?;
+        self.game.update(ctx)
+    }
+
+    fn draw(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), GameError> {
+        self.game.draw(ctx, canvas)?;
+        self.draw_scoreboard(ctx, canvas)?;
+        self.draw_controls(ctx, canvas)?;
+        self.ui.draw(ctx, canvas)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use std::{
+    fs::read_dir,
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, Receiver, Sender},
+};
+
+use ggez::{
+    glam::vec2,
+    graphics::{Canvas, Color, Rect, Text},
+    Context, GameError,
+};
+use log::{trace, warn};
+
+use crate::{
+    main_client::MainEvent,
+    replay::Replay,
+    util::{ContextExt, DrawableWihParamsExt, TextExt},
+    Shared,
+};
+
+use ggez_no_re::{
+    sub_event_handler::SubEventHandler,
+    ui_manager::{button::Button, Bounds, UIElement, UIManager},
+};
+
+/// How many of the most recent replays are listed.
+const MAX_LISTED: usize = 10;
+
+#[derive(Clone, Debug)]
+enum ReplayMenuEvent {
+    MainEvent(MainEvent),
+}
+
+/// Lists the replays in the save directory, newest first, to pick one to watch.
+pub struct ReplayMenuClient {
+    parent_channel: Sender<MainEvent>,
+    _event_sender: Sender<ReplayMenuEvent>,
+    event_receiver: Receiver<ReplayMenuEvent>,
+    ui: UIManager<ReplayMenuEvent, ReplayMenuEvent>,
+    replay_uis: Vec<UIManager<ReplayMenuEvent, ReplayMenuEvent>>,
+    save_dir: PathBuf,
+}
+
+impl ReplayMenuClient {
+    pub fn new(parent_channel: Sender<MainEvent>, shared: Shared) -> ReplayMenuClient {
+        let (event_sender, event_receiver) = channel();
+        let save_dir = shared
+            .args
+            .save_games
+            .clone()
+            .flatten()
+            .unwrap_or_else(|| PathBuf::from("saves/"));
+        let ui = UIManager::new(
+            event_sender.clone(),
+            [UIElement::Button(Button::new(
+                Bounds::absolute(Rect::new(30.0, 30.0, 120.0, 40.0)),
+                Text::new("Back"),
+                ReplayMenuEvent::MainEvent(MainEvent::MainMenu),
+            ))],
+        );
+        let replay_uis = Self::list_replays(&save_dir)
+            .into_iter()
+            .enumerate()
+            .map(|(i, (path, label))| {
+                UIManager::new(
+                    event_sender.clone(),
+                    [UIElement::Button(Button::new(
+                        Bounds {
+                            relative: Rect::new(0.5, 0.0, 0.0, 0.0),
+                            absolute: Rect::new(-250.0, 200.0 + 50.0 * i as f32, 500.0, 40.0),
+                        },
+                        Text::new(label),
+                        ReplayMenuEvent::MainEvent(MainEvent::WatchReplay(path)),
+                    ))],
+                )
+            })
+            .collect();
+        ReplayMenuClient {
+            parent_channel,
+            _event_sender: event_sender,
+            event_receiver,
+            ui,
+            replay_uis,
+            save_dir,
+        }
+    }
+
+    /// Finds the most recent replays in the directory, with a label describing each.
+    /// Replays are named after when their game started, so sorting by name puts the
+    /// newest first.
+    fn list_replays(dir: &Path) -> Vec<(PathBuf, String)> {
+        let Ok(entries) = read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.ends_with(".replay") || name.ends_with(".replay.json"))
+            })
+            .collect();
+        paths.sort();
+        paths.reverse();
+        paths
+            .into_iter()
+            .filter_map(|path| match Replay::read(&path) {
+                Ok(replay) => {
+                    let name = path.file_name()?.to_str()?.split('.').next()?.to_string();
+                    let label = format!(
+                        "{name} - {} players, {} moves",
+                        replay.players.len(),
+                        replay.messages.len()
+                    );
+                    Some((path, label))
+                }
+                Err(err) => {
+                    warn!("skipping replay {}: {err}", path.display());
+                    None
+                }
+            })
+            .take(MAX_LISTED)
+            .collect()
+    }
+}
+
+impl SubEventHandler for ReplayMenuClient {
+    fn update(&mut self, ctx: &mut Context) -> Result<(), GameError> {
+        self.ui.update(ctx)?;
+        for ui in &mut self.replay_uis {
+            ui.update(ctx)?;
+        }
+        while let Ok(event) = self.event_receiver.try_recv() {
+            trace!("event = {event:?}");
+            match event {
+                ReplayMenuEvent::MainEvent(event) => self.parent_channel.send(event).unwrap(),
+            }
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), GameError> {
+        let res = ctx.res();
+        Text::new("Replays")
+            .size(64.0)
+            .centered_on(ctx, res * vec2(0.5, 0.0) + vec2(0.0, 100.0))?
+            .color(Color::BLACK)
+            .draw(canvas);
+        if self.replay_uis.is_empty() {
+            Text::new(format!(
+                "No replays found in {}; play with --save-games to record them",
+                self.save_dir.display()
+            ))
+            .centered_on(ctx, res * vec2(0.5, 0.0) + vec2(0.0, 220.0))?
+            .color(Color::BLACK)
+            .draw(canvas);
+        }
+        self.ui.draw(ctx, canvas)?;
+        for ui in &mut self.replay_uis {
+            ui.draw(ctx, canvas)?;
+        }
+        Ok(())
+    }
+}
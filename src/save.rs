@@ -0,0 +1,406 @@
+use std::{
+    fmt::Display,
+    fs::{create_dir_all, read, write},
+    path::Path,
+};
+
+use clap::ValueEnum;
+use ggez::GameError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::game::rules::GameState;
+
+mod v0;
+
+/// Marks the start of a binary save file.
+const MAGIC: &[u8; 8] = b"CARCSAVE";
+/// Marks a JSON save file, in its `format` field.
+const JSON_FORMAT: &str = "carcassonne-save";
+
+/// Bumped whenever a change to the game state stops older saves from decoding; each bump
+/// needs a case in `migrate` that upgrades saves from the version before. Version 0 is the
+/// headerless saves of 1.5.1, which `v0` upgrades. Replays share the header and version
+/// number of saves.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Describes a save file, readable without decoding the game in it. The layout of this
+/// struct is part of the binary format, so it should never change.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SaveHeader {
+    pub format_version: u32,
+    /// Version of the game that wrote the save.
+    pub game_version: String,
+    pub expansions: Vec<String>,
+}
+
+impl SaveHeader {
+    fn current(state: &GameState) -> SaveHeader {
+        SaveHeader {
+            format_version: FORMAT_VERSION,
+            game_version: env!("CARGO_PKG_VERSION").to_string(),
+            expansions: state.expansions.clone(),
+        }
+    }
+
+    /// Saves from before the container format were a bare game state with no header.
+    fn legacy(state: &GameState) -> SaveHeader {
+        SaveHeader {
+            format_version: 0,
+            game_version: "1.5.1".to_string(),
+            expansions: state.expansions.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SaveFormat {
+    /// Compact, and quick to write every turn
+    Binary,
+    /// Human readable
+    Json,
+}
+
+impl SaveFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            SaveFormat::Binary => "save",
+            SaveFormat::Json => "save.json",
+        }
+    }
+
+    pub fn replay_extension(self) -> &'static str {
+        match self {
+            SaveFormat::Binary => "replay",
+            SaveFormat::Json => "replay.json",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    /// Written by a newer version of the game than this one.
+    TooNew(SaveHeader),
+    NotASave(String),
+    /// The game in the save couldn't be decoded as the format version it claims to be.
+    Unreadable {
+        header: SaveHeader,
+        reason: String,
+    },
+    Encode(String),
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "{err}"),
+            SaveError::NotASave(reason) => write!(f, "Not a save file: {reason}"),
+            SaveError::TooNew(header) => write!(
+                f,
+                "This save was made with version {} of the game (save format {}), but this is version {} (save format {FORMAT_VERSION}); update the game to load it",
+                header.game_version,
+                header.format_version,
+                env!("CARGO_PKG_VERSION"),
+            ),
+            SaveError::Unreadable { header, reason } => write!(
+                f,
+                "Couldn't read save made with version {} of the game (save format {}): {reason}",
+                header.game_version, header.format_version,
+            ),
+            SaveError::Encode(reason) => write!(f, "Couldn't write save: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<std::io::Error> for SaveError {
+    fn from(err: std::io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+impl From<SaveError> for GameError {
+    fn from(err: SaveError) -> Self {
+        GameError::CustomError(err.to_string())
+    }
+}
+
+#[derive(Serialize)]
+struct JsonSave<'a> {
+    format: &'a str,
+    header: SaveHeader,
+    state: &'a GameState,
+}
+
+#[derive(Deserialize)]
+struct JsonSaveContents {
+    format: String,
+    header: SaveHeader,
+    state: Value,
+}
+
+/// The undecoded game state in a save.
+enum Payload<'a> {
+    Binary(&'a [u8]),
+    Json(Value),
+}
+
+/// A game loaded from a save file, upgraded to the current format.
+pub struct SaveFile {
+    pub header: SaveHeader,
+    pub state: GameState,
+}
+
+impl SaveFile {
+    pub fn write(
+        path: impl AsRef<Path>,
+        state: &GameState,
+        format: SaveFormat,
+    ) -> Result<(), SaveError> {
+        if let Some(parent) = path.as_ref().parent() {
+            create_dir_all(parent)?;
+        }
+        let header = SaveHeader::current(state);
+        let bytes = match format {
+            SaveFormat::Binary => {
+                let mut bytes = MAGIC.to_vec();
+                bincode::serialize_into(&mut bytes, &header)
+                    .and_then(|_| bincode::serialize_into(&mut bytes, state))
+                    .map_err(|err| SaveError::Encode(err.to_string()))?;
+                bytes
+            }
+            SaveFormat::Json => serde_json::to_vec_pretty(&JsonSave {
+                format: JSON_FORMAT,
+                header,
+                state,
+            })
+            .map_err(|err| SaveError::Encode(err.to_string()))?,
+        };
+        Ok(write(path, bytes)?)
+    }
+
+    pub fn read(path: impl AsRef<Path>) -> Result<SaveFile, SaveError> {
+        let bytes = read(path)?;
+        let Some((header, payload)) = split(&bytes)? else {
+            return read_legacy(&bytes);
+        };
+        if header.format_version > FORMAT_VERSION {
+            return Err(SaveError::TooNew(header));
+        }
+        match migrate(header.format_version, payload) {
+            Ok(state) => Ok(SaveFile { header, state }),
+            Err(reason) => Err(SaveError::Unreadable { header, reason }),
+        }
+    }
+
+    /// Reads only the header of a save, without decoding the game.
+    pub fn read_header(path: impl AsRef<Path>) -> Result<SaveHeader, SaveError> {
+        let bytes = read(path)?;
+        match split(&bytes)? {
+            Some((header, _)) => Ok(header),
+            // there's nothing to go on but the game itself
+            None => read_legacy(&bytes).map(|save| save.header),
+        }
+    }
+}
+
+/// Reads a save with no header, which can only be a bare game state from 1.5.1.
+fn read_legacy(bytes: &[u8]) -> Result<SaveFile, SaveError> {
+    let state = v0::migrate(bytes).map_err(|err| {
+        SaveError::NotASave(format!("no header, and not a save from 1.5.1 ({err})"))
+    })?;
+    Ok(SaveFile {
+        header: SaveHeader::legacy(&state),
+        state,
+    })
+}
+
+/// Separates the header of a save from its game state, working out which variant of the
+/// format it's in. Returns `None` if there's no header.
+fn split(bytes: &[u8]) -> Result<Option<(SaveHeader, Payload<'_>)>, SaveError> {
+    if let Some(mut payload) = bytes.strip_prefix(MAGIC) {
+        let header = bincode::deserialize_from(&mut payload)
+            .map_err(|err| SaveError::NotASave(format!("damaged header: {err}")))?;
+        return Ok(Some((header, Payload::Binary(payload))));
+    }
+    if bytes.trim_ascii_start().starts_with(b"{") {
+        let contents: JsonSaveContents =
+            serde_json::from_slice(bytes).map_err(|err| SaveError::NotASave(err.to_string()))?;
+        if contents.format != JSON_FORMAT {
+            return Err(SaveError::NotASave(format!(
+                "its format is '{}'",
+                contents.format
+            )));
+        }
+        return Ok(Some((contents.header, Payload::Json(contents.state))));
+    }
+    Ok(None)
+}
+
+/// Decodes the game state of a save written in the given format version, upgrading saves
+/// in older formats to the current game state.
+fn migrate(format_version: u32, payload: Payload) -> Result<GameState, String> {
+    match (format_version, payload) {
+        (FORMAT_VERSION, Payload::Binary(bytes)) => {
+            bincode::deserialize(bytes).map_err(|err| err.to_string())
+        }
+        (FORMAT_VERSION, Payload::Json(state)) => {
+            serde_json::from_value(state).map_err(|err| err.to_string())
+        }
+        (format_version, _) => Err(format!(
+            "no way to upgrade saves from format {format_version}"
+        )),
+    }
+}
+
+/// Serializes a map as a list of key-value pairs, so that maps keyed by tuples can be
+/// written to JSON. Bincode encodes a map and a list of pairs the same way, so using this
+/// on a field doesn't change the binary format.
+pub mod map_as_pairs {
+    use std::{collections::HashMap, hash::Hash};
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map)
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Vec::<(K, V)>::deserialize(deserializer).map(|pairs| pairs.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env::temp_dir, fs::write, path::PathBuf, process};
+
+    use ggez::graphics::Color;
+
+    use crate::{
+        game::{
+            player::{Player, PlayerType},
+            rules::{GameState, TurnPhase},
+            setup::{GameExpansions, LibraryConfiguration},
+            MeepleKind, ScoringRules,
+        },
+        pos::GridPos,
+        tile::SegmentType,
+    };
+
+    use super::{SaveError, SaveFile, SaveFormat, FORMAT_VERSION, MAGIC};
+
+    /// A path in the temp directory that no other test, or test run, writes to.
+    fn temp_path(name: &str) -> PathBuf {
+        temp_dir().join(format!("carcassonne_{}_{name}", process::id()))
+    }
+
+    fn test_state() -> GameState {
+        GameState::new(
+            7,
+            vec![Player::new(Color::RED), Player::new(Color::BLUE)],
+            &GameExpansions {
+                inns_and_cathedrals: true,
+                ..Default::default()
+            },
+            &LibraryConfiguration::default(),
+            &ScoringRules::default(),
+            PlayerType::Local,
+        )
+    }
+
+    #[test]
+    fn save_round_trip() {
+        let state = test_state();
+        for format in [SaveFormat::Binary, SaveFormat::Json] {
+            let path = temp_path(&format!("save_round_trip.{}", format.extension()));
+            SaveFile::write(&path, &state, format).unwrap();
+            let save = SaveFile::read(&path).unwrap();
+            assert_eq!(save.header.format_version, FORMAT_VERSION);
+            assert_eq!(
+                save.header.expansions,
+                vec!["Inns & Cathedrals".to_string()]
+            );
+            assert_eq!(
+                save.state.game.placed_tiles.len(),
+                state.game.placed_tiles.len()
+            );
+            assert_eq!(save.state.game.library.len(), state.game.library.len());
+        }
+    }
+
+    #[test]
+    fn load_legacy_save() {
+        // written by version 1.5.1: red has placed a crossroads east of the starting tile, with
+        // a meeple on its east road, and it's blue's turn to place a curve
+        let path = temp_path("load_legacy_save.save");
+        write(&path, include_bytes!("save/fixtures/1.5.1.save")).unwrap();
+        let save = SaveFile::read(&path).unwrap();
+        assert_eq!(save.header.format_version, 0);
+        assert_eq!(save.header.game_version, "1.5.1");
+        assert!(save.header.expansions.is_empty());
+
+        let game = &save.state.game;
+        assert_eq!(game.library.len(), 2);
+        assert_eq!(game.players.len(), 2);
+        assert_eq!(
+            game.placed_tiles[&GridPos(1, 0)].segments[0].stype,
+            SegmentType::Village
+        );
+        for (seg_ident, group_ident) in &game.group_associations {
+            assert!(game.groups[*group_ident].segments.contains(seg_ident));
+        }
+        let road = &game.groups[game.group_associations[&(GridPos(1, 0), 2)]];
+        let [(_, owner, MeepleKind::Normal)] = road.meeples[..] else {
+            panic!("expected one normal meeple on the road");
+        };
+        assert_eq!(game.players[owner].meeples, 6);
+        assert_eq!(save.state.turn_order.back(), Some(&owner));
+        assert!(matches!(
+            save.state.turn_phase,
+            TurnPhase::TilePlacement {
+                bonus_turn: false,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn reject_headerless_non_save() {
+        let path = temp_path("reject_headerless_non_save.save");
+        write(&path, b"not a save at all").unwrap();
+        assert!(matches!(SaveFile::read(&path), Err(SaveError::NotASave(_))));
+        assert!(matches!(
+            SaveFile::read_header(&path),
+            Err(SaveError::NotASave(_))
+        ));
+    }
+
+    #[test]
+    fn reject_newer_save() {
+        let mut bytes = MAGIC.to_vec();
+        bincode::serialize_into(
+            &mut bytes,
+            &super::SaveHeader {
+                format_version: FORMAT_VERSION + 1,
+                game_version: "99.0.0".to_string(),
+                expansions: Vec::new(),
+            },
+        )
+        .unwrap();
+        let path = temp_path("reject_newer_save.save");
+        write(&path, bytes).unwrap();
+        assert!(matches!(SaveFile::read(&path), Err(SaveError::TooNew(_))));
+    }
+}
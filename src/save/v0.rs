@@ -0,0 +1,302 @@
+//! Frozen copies of the game state as version 1.5.1 wrote it, before saves had a header.
+//! These must never change; types whose layout hasn't changed since are used directly.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use ggez::{glam::Vec2, graphics::Color};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use slotmap::{Key, SlotMap};
+
+use crate::{
+    game::{
+        player::{Player, PlayerType},
+        rules::{GameState, RuleRng, TurnPhase},
+        setup::GameExpansions,
+        EdgeIdentifier, Game, GroupIdentifier, MeepleKind, PlayerIdentifier, ScoringDetails,
+        SegmentGroup, SegmentIdentifier, ShapeDetails,
+    },
+    pos::GridPos,
+    tile::{
+        Mounts, Orientation, Segment, SegmentAttribute, SegmentBorderPiece, SegmentType, Tile,
+        TileAttribute, TileEdge,
+    },
+};
+use ggez_no_re::line::Line;
+
+/// Gardens were added in the middle of the list, which moved the variants after them.
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+enum SegmentTypeV0 {
+    Farm,
+    City,
+    Road,
+    Monastary,
+    Village,
+    River,
+}
+
+impl From<SegmentTypeV0> for SegmentType {
+    fn from(stype: SegmentTypeV0) -> Self {
+        match stype {
+            SegmentTypeV0::Farm => SegmentType::Farm,
+            SegmentTypeV0::City => SegmentType::City,
+            SegmentTypeV0::Road => SegmentType::Road,
+            SegmentTypeV0::Monastary => SegmentType::Monastary,
+            SegmentTypeV0::Village => SegmentType::Village,
+            SegmentTypeV0::River => SegmentType::River,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SegmentV0 {
+    stype: SegmentTypeV0,
+    poly: Vec<usize>,
+    attributes: Vec<SegmentAttribute>,
+    meeple_spot: Vec2,
+    edge_definition: Vec<SegmentBorderPiece>,
+}
+
+/// Tiles had no art yet, so migrated tiles are drawn from their outlines.
+#[derive(Deserialize)]
+struct TileV0 {
+    verts: Line,
+    segments: Vec<SegmentV0>,
+    mounts: Mounts,
+    segment_adjacency: Vec<bool>,
+    attributes: Vec<TileAttribute>,
+    edge_verts_map: HashMap<TileEdge, [usize; 2]>,
+    rotation: usize,
+}
+
+impl TileV0 {
+    fn has_river(&self) -> bool {
+        self.segments
+            .iter()
+            .any(|segment| segment.stype == SegmentTypeV0::River)
+    }
+}
+
+impl From<TileV0> for Tile {
+    fn from(tile: TileV0) -> Self {
+        Tile {
+            verts: tile.verts,
+            segments: tile
+                .segments
+                .into_iter()
+                .map(|segment| Segment {
+                    stype: segment.stype.into(),
+                    poly: segment.poly,
+                    attributes: segment.attributes,
+                    meeple_spot: segment.meeple_spot,
+                    edge_definition: segment.edge_definition,
+                })
+                .collect(),
+            mounts: tile.mounts,
+            segment_adjacency: tile.segment_adjacency,
+            attributes: tile.attributes,
+            edge_verts_map: tile.edge_verts_map,
+            rotation: tile.rotation,
+            art: None,
+        }
+    }
+}
+
+fn migrate_tiles(tiles: HashMap<GridPos, TileV0>) -> HashMap<GridPos, Tile> {
+    tiles
+        .into_iter()
+        .map(|(pos, tile)| (pos, tile.into()))
+        .collect()
+}
+
+/// Written out in the current layout of [`Player`], see [`migrate_slots`].
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(into = "Player")]
+struct PlayerV0 {
+    meeples: usize,
+    score: usize,
+    color: Color,
+    ptype: PlayerType,
+}
+
+impl From<PlayerV0> for Player {
+    fn from(player: PlayerV0) -> Self {
+        Player {
+            meeples: player.meeples,
+            score: player.score,
+            ..Player::new_inner(player.color, player.ptype)
+        }
+    }
+}
+
+/// Written out in the current layout of [`SegmentGroup`], see [`migrate_slots`].
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(into = "SegmentGroup")]
+struct SegmentGroupV0 {
+    gtype: SegmentTypeV0,
+    segments: Vec<SegmentIdentifier>,
+    free_edges: HashSet<EdgeIdentifier>,
+    /// Every meeple was a normal one.
+    meeples: Vec<(SegmentIdentifier, PlayerIdentifier)>,
+    scoring_details: Option<ScoringDetails>,
+    shape_details: Option<ShapeDetails>,
+}
+
+impl From<SegmentGroupV0> for SegmentGroup {
+    fn from(group: SegmentGroupV0) -> Self {
+        SegmentGroup {
+            gtype: group.gtype.into(),
+            segments: group.segments,
+            free_edges: group.free_edges,
+            meeples: group
+                .meeples
+                .into_iter()
+                .map(|(seg_ident, player_ident)| (seg_ident, player_ident, MeepleKind::Normal))
+                .collect(),
+            scoring_details: group.scoring_details,
+            shape_details: group.shape_details,
+        }
+    }
+}
+
+/// Upgrades the values of a slot map while keeping their keys, which are referred to all
+/// over the game state. Slot maps can't be rebuilt with chosen keys, so the map is written
+/// out with its values converted to their new layout, and read back in.
+fn migrate_slots<K, Old, New>(slots: &SlotMap<K, Old>) -> Result<SlotMap<K, New>, String>
+where
+    K: Key,
+    Old: Serialize,
+    New: DeserializeOwned,
+{
+    bincode::serialize(slots)
+        .and_then(|bytes| bincode::deserialize(&bytes))
+        .map_err(|err| err.to_string())
+}
+
+#[derive(Deserialize)]
+struct GameV0 {
+    local_player: PlayerType,
+    library: Vec<TileV0>,
+    placed_tiles: HashMap<GridPos, TileV0>,
+    groups: SlotMap<GroupIdentifier, SegmentGroupV0>,
+    group_associations: HashMap<SegmentIdentifier, GroupIdentifier>,
+    players: SlotMap<PlayerIdentifier, PlayerV0>,
+    valid_placements: Vec<GridPos>,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Deserialize)]
+enum TurnPhaseV0 {
+    Pregame {
+        tiles: HashMap<GridPos, TileV0>,
+        held: Option<TileV0>,
+        preview_location: Option<GridPos>,
+        open_edges: Vec<(GridPos, Orientation)>,
+    },
+    TilePlacement {
+        tile: TileV0,
+        placeable_positions: Vec<GridPos>,
+        preview_location: Option<GridPos>,
+    },
+    MeeplePlacement {
+        placed_position: GridPos,
+        closed_groups: Vec<GroupIdentifier>,
+    },
+    /// The time is on the clock of the game that wrote the save, so it's dropped.
+    EndGame {
+        #[allow(unused)]
+        next_tick: Option<f32>,
+    },
+}
+
+#[derive(Deserialize)]
+struct GameStateV0 {
+    game: GameV0,
+    turn_phase: TurnPhaseV0,
+    turn_order: VecDeque<PlayerIdentifier>,
+}
+
+impl GameStateV0 {
+    /// The only expansion in 1.5.1 was the first river, which wasn't recorded anywhere
+    /// but in the tiles.
+    fn expansions(&self) -> Vec<String> {
+        let pregame_tiles: Vec<&TileV0> = match &self.turn_phase {
+            TurnPhaseV0::Pregame { tiles, held, .. } => tiles.values().chain(held).collect(),
+            TurnPhaseV0::TilePlacement { tile, .. } => vec![tile],
+            _ => Vec::new(),
+        };
+        let rivers_1 = self
+            .game
+            .library
+            .iter()
+            .chain(self.game.placed_tiles.values())
+            .chain(pregame_tiles)
+            .any(TileV0::has_river);
+        GameExpansions {
+            rivers_1,
+            ..Default::default()
+        }
+        .names()
+    }
+}
+
+/// Decodes a bare game state written by version 1.5.1, and upgrades it to the current one.
+pub fn migrate(bytes: &[u8]) -> Result<GameState, String> {
+    let state: GameStateV0 = bincode::deserialize(bytes).map_err(|err| err.to_string())?;
+    let expansions = state.expansions();
+    let GameStateV0 {
+        game,
+        turn_phase,
+        turn_order,
+    } = state;
+    let turn_phase = match turn_phase {
+        TurnPhaseV0::Pregame {
+            tiles,
+            held,
+            preview_location,
+            open_edges,
+        } => TurnPhase::Pregame {
+            tiles: migrate_tiles(tiles),
+            held: held.map(Tile::from),
+            preview_location,
+            open_edges,
+        },
+        TurnPhaseV0::TilePlacement {
+            tile,
+            placeable_positions,
+            preview_location,
+        } => TurnPhase::TilePlacement {
+            tile: tile.into(),
+            placeable_positions,
+            preview_location,
+            bonus_turn: false,
+        },
+        TurnPhaseV0::MeeplePlacement {
+            placed_position,
+            closed_groups,
+        } => TurnPhase::MeeplePlacement {
+            placed_position,
+            closed_groups,
+            earned_bonus_turn: false,
+        },
+        TurnPhaseV0::EndGame { .. } => TurnPhase::EndGame,
+    };
+    let game = Game {
+        local_player: game.local_player,
+        library: game.library.into_iter().map(Tile::from).collect(),
+        placed_tiles: migrate_tiles(game.placed_tiles),
+        groups: migrate_slots(&game.groups)?,
+        group_associations: game.group_associations,
+        players: migrate_slots(&game.players)?,
+        valid_placements: game.valid_placements,
+        ..Game::new_inner(Vec::new(), PlayerType::Local)
+    };
+    Ok(GameState {
+        game,
+        turn_phase,
+        turn_order,
+        goods_scored: false,
+        fairy_in_play: false,
+        rng: RuleRng::default(),
+        expansions,
+    })
+}
@@ -0,0 +1,113 @@
+use core::panic;
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::mpsc::{channel, Receiver},
+};
+
+use ggez::graphics::{Rect, Text};
+use ggez_no_re::{
+    sub_event_handler::SubEventHandler,
+    ui_manager::{
+        button::Button, checkbox::Checkbox, Bounds, UIElement, UIElementRenderable, UIManager,
+    },
+    util::AnchorPoint,
+};
+
+use crate::game::{FarmerRules, ScoringRules};
+
+#[derive(Clone, Debug)]
+enum ScoringRulesEvent {
+    CycleFarmerRules,
+}
+
+pub struct ScoringRulesSelector {
+    _root: Bounds,
+    event_receiver: Receiver<ScoringRulesEvent>,
+    ui: UIManager<ScoringRulesEvent, ScoringRulesEvent>,
+    farmer_rules: FarmerRules,
+    farmer_rules_button: Rc<RefCell<Button<ScoringRulesEvent>>>,
+    small_cities_checkbox: Rc<RefCell<Checkbox>>,
+}
+
+impl ScoringRulesSelector {
+    pub fn new(root: Bounds) -> Self {
+        let Bounds { relative, absolute } = root;
+        let (event_sender, event_receiver) = channel();
+        let (
+            ui,
+            [UIElement::Button(farmer_rules_button), UIElement::Checkbox(small_cities_checkbox)],
+        ) = UIManager::new_and_rc_elements(
+            event_sender,
+            [
+                UIElement::Button(Button::new(
+                    Bounds {
+                        relative,
+                        absolute: Rect::new(absolute.x, absolute.y, 220.0, 30.0),
+                    },
+                    Text::new(FarmerRules::default().name()),
+                    ScoringRulesEvent::CycleFarmerRules,
+                )),
+                UIElement::Checkbox(Checkbox::new(Bounds {
+                    relative,
+                    absolute: Rect::new(absolute.x + 10.0, absolute.y + 40.0, 20.0, 20.0),
+                })),
+            ],
+        )
+        else {
+            panic!()
+        };
+        ScoringRulesSelector {
+            _root: root,
+            event_receiver,
+            ui,
+            farmer_rules: FarmerRules::default(),
+            farmer_rules_button,
+            small_cities_checkbox,
+        }
+    }
+
+    pub fn get_selected_rules(&self) -> ScoringRules {
+        ScoringRules {
+            farmers: self.farmer_rules,
+            small_cities: self.small_cities_checkbox.borrow().checked,
+        }
+    }
+}
+
+impl SubEventHandler for ScoringRulesSelector {
+    fn update(&mut self, ctx: &mut ggez::Context) -> Result<(), ggez::GameError> {
+        self.ui.update(ctx)?;
+        while let Ok(event) = self.event_receiver.try_recv() {
+            match event {
+                ScoringRulesEvent::CycleFarmerRules => {
+                    let index = FarmerRules::ALL
+                        .iter()
+                        .position(|rules| *rules == self.farmer_rules)
+                        .unwrap();
+                    self.farmer_rules = FarmerRules::ALL[(index + 1) % FarmerRules::ALL.len()];
+                    self.farmer_rules_button.borrow_mut().text =
+                        Text::new(self.farmer_rules.name());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn draw(
+        &mut self,
+        ctx: &mut ggez::Context,
+        canvas: &mut ggez::graphics::Canvas,
+    ) -> Result<(), ggez::GameError> {
+        self.ui.draw(ctx, canvas)?;
+
+        self.small_cities_checkbox.borrow().render_label(
+            ctx,
+            canvas,
+            &Text::new(" 2 Point Small Cities"),
+            AnchorPoint::CenterEast,
+        )?;
+
+        Ok(())
+    }
+}
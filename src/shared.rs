@@ -1,10 +1,16 @@
+use std::{cell::RefCell, rc::Rc};
+
 use ggez::winit::{event::MouseButton, keyboard::NamedKey};
 use ggez_no_re::{
     discord::DiscordPresence, keybinds, persist::PersistenceManager, util::ResultExt,
 };
 use serde::{Deserialize, Serialize};
 
-use crate::Args;
+use crate::{
+    game_client::LibraryPreset,
+    tile::{texture_pack::TexturePack, tileset::Tileset},
+    Args,
+};
 
 const DISCORD_APP_ID: &'static str = include_str!("../discord-app-id.txt");
 
@@ -13,18 +19,38 @@ pub struct Shared {
     pub args: Args,
     pub persistent: PersistenceManager<SaveData>,
     pub discord: Option<DiscordPresence>,
+    pub tilesets: Rc<RefCell<Vec<Tileset>>>,
+    pub texture_pack: Rc<RefCell<Option<TexturePack>>>,
 }
 
 impl Shared {
     pub fn new(args: Args) -> Shared {
         let persistent = PersistenceManager::new(&args.save_path);
         let discord = DiscordPresence::try_from(DISCORD_APP_ID).log_and_ok();
+        let tilesets = Rc::new(RefCell::new(Tileset::load_dir(&args.tilesets)));
+        let texture_pack = persistent
+            .borrow()
+            .texture_pack
+            .as_ref()
+            .filter(|name| TexturePack::list(&args.texture_packs).contains(name))
+            .map(|name| TexturePack::open(&args.texture_packs, name));
         Shared {
             args,
             persistent,
             discord,
+            tilesets,
+            texture_pack: Rc::new(RefCell::new(texture_pack)),
         }
     }
+
+    /// Switches to the named texture pack, or back to plain polygon tiles, and remembers
+    /// the choice.
+    pub fn select_texture_pack(&self, name: Option<String>) {
+        *self.texture_pack.borrow_mut() = name
+            .as_ref()
+            .map(|name| TexturePack::open(&self.args.texture_packs, name));
+        self.persistent.borrow_mut().texture_pack = name;
+    }
 }
 
 keybinds! {
@@ -58,6 +84,10 @@ pub struct SaveData {
     pub host_port: u16,
     pub username: String,
     pub keybinds: Keybinds,
+    #[serde(default)]
+    pub library_presets: Vec<LibraryPreset>,
+    #[serde(default)]
+    pub texture_pack: Option<String>,
 }
 
 impl Default for SaveData {
@@ -67,6 +97,8 @@ impl Default for SaveData {
             host_port: 11069,
             username: String::new(),
             keybinds: Keybinds::default(),
+            library_presets: Vec::new(),
+            texture_pack: None,
         }
     }
 }
@@ -1,4 +1,8 @@
+pub mod procedural;
+pub mod texture_pack;
 pub mod tile_definitions;
+pub mod tileset;
+pub mod validation;
 
 use std::{collections::HashMap, vec};
 
@@ -9,6 +13,40 @@ use ggez::{
 };
 use serde::{Deserialize, Serialize};
 use tile_definitions::{
+    abbots_and_gardens::{GARDEN, GARDEN_EDGE_CITY, GARDEN_STRAIGHT_ROAD},
+    hills_and_sheep::{
+        HILL_CORNER_CITY, HILL_CORNER_CITY_CURVE_ROAD, HILL_CROSSROADS, HILL_CURVE_ROAD,
+        HILL_EDGE_CITY, HILL_EDGE_CITY_STRAIGHT_ROAD, HILL_FARM, HILL_MONASTARY,
+        HILL_STRAIGHT_ROAD, HILL_THREE_QUARTER_CITY,
+    },
+    inns_and_cathedrals::{
+        CATHEDRAL, INN_CORNER_CITY_CURVE_ROAD, INN_CROSSROADS, INN_CURVE_ROAD,
+        INN_EDGE_CITY_CROSSROADS, INN_EDGE_CITY_LEFT_CURVE_ROAD, INN_EDGE_CITY_RIGHT_CURVE_ROAD,
+        INN_EDGE_CITY_ROAD, INN_FORTIFIED_CORNER_CITY_CURVE_ROAD, INN_FOUR_WAY_CROSSROADS,
+        INN_ROAD_MONASTARY, INN_STRAIGHT_ROAD, INN_THREE_QUARTER_CITY_ENTRANCE,
+    },
+    princess_and_dragon::{
+        DRAGON_BRIDGE_CITY, DRAGON_CORNER_CITY, DRAGON_CROSSROADS, DRAGON_CURVE_ROAD,
+        DRAGON_EDGE_CITY, DRAGON_EDGE_CITY_CROSSROADS, DRAGON_MONASTARY, DRAGON_STRAIGHT_ROAD,
+        DRAGON_THREE_QUARTER_CITY, PORTAL_CURVE_ROAD, PORTAL_EDGE_CITY, PRINCESS_BRIDGE_CITY,
+        PRINCESS_CORNER_CITY, PRINCESS_CORNER_CITY_CURVE_ROAD, PRINCESS_EDGE_CITY,
+        PRINCESS_THREE_QUARTER_CITY, VOLCANO, VOLCANO_CURVE_ROAD, VOLCANO_EDGE_CITY,
+        VOLCANO_STRAIGHT_ROAD,
+    },
+    tower::{
+        TOWER_CORNER_CITY, TOWER_CORNER_CITY_CURVE_ROAD, TOWER_CROSSROADS, TOWER_CURVE_ROAD,
+        TOWER_EDGE_CITY, TOWER_EDGE_CITY_LEFT_CURVE_ROAD, TOWER_EDGE_CITY_STRAIGHT_ROAD,
+        TOWER_FARM, TOWER_FOUR_WAY_CROSSROADS, TOWER_MONASTARY, TOWER_OPPOSING_EDGE_CITIES,
+        TOWER_STRAIGHT_ROAD, TOWER_THREE_QUARTER_CITY,
+    },
+    traders_and_builders::{
+        CLOTH_BRIDGE_CITY, CLOTH_CORNER_CITY_CURVE_ROAD, CLOTH_EDGE_CITY_RIGHT_CURVE_ROAD,
+        CLOTH_THREE_QUARTER_CITY, EDGE_CITY_STRAIGHT_ROAD, GRAIN_CORNER_CITY,
+        GRAIN_EDGE_CITY_LEFT_CURVE_ROAD, GRAIN_OPPOSING_EDGE_CITIES,
+        GRAIN_THREE_QUARTER_CITY_ENTRANCE, VILLAGE_CROSSROADS, WINE_BRIDGE_CITY,
+        WINE_CORNER_CITY_CURVE_ROAD, WINE_EDGE_CITY, WINE_EDGE_CITY_CROSSROADS,
+        WINE_THREE_QUARTER_CITY, WINE_THREE_QUARTER_CITY_ENTRANCE,
+    },
     ADJACENT_EDGE_CITIES, BRIDGE_CITY, CORNER_CITY, CORNER_CITY_CURVE_ROAD, CROSSROADS, CURVE_ROAD,
     EDGE_CITY, EDGE_CITY_CROSSROADS, EDGE_CITY_LEFT_CURVE_ROAD, EDGE_CITY_RIGHT_CURVE_ROAD,
     FORITIFED_THREE_QUARTER_CITY_ENTRANCE, FORTIFIED_BRIDGE_CITY, FORTIFIED_CORNER_CITY,
@@ -17,6 +55,8 @@ use tile_definitions::{
     STRAIGHT_ROAD, THREE_QUARTER_CITY, THREE_QUARTER_CITY_ENTRANCE,
 };
 
+use texture_pack::{TexturePack, TileArt};
+
 use crate::{
     game::SegmentIndex,
     pos::GridPos,
@@ -27,10 +67,92 @@ use ggez_no_re::line::Line;
 #[cfg(test)]
 mod test;
 
-const DEFAULT_MIDDLE_SECTION_WIDTH: f32 = 0.1;
+pub const DEFAULT_MIDDLE_SECTION_WIDTH: f32 = 0.1;
 
 const MOUNTS_PER_SIDE: usize = 3;
 
+static SHIELD_OUTLINE: [Vec2; 5] = [
+    vec2(-0.075, -0.075),
+    vec2(0.075, -0.075),
+    vec2(0.075, 0.075),
+    vec2(0.0, 0.15),
+    vec2(-0.075, 0.075),
+];
+static INN_OUTLINE: [Vec2; 5] = [
+    vec2(-0.05, 0.04),
+    vec2(-0.05, -0.02),
+    vec2(0.0, -0.06),
+    vec2(0.05, -0.02),
+    vec2(0.05, 0.04),
+];
+static GOODS_OUTLINE: [Vec2; 4] = [
+    vec2(0.0, -0.06),
+    vec2(0.06, 0.0),
+    vec2(0.0, 0.06),
+    vec2(-0.06, 0.0),
+];
+static CATHEDRAL_OUTLINE: [Vec2; 9] = [
+    vec2(-0.1, 0.1),
+    vec2(-0.1, -0.02),
+    vec2(-0.03, -0.02),
+    vec2(-0.03, -0.1),
+    vec2(0.0, -0.15),
+    vec2(0.03, -0.1),
+    vec2(0.03, -0.02),
+    vec2(0.1, -0.02),
+    vec2(0.1, 0.1),
+];
+static VOLCANO_OUTLINE: [Vec2; 6] = [
+    vec2(-0.12, 0.08),
+    vec2(-0.04, -0.06),
+    vec2(-0.02, -0.04),
+    vec2(0.02, -0.04),
+    vec2(0.04, -0.06),
+    vec2(0.12, 0.08),
+];
+static DRAGON_OUTLINE: [Vec2; 7] = [
+    vec2(-0.1, 0.04),
+    vec2(-0.06, -0.06),
+    vec2(-0.02, 0.0),
+    vec2(0.02, -0.08),
+    vec2(0.06, 0.0),
+    vec2(0.1, -0.04),
+    vec2(0.06, 0.06),
+];
+static PORTAL_OUTLINE: [Vec2; 7] = [
+    vec2(-0.05, 0.08),
+    vec2(-0.05, -0.03),
+    vec2(-0.03, -0.07),
+    vec2(0.0, -0.08),
+    vec2(0.03, -0.07),
+    vec2(0.05, -0.03),
+    vec2(0.05, 0.08),
+];
+static TOWER_FOUNDATION_OUTLINE: [Vec2; 4] = [
+    vec2(-0.08, -0.08),
+    vec2(0.08, -0.08),
+    vec2(0.08, 0.08),
+    vec2(-0.08, 0.08),
+];
+static HILL_OUTLINE: [Vec2; 7] = [
+    vec2(-0.14, 0.05),
+    vec2(-0.1, -0.01),
+    vec2(-0.05, -0.04),
+    vec2(0.0, -0.05),
+    vec2(0.05, -0.04),
+    vec2(0.1, -0.01),
+    vec2(0.14, 0.05),
+];
+static PRINCESS_OUTLINE: [Vec2; 7] = [
+    vec2(-0.06, 0.04),
+    vec2(-0.06, -0.04),
+    vec2(-0.03, 0.0),
+    vec2(0.0, -0.06),
+    vec2(0.03, 0.0),
+    vec2(0.06, -0.04),
+    vec2(0.06, 0.04),
+];
+
 pub type Mount = [usize; MOUNTS_PER_SIDE];
 pub type TileEdge = (TileEdgeSpan, Orientation);
 
@@ -95,6 +217,7 @@ pub enum SegmentType {
     City,
     Road,
     Monastary,
+    Garden,
     Village,
     River,
 }
@@ -107,6 +230,7 @@ impl SegmentType {
             City => Color::from_rgb(222, 133, 38),
             Road => Color::from_rgb(207, 194, 149),
             Monastary => Color::from_rgb(183, 222, 235),
+            Garden => Color::from_rgb(112, 170, 80),
             Village => Color::from_rgb(227, 204, 166),
             River => Color::from_rgb(84, 118, 218),
         }
@@ -119,6 +243,7 @@ impl SegmentType {
             City => "City",
             Road => "Road",
             Monastary => "Monastary",
+            Garden => "Garden",
             Village => "Village",
             River => "River",
         }
@@ -176,8 +301,129 @@ impl Mounts {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum SegmentAttribute {
-    Fortified { shield_location: Vec2 },
+    Fortified {
+        shield_location: Vec2,
+    },
     CustomMeepleSpot(Vec2),
+    Inn {
+        inn_location: Vec2,
+    },
+    Cathedral {
+        cathedral_location: Vec2,
+    },
+    Goods {
+        good: TradeGood,
+        goods_location: Vec2,
+    },
+    /// From the Princess & Dragon expansion; the dragon appears here when the tile is placed.
+    Volcano {
+        volcano_location: Vec2,
+    },
+    /// From the Princess & Dragon expansion; placing the tile sends the dragon on a flight.
+    Dragon {
+        dragon_location: Vec2,
+    },
+    /// From the Princess & Dragon expansion; lets a meeple be placed on any unfinished group.
+    Portal {
+        portal_location: Vec2,
+    },
+    /// From the Princess & Dragon expansion; lets a knight be sent away from the city.
+    Princess {
+        princess_location: Vec2,
+    },
+    /// From the Tower expansion; tower pieces can be stacked here in place of placing a meeple.
+    TowerFoundation {
+        tower_location: Vec2,
+    },
+    /// From the Hills & Sheep expansion; meeples on the tile win ties for their group.
+    Hill {
+        hill_location: Vec2,
+    },
+}
+
+impl SegmentAttribute {
+    /// The icon drawn on the tile for the attribute, as the location of the icon, its
+    /// outline around that location, and its color.
+    pub fn icon(&self) -> Option<(Vec2, &'static [Vec2], Color)> {
+        use SegmentAttribute::*;
+        let (location, outline, color) = match self {
+            Fortified { shield_location } => (
+                shield_location,
+                &SHIELD_OUTLINE[..],
+                Color::from_rgb(134, 146, 228),
+            ),
+            Inn { inn_location } => (inn_location, &INN_OUTLINE[..], Color::from_rgb(176, 64, 48)),
+            Cathedral { cathedral_location } => (
+                cathedral_location,
+                &CATHEDRAL_OUTLINE[..],
+                Color::from_rgb(120, 120, 132),
+            ),
+            Goods {
+                good,
+                goods_location,
+            } => (goods_location, &GOODS_OUTLINE[..], good.color()),
+            Volcano { volcano_location } => (
+                volcano_location,
+                &VOLCANO_OUTLINE[..],
+                Color::from_rgb(110, 50, 40),
+            ),
+            Dragon { dragon_location } => (
+                dragon_location,
+                &DRAGON_OUTLINE[..],
+                Color::from_rgb(200, 30, 30),
+            ),
+            Portal { portal_location } => (
+                portal_location,
+                &PORTAL_OUTLINE[..],
+                Color::from_rgb(150, 80, 200),
+            ),
+            Princess { princess_location } => (
+                princess_location,
+                &PRINCESS_OUTLINE[..],
+                Color::from_rgb(230, 120, 180),
+            ),
+            TowerFoundation { tower_location } => (
+                tower_location,
+                &TOWER_FOUNDATION_OUTLINE[..],
+                Color::from_rgb(150, 150, 150),
+            ),
+            Hill { hill_location } => (
+                hill_location,
+                &HILL_OUTLINE[..],
+                Color::from_rgb(96, 140, 64),
+            ),
+            CustomMeepleSpot(_) => return None,
+        };
+        Some((*location, outline, color))
+    }
+}
+
+/// Goods found in cities from the Traders & Builders expansion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, std::hash::Hash, Serialize, Deserialize)]
+pub enum TradeGood {
+    Wine,
+    Grain,
+    Cloth,
+}
+
+impl TradeGood {
+    pub const ALL: [TradeGood; 3] = [TradeGood::Wine, TradeGood::Grain, TradeGood::Cloth];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TradeGood::Wine => "Wine",
+            TradeGood::Grain => "Grain",
+            TradeGood::Cloth => "Cloth",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            TradeGood::Wine => Color::from_rgb(128, 24, 72),
+            TradeGood::Grain => Color::from_rgb(230, 190, 60),
+            TradeGood::Cloth => Color::from_rgb(60, 110, 200),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -516,7 +762,7 @@ pub enum SegmentBorderPiece {
     Break,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum SegmentDefinition {
     Segment {
         stype: SegmentType,
@@ -529,16 +775,28 @@ pub enum SegmentDefinition {
     },
 }
 
+impl SegmentDefinition {
+    pub fn edges(&self) -> &[SegmentBorderPiece] {
+        match self {
+            SegmentDefinition::Segment { edges, .. }
+            | SegmentDefinition::SpecialSegment { edges, .. } => edges,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Tile {
     pub verts: Line,
     pub segments: Vec<Segment>,
     pub mounts: Mounts,
-    segment_adjacency: Vec<bool>,
+    pub segment_adjacency: Vec<bool>,
     #[allow(unused)]
     pub attributes: Vec<TileAttribute>,
+    #[serde(with = "crate::save::map_as_pairs")]
     pub edge_verts_map: HashMap<TileEdge, [usize; 2]>,
     pub rotation: usize,
+    #[serde(default)]
+    pub art: Option<TileArt>,
 }
 
 impl Tile {
@@ -571,6 +829,111 @@ impl Tile {
         ]
     }
 
+    pub fn inns_and_cathedrals_library_tallies() -> Vec<(&'static Tile, usize)> {
+        vec![
+            (&*CATHEDRAL, 2),
+            (&*INN_STRAIGHT_ROAD, 2),
+            (&*INN_CURVE_ROAD, 2),
+            (&*INN_CROSSROADS, 2),
+            (&*INN_FOUR_WAY_CROSSROADS, 1),
+            (&*INN_EDGE_CITY_ROAD, 2),
+            (&*INN_EDGE_CITY_LEFT_CURVE_ROAD, 1),
+            (&*INN_EDGE_CITY_RIGHT_CURVE_ROAD, 1),
+            (&*INN_EDGE_CITY_CROSSROADS, 1),
+            (&*INN_CORNER_CITY_CURVE_ROAD, 1),
+            (&*INN_FORTIFIED_CORNER_CITY_CURVE_ROAD, 1),
+            (&*INN_THREE_QUARTER_CITY_ENTRANCE, 1),
+            (&*INN_ROAD_MONASTARY, 1),
+        ]
+    }
+
+    pub fn traders_and_builders_library_tallies() -> Vec<(&'static Tile, usize)> {
+        vec![
+            (&*WINE_EDGE_CITY, 2),
+            (&*WINE_THREE_QUARTER_CITY, 2),
+            (&*WINE_CORNER_CITY_CURVE_ROAD, 2),
+            (&*WINE_EDGE_CITY_CROSSROADS, 1),
+            (&*WINE_BRIDGE_CITY, 1),
+            (&*WINE_THREE_QUARTER_CITY_ENTRANCE, 1),
+            (&*GRAIN_CORNER_CITY, 2),
+            (&*GRAIN_THREE_QUARTER_CITY_ENTRANCE, 2),
+            (&*GRAIN_OPPOSING_EDGE_CITIES, 1),
+            (&*GRAIN_EDGE_CITY_LEFT_CURVE_ROAD, 1),
+            (&*CLOTH_CORNER_CITY_CURVE_ROAD, 2),
+            (&*CLOTH_THREE_QUARTER_CITY, 1),
+            (&*CLOTH_BRIDGE_CITY, 1),
+            (&*CLOTH_EDGE_CITY_RIGHT_CURVE_ROAD, 1),
+            (&*EDGE_CITY_STRAIGHT_ROAD, 2),
+            (&*VILLAGE_CROSSROADS, 2),
+        ]
+    }
+
+    pub fn abbots_and_gardens_library_tallies() -> Vec<(&'static Tile, usize)> {
+        vec![
+            (&*GARDEN, 2),
+            (&*GARDEN_EDGE_CITY, 2),
+            (&*GARDEN_STRAIGHT_ROAD, 2),
+        ]
+    }
+
+    pub fn princess_and_dragon_library_tallies() -> Vec<(&'static Tile, usize)> {
+        vec![
+            (&*VOLCANO, 2),
+            (&*VOLCANO_EDGE_CITY, 2),
+            (&*VOLCANO_STRAIGHT_ROAD, 1),
+            (&*VOLCANO_CURVE_ROAD, 1),
+            (&*DRAGON_STRAIGHT_ROAD, 2),
+            (&*DRAGON_EDGE_CITY, 2),
+            (&*DRAGON_CURVE_ROAD, 2),
+            (&*DRAGON_CROSSROADS, 2),
+            (&*DRAGON_CORNER_CITY, 2),
+            (&*DRAGON_THREE_QUARTER_CITY, 1),
+            (&*DRAGON_MONASTARY, 1),
+            (&*DRAGON_BRIDGE_CITY, 1),
+            (&*DRAGON_EDGE_CITY_CROSSROADS, 1),
+            (&*PORTAL_CURVE_ROAD, 2),
+            (&*PORTAL_EDGE_CITY, 1),
+            (&*PRINCESS_EDGE_CITY, 2),
+            (&*PRINCESS_CORNER_CITY, 2),
+            (&*PRINCESS_THREE_QUARTER_CITY, 1),
+            (&*PRINCESS_BRIDGE_CITY, 1),
+            (&*PRINCESS_CORNER_CITY_CURVE_ROAD, 1),
+        ]
+    }
+
+    pub fn tower_library_tallies() -> Vec<(&'static Tile, usize)> {
+        vec![
+            (&*TOWER_FARM, 1),
+            (&*TOWER_STRAIGHT_ROAD, 2),
+            (&*TOWER_CURVE_ROAD, 2),
+            (&*TOWER_CROSSROADS, 2),
+            (&*TOWER_FOUR_WAY_CROSSROADS, 1),
+            (&*TOWER_EDGE_CITY, 2),
+            (&*TOWER_EDGE_CITY_STRAIGHT_ROAD, 1),
+            (&*TOWER_EDGE_CITY_LEFT_CURVE_ROAD, 1),
+            (&*TOWER_OPPOSING_EDGE_CITIES, 1),
+            (&*TOWER_CORNER_CITY, 2),
+            (&*TOWER_CORNER_CITY_CURVE_ROAD, 1),
+            (&*TOWER_THREE_QUARTER_CITY, 1),
+            (&*TOWER_MONASTARY, 1),
+        ]
+    }
+
+    pub fn hills_and_sheep_library_tallies() -> Vec<(&'static Tile, usize)> {
+        vec![
+            (&*HILL_FARM, 1),
+            (&*HILL_STRAIGHT_ROAD, 2),
+            (&*HILL_CURVE_ROAD, 2),
+            (&*HILL_CROSSROADS, 2),
+            (&*HILL_EDGE_CITY, 2),
+            (&*HILL_EDGE_CITY_STRAIGHT_ROAD, 2),
+            (&*HILL_CORNER_CITY, 2),
+            (&*HILL_CORNER_CITY_CURVE_ROAD, 2),
+            (&*HILL_THREE_QUARTER_CITY, 1),
+            (&*HILL_MONASTARY, 2),
+        ]
+    }
+
     pub fn default_library() -> Vec<Tile> {
         Self::default_library_tallies()
             .into_iter()
@@ -734,6 +1097,7 @@ impl Tile {
             attributes,
             edge_verts_map,
             rotation: 0,
+            art: None,
         }
     }
 
@@ -741,6 +1105,11 @@ impl Tile {
         Tile::new_with_attributes(verts, segment_definitions, Vec::new())
     }
 
+    pub fn with_art(mut self, image: impl Into<String>, rotation: usize) -> Self {
+        self.art = Some(TileArt::new(image, rotation));
+        self
+    }
+
     pub fn segment_polygon(&self, seg_index: SegmentIndex) -> impl Iterator<Item = Vec2> + '_ {
         self.segments[seg_index]
             .poly
@@ -769,6 +1138,35 @@ impl Tile {
         Ok(())
     }
 
+    /// Draws the tile's art from the texture pack, falling back to the segment polygons
+    /// if there's no pack selected or it has no image for this tile. With `overlay` set,
+    /// the segment polygons are drawn translucently over the art.
+    pub fn render_textured(
+        &self,
+        ctx: &Context,
+        canvas: &mut Canvas,
+        bounds: Rect,
+        textures: Option<&TexturePack>,
+        overlay: bool,
+    ) -> Result<(), GameError> {
+        let textured = match (textures, &self.art) {
+            (Some(textures), Some(art)) => textures.draw(ctx, canvas, art, self.rotation, bounds),
+            _ => false,
+        };
+        if !textured {
+            self.render(ctx, canvas, bounds)
+        } else if overlay {
+            for (i, segment) in self.segments.iter().enumerate() {
+                let mut color = segment.stype.color();
+                color.a = 0.5;
+                self.render_segment(i, ctx, canvas, bounds, Some(color))?;
+            }
+            Ok(())
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn render_segment(
         &self,
         seg_index: usize,
@@ -788,27 +1186,21 @@ impl Tile {
             )?,
             DrawParam::default(),
         );
-        for attribute in &segment.attributes {
-            if let SegmentAttribute::Fortified { shield_location } = attribute {
-                canvas.draw(
-                    &Mesh::new_polygon(
-                        ctx,
-                        DrawMode::fill(),
-                        &[
-                            vec2(-0.075, -0.075),
-                            vec2(0.075, -0.075),
-                            vec2(0.075, 0.075),
-                            vec2(0.0, 0.15),
-                            vec2(-0.075, 0.075),
-                        ]
-                        .into_iter()
-                        .map(|offset| refit_to_rect(*shield_location + offset, bounds))
+        for (location, outline, color) in
+            segment.attributes.iter().filter_map(SegmentAttribute::icon)
+        {
+            canvas.draw(
+                &Mesh::new_polygon(
+                    ctx,
+                    DrawMode::fill(),
+                    &outline
+                        .iter()
+                        .map(|offset| refit_to_rect(location + *offset, bounds))
                         .collect::<Vec<_>>(),
-                        Color::from_rgb(134, 146, 228),
-                    )?,
-                    DrawParam::default(),
-                );
-            }
+                    color,
+                )?,
+                DrawParam::default(),
+            );
         }
         Ok(())
     }
@@ -830,8 +1222,39 @@ impl Tile {
                 }
             }
             for attribute in &mut segment.attributes {
-                if let SegmentAttribute::Fortified { shield_location } = attribute {
-                    shield_location.rotate_();
+                match attribute {
+                    SegmentAttribute::Fortified {
+                        shield_location: location,
+                    }
+                    | SegmentAttribute::Inn {
+                        inn_location: location,
+                    }
+                    | SegmentAttribute::Cathedral {
+                        cathedral_location: location,
+                    }
+                    | SegmentAttribute::Goods {
+                        goods_location: location,
+                        ..
+                    }
+                    | SegmentAttribute::Volcano {
+                        volcano_location: location,
+                    }
+                    | SegmentAttribute::Dragon {
+                        dragon_location: location,
+                    }
+                    | SegmentAttribute::Portal {
+                        portal_location: location,
+                    }
+                    | SegmentAttribute::Princess {
+                        princess_location: location,
+                    }
+                    | SegmentAttribute::TowerFoundation {
+                        tower_location: location,
+                    }
+                    | SegmentAttribute::Hill {
+                        hill_location: location,
+                    } => location.rotate_(),
+                    SegmentAttribute::CustomMeepleSpot(_) => {}
                 }
             }
         }
@@ -864,6 +1287,52 @@ impl Tile {
         }
     }
 
+    /// Describes the layout of the tile in its current rotation, with segments relabeled
+    /// in the order they're met going around the edges. Rotations of a symmetric tile
+    /// produce identical signatures.
+    pub fn rotation_signature(&self) -> Vec<(usize, SegmentType, bool)> {
+        let edge_segments: Vec<SegmentIndex> = Orientation::iter_with_offsets()
+            .flat_map(|(orientation, _)| *self.mounts.by_orientation(orientation))
+            .collect();
+        let interior_segments =
+            (0..self.segments.len()).filter(|seg_index| !edge_segments.contains(seg_index));
+        let mut labels: HashMap<SegmentIndex, usize> = HashMap::new();
+        edge_segments
+            .iter()
+            .cloned()
+            .chain(interior_segments)
+            .map(|seg_index| {
+                let next_label = labels.len();
+                let label = *labels.entry(seg_index).or_insert(next_label);
+                let segment = &self.segments[seg_index];
+                let decorated = segment
+                    .attributes
+                    .iter()
+                    .any(|attribute| !matches!(attribute, SegmentAttribute::CustomMeepleSpot(_)));
+                (label, segment.stype, decorated)
+            })
+            .collect()
+    }
+
+    /// Whether any of the tile's segments has an attribute matching the predicate.
+    pub fn has_attribute(&self, predicate: impl Fn(&SegmentAttribute) -> bool) -> bool {
+        self.segments
+            .iter()
+            .flat_map(|segment| &segment.attributes)
+            .any(predicate)
+    }
+
+    /// The edges of the tile that the river flows through.
+    pub fn river_edges(&self) -> Vec<Orientation> {
+        Orientation::iter_with_offsets()
+            .map(|(orientation, _)| orientation)
+            .filter(|orientation| {
+                self.segments[self.mounts.by_orientation(*orientation)[1]].stype
+                    == SegmentType::River
+            })
+            .collect()
+    }
+
     pub fn validate_mounting(
         &self,
         adjacent: &Tile,
@@ -900,7 +1369,7 @@ impl Tile {
         seg_index: SegmentIndex,
     ) -> impl Iterator<Item = (SegmentIndex, &Segment)> {
         let n = self.segments.len();
-        self.segment_adjacency[n * seg_index..(n + 1) * seg_index]
+        self.segment_adjacency[n * seg_index..n * (seg_index + 1)]
             .iter()
             .enumerate()
             .filter_map(|(i, a)| a.then_some((i, &self.segments[i])))
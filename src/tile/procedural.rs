@@ -0,0 +1,447 @@
+use ggez::glam::{vec2, Vec2};
+use ggez_no_re::{line::Line, util::point_in_polygon};
+use rand::Rng;
+
+use crate::game::rules::RuleRng;
+
+use super::{
+    Orientation, SegmentBorderPiece, SegmentDefinition, SegmentType, Tile, TileEdge, TileEdgeSpan,
+};
+
+use Orientation::*;
+use SegmentBorderPiece::*;
+use TileEdgeSpan::*;
+
+const SIDES: [Orientation; 4] = [North, East, South, West];
+
+/// Resolution of the grid searched for the point furthest inside each segment, where
+/// its meeple is drawn.
+const MEEPLE_SPOT_GRID: usize = 40;
+
+/// What runs along one side of a generated tile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeKind {
+    Farm,
+    /// A road through the middle of the side, with farm either side of it.
+    Road,
+    City,
+}
+
+/// Everything needed to lay out a generated tile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TileSignature {
+    /// What runs along each side, in the order north, east, south, west.
+    pub edges: [EdgeKind; 4],
+    /// Whether the city sides join up through the middle of the tile, rather than each
+    /// being a separate city. Has no effect with fewer than two city sides, and four city
+    /// sides are always joined.
+    pub connected_city: bool,
+    /// Whether roads end at a monastary in the middle of the tile rather than a village.
+    /// Only used when there is at most one road, and no city joining through the middle.
+    pub monastary: bool,
+}
+
+/// A point on the outside of the tile, going clockwise.
+#[derive(Clone, Copy, Debug)]
+enum Rim {
+    Edge(TileEdge),
+    /// A city along the whole side, which farms go around.
+    Cap(Orientation),
+    /// A road leading into the middle of the tile from the side.
+    Spoke(Orientation),
+}
+
+/// Moves a point given relative to the north side of the tile to the same place relative
+/// to the given side.
+fn to_side(point: Vec2, side: Orientation) -> Vec2 {
+    let turns = SIDES.iter().position(|s| *s == side).unwrap();
+    (0..turns).fold(point, |point, _| vec2(1.0 - point.y, point.x))
+}
+
+fn side_index(side: Orientation) -> usize {
+    SIDES.iter().position(|s| *s == side).unwrap()
+}
+
+#[derive(Default)]
+struct TileBuilder {
+    verts: Line,
+}
+
+impl TileBuilder {
+    /// Index of a vertex at the given point relative to the side, reusing any vertex
+    /// already there so that neighbouring segments share it.
+    fn vert(&mut self, point: Vec2, side: Orientation) -> usize {
+        let point = to_side(point, side);
+        match self
+            .verts
+            .iter()
+            .position(|vert| vert.distance(point) < 1e-4)
+        {
+            Some(index) => index,
+            None => {
+                self.verts.push(point);
+                self.verts.len() - 1
+            }
+        }
+    }
+
+    fn cap_corners(&mut self, side: Orientation) -> [usize; 2] {
+        [
+            self.vert(vec2(0.35, 0.3), side),
+            self.vert(vec2(0.65, 0.3), side),
+        ]
+    }
+
+    /// Border pieces running clockwise along the given stretch of the outside of the tile.
+    /// With `closed`, the pieces finish back at the corner they started from, so that a
+    /// hole can follow.
+    fn walk(&mut self, rim: &[Rim], closed: bool) -> Vec<SegmentBorderPiece> {
+        let mut pieces = Vec::new();
+        for (i, item) in rim.iter().enumerate() {
+            match *item {
+                Rim::Edge(edge) => pieces.push(Edge(edge)),
+                Rim::Cap(side) => {
+                    // where two cities meet at a corner, the farm squeezes through it
+                    if i == 0 || matches!(rim[i - 1], Rim::Cap(_)) {
+                        pieces.push(Vert(self.vert(vec2(0.0, 0.0), side)));
+                    }
+                    let [left, right] = self.cap_corners(side);
+                    pieces.extend([Vert(left), Vert(right)]);
+                    if closed && i == rim.len() - 1 {
+                        pieces.push(Vert(self.vert(vec2(1.0, 0.0), side)));
+                    }
+                }
+                Rim::Spoke(_) => unreachable!(),
+            }
+        }
+        pieces
+    }
+
+    /// Lays out a tile whose cities each sit against their own side, with any roads
+    /// meeting in the middle.
+    fn open_center(&mut self, signature: &TileSignature) -> Vec<SegmentDefinition> {
+        use SegmentType::*;
+        let mut segments = Vec::new();
+        let kind = |side: Orientation| signature.edges[side_index(side)];
+        let roads: Vec<Orientation> = SIDES
+            .into_iter()
+            .filter(|side| kind(*side) == EdgeKind::Road)
+            .collect();
+
+        for side in SIDES
+            .into_iter()
+            .filter(|side| kind(*side) == EdgeKind::City)
+        {
+            let [left, right] = self.cap_corners(side);
+            segments.push(SegmentDefinition::Segment {
+                stype: City,
+                edges: vec![Edge((Full, side)), Vert(right), Vert(left)],
+            });
+        }
+
+        let hub = match roads.len() {
+            2 => None,
+            0 if !signature.monastary => None,
+            0 | 1 if signature.monastary => Some(Monastary),
+            _ => Some(Village),
+        };
+        // the middle of the tile, clockwise, with where each road meets it
+        let mut hub_ring = Vec::new();
+        let mut spoke_ends = [(0, 0); 4];
+        if hub.is_some() {
+            let inset = if roads.is_empty() { 0.35 } else { 0.4 };
+            for side in SIDES {
+                hub_ring.push(self.vert(vec2(inset, inset), side));
+                if kind(side) == EdgeKind::Road {
+                    let left = self.vert(vec2(0.45, inset), side);
+                    let right = self.vert(vec2(0.55, inset), side);
+                    spoke_ends[side_index(side)] = (hub_ring.len(), hub_ring.len() + 1);
+                    hub_ring.extend([left, right]);
+                }
+            }
+        }
+
+        // the corners where two roads turn into each other, on the inside and outside
+        let turn = match roads[..] {
+            [a, b] if b == a.rotate() => Some((a, b)),
+            [a, b] if a == b.rotate() => Some((b, a)),
+            _ => None,
+        };
+        let turn_corners = turn.map(|(a, _)| {
+            [
+                self.vert(vec2(0.55, 0.45), a),
+                self.vert(vec2(0.45, 0.55), a),
+            ]
+        });
+
+        if let Some(stype) = hub {
+            segments.push(SegmentDefinition::Segment {
+                stype,
+                edges: hub_ring.iter().copied().map(Vert).collect(),
+            });
+            for side in &roads {
+                let (left, right) = spoke_ends[side_index(*side)];
+                segments.push(SegmentDefinition::Segment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, *side)),
+                        Vert(hub_ring[right]),
+                        Vert(hub_ring[left]),
+                    ],
+                });
+            }
+        } else if let [a, b] = roads[..] {
+            // two roads with nothing between them join into one
+            let edges = match (turn, turn_corners) {
+                (Some((a, b)), Some([inside, outside])) => vec![
+                    Edge((Middle, a)),
+                    Vert(inside),
+                    Edge((Middle, b)),
+                    Vert(outside),
+                ],
+                _ => vec![Edge((Middle, a)), Edge((Middle, b))],
+            };
+            segments.push(SegmentDefinition::Segment { stype: Road, edges });
+        }
+
+        let mut rim: Vec<Rim> = SIDES
+            .into_iter()
+            .flat_map(|side| match kind(side) {
+                EdgeKind::Farm => vec![Rim::Edge((Full, side))],
+                EdgeKind::Road => vec![
+                    Rim::Edge((Beginning, side)),
+                    Rim::Spoke(side),
+                    Rim::Edge((End, side)),
+                ],
+                EdgeKind::City => vec![Rim::Cap(side)],
+            })
+            .collect();
+
+        if roads.is_empty() {
+            // a single farm all the way around, with a hole for any monastary
+            if let Some(start) = rim.iter().position(|item| matches!(item, Rim::Edge(_))) {
+                rim.rotate_left(start);
+            }
+            let mut edges = self.walk(&rim, hub.is_some());
+            if let (false, Rim::Edge((_, side))) = (hub_ring.is_empty(), rim[0]) {
+                // the hole is joined to the corner the farm starts from, straight across
+                // from the nearest corner of the monastary
+                hub_ring.rotate_left(side_index(side));
+                edges.push(Vert(hub_ring[0]));
+                edges.extend(hub_ring.iter().rev().copied().map(Vert));
+            }
+            segments.push(SegmentDefinition::Segment { stype: Farm, edges });
+            return segments;
+        }
+
+        // a farm between each pair of roads
+        let first_spoke = rim
+            .iter()
+            .position(|item| matches!(item, Rim::Spoke(_)))
+            .unwrap();
+        rim.rotate_left(first_spoke);
+        let spokes: Vec<usize> = rim
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| matches!(item, Rim::Spoke(_)).then_some(i))
+            .collect();
+        for (k, &start) in spokes.iter().enumerate() {
+            let end = spokes.get(k + 1).copied().unwrap_or(rim.len());
+            let (Rim::Spoke(from), Rim::Spoke(to)) = (rim[start], rim[end % rim.len()]) else {
+                unreachable!();
+            };
+            let mut edges = self.walk(&rim[start + 1..end], false);
+            if hub.is_some() {
+                let left = spoke_ends[side_index(to)].0;
+                let right = spoke_ends[side_index(from)].1;
+                edges.push(Vert(hub_ring[left]));
+                let n = hub_ring.len();
+                let mut position = left;
+                while position != right {
+                    position = (position + n - 1) % n;
+                    edges.push(Vert(hub_ring[position]));
+                }
+            } else if let (Some((a, _)), Some([inside, outside])) = (turn, turn_corners) {
+                edges.push(Vert(if from == a { inside } else { outside }));
+            }
+            segments.push(SegmentDefinition::Segment { stype: Farm, edges });
+        }
+        segments
+    }
+
+    /// Lays out a tile whose city sides all join up through the middle, with any roads
+    /// ending where they meet the city.
+    fn connected_city(&mut self, signature: &TileSignature) -> Vec<SegmentDefinition> {
+        use SegmentType::*;
+        let kind = |side: Orientation| signature.edges[side_index(side)];
+        let is_city = |side: Orientation| kind(side) == EdgeKind::City;
+        let previous = |side: Orientation| side.rotate().rotate().rotate();
+
+        // the edge of the city on each side it doesn't reach, clockwise
+        let mut notches: [Vec<usize>; 4] = Default::default();
+        let mut spoke_ends = [(0, 0); 4];
+        let mut city = Vec::new();
+        for side in SIDES {
+            if is_city(side) {
+                city.push(Edge((Full, side)));
+                continue;
+            }
+            let notch = &mut notches[side_index(side)];
+            if is_city(previous(side)) {
+                notch.push(self.vert(vec2(0.35, 0.3), side));
+            }
+            if kind(side) == EdgeKind::Road {
+                spoke_ends[side_index(side)] = (notch.len(), notch.len() + 1);
+                notch.push(self.vert(vec2(0.45, 0.3), side));
+                notch.push(self.vert(vec2(0.55, 0.3), side));
+            }
+            notch.push(if is_city(side.rotate()) {
+                self.vert(vec2(0.65, 0.3), side)
+            } else {
+                self.vert(vec2(0.7, 0.3), side)
+            });
+            city.extend(notch.iter().copied().map(Vert));
+        }
+        let mut segments = vec![SegmentDefinition::Segment {
+            stype: City,
+            edges: city,
+        }];
+
+        for side in SIDES
+            .into_iter()
+            .filter(|side| kind(*side) == EdgeKind::Road)
+        {
+            let notch = &notches[side_index(side)];
+            let (left, right) = spoke_ends[side_index(side)];
+            segments.push(SegmentDefinition::Segment {
+                stype: Road,
+                edges: vec![Edge((Middle, side)), Vert(notch[right]), Vert(notch[left])],
+            });
+        }
+
+        // each run of sides between the city holds farms, split up by any roads
+        for start in SIDES
+            .into_iter()
+            .filter(|side| !is_city(*side) && is_city(previous(*side)))
+        {
+            let mut run = vec![start];
+            while !is_city(run.last().unwrap().rotate()) {
+                run.push(run.last().unwrap().rotate());
+            }
+            let mut rim = Vec::new();
+            let mut notch = Vec::new();
+            // positions in the notch of the farthest point of each farm, clockwise
+            let mut bounds = vec![0];
+            for side in run {
+                let offset = notch.len();
+                notch.extend(notches[side_index(side)].iter().copied());
+                match kind(side) {
+                    EdgeKind::Road => {
+                        let (left, right) = spoke_ends[side_index(side)];
+                        rim.extend([
+                            Rim::Edge((Beginning, side)),
+                            Rim::Spoke(side),
+                            Rim::Edge((End, side)),
+                        ]);
+                        bounds.extend([offset + left, offset + right]);
+                    }
+                    _ => rim.push(Rim::Edge((Full, side))),
+                }
+            }
+            bounds.push(notch.len() - 1);
+            for (pieces, bound) in rim
+                .split(|item| matches!(item, Rim::Spoke(_)))
+                .zip(bounds.chunks(2))
+            {
+                let mut edges = self.walk(pieces, false);
+                edges.extend(notch[bound[0]..=bound[1]].iter().rev().copied().map(Vert));
+                segments.push(SegmentDefinition::Segment { stype: Farm, edges });
+            }
+        }
+        segments
+    }
+}
+
+impl TileSignature {
+    pub fn random(rng: &mut impl Rng) -> TileSignature {
+        let edges = [(); 4].map(|_| match rng.gen_range(0..20) {
+            0..=6 => EdgeKind::City,
+            7..=13 => EdgeKind::Road,
+            _ => EdgeKind::Farm,
+        });
+        TileSignature {
+            edges,
+            connected_city: rng.gen_bool(0.5),
+            // tiles with nothing else on them always get a monastary
+            monastary: edges.iter().all(|kind| *kind == EdgeKind::Farm) || rng.gen_bool(0.4),
+        }
+    }
+
+    fn city_sides(&self) -> usize {
+        self.edges
+            .iter()
+            .filter(|kind| **kind == EdgeKind::City)
+            .count()
+    }
+
+    pub fn tile(&self) -> Tile {
+        let mut builder = TileBuilder::default();
+        let joined = match self.city_sides() {
+            4 => true,
+            2 | 3 => self.connected_city,
+            _ => false,
+        };
+        let segments = if joined {
+            builder.connected_city(self)
+        } else {
+            builder.open_center(self)
+        };
+        let mut tile = Tile::new(builder.verts, segments);
+        for seg_index in 0..tile.segments.len() {
+            let polygon: Vec<Vec2> = tile.segment_polygon(seg_index).collect();
+            tile.segments[seg_index].meeple_spot = interior_point(&polygon);
+        }
+        tile
+    }
+}
+
+/// Generates a deck of random tiles, which is always the same for the same seed, on every
+/// build and platform, so multiplayer clients all rebuild the host's deck.
+pub fn generate_library(seed: u64, count: usize) -> Vec<Tile> {
+    generate_signatures(seed, count)
+        .iter()
+        .map(TileSignature::tile)
+        .collect()
+}
+
+/// The layouts of the tiles [`generate_library`] builds for the seed.
+pub fn generate_signatures(seed: u64, count: usize) -> Vec<TileSignature> {
+    let mut rng = RuleRng::new(seed);
+    (0..count)
+        .map(|_| TileSignature::random(&mut rng))
+        .collect()
+}
+
+/// The point on a grid over the tile that lies furthest inside the polygon.
+fn interior_point(polygon: &[Vec2]) -> Vec2 {
+    let n = polygon.len();
+    let clearance = |point: Vec2| {
+        (0..n)
+            .map(|i| distance_to_side(point, polygon[i], polygon[(i + 1) % n]))
+            .fold(f32::INFINITY, f32::min)
+    };
+    (1..MEEPLE_SPOT_GRID)
+        .flat_map(|x| (1..MEEPLE_SPOT_GRID).map(move |y| vec2(x as f32, y as f32)))
+        .map(|point| point / MEEPLE_SPOT_GRID as f32)
+        .filter(|point| point_in_polygon(*point, polygon))
+        .map(|point| (clearance(point), point))
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, point)| point)
+        .unwrap_or_else(|| polygon.iter().sum::<Vec2>() / n as f32)
+}
+
+fn distance_to_side(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let side = b - a;
+    let t = ((point - a).dot(side) / side.length_squared()).clamp(0.0, 1.0);
+    point.distance(a + side * t)
+}
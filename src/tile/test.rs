@@ -1,7 +1,9 @@
 use ggez::glam::vec2;
 
 use crate::tile::{
-    tile_definitions::{CURVE_ROAD, STRAIGHT_ROAD},
+    tile_definitions::{CURVE_ROAD, ROAD_MONASTARY, STRAIGHT_ROAD},
+    tileset::Tileset,
+    validation::TileDefect,
     MountingPair, Orientation,
 };
 
@@ -111,3 +113,262 @@ fn test_tile_definition() {
     );
     dbg!(tile);
 }
+
+#[test]
+fn load_tileset() {
+    let tilesets = Tileset::load_dir("tilesets");
+    let country_roads = tilesets
+        .iter()
+        .find(|tileset| tileset.expansion == "Country Roads")
+        .unwrap();
+    assert_eq!(country_roads.library().len(), 5);
+    let (long_road, _) = &country_roads.library_tallies()[1];
+    assert_eq!(
+        long_road.rotation_signature(),
+        STRAIGHT_ROAD.rotation_signature()
+    );
+}
+
+#[test]
+fn reject_unbuildable_tileset() {
+    use crate::tile::{
+        tileset::TileDefinition, validation::validate_all_tiles, SegmentBorderPiece,
+        SegmentDefinition, SegmentType, TileEdgeSpan,
+    };
+
+    use Orientation::*;
+    use SegmentBorderPiece::*;
+    use SegmentDefinition::*;
+    use SegmentType::*;
+    use TileEdgeSpan::*;
+
+    let definition = |verts, segments| TileDefinition {
+        name: "Broken".to_string(),
+        verts,
+        segments,
+        attributes: Vec::new(),
+        count: 1,
+        art: None,
+    };
+    let farm = Segment {
+        stype: Farm,
+        edges: vec![
+            Edge((Full, North)),
+            Edge((Full, East)),
+            Edge((Full, South)),
+            Edge((Full, West)),
+        ],
+    };
+    assert_eq!(definition(vec![], vec![farm.clone()]).check(), Ok(()));
+
+    let mut tileset = Tileset {
+        expansion: "Broken".to_string(),
+        tiles: vec![definition(
+            vec![vec2(0.5, 0.5)],
+            vec![Segment {
+                stype: Road,
+                edges: vec![
+                    Edge((Middle, North)),
+                    Vert(1),
+                    Edge((Middle, South)),
+                    Vert(0),
+                ],
+            }],
+        )],
+    };
+    assert!(tileset.check().unwrap_err().contains("vertex 1"));
+    let defects = validate_all_tiles(std::slice::from_ref(&tileset));
+    assert!(defects
+        .iter()
+        .any(|(_, _, defect)| matches!(defect, TileDefect::MissingVertex { vertex: 1, .. })));
+
+    tileset.tiles = vec![definition(
+        vec![],
+        vec![
+            farm,
+            Segment {
+                stype: City,
+                edges: vec![Break],
+            },
+        ],
+    )];
+    assert!(tileset
+        .check()
+        .unwrap_err()
+        .contains("segment 1 has no outline"));
+    tileset.tiles = vec![definition(vec![], vec![])];
+    assert!(tileset.check().is_err());
+}
+
+#[test]
+fn validate_tiles() {
+    use crate::tile::{
+        validation::validate_all_tiles, SegmentBorderPiece, SegmentDefinition, SegmentType, Tile,
+        TileEdgeSpan,
+    };
+
+    use Orientation::*;
+    use SegmentBorderPiece::*;
+    use SegmentDefinition::*;
+    use SegmentType::*;
+    use TileEdgeSpan::*;
+
+    assert_eq!(STRAIGHT_ROAD.validate(), Vec::new());
+    assert_eq!(ROAD_MONASTARY.validate(), Vec::new());
+    let defects = validate_all_tiles(&[]);
+    assert!(defects.is_empty(), "{defects:?}");
+
+    let missing_side = Tile::new(
+        vec![],
+        vec![Segment {
+            stype: Farm,
+            edges: vec![Edge((Full, West)), Edge((Full, North)), Edge((Full, East))],
+        }],
+    );
+    let defects = missing_side.validate();
+    assert!(defects.contains(&(
+        0,
+        TileDefect::EdgeCoverage {
+            orientation: South,
+            slot: 0,
+            segments: 0,
+        }
+    )));
+    assert!(defects.contains(&(
+        1,
+        TileDefect::InconsistentMount {
+            orientation: West,
+            slot: 1,
+        }
+    )));
+}
+
+#[test]
+fn overlapping_segments() {
+    use crate::tile::{SegmentBorderPiece, SegmentDefinition, SegmentType, Tile};
+
+    // the overlap and the gap left over are the same size, so the area still adds up
+    let height = 2.0 / 3.0;
+    let farm = |edges| SegmentDefinition::Segment {
+        stype: SegmentType::Farm,
+        edges,
+    };
+    let overlapping = Tile::new(
+        vec![
+            vec2(0.0, 0.0),
+            vec2(0.6, 0.0),
+            vec2(0.6, 1.0),
+            vec2(0.0, 1.0),
+            vec2(0.4, 0.0),
+            vec2(1.0, 0.0),
+            vec2(1.0, height),
+            vec2(0.4, height),
+        ],
+        vec![
+            farm((0..4).map(SegmentBorderPiece::Vert).collect()),
+            farm((4..8).map(SegmentBorderPiece::Vert).collect()),
+        ],
+    );
+    let defects = overlapping.validate();
+    assert!(defects.contains(&(0, TileDefect::OverlappingSegments(0, 1))));
+    assert!(!defects
+        .iter()
+        .any(|(_, defect)| matches!(defect, TileDefect::IncompleteCoverage { .. })));
+}
+
+#[test]
+fn procedural_tiles() {
+    use crate::tile::procedural::{generate_library, generate_signatures, EdgeKind, TileSignature};
+
+    let kinds = [EdgeKind::Farm, EdgeKind::Road, EdgeKind::City];
+    for i in 0..kinds.len().pow(4) {
+        let edges = [0, 1, 2, 3].map(|side| kinds[i / kinds.len().pow(side) % kinds.len()]);
+        for connected_city in [false, true] {
+            for monastary in [false, true] {
+                let signature = TileSignature {
+                    edges,
+                    connected_city,
+                    monastary,
+                };
+                assert_eq!(signature.tile().validate(), Vec::new(), "{signature:?}");
+            }
+        }
+    }
+
+    assert_eq!(generate_library(7, 30).len(), 30);
+
+    // multiplayer clients rebuild the host's deck from its seed, so the same seed has to
+    // lay out the same tiles on every build
+    let code = |signature: &TileSignature| {
+        signature
+            .edges
+            .iter()
+            .map(|kind| match kind {
+                EdgeKind::Farm => 'F',
+                EdgeKind::Road => 'R',
+                EdgeKind::City => 'C',
+            })
+            .chain(signature.connected_city.then_some('+'))
+            .chain(signature.monastary.then_some('m'))
+            .collect::<String>()
+    };
+    let signatures: Vec<String> = generate_signatures(7, 30).iter().map(code).collect();
+    assert_eq!(
+        signatures,
+        [
+            "CFRR+m", "RCFF", "FCRFm", "RFFC+", "RFCRm", "CCFC+m", "FRRC+", "CRRR+", "RRRF",
+            "RCRR", "CRRFm", "FCCCm", "CFCF", "CFFF", "RCRF+", "RRCRm", "FCFC+", "RCRC+", "FRRC+",
+            "CCCF", "RCCR+m", "RRFF+", "FRFF+", "FRCR", "CRCR+", "RCCF+", "CRFR", "RCCR", "FCRC",
+            "RRCR+m",
+        ]
+    );
+}
+
+#[test]
+fn builtin_tile_art() {
+    use crate::tile::validation::all_tiles;
+
+    for (label, tile) in all_tiles(&[]) {
+        assert!(tile.art.is_some(), "{label}");
+    }
+    assert_eq!(
+        STRAIGHT_ROAD.art.as_ref().unwrap().image,
+        "straight_road.png"
+    );
+
+    let rotated = STRAIGHT_ROAD.clone().rotated();
+    assert_eq!(rotated.art, STRAIGHT_ROAD.art);
+    assert_eq!(rotated.rotation, 1);
+}
+
+#[test]
+fn expansion_library_sizes() {
+    use crate::tile::Tile;
+
+    let size = |tallies: Vec<(&Tile, usize)>| tallies.iter().map(|(_, count)| count).sum::<usize>();
+    assert_eq!(size(Tile::inns_and_cathedrals_library_tallies()), 18);
+    assert_eq!(size(Tile::traders_and_builders_library_tallies()), 24);
+    assert_eq!(size(Tile::princess_and_dragon_library_tallies()), 30);
+    assert_eq!(size(Tile::tower_library_tallies()), 18);
+    assert_eq!(size(Tile::hills_and_sheep_library_tallies()), 18);
+}
+
+#[test]
+fn traders_and_builders_goods() {
+    use crate::tile::{SegmentAttribute, Tile, TradeGood};
+
+    let goods = |good: TradeGood| -> usize {
+        Tile::traders_and_builders_library_tallies()
+            .into_iter()
+            .filter(|(tile, _)| {
+                tile.has_attribute(
+                    |a| matches!(a, SegmentAttribute::Goods { good: g, .. } if *g == good),
+                )
+            })
+            .map(|(_, count)| count)
+            .sum()
+    };
+    assert_eq!(goods(TradeGood::Wine), 9);
+    assert_eq!(goods(TradeGood::Grain), 6);
+    assert_eq!(goods(TradeGood::Cloth), 5);
+}
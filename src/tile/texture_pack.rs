@@ -0,0 +1,117 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    f32::consts::PI,
+    fs::{read, read_dir},
+    path::{Path, PathBuf},
+};
+
+use ggez::{
+    glam::vec2,
+    graphics::{Canvas, DrawParam, Image, Rect},
+    Context,
+};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+/// The artwork a tile is drawn with when a texture pack is selected.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TileArt {
+    /// File name of the image, looked up inside the selected texture pack.
+    pub image: String,
+    /// Clockwise quarter turns to apply to the image so that it lines up with the tile
+    /// definition in its unrotated orientation.
+    #[serde(default)]
+    pub rotation: usize,
+}
+
+impl TileArt {
+    pub fn new(image: impl Into<String>, rotation: usize) -> TileArt {
+        TileArt {
+            image: image.into(),
+            rotation,
+        }
+    }
+}
+
+/// A directory of tile images. Images are loaded the first time they're drawn, and images
+/// that are missing or fail to load are remembered so that the tile falls back to polygon
+/// rendering without retrying every frame.
+pub struct TexturePack {
+    pub name: String,
+    path: PathBuf,
+    images: RefCell<HashMap<String, Option<Image>>>,
+}
+
+impl TexturePack {
+    pub fn open(dir: impl AsRef<Path>, name: &str) -> TexturePack {
+        TexturePack {
+            name: name.to_string(),
+            path: dir.as_ref().join(name),
+            images: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Lists the names of the texture packs in the directory; each subdirectory is a pack.
+    pub fn list(dir: impl AsRef<Path>) -> Vec<String> {
+        let Ok(entries) = read_dir(dir.as_ref()) else {
+            debug!("no texture packs found at {}", dir.as_ref().display());
+            return Vec::new();
+        };
+        let mut names: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_dir())
+            .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn image(&self, ctx: &Context, name: &str) -> Option<Image> {
+        self.images
+            .borrow_mut()
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                let path = self.path.join(name);
+                match read(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|bytes| Image::from_bytes(ctx, &bytes).map_err(|err| err.to_string()))
+                {
+                    Ok(image) => Some(image),
+                    Err(err) => {
+                        warn!("failed to load tile image {}: {err}", path.display());
+                        None
+                    }
+                }
+            })
+            .clone()
+    }
+
+    /// Draws the art into the bounds, turned by an extra `rotation` clockwise quarter
+    /// turns. Returns false if the pack has no usable image for it.
+    pub fn draw(
+        &self,
+        ctx: &Context,
+        canvas: &mut Canvas,
+        art: &TileArt,
+        rotation: usize,
+        bounds: Rect,
+    ) -> bool {
+        let Some(image) = self.image(ctx, &art.image) else {
+            return false;
+        };
+        let quarter_turns = (art.rotation + rotation) % 4;
+        canvas.draw(
+            &image,
+            DrawParam::default()
+                .dest(bounds.center())
+                .offset(vec2(0.5, 0.5))
+                .rotation(quarter_turns as f32 * PI / 2.0)
+                .scale(vec2(
+                    bounds.w / image.width() as f32,
+                    bounds.h / image.height() as f32,
+                )),
+        );
+        true
+    }
+}
@@ -21,8 +21,8 @@ pub mod rivers_1 {
     use lazy_static::lazy_static;
 
     use crate::tile::{
-        Orientation, SegmentBorderPiece, SegmentDefinition, SegmentType, Tile, TileAttribute,
-        TileEdgeSpan,
+        Orientation, SegmentAttribute, SegmentBorderPiece, SegmentDefinition, SegmentType, Tile,
+        TileAttribute, TileEdgeSpan,
     };
 
     use Orientation::*;
@@ -80,7 +80,7 @@ pub mod rivers_1 {
                     stype: Monastary,
                     edges: (28..=32).map(Vert).collect()
                 },
-                Segment {
+                SpecialSegment {
                     stype: Farm,
                     edges: empty()
                         .chain([Edge((End, North)), Edge((Full, East)), Break])
@@ -92,11 +92,13 @@ pub mod rivers_1 {
                             Edge((Beginning, North)),
                         ])
                         .chain((0..=27).rev().map(Vert))
-                        .collect()
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.15))]
                 }
             ],
             vec![TileAttribute::MiddleSegmentWidth(North, 0.2)]
-        );
+        )
+        .with_art("monastary_pond.png", 0);
         pub static ref RIVER_CROSSING: Tile = Tile::new_with_attributes(
             vec![
                 vec2(0.40, 0.95),
@@ -143,14 +145,15 @@ pub mod rivers_1 {
                 vec2(0.10, 0.55),
             ],
             vec![
-                Segment {
+                SpecialSegment {
                     stype: River,
                     edges: empty()
                         .chain([Edge((Middle, South))])
-                        .chain((0..=11).map(Vert))
+                        .chain((0..=5).chain([39, 29]).chain(6..=11).map(Vert))
                         .chain([Edge((Middle, North))])
-                        .chain((13..=24).map(Vert))
-                        .collect()
+                        .chain((13..=17).chain([29, 39]).chain(18..=24).map(Vert))
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.3, 0.68))]
                 },
                 Segment {
                     stype: Road,
@@ -161,12 +164,13 @@ pub mod rivers_1 {
                         .chain((34..=38).chain([18, 39, 5]).chain(40..=41).map(Vert))
                         .collect()
                 },
-                Segment {
+                SpecialSegment {
                     stype: Farm,
                     edges: empty()
                         .chain([(End, South), (Beginning, West)].map(Edge))
                         .chain((0..=5).chain(40..=41).rev().map(Vert))
-                        .collect()
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.12, 0.87))]
                 },
                 Segment {
                     stype: Farm,
@@ -194,7 +198,8 @@ pub mod rivers_1 {
                 TileAttribute::MiddleSegmentWidth(North, 0.2),
                 TileAttribute::MiddleSegmentWidth(South, 0.2),
             ]
-        );
+        )
+        .with_art("river_crossing.png", 0);
         pub static ref RIVER_CORNER: Tile = Tile::new_with_attributes(
             vec![
                 vec2(0.40, 0.80),
@@ -211,14 +216,15 @@ pub mod rivers_1 {
                 vec2(0.60, 0.90),
             ],
             vec![
-                Segment {
+                SpecialSegment {
                     stype: River,
                     edges: empty()
                         .chain([Edge((Middle, South))])
                         .chain((0..=5).map(Vert))
                         .chain([Edge((Middle, West))])
                         .chain((6..=11).map(Vert))
-                        .collect()
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.55, 0.61))]
                 },
                 Segment {
                     stype: Farm,
@@ -227,7 +233,7 @@ pub mod rivers_1 {
                         .chain((0..=5).rev().map(Vert))
                         .collect()
                 },
-                Segment {
+                SpecialSegment {
                     stype: Farm,
                     edges: empty()
                         .chain([
@@ -237,14 +243,16 @@ pub mod rivers_1 {
                             Edge((Beginning, South))
                         ])
                         .chain((6..=11).rev().map(Vert))
-                        .collect()
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.73, 0.26))]
                 }
             ],
             vec![
                 TileAttribute::MiddleSegmentWidth(West, 0.2),
                 TileAttribute::MiddleSegmentWidth(South, 0.2),
             ]
-        );
+        )
+        .with_art("river_corner.png", 0);
         pub static ref CORNER_CITY_RIVER: Tile = Tile::new_with_attributes(
             vec![
                 vec2(0.90, 0.60),
@@ -269,12 +277,13 @@ pub mod rivers_1 {
                     stype: City,
                     edges: vec![Edge((Full, West)), Edge((Full, North))]
                 },
-                Segment {
+                SpecialSegment {
                     stype: Farm,
                     edges: empty()
                         .chain([(End, South), (Beginning, East)].map(Edge))
                         .chain((3..=6).rev().map(Vert))
-                        .collect()
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.29, 0.88))]
                 },
                 Segment {
                     stype: Farm,
@@ -288,7 +297,8 @@ pub mod rivers_1 {
                 TileAttribute::MiddleSegmentWidth(East, 0.2),
                 TileAttribute::MiddleSegmentWidth(South, 0.2),
             ]
-        );
+        )
+        .with_art("corner_city_river.png", 0);
         pub static ref RIVER_MONASTARY_BRIDGE: Tile = Tile::new_with_attributes(
             vec![
                 vec2(0.60, 0.09),
@@ -325,27 +335,36 @@ pub mod rivers_1 {
                 vec2(0.78, 0.55),
             ],
             vec![
-                Segment {
+                SpecialSegment {
                     stype: River,
                     edges: empty()
                         .chain([Edge((Middle, North))])
-                        .chain((0..=5).map(Vert))
+                        .chain((0..=3).chain([31, 9, 29, 30]).chain(4..=5).map(Vert))
                         .chain([Edge((Middle, South))])
                         .chain((6..=8).chain([29]).chain(9..=12).map(Vert))
-                        .collect()
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.51, 0.09))]
                 },
-                Segment {
+                SpecialSegment {
                     stype: Road,
                     edges: empty()
                         .chain([Edge((Middle, East))])
-                        .chain((13..=20).chain([9]).chain(21..=23).map(Vert))
-                        .collect()
+                        .chain(
+                            (13..=15)
+                                .chain([30, 29])
+                                .chain(16..=20)
+                                .chain([9, 31])
+                                .chain(21..=23)
+                                .map(Vert)
+                        )
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.9, 0.5))]
                 },
                 Segment {
                     stype: Monastary,
                     edges: (24..=28).map(Vert).collect()
                 },
-                Segment {
+                SpecialSegment {
                     stype: Farm,
                     edges: empty()
                         .chain([(End, South), (Full, West), (Beginning, North)].map(Edge))
@@ -360,7 +379,8 @@ pub mod rivers_1 {
                                 .rev()
                                 .map(Vert)
                         )
-                        .collect()
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.8))]
                 },
                 Segment {
                     stype: Farm,
@@ -369,19 +389,21 @@ pub mod rivers_1 {
                         .chain([5, 4, 30, 15, 14, 13].map(Vert))
                         .collect()
                 },
-                Segment {
+                SpecialSegment {
                     stype: Farm,
                     edges: empty()
                         .chain([(End, North), (Beginning, East)].map(Edge))
                         .chain([23, 22, 21, 31, 3, 2, 1, 0].map(Vert))
-                        .collect()
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.88, 0.12))]
                 }
             ],
             vec![
                 TileAttribute::MiddleSegmentWidth(North, 0.2),
                 TileAttribute::MiddleSegmentWidth(South, 0.2),
             ]
-        );
+        )
+        .with_art("river_monastary_bridge.png", 0);
         pub static ref CURVY_STRAIGHT_RIVER: Tile = Tile::new_with_attributes(
             vec![
                 vec2(0.40, 0.93),
@@ -420,26 +442,29 @@ pub mod rivers_1 {
                         .chain((13..=24).map(Vert))
                         .collect()
                 },
-                Segment {
+                SpecialSegment {
                     stype: Farm,
                     edges: empty()
                         .chain([(End, South), (Full, West), (Beginning, North)].map(Edge))
                         .chain((0..=12).rev().map(Vert))
-                        .collect()
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.2))]
                 },
-                Segment {
+                SpecialSegment {
                     stype: Farm,
                     edges: empty()
                         .chain([(End, North), (Full, East), (Beginning, South)].map(Edge))
                         .chain((13..=24).rev().map(Vert))
-                        .collect()
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.8, 0.2))]
                 }
             ],
             vec![
                 TileAttribute::MiddleSegmentWidth(North, 0.2),
                 TileAttribute::MiddleSegmentWidth(South, 0.2),
             ]
-        );
+        )
+        .with_art("curvy_straight_river.png", 0);
         pub static ref CORNER_ROAD_CORNER_RIVER: Tile = Tile::new_with_attributes(
             vec![
                 vec2(0.60, 0.20),
@@ -463,7 +488,7 @@ pub mod rivers_1 {
                         Vert(3),
                     ]
                 },
-                Segment {
+                SpecialSegment {
                     stype: Road,
                     edges: vec![
                         Edge((Middle, West)),
@@ -472,7 +497,8 @@ pub mod rivers_1 {
                         Edge((Middle, South)),
                         Vert(6),
                         Vert(7)
-                    ]
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.26, 0.54))]
                 },
                 Segment {
                     stype: Farm,
@@ -510,7 +536,8 @@ pub mod rivers_1 {
                 TileAttribute::MiddleSegmentWidth(North, 0.2),
                 TileAttribute::MiddleSegmentWidth(East, 0.2),
             ],
-        );
+        )
+        .with_art("corner_road_corner_river.png", 0);
         pub static ref STRAIGHT_RIVER: Tile = Tile::new_with_attributes(
             vec![],
             vec![
@@ -539,7 +566,8 @@ pub mod rivers_1 {
                 TileAttribute::MiddleSegmentWidth(East, 0.2),
                 TileAttribute::MiddleSegmentWidth(West, 0.2),
             ]
-        );
+        )
+        .with_art("straight_river.png", 0);
         pub static ref STRAIGHT_RIVER_DUELING_CITIES: Tile = Tile::new_with_attributes(
             vec![
                 vec2(0.25, 0.25),
@@ -560,30 +588,33 @@ pub mod rivers_1 {
                     stype: City,
                     edges: vec![Edge((Full, East)), Vert(2), Vert(3)]
                 },
-                Segment {
+                SpecialSegment {
                     stype: Farm,
                     edges: vec![
                         Edge((Beginning, North)),
                         Edge((End, South)),
                         Vert(1),
                         Vert(0)
-                    ]
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.28, 0.12))]
                 },
-                Segment {
+                SpecialSegment {
                     stype: Farm,
                     edges: vec![
                         Edge((Beginning, South)),
                         Edge((End, North)),
                         Vert(3),
                         Vert(2)
-                    ]
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.72, 0.12))]
                 }
             ],
             vec![
                 TileAttribute::MiddleSegmentWidth(North, 0.2),
                 TileAttribute::MiddleSegmentWidth(South, 0.2),
             ]
-        );
+        )
+        .with_art("straight_river_dueling_cities.png", 0);
         pub static ref CITY_RIVER_CROSSING: Tile = Tile::new_with_attributes(
             vec![
                 vec2(0.60, 0.40),
@@ -623,28 +654,30 @@ pub mod rivers_1 {
                     stype: River,
                     edges: empty()
                         .chain([Edge((Middle, North))])
-                        .chain((0..=3).map(Vert))
+                        .chain([0, 29, 28, 27, 21, 30, 1, 2, 3].map(Vert))
                         .chain([Edge((Middle, South))])
-                        .chain((4..=7).map(Vert))
+                        .chain([4, 5, 6, 27, 28, 7].map(Vert))
                         .collect()
                 },
-                Segment {
+                SpecialSegment {
                     stype: Road,
                     edges: empty()
                         .chain([Edge((Middle, West))])
                         .chain((8..=24).map(Vert))
-                        .collect()
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.05, 0.5))]
                 },
                 Segment {
                     stype: City,
                     edges: vec![Edge((Full, East)), Vert(25), Vert(26)]
                 },
-                Segment {
+                SpecialSegment {
                     stype: Farm,
                     edges: empty()
                         .chain([(End, South), (Beginning, West)].map(Edge))
                         .chain([24, 23, 22, 27, 6, 5, 4].map(Vert))
-                        .collect()
+                        .collect(),
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.13, 0.87))]
                 },
                 Segment {
                     stype: Farm,
@@ -678,7 +711,8 @@ pub mod rivers_1 {
                 TileAttribute::MiddleSegmentWidth(North, 0.2),
                 TileAttribute::MiddleSegmentWidth(South, 0.2)
             ]
-        );
+        )
+        .with_art("city_river_crossing.png", 0);
         pub static ref CORNER_ROAD_WATERFALL: Tile = Tile::new_with_attributes(
             vec![
                 vec2(0.55, 0.22),
@@ -702,7 +736,7 @@ pub mod rivers_1 {
                         .chain((4..=11).map(Vert))
                         .collect()
                 },
-                Segment {
+                SpecialSegment {
                     stype: Road,
                     edges: vec![
                         Edge((Middle, North)),
@@ -711,7 +745,8 @@ pub mod rivers_1 {
                         Edge((Middle, East)),
                         Vert(2),
                         Vert(3)
-                    ]
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.05))]
                 },
                 Segment {
                     stype: Farm,
@@ -738,260 +773,579 @@ pub mod rivers_1 {
                 }
             ],
             vec![TileAttribute::MiddleSegmentWidth(South, 0.2)]
-        );
+        )
+        .with_art("corner_road_waterfall.png", 0);
     }
 }
 
-lazy_static! {
-    pub static ref STARTING_TILE: Tile = Tile::new(
-        vec![vec2(0.35, 0.3), vec2(0.65, 0.3)],
-        vec![
-            Segment {
-                stype: City,
-                edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
-            },
-            Segment {
-                stype: Road,
-                edges: vec![Edge((Middle, West)), Edge((Middle, East))]
-            },
-            SpecialSegment {
-                stype: Farm,
-                edges: vec![Edge((End, West)), Vert(0), Vert(1), Edge((Beginning, East))],
-                attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.315))]
-            },
-            Segment {
-                stype: Farm,
-                edges: vec![
-                    Edge((Beginning, West)),
-                    Edge((End, East)),
-                    Edge((Full, South))
-                ]
-            }
-        ]
-    );
-    pub static ref STRAIGHT_ROAD: Tile = Tile::new(
-        vec![],
-        vec![
-            Segment {
-                stype: Farm,
-                edges: vec![
-                    Edge((End, West)),
-                    Edge((Full, North)),
-                    Edge((Beginning, East))
-                ]
-            },
-            Segment {
-                stype: Road,
-                edges: vec![Edge((Middle, West)), Edge((Middle, East))]
-            },
-            Segment {
-                stype: Farm,
-                edges: vec![
-                    Edge((Beginning, West)),
-                    Edge((End, East)),
-                    Edge((Full, South))
-                ]
-            }
-        ]
-    );
-    pub static ref CURVE_ROAD: Tile = Tile::new(
-        vec![vec2(0.45, 0.45), vec2(0.55, 0.55)],
-        vec![
-            Segment {
-                stype: Farm,
-                edges: vec![Edge((End, West)), Edge((Beginning, North)), Vert(0)]
-            },
-            SpecialSegment {
-                stype: Road,
-                edges: vec![
-                    Edge((Middle, West)),
-                    Vert(0),
-                    Edge((Middle, North)),
-                    Vert(1)
-                ],
-                attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))]
-            },
-            SpecialSegment {
-                stype: Farm,
-                edges: vec![
-                    Edge((Beginning, West)),
-                    Vert(1),
-                    Edge((End, North)),
-                    Edge((Full, East)),
-                    Edge((Full, South))
-                ],
-                attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.75, 0.75))]
-            }
-        ]
-    );
-    pub static ref CROSSROADS: Tile = Tile::new(
-        vec![
-            vec2(0.35, 0.45),
-            vec2(0.65, 0.45),
-            vec2(0.65, 0.55),
-            vec2(0.55, 0.65),
-            vec2(0.45, 0.65),
-            vec2(0.35, 0.55)
-        ],
-        vec![
-            Segment {
-                stype: Village,
-                edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4), Vert(5)]
-            },
-            SpecialSegment {
-                stype: Farm,
-                edges: vec![
-                    Edge((End, West)),
-                    Edge((Full, North)),
-                    Edge((Beginning, East)),
-                    Vert(1),
-                    Vert(0)
-                ],
-                attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.25))]
-            },
-            Segment {
-                stype: Road,
-                edges: vec![Edge((Middle, East)), Vert(2), Vert(1)]
-            },
-            Segment {
-                stype: Farm,
-                edges: vec![
-                    Edge((End, East)),
-                    Edge((Beginning, South)),
-                    Vert(3),
-                    Vert(2)
-                ]
-            },
-            Segment {
-                stype: Road,
-                edges: vec![Edge((Middle, South)), Vert(4), Vert(3)]
-            },
-            Segment {
-                stype: Farm,
-                edges: vec![
-                    Edge((End, South)),
-                    Edge((Beginning, West)),
-                    Vert(5),
-                    Vert(4)
-                ]
-            },
-            Segment {
-                stype: Road,
-                edges: vec![Edge((Middle, West)), Vert(0), Vert(5)]
-            }
-        ]
-    );
-    pub static ref FOUR_WAY_CROSSROADS: Tile = Tile::new(
-        vec![
-            vec2(0.35, 0.45),
-            vec2(0.45, 0.35),
-            vec2(0.55, 0.35),
-            vec2(0.65, 0.45),
-            vec2(0.65, 0.55),
-            vec2(0.55, 0.65),
-            vec2(0.45, 0.65),
-            vec2(0.35, 0.55)
-        ],
-        vec![
-            Segment {
-                stype: Village,
-                edges: (0..=7).map(Vert).collect()
-            },
-            Segment {
-                stype: Farm,
-                edges: vec![
-                    Edge((End, West)),
-                    Edge((Beginning, North)),
-                    Vert(1),
-                    Vert(0)
-                ]
-            },
-            Segment {
-                stype: Road,
-                edges: vec![Edge((Middle, North)), Vert(2), Vert(1)]
-            },
-            Segment {
-                stype: Farm,
-                edges: vec![
-                    Edge((End, North)),
-                    Edge((Beginning, East)),
-                    Vert(3),
-                    Vert(2)
-                ]
-            },
-            Segment {
-                stype: Road,
-                edges: vec![Edge((Middle, East)), Vert(4), Vert(3)]
-            },
-            Segment {
-                stype: Farm,
-                edges: vec![
-                    Edge((End, East)),
-                    Edge((Beginning, South)),
-                    Vert(5),
-                    Vert(4)
-                ]
-            },
-            Segment {
-                stype: Road,
-                edges: vec![Edge((Middle, South)), Vert(6), Vert(5)]
-            },
-            Segment {
-                stype: Farm,
-                edges: vec![
-                    Edge((End, South)),
-                    Edge((Beginning, West)),
-                    Vert(7),
-                    Vert(6)
-                ]
-            },
-            Segment {
-                stype: Road,
-                edges: vec![Edge((Middle, West)), Vert(0), Vert(7)]
-            }
-        ]
-    );
-    pub static ref EDGE_CITY: Tile = Tile::new(
-        vec![vec2(0.35, 0.3), vec2(0.65, 0.3),],
-        vec![
-            Segment {
-                stype: City,
-                edges: vec![Edge((Full, North)), Vert(1), Vert(0),]
-            },
-            SpecialSegment {
-                stype: Farm,
-                edges: vec![
-                    Edge((Full, East)),
-                    Edge((Full, South)),
-                    Edge((Full, West)),
-                    Vert(0),
-                    Vert(1),
-                ],
-                attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.65))]
-            },
-        ]
-    );
-    pub static ref EDGE_CITY_LEFT_CURVE_ROAD: Tile = Tile::new(
-        vec![
-            vec2(0.35, 0.3),
-            vec2(0.65, 0.3),
-            vec2(0.45, 0.55),
-            vec2(0.55, 0.45)
-        ],
+pub mod rivers_2 {
+    use std::vec;
+
+    use ggez::glam::vec2;
+    use lazy_static::lazy_static;
+
+    use crate::tile::{
+        Orientation, SegmentAttribute, SegmentBorderPiece, SegmentDefinition, SegmentType, Tile,
+        TileAttribute, TileEdgeSpan,
+    };
+
+    use Orientation::*;
+    use SegmentBorderPiece::*;
+    use SegmentDefinition::*;
+    use SegmentType::*;
+    use TileEdgeSpan::*;
+
+    lazy_static! {
+        pub static ref RIVER_SPRING: Tile = Tile::new_with_attributes(
+            vec![
+                vec2(0.4, 0.55),
+                vec2(0.35, 0.45),
+                vec2(0.45, 0.35),
+                vec2(0.55, 0.35),
+                vec2(0.65, 0.45),
+                vec2(0.6, 0.55),
+            ],
+            vec![
+                Segment {
+                    stype: River,
+                    edges: vec![
+                        Edge((Middle, South)),
+                        Vert(0),
+                        Vert(1),
+                        Vert(2),
+                        Vert(3),
+                        Vert(4),
+                        Vert(5)
+                    ]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Edge((Beginning, South)),
+                        Vert(5),
+                        Vert(4),
+                        Vert(3),
+                        Vert(2),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.25, 0.25))]
+                }
+            ],
+            vec![TileAttribute::MiddleSegmentWidth(South, 0.2)]
+        )
+        .with_art("river_spring.png", 0);
+        pub static ref RIVER_FORK: Tile = Tile::new_with_attributes(
+            vec![
+                vec2(0.4, 0.4),
+                vec2(0.6, 0.4),
+                vec2(0.6, 0.6),
+                vec2(0.4, 0.6),
+            ],
+            vec![
+                Segment {
+                    stype: River,
+                    edges: vec![
+                        Edge((Middle, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Middle, East)),
+                        Vert(2),
+                        Edge((Middle, South)),
+                        Vert(3)
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Full, North)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0)
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, East)), Edge((Beginning, South)), Vert(2)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, South)), Edge((Beginning, West)), Vert(3)]
+                }
+            ],
+            vec![
+                TileAttribute::MiddleSegmentWidth(West, 0.2),
+                TileAttribute::MiddleSegmentWidth(East, 0.2),
+                TileAttribute::MiddleSegmentWidth(South, 0.2),
+            ]
+        )
+        .with_art("river_fork.png", 0);
+        pub static ref RIVER_INN_CROSSING: Tile = Tile::new_with_attributes(
+            vec![
+                vec2(0.4, 0.45),
+                vec2(0.6, 0.45),
+                vec2(0.6, 0.55),
+                vec2(0.4, 0.55),
+            ],
+            vec![
+                SpecialSegment {
+                    stype: River,
+                    edges: vec![
+                        Edge((Middle, North)),
+                        Vert(1),
+                        Vert(2),
+                        Edge((Middle, South)),
+                        Vert(3),
+                        Vert(2),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.1))]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Middle, East)),
+                        Vert(2),
+                        Vert(3)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.8, 0.5)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.5))
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, West)), Edge((Beginning, North)), Vert(0)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, North)), Edge((Beginning, East)), Vert(1)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, East)), Edge((Beginning, South)), Vert(2)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, South)), Edge((Beginning, West)), Vert(3)]
+                }
+            ],
+            vec![
+                TileAttribute::MiddleSegmentWidth(North, 0.2),
+                TileAttribute::MiddleSegmentWidth(South, 0.2),
+            ]
+        )
+        .with_art("river_inn_crossing.png", 0);
+        pub static ref RIVER_EDGE_CITY: Tile = Tile::new_with_attributes(
+            vec![vec2(0.8, 0.25), vec2(0.8, 0.75)],
+            vec![
+                Segment {
+                    stype: River,
+                    edges: vec![Edge((Middle, North)), Edge((Middle, South))]
+                },
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, East)), Vert(1), Vert(0)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, North)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Beginning, South))
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Full, West)),
+                        Edge((Beginning, North))
+                    ]
+                }
+            ],
+            vec![
+                TileAttribute::MiddleSegmentWidth(North, 0.2),
+                TileAttribute::MiddleSegmentWidth(South, 0.2),
+            ]
+        )
+        .with_art("river_edge_city.png", 0);
+        pub static ref RIVER_LAKE: Tile = Tile::new_with_attributes(
+            vec![
+                vec2(0.6, 0.3),
+                vec2(0.75, 0.4),
+                vec2(0.75, 0.6),
+                vec2(0.6, 0.7),
+                vec2(0.4, 0.7),
+                vec2(0.25, 0.6),
+                vec2(0.25, 0.4),
+                vec2(0.4, 0.3),
+            ],
+            vec![
+                Segment {
+                    stype: River,
+                    edges: vec![
+                        Edge((Middle, North)),
+                        Vert(0),
+                        Vert(1),
+                        Vert(2),
+                        Vert(3),
+                        Vert(4),
+                        Vert(5),
+                        Vert(6),
+                        Vert(7)
+                    ]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, North)),
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Edge((Full, West)),
+                        Edge((Beginning, North)),
+                        Vert(7),
+                        Vert(6),
+                        Vert(5),
+                        Vert(4),
+                        Vert(3),
+                        Vert(2),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.85))]
+                }
+            ],
+            vec![TileAttribute::MiddleSegmentWidth(North, 0.2)]
+        )
+        .with_art("river_lake.png", 0);
+        pub static ref RIVER_CITY_LAKE: Tile = Tile::new_with_attributes(
+            vec![
+                vec2(0.6, 0.25),
+                vec2(0.75, 0.35),
+                vec2(0.75, 0.5),
+                vec2(0.6, 0.6),
+                vec2(0.4, 0.6),
+                vec2(0.25, 0.5),
+                vec2(0.25, 0.35),
+                vec2(0.4, 0.25),
+                vec2(0.25, 0.8),
+                vec2(0.75, 0.8),
+            ],
+            vec![
+                Segment {
+                    stype: River,
+                    edges: vec![
+                        Edge((Middle, North)),
+                        Vert(0),
+                        Vert(1),
+                        Vert(2),
+                        Vert(3),
+                        Vert(4),
+                        Vert(5),
+                        Vert(6),
+                        Vert(7)
+                    ]
+                },
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, South)), Vert(8), Vert(9)]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, North)),
+                        Edge((Full, East)),
+                        Vert(9),
+                        Vert(8),
+                        Edge((Full, West)),
+                        Edge((Beginning, North)),
+                        Vert(7),
+                        Vert(6),
+                        Vert(5),
+                        Vert(4),
+                        Vert(3),
+                        Vert(2),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.12, 0.5))]
+                }
+            ],
+            vec![TileAttribute::MiddleSegmentWidth(North, 0.2)]
+        )
+        .with_art("river_city_lake.png", 0);
+    }
+}
+
+lazy_static! {
+    pub static ref STARTING_TILE: Tile = Tile::new(
+        vec![vec2(0.35, 0.3), vec2(0.65, 0.3)],
         vec![
             Segment {
                 stype: City,
                 edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
             },
-            SpecialSegment {
+            Segment {
                 stype: Road,
-                edges: vec![
-                    Edge((Middle, West)),
-                    Vert(3),
-                    Edge((Middle, South)),
-                    Vert(2)
-                ],
-                attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))]
+                edges: vec![Edge((Middle, West)), Edge((Middle, East))]
+            },
+            SpecialSegment {
+                stype: Farm,
+                edges: vec![Edge((End, West)), Vert(0), Vert(1), Edge((Beginning, East))],
+                attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.315))]
+            },
+            Segment {
+                stype: Farm,
+                edges: vec![
+                    Edge((Beginning, West)),
+                    Edge((End, East)),
+                    Edge((Full, South))
+                ]
+            }
+        ]
+    )
+    .with_art("starting_tile.png", 0);
+    pub static ref STRAIGHT_ROAD: Tile = Tile::new(
+        vec![],
+        vec![
+            Segment {
+                stype: Farm,
+                edges: vec![
+                    Edge((End, West)),
+                    Edge((Full, North)),
+                    Edge((Beginning, East))
+                ]
+            },
+            Segment {
+                stype: Road,
+                edges: vec![Edge((Middle, West)), Edge((Middle, East))]
+            },
+            Segment {
+                stype: Farm,
+                edges: vec![
+                    Edge((Beginning, West)),
+                    Edge((End, East)),
+                    Edge((Full, South))
+                ]
+            }
+        ]
+    )
+    .with_art("straight_road.png", 0);
+    pub static ref CURVE_ROAD: Tile = Tile::new(
+        vec![vec2(0.45, 0.45), vec2(0.55, 0.55)],
+        vec![
+            Segment {
+                stype: Farm,
+                edges: vec![Edge((End, West)), Edge((Beginning, North)), Vert(0)]
+            },
+            SpecialSegment {
+                stype: Road,
+                edges: vec![
+                    Edge((Middle, West)),
+                    Vert(0),
+                    Edge((Middle, North)),
+                    Vert(1)
+                ],
+                attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))]
+            },
+            SpecialSegment {
+                stype: Farm,
+                edges: vec![
+                    Edge((Beginning, West)),
+                    Vert(1),
+                    Edge((End, North)),
+                    Edge((Full, East)),
+                    Edge((Full, South))
+                ],
+                attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.75, 0.75))]
+            }
+        ]
+    )
+    .with_art("curve_road.png", 0);
+    pub static ref CROSSROADS: Tile = Tile::new(
+        vec![
+            vec2(0.35, 0.45),
+            vec2(0.65, 0.45),
+            vec2(0.65, 0.55),
+            vec2(0.55, 0.65),
+            vec2(0.45, 0.65),
+            vec2(0.35, 0.55)
+        ],
+        vec![
+            Segment {
+                stype: Village,
+                edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4), Vert(5)]
+            },
+            SpecialSegment {
+                stype: Farm,
+                edges: vec![
+                    Edge((End, West)),
+                    Edge((Full, North)),
+                    Edge((Beginning, East)),
+                    Vert(1),
+                    Vert(0)
+                ],
+                attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.25))]
+            },
+            Segment {
+                stype: Road,
+                edges: vec![Edge((Middle, East)), Vert(2), Vert(1)]
+            },
+            Segment {
+                stype: Farm,
+                edges: vec![
+                    Edge((End, East)),
+                    Edge((Beginning, South)),
+                    Vert(3),
+                    Vert(2)
+                ]
+            },
+            Segment {
+                stype: Road,
+                edges: vec![Edge((Middle, South)), Vert(4), Vert(3)]
+            },
+            Segment {
+                stype: Farm,
+                edges: vec![
+                    Edge((End, South)),
+                    Edge((Beginning, West)),
+                    Vert(5),
+                    Vert(4)
+                ]
+            },
+            Segment {
+                stype: Road,
+                edges: vec![Edge((Middle, West)), Vert(0), Vert(5)]
+            }
+        ]
+    )
+    .with_art("crossroads.png", 0);
+    pub static ref FOUR_WAY_CROSSROADS: Tile = Tile::new(
+        vec![
+            vec2(0.35, 0.45),
+            vec2(0.45, 0.35),
+            vec2(0.55, 0.35),
+            vec2(0.65, 0.45),
+            vec2(0.65, 0.55),
+            vec2(0.55, 0.65),
+            vec2(0.45, 0.65),
+            vec2(0.35, 0.55)
+        ],
+        vec![
+            Segment {
+                stype: Village,
+                edges: (0..=7).map(Vert).collect()
+            },
+            Segment {
+                stype: Farm,
+                edges: vec![
+                    Edge((End, West)),
+                    Edge((Beginning, North)),
+                    Vert(1),
+                    Vert(0)
+                ]
+            },
+            Segment {
+                stype: Road,
+                edges: vec![Edge((Middle, North)), Vert(2), Vert(1)]
+            },
+            Segment {
+                stype: Farm,
+                edges: vec![
+                    Edge((End, North)),
+                    Edge((Beginning, East)),
+                    Vert(3),
+                    Vert(2)
+                ]
+            },
+            Segment {
+                stype: Road,
+                edges: vec![Edge((Middle, East)), Vert(4), Vert(3)]
+            },
+            Segment {
+                stype: Farm,
+                edges: vec![
+                    Edge((End, East)),
+                    Edge((Beginning, South)),
+                    Vert(5),
+                    Vert(4)
+                ]
+            },
+            Segment {
+                stype: Road,
+                edges: vec![Edge((Middle, South)), Vert(6), Vert(5)]
+            },
+            Segment {
+                stype: Farm,
+                edges: vec![
+                    Edge((End, South)),
+                    Edge((Beginning, West)),
+                    Vert(7),
+                    Vert(6)
+                ]
+            },
+            Segment {
+                stype: Road,
+                edges: vec![Edge((Middle, West)), Vert(0), Vert(7)]
+            }
+        ]
+    )
+    .with_art("four_way_crossroads.png", 0);
+    pub static ref EDGE_CITY: Tile = Tile::new(
+        vec![vec2(0.35, 0.3), vec2(0.65, 0.3),],
+        vec![
+            Segment {
+                stype: City,
+                edges: vec![Edge((Full, North)), Vert(1), Vert(0),]
+            },
+            SpecialSegment {
+                stype: Farm,
+                edges: vec![
+                    Edge((Full, East)),
+                    Edge((Full, South)),
+                    Edge((Full, West)),
+                    Vert(0),
+                    Vert(1),
+                ],
+                attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.65))]
+            },
+        ]
+    )
+    .with_art("edge_city.png", 0);
+    pub static ref EDGE_CITY_LEFT_CURVE_ROAD: Tile = Tile::new(
+        vec![
+            vec2(0.35, 0.3),
+            vec2(0.65, 0.3),
+            vec2(0.45, 0.55),
+            vec2(0.55, 0.45)
+        ],
+        vec![
+            Segment {
+                stype: City,
+                edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+            },
+            SpecialSegment {
+                stype: Road,
+                edges: vec![
+                    Edge((Middle, West)),
+                    Vert(3),
+                    Edge((Middle, South)),
+                    Vert(2)
+                ],
+                attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))]
             },
             SpecialSegment {
                 stype: Farm,
@@ -1010,7 +1364,8 @@ lazy_static! {
                 edges: vec![Edge((End, South)), Edge((Beginning, West)), Vert(2)]
             }
         ]
-    );
+    )
+    .with_art("edge_city_left_curve_road.png", 0);
     pub static ref EDGE_CITY_RIGHT_CURVE_ROAD: Tile = Tile::new(
         vec![
             vec2(0.35, 0.3),
@@ -1050,7 +1405,8 @@ lazy_static! {
                 edges: vec![Edge((End, East)), Edge((Beginning, South)), Vert(3)]
             }
         ]
-    );
+    )
+    .with_art("edge_city_right_curve_road.png", 0);
     pub static ref EDGE_CITY_CROSSROADS: Tile = Tile::new(
         vec![
             vec2(0.35, 0.45),
@@ -1114,7 +1470,8 @@ lazy_static! {
                 edges: vec![Edge((Middle, West)), Vert(0), Vert(5)]
             }
         ]
-    );
+    )
+    .with_art("edge_city_crossroads.png", 0);
     pub static ref ADJACENT_EDGE_CITIES: Tile = Tile::new(
         vec![
             vec2(0.35, 0.3),
@@ -1146,7 +1503,8 @@ lazy_static! {
                 attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.35, 0.65))]
             },
         ]
-    );
+    )
+    .with_art("adjacent_edge_cities.png", 0);
     pub static ref OPPOSING_EDGE_CITIES: Tile = Tile::new(
         vec![
             vec2(0.35, 0.3),
@@ -1175,7 +1533,8 @@ lazy_static! {
                 ]
             }
         ]
-    );
+    )
+    .with_art("opposing_edge_cities.png", 0);
     pub static ref BRIDGE_CITY: Tile = Tile::new(
         vec![
             vec2(0.35, 0.3),
@@ -1204,7 +1563,8 @@ lazy_static! {
                 ]
             }
         ]
-    );
+    )
+    .with_art("bridge_city.png", 0);
     pub static ref FORTIFIED_BRIDGE_CITY: Tile = Tile::new(
         vec![
             vec2(0.35, 0.3),
@@ -1236,7 +1596,8 @@ lazy_static! {
                 }]
             }
         ]
-    );
+    )
+    .with_art("fortified_bridge_city.png", 0);
     pub static ref THREE_QUARTER_CITY: Tile = Tile::new(
         vec![vec2(0.35, 0.7), vec2(0.65, 0.7),],
         vec![
@@ -1256,7 +1617,8 @@ lazy_static! {
                 edges: vec![Edge((Full, South)), Vert(0), Vert(1)]
             }
         ]
-    );
+    )
+    .with_art("three_quarter_city.png", 0);
     pub static ref FORTIFIED_THREE_QUARTER_CITY: Tile = Tile::new(
         vec![vec2(0.35, 0.7), vec2(0.65, 0.7),],
         vec![
@@ -1281,7 +1643,8 @@ lazy_static! {
                 edges: vec![Edge((Full, South)), Vert(0), Vert(1)]
             }
         ]
-    );
+    )
+    .with_art("fortified_three_quarter_city.png", 0);
     pub static ref THREE_QUARTER_CITY_ENTRANCE: Tile = Tile::new(
         vec![
             vec2(0.35, 0.7),
@@ -1314,7 +1677,8 @@ lazy_static! {
                 edges: vec![Edge((End, South)), Vert(0), Vert(1)]
             }
         ]
-    );
+    )
+    .with_art("three_quarter_city_entrance.png", 0);
     pub static ref FORITIFED_THREE_QUARTER_CITY_ENTRANCE: Tile = Tile::new(
         vec![
             vec2(0.35, 0.7),
@@ -1352,7 +1716,8 @@ lazy_static! {
                 edges: vec![Edge((End, South)), Vert(0), Vert(1)]
             }
         ]
-    );
+    )
+    .with_art("foritifed_three_quarter_city_entrance.png", 0);
     pub static ref CORNER_CITY: Tile = Tile::new(
         vec![],
         vec![
@@ -1365,7 +1730,8 @@ lazy_static! {
                 edges: vec![Edge((Full, East)), Edge((Full, South))]
             }
         ]
-    );
+    )
+    .with_art("corner_city.png", 0);
     pub static ref FORTIFIED_CORNER_CITY: Tile = Tile::new(
         vec![],
         vec![
@@ -1381,7 +1747,8 @@ lazy_static! {
                 edges: vec![Edge((Full, East)), Edge((Full, South))]
             }
         ]
-    );
+    )
+    .with_art("fortified_corner_city.png", 0);
     pub static ref CORNER_CITY_CURVE_ROAD: Tile = Tile::new(
         vec![
             vec2(0.45, 0.7),
@@ -1426,7 +1793,8 @@ lazy_static! {
                 ]
             }
         ]
-    );
+    )
+    .with_art("corner_city_curve_road.png", 0);
     pub static ref FORTIFIED_CORNER_CITY_CURVE_ROAD: Tile = Tile::new(
         vec![
             vec2(0.45, 0.7),
@@ -1474,7 +1842,8 @@ lazy_static! {
                 ]
             }
         ]
-    );
+    )
+    .with_art("fortified_corner_city_curve_road.png", 0);
     pub static ref FULL_FORTIFIED_CITY: Tile = Tile::new(
         vec![],
         vec![SpecialSegment {
@@ -1489,7 +1858,8 @@ lazy_static! {
                 shield_location: vec2(0.2, 0.2)
             }]
         }]
-    );
+    )
+    .with_art("full_fortified_city.png", 0);
     pub static ref MONASTARY: Tile = Tile::new(
         vec![
             vec2(0.3, 0.7),
@@ -1522,7 +1892,8 @@ lazy_static! {
                 attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.5))],
             }
         ]
-    );
+    )
+    .with_art("monastary.png", 0);
     pub static ref ROAD_MONASTARY: Tile = Tile::new(
         vec![
             vec2(0.3, 0.7),
@@ -1561,7 +1932,8 @@ lazy_static! {
                 edges: vec![Edge((Middle, South)), Vert(6), Vert(5),]
             }
         ]
-    );
+    )
+    .with_art("road_monastary.png", 0);
     pub static ref _DEBUG_EMPTY_FIELD: Tile = Tile::new(
         vec![],
         vec![Segment {
@@ -1573,5 +1945,3012 @@ lazy_static! {
                 Edge((Full, South))
             ]
         }]
-    );
+    )
+    .with_art("_debug_empty_field.png", 0);
+}
+
+pub mod inns_and_cathedrals {
+    use ggez::glam::vec2;
+    use lazy_static::lazy_static;
+
+    use crate::tile::{
+        Orientation, SegmentAttribute, SegmentBorderPiece, SegmentDefinition, SegmentType, Tile,
+        TileEdgeSpan,
+    };
+
+    use Orientation::*;
+    use SegmentBorderPiece::*;
+    use SegmentDefinition::*;
+    use SegmentType::*;
+    use TileEdgeSpan::*;
+
+    lazy_static! {
+        pub static ref CATHEDRAL: Tile = Tile::new(
+            vec![],
+            vec![SpecialSegment {
+                stype: City,
+                edges: vec![
+                    Edge((Full, West)),
+                    Edge((Full, North)),
+                    Edge((Full, East)),
+                    Edge((Full, South)),
+                ],
+                attributes: vec![
+                    SegmentAttribute::Cathedral {
+                        cathedral_location: vec2(0.5, 0.45)
+                    },
+                    SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.75))
+                ]
+            }]
+        )
+        .with_art("cathedral.png", 0);
+        pub static ref INN_STRAIGHT_ROAD: Tile = Tile::new(
+            vec![],
+            vec![
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Full, North)),
+                        Edge((Beginning, East))
+                    ]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Edge((Middle, East))],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.7, 0.5)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.3, 0.5))
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Edge((End, East)),
+                        Edge((Full, South))
+                    ]
+                }
+            ]
+        )
+        .with_art("inn_straight_road.png", 0);
+        pub static ref INN_CURVE_ROAD: Tile = Tile::new(
+            vec![vec2(0.45, 0.45), vec2(0.55, 0.55)],
+            vec![
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, West)), Edge((Beginning, North)), Vert(0)]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, West)),
+                        Vert(0),
+                        Edge((Middle, North)),
+                        Vert(1)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.5, 0.2)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.5))
+                    ]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Vert(1),
+                        Edge((End, North)),
+                        Edge((Full, East)),
+                        Edge((Full, South))
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.75, 0.75))]
+                }
+            ]
+        )
+        .with_art("inn_curve_road.png", 0);
+        pub static ref INN_CROSSROADS: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.45),
+                vec2(0.65, 0.45),
+                vec2(0.65, 0.55),
+                vec2(0.55, 0.65),
+                vec2(0.45, 0.65),
+                vec2(0.35, 0.55)
+            ],
+            vec![
+                Segment {
+                    stype: Village,
+                    edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4), Vert(5)]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Full, North)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.25))]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, East)), Vert(2), Vert(1)],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.9, 0.5)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.73, 0.5))
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(4), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, West)),
+                        Vert(5),
+                        Vert(4)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Vert(0), Vert(5)]
+                }
+            ]
+        )
+        .with_art("inn_crossroads.png", 0);
+        pub static ref INN_EDGE_CITY_ROAD: Tile = Tile::new(
+            vec![vec2(0.35, 0.3), vec2(0.65, 0.3)],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Edge((Middle, East))],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.7, 0.5)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.3, 0.5))
+                    ]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![Edge((End, West)), Vert(0), Vert(1), Edge((Beginning, East))],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.315))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Edge((End, East)),
+                        Edge((Full, South))
+                    ]
+                }
+            ]
+        )
+        .with_art("inn_edge_city_road.png", 0);
+        pub static ref INN_CORNER_CITY_CURVE_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.45, 0.7),
+                vec2(0.7, 0.45),
+                vec2(0.77071, 0.55),
+                vec2(0.55, 0.77071)
+            ],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, West)), Edge((Full, North))],
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, South)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Middle, East)),
+                        Vert(2),
+                        Vert(3)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.5, 0.88)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.618, 0.618))
+                    ]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.86, 0.32))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2),
+                    ]
+                }
+            ]
+        )
+        .with_art("inn_corner_city_curve_road.png", 0);
+        pub static ref INN_EDGE_CITY_LEFT_CURVE_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+                vec2(0.45, 0.55),
+                vec2(0.55, 0.45)
+            ],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, West)),
+                        Vert(3),
+                        Edge((Middle, South)),
+                        Vert(2)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.5, 0.82)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))
+                    ]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Full, East)),
+                        Edge((Beginning, South)),
+                        Vert(3)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.75, 0.5))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, South)), Edge((Beginning, West)), Vert(2)]
+                }
+            ]
+        )
+        .with_art("inn_edge_city_left_curve_road.png", 0);
+        pub static ref INN_EDGE_CITY_RIGHT_CURVE_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+                vec2(0.45, 0.45),
+                vec2(0.55, 0.55)
+            ],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, East)),
+                        Vert(3),
+                        Edge((Middle, South)),
+                        Vert(2)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.82, 0.5)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))
+                    ]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Beginning, East)),
+                        Vert(2),
+                        Edge((End, South)),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.25, 0.5))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, East)), Edge((Beginning, South)), Vert(3)]
+                }
+            ]
+        )
+        .with_art("inn_edge_city_right_curve_road.png", 0);
+        pub static ref INN_EDGE_CITY_CROSSROADS: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.45),
+                vec2(0.65, 0.45),
+                vec2(0.65, 0.55),
+                vec2(0.55, 0.65),
+                vec2(0.45, 0.65),
+                vec2(0.35, 0.55),
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+            ],
+            vec![
+                Segment {
+                    stype: Village,
+                    edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4), Vert(5)]
+                },
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(7), Vert(6)]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Vert(6),
+                        Vert(7),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.315))]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, East)), Vert(2), Vert(1)],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.9, 0.5)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.73, 0.5))
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(4), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, West)),
+                        Vert(5),
+                        Vert(4)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Vert(0), Vert(5)]
+                }
+            ]
+        )
+        .with_art("inn_edge_city_crossroads.png", 0);
+        pub static ref INN_FOUR_WAY_CROSSROADS: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.45),
+                vec2(0.45, 0.35),
+                vec2(0.55, 0.35),
+                vec2(0.65, 0.45),
+                vec2(0.65, 0.55),
+                vec2(0.55, 0.65),
+                vec2(0.45, 0.65),
+                vec2(0.35, 0.55)
+            ],
+            vec![
+                Segment {
+                    stype: Village,
+                    edges: (0..=7).map(Vert).collect()
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Beginning, North)),
+                        Vert(1),
+                        Vert(0)
+                    ]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, North)), Vert(2), Vert(1)],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.5, 0.1)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.27))
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, North)),
+                        Edge((Beginning, East)),
+                        Vert(3),
+                        Vert(2)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, East)), Vert(4), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(5),
+                        Vert(4)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(6), Vert(5)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, West)),
+                        Vert(7),
+                        Vert(6)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Vert(0), Vert(7)]
+                }
+            ]
+        )
+        .with_art("inn_four_way_crossroads.png", 0);
+        pub static ref INN_THREE_QUARTER_CITY_ENTRANCE: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.7),
+                vec2(0.45, 0.7),
+                vec2(0.55, 0.7),
+                vec2(0.65, 0.7),
+            ],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Vert(3),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.35))]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(1), Vert(2)],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.5, 0.9)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.77))
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Beginning, South)), Vert(2), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, South)), Vert(0), Vert(1)]
+                }
+            ]
+        )
+        .with_art("inn_three_quarter_city_entrance.png", 0);
+        pub static ref INN_FORTIFIED_CORNER_CITY_CURVE_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.45, 0.7),
+                vec2(0.7, 0.45),
+                vec2(0.77071, 0.55),
+                vec2(0.55, 0.77071)
+            ],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, West)), Edge((Full, North))],
+                    attributes: vec![SegmentAttribute::Fortified {
+                        shield_location: vec2(0.2, 0.2)
+                    }]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, South)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Middle, East)),
+                        Vert(2),
+                        Vert(3)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.5, 0.88)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.618, 0.618))
+                    ]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.86, 0.32))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2),
+                    ]
+                }
+            ]
+        )
+        .with_art("inn_fortified_corner_city_curve_road.png", 0);
+        pub static ref INN_ROAD_MONASTARY: Tile = Tile::new(
+            vec![
+                vec2(0.3, 0.7),
+                vec2(0.3, 0.3),
+                vec2(0.5, 0.15),
+                vec2(0.7, 0.3),
+                vec2(0.7, 0.7),
+                vec2(0.55, 0.7),
+                vec2(0.45, 0.7)
+            ],
+            vec![
+                Segment {
+                    stype: Monastary,
+                    edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4),]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Edge((Beginning, South)),
+                        Vert(5),
+                        Vert(4),
+                        Vert(3),
+                        Vert(2),
+                        Vert(1),
+                        Vert(0),
+                        Vert(6),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.5))],
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(6), Vert(5),],
+                    attributes: vec![
+                        SegmentAttribute::Inn {
+                            inn_location: vec2(0.5, 0.9)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.77))
+                    ]
+                }
+            ]
+        )
+        .with_art("inn_road_monastary.png", 0);
+    }
+}
+
+pub mod traders_and_builders {
+    use ggez::glam::vec2;
+    use lazy_static::lazy_static;
+
+    use crate::tile::{
+        Orientation, SegmentAttribute, SegmentBorderPiece, SegmentDefinition, SegmentType, Tile,
+        TileEdgeSpan, TradeGood,
+    };
+
+    use Orientation::*;
+    use SegmentBorderPiece::*;
+    use SegmentDefinition::*;
+    use SegmentType::*;
+    use TileEdgeSpan::*;
+
+    lazy_static! {
+        pub static ref WINE_EDGE_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.3), vec2(0.65, 0.3),],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0),],
+                    attributes: vec![SegmentAttribute::Goods {
+                        good: TradeGood::Wine,
+                        goods_location: vec2(0.25, 0.1)
+                    }]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.65))]
+                },
+            ]
+        )
+        .with_art("wine_edge_city.png", 0);
+        pub static ref GRAIN_CORNER_CITY: Tile = Tile::new(
+            vec![],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, West)), Edge((Full, North))],
+                    attributes: vec![SegmentAttribute::Goods {
+                        good: TradeGood::Grain,
+                        goods_location: vec2(0.15, 0.15)
+                    }]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, East)), Edge((Full, South))]
+                }
+            ]
+        )
+        .with_art("grain_corner_city.png", 0);
+        pub static ref CLOTH_CORNER_CITY_CURVE_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.45, 0.7),
+                vec2(0.7, 0.45),
+                vec2(0.77071, 0.55),
+                vec2(0.55, 0.77071)
+            ],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, West)), Edge((Full, North))],
+                    attributes: vec![SegmentAttribute::Goods {
+                        good: TradeGood::Cloth,
+                        goods_location: vec2(0.15, 0.15)
+                    }]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, South)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Middle, East)),
+                        Vert(2),
+                        Vert(3)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.618, 0.618))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.86, 0.32))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2),
+                    ]
+                }
+            ]
+        )
+        .with_art("cloth_corner_city_curve_road.png", 0);
+        pub static ref WINE_THREE_QUARTER_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.7), vec2(0.65, 0.7),],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Goods {
+                            good: TradeGood::Wine,
+                            goods_location: vec2(0.2, 0.2)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.35))
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, South)), Vert(0), Vert(1)]
+                }
+            ]
+        )
+        .with_art("wine_three_quarter_city.png", 0);
+        pub static ref CLOTH_THREE_QUARTER_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.7), vec2(0.65, 0.7),],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Goods {
+                            good: TradeGood::Cloth,
+                            goods_location: vec2(0.2, 0.2)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.35))
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, South)), Vert(0), Vert(1)]
+                }
+            ]
+        )
+        .with_art("cloth_three_quarter_city.png", 0);
+        pub static ref GRAIN_OPPOSING_EDGE_CITIES: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+                vec2(0.35, 0.7),
+                vec2(0.65, 0.7)
+            ],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)],
+                    attributes: vec![SegmentAttribute::Goods {
+                        good: TradeGood::Grain,
+                        goods_location: vec2(0.25, 0.1)
+                    }]
+                },
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, South)), Vert(2), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Full, East)),
+                        Vert(3),
+                        Vert(2)
+                    ]
+                }
+            ]
+        )
+        .with_art("grain_opposing_edge_cities.png", 0);
+        pub static ref WINE_CORNER_CITY_CURVE_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.45, 0.7),
+                vec2(0.7, 0.45),
+                vec2(0.77071, 0.55),
+                vec2(0.55, 0.77071)
+            ],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, West)), Edge((Full, North))],
+                    attributes: vec![SegmentAttribute::Goods {
+                        good: TradeGood::Wine,
+                        goods_location: vec2(0.15, 0.15)
+                    }]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, South)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Middle, East)),
+                        Vert(2),
+                        Vert(3)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.618, 0.618))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.86, 0.32))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2),
+                    ]
+                }
+            ]
+        )
+        .with_art("wine_corner_city_curve_road.png", 0);
+        pub static ref WINE_EDGE_CITY_CROSSROADS: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.45),
+                vec2(0.65, 0.45),
+                vec2(0.65, 0.55),
+                vec2(0.55, 0.65),
+                vec2(0.45, 0.65),
+                vec2(0.35, 0.55),
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+            ],
+            vec![
+                Segment {
+                    stype: Village,
+                    edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4), Vert(5)]
+                },
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(7), Vert(6)],
+                    attributes: vec![SegmentAttribute::Goods {
+                        good: TradeGood::Wine,
+                        goods_location: vec2(0.25, 0.1)
+                    }]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Vert(6),
+                        Vert(7),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.315))]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, East)), Vert(2), Vert(1)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(4), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, West)),
+                        Vert(5),
+                        Vert(4)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Vert(0), Vert(5)]
+                }
+            ]
+        )
+        .with_art("wine_edge_city_crossroads.png", 0);
+        pub static ref WINE_BRIDGE_CITY: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+                vec2(0.35, 0.7),
+                vec2(0.65, 0.7)
+            ],
+            vec![
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, South)), Vert(2), Vert(3)]
+                },
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Full, East)),
+                        Vert(3),
+                        Vert(2)
+                    ],
+                    attributes: vec![SegmentAttribute::Goods {
+                        good: TradeGood::Wine,
+                        goods_location: vec2(0.15, 0.5)
+                    }]
+                }
+            ]
+        )
+        .with_art("wine_bridge_city.png", 0);
+        pub static ref WINE_THREE_QUARTER_CITY_ENTRANCE: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.7),
+                vec2(0.45, 0.7),
+                vec2(0.55, 0.7),
+                vec2(0.65, 0.7),
+            ],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Vert(3),
+                        Vert(0)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Goods {
+                            good: TradeGood::Wine,
+                            goods_location: vec2(0.2, 0.2)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.35))
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(1), Vert(2)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Beginning, South)), Vert(2), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, South)), Vert(0), Vert(1)]
+                }
+            ]
+        )
+        .with_art("wine_three_quarter_city_entrance.png", 0);
+        pub static ref GRAIN_THREE_QUARTER_CITY_ENTRANCE: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.7),
+                vec2(0.45, 0.7),
+                vec2(0.55, 0.7),
+                vec2(0.65, 0.7),
+            ],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Vert(3),
+                        Vert(0)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Goods {
+                            good: TradeGood::Grain,
+                            goods_location: vec2(0.2, 0.2)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.35))
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(1), Vert(2)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Beginning, South)), Vert(2), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, South)), Vert(0), Vert(1)]
+                }
+            ]
+        )
+        .with_art("grain_three_quarter_city_entrance.png", 0);
+        pub static ref GRAIN_EDGE_CITY_LEFT_CURVE_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+                vec2(0.45, 0.55),
+                vec2(0.55, 0.45)
+            ],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)],
+                    attributes: vec![SegmentAttribute::Goods {
+                        good: TradeGood::Grain,
+                        goods_location: vec2(0.25, 0.1)
+                    }]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, West)),
+                        Vert(3),
+                        Edge((Middle, South)),
+                        Vert(2)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Full, East)),
+                        Edge((Beginning, South)),
+                        Vert(3)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.75, 0.5))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, South)), Edge((Beginning, West)), Vert(2)]
+                }
+            ]
+        )
+        .with_art("grain_edge_city_left_curve_road.png", 0);
+        pub static ref CLOTH_BRIDGE_CITY: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+                vec2(0.35, 0.7),
+                vec2(0.65, 0.7)
+            ],
+            vec![
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, South)), Vert(2), Vert(3)]
+                },
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Full, East)),
+                        Vert(3),
+                        Vert(2)
+                    ],
+                    attributes: vec![SegmentAttribute::Goods {
+                        good: TradeGood::Cloth,
+                        goods_location: vec2(0.15, 0.5)
+                    }]
+                }
+            ]
+        )
+        .with_art("cloth_bridge_city.png", 0);
+        pub static ref CLOTH_EDGE_CITY_RIGHT_CURVE_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+                vec2(0.45, 0.45),
+                vec2(0.55, 0.55)
+            ],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)],
+                    attributes: vec![SegmentAttribute::Goods {
+                        good: TradeGood::Cloth,
+                        goods_location: vec2(0.25, 0.1)
+                    }]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, East)),
+                        Vert(3),
+                        Edge((Middle, South)),
+                        Vert(2)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Beginning, East)),
+                        Vert(2),
+                        Edge((End, South)),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.25, 0.5))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, East)), Edge((Beginning, South)), Vert(3)]
+                }
+            ]
+        )
+        .with_art("cloth_edge_city_right_curve_road.png", 0);
+        pub static ref EDGE_CITY_STRAIGHT_ROAD: Tile = Tile::new(
+            vec![vec2(0.35, 0.3), vec2(0.65, 0.3)],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Edge((Middle, East))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![Edge((End, West)), Vert(0), Vert(1), Edge((Beginning, East))],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.315))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Edge((End, East)),
+                        Edge((Full, South))
+                    ]
+                }
+            ]
+        )
+        .with_art("edge_city_straight_road.png", 0);
+        pub static ref VILLAGE_CROSSROADS: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.45),
+                vec2(0.65, 0.45),
+                vec2(0.65, 0.55),
+                vec2(0.55, 0.65),
+                vec2(0.45, 0.65),
+                vec2(0.35, 0.55)
+            ],
+            vec![
+                Segment {
+                    stype: Village,
+                    edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4), Vert(5)]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Full, North)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.25))]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, East)), Vert(2), Vert(1)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(4), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, West)),
+                        Vert(5),
+                        Vert(4)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Vert(0), Vert(5)]
+                }
+            ]
+        )
+        .with_art("village_crossroads.png", 0);
+    }
+}
+
+pub mod abbots_and_gardens {
+    use ggez::glam::vec2;
+    use lazy_static::lazy_static;
+
+    use crate::tile::{
+        Orientation, SegmentAttribute, SegmentBorderPiece, SegmentDefinition, SegmentType, Tile,
+        TileEdgeSpan,
+    };
+
+    use Orientation::*;
+    use SegmentBorderPiece::*;
+    use SegmentDefinition::*;
+    use SegmentType::*;
+    use TileEdgeSpan::*;
+
+    lazy_static! {
+        pub static ref GARDEN: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.25),
+                vec2(0.65, 0.25),
+                vec2(0.75, 0.35),
+                vec2(0.75, 0.65),
+                vec2(0.65, 0.75),
+                vec2(0.35, 0.75),
+                vec2(0.25, 0.65),
+                vec2(0.25, 0.35)
+            ],
+            vec![
+                Segment {
+                    stype: Garden,
+                    edges: vec![
+                        Vert(0),
+                        Vert(1),
+                        Vert(2),
+                        Vert(3),
+                        Vert(4),
+                        Vert(5),
+                        Vert(6),
+                        Vert(7),
+                    ]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Break,
+                        Vert(5),
+                        Vert(4),
+                        Vert(3),
+                        Vert(2),
+                        Vert(1),
+                        Vert(0),
+                        Vert(7),
+                        Vert(6),
+                        Vert(5),
+                        Break,
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.88, 0.5))],
+                }
+            ]
+        )
+        .with_art("garden.png", 0);
+        pub static ref GARDEN_EDGE_CITY: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+                vec2(0.4, 0.5),
+                vec2(0.6, 0.5),
+                vec2(0.7, 0.6),
+                vec2(0.7, 0.75),
+                vec2(0.6, 0.85),
+                vec2(0.4, 0.85),
+                vec2(0.3, 0.75),
+                vec2(0.3, 0.6)
+            ],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0),]
+                },
+                Segment {
+                    stype: Garden,
+                    edges: vec![
+                        Vert(2),
+                        Vert(3),
+                        Vert(4),
+                        Vert(5),
+                        Vert(6),
+                        Vert(7),
+                        Vert(8),
+                        Vert(9),
+                    ]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                        Break,
+                        Vert(2),
+                        Vert(9),
+                        Vert(8),
+                        Vert(7),
+                        Vert(6),
+                        Vert(5),
+                        Vert(4),
+                        Vert(3),
+                        Vert(2),
+                        Vert(1),
+                        Break,
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.15, 0.65))]
+                },
+            ]
+        )
+        .with_art("garden_edge_city.png", 0);
+        pub static ref GARDEN_STRAIGHT_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.4, 0.65),
+                vec2(0.6, 0.65),
+                vec2(0.68, 0.72),
+                vec2(0.68, 0.83),
+                vec2(0.6, 0.9),
+                vec2(0.4, 0.9),
+                vec2(0.32, 0.83),
+                vec2(0.32, 0.72)
+            ],
+            vec![
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Full, North)),
+                        Edge((Beginning, East))
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Edge((Middle, East))]
+                },
+                Segment {
+                    stype: Garden,
+                    edges: vec![
+                        Vert(0),
+                        Vert(1),
+                        Vert(2),
+                        Vert(3),
+                        Vert(4),
+                        Vert(5),
+                        Vert(6),
+                        Vert(7),
+                    ]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Edge((End, East)),
+                        Edge((Full, South)),
+                        Break,
+                        Vert(0),
+                        Vert(7),
+                        Vert(6),
+                        Vert(5),
+                        Vert(4),
+                        Vert(3),
+                        Vert(2),
+                        Vert(1),
+                        Vert(0),
+                        Break,
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.15, 0.75))]
+                }
+            ]
+        )
+        .with_art("garden_straight_road.png", 0);
+    }
+}
+
+pub mod princess_and_dragon {
+    use ggez::glam::vec2;
+    use lazy_static::lazy_static;
+
+    use crate::tile::{
+        Orientation, SegmentAttribute, SegmentBorderPiece, SegmentDefinition, SegmentType, Tile,
+        TileEdgeSpan,
+    };
+
+    use Orientation::*;
+    use SegmentBorderPiece::*;
+    use SegmentDefinition::*;
+    use SegmentType::*;
+    use TileEdgeSpan::*;
+
+    lazy_static! {
+        pub static ref VOLCANO: Tile = Tile::new(
+            vec![],
+            vec![SpecialSegment {
+                stype: Farm,
+                edges: vec![
+                    Edge((Full, West)),
+                    Edge((Full, North)),
+                    Edge((Full, East)),
+                    Edge((Full, South)),
+                ],
+                attributes: vec![
+                    SegmentAttribute::Volcano {
+                        volcano_location: vec2(0.5, 0.45)
+                    },
+                    SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.75))
+                ]
+            }]
+        )
+        .with_art("volcano.png", 0);
+        pub static ref VOLCANO_EDGE_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.3), vec2(0.65, 0.3),],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0),]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Volcano {
+                            volcano_location: vec2(0.5, 0.6)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.75))
+                    ]
+                },
+            ]
+        )
+        .with_art("volcano_edge_city.png", 0);
+        pub static ref DRAGON_STRAIGHT_ROAD: Tile = Tile::new(
+            vec![],
+            vec![
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Full, North)),
+                        Edge((Beginning, East))
+                    ],
+                    attributes: vec![SegmentAttribute::Dragon {
+                        dragon_location: vec2(0.7, 0.25)
+                    }]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Edge((Middle, East))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Edge((End, East)),
+                        Edge((Full, South))
+                    ]
+                }
+            ]
+        )
+        .with_art("dragon_straight_road.png", 0);
+        pub static ref DRAGON_EDGE_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.3), vec2(0.65, 0.3),],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0),],
+                    attributes: vec![SegmentAttribute::Dragon {
+                        dragon_location: vec2(0.25, 0.12)
+                    }]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.65))]
+                },
+            ]
+        )
+        .with_art("dragon_edge_city.png", 0);
+        pub static ref PORTAL_CURVE_ROAD: Tile = Tile::new(
+            vec![vec2(0.45, 0.45), vec2(0.55, 0.55)],
+            vec![
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, West)), Edge((Beginning, North)), Vert(0)]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, West)),
+                        Vert(0),
+                        Edge((Middle, North)),
+                        Vert(1)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Vert(1),
+                        Edge((End, North)),
+                        Edge((Full, East)),
+                        Edge((Full, South))
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Portal {
+                            portal_location: vec2(0.75, 0.7)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.8, 0.3))
+                    ]
+                }
+            ]
+        )
+        .with_art("portal_curve_road.png", 0);
+        pub static ref PRINCESS_EDGE_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.3), vec2(0.65, 0.3),],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0),],
+                    attributes: vec![SegmentAttribute::Princess {
+                        princess_location: vec2(0.75, 0.12)
+                    }]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.65))]
+                },
+            ]
+        )
+        .with_art("princess_edge_city.png", 0);
+        pub static ref PRINCESS_CORNER_CITY: Tile = Tile::new(
+            vec![],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Edge((Full, East))],
+                    attributes: vec![SegmentAttribute::Princess {
+                        princess_location: vec2(0.8, 0.2)
+                    }]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, South)), Edge((Full, West))]
+                }
+            ]
+        )
+        .with_art("princess_corner_city.png", 0);
+        pub static ref VOLCANO_STRAIGHT_ROAD: Tile = Tile::new(
+            vec![],
+            vec![
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Full, North)),
+                        Edge((Beginning, East))
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Volcano {
+                            volcano_location: vec2(0.5, 0.25)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.25))
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Edge((Middle, East))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Edge((End, East)),
+                        Edge((Full, South))
+                    ]
+                }
+            ]
+        )
+        .with_art("volcano_straight_road.png", 0);
+        pub static ref VOLCANO_CURVE_ROAD: Tile = Tile::new(
+            vec![vec2(0.45, 0.45), vec2(0.55, 0.55)],
+            vec![
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, West)), Edge((Beginning, North)), Vert(0)]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, West)),
+                        Vert(0),
+                        Edge((Middle, North)),
+                        Vert(1)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Vert(1),
+                        Edge((End, North)),
+                        Edge((Full, East)),
+                        Edge((Full, South))
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Volcano {
+                            volcano_location: vec2(0.75, 0.75)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.8, 0.35))
+                    ]
+                }
+            ]
+        )
+        .with_art("volcano_curve_road.png", 0);
+        pub static ref DRAGON_CURVE_ROAD: Tile = Tile::new(
+            vec![vec2(0.45, 0.45), vec2(0.55, 0.55)],
+            vec![
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, West)), Edge((Beginning, North)), Vert(0)]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, West)),
+                        Vert(0),
+                        Edge((Middle, North)),
+                        Vert(1)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Vert(1),
+                        Edge((End, North)),
+                        Edge((Full, East)),
+                        Edge((Full, South))
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Dragon {
+                            dragon_location: vec2(0.75, 0.75)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.8, 0.35))
+                    ]
+                }
+            ]
+        )
+        .with_art("dragon_curve_road.png", 0);
+        pub static ref DRAGON_CROSSROADS: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.45),
+                vec2(0.65, 0.45),
+                vec2(0.65, 0.55),
+                vec2(0.55, 0.65),
+                vec2(0.45, 0.65),
+                vec2(0.35, 0.55)
+            ],
+            vec![
+                Segment {
+                    stype: Village,
+                    edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4), Vert(5)]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Full, North)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Dragon {
+                            dragon_location: vec2(0.75, 0.2)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.25))
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, East)), Vert(2), Vert(1)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(4), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, West)),
+                        Vert(5),
+                        Vert(4)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Vert(0), Vert(5)]
+                }
+            ]
+        )
+        .with_art("dragon_crossroads.png", 0);
+        pub static ref DRAGON_CORNER_CITY: Tile = Tile::new(
+            vec![],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, West)), Edge((Full, North))],
+                    attributes: vec![SegmentAttribute::Dragon {
+                        dragon_location: vec2(0.2, 0.2)
+                    }]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, East)), Edge((Full, South))]
+                }
+            ]
+        )
+        .with_art("dragon_corner_city.png", 0);
+        pub static ref DRAGON_THREE_QUARTER_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.7), vec2(0.65, 0.7),],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Dragon {
+                            dragon_location: vec2(0.2, 0.2)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.35))
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, South)), Vert(0), Vert(1)]
+                }
+            ]
+        )
+        .with_art("dragon_three_quarter_city.png", 0);
+        pub static ref DRAGON_MONASTARY: Tile = Tile::new(
+            vec![
+                vec2(0.3, 0.7),
+                vec2(0.3, 0.3),
+                vec2(0.5, 0.15),
+                vec2(0.7, 0.3),
+                vec2(0.7, 0.7)
+            ],
+            vec![
+                Segment {
+                    stype: Monastary,
+                    edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4),]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Break,
+                        Vert(0),
+                        Vert(4),
+                        Vert(3),
+                        Vert(2),
+                        Vert(1),
+                        Vert(0),
+                        Break,
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Dragon {
+                            dragon_location: vec2(0.5, 0.85)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.5))
+                    ],
+                }
+            ]
+        )
+        .with_art("dragon_monastary.png", 0);
+        pub static ref DRAGON_BRIDGE_CITY: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+                vec2(0.35, 0.7),
+                vec2(0.65, 0.7)
+            ],
+            vec![
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, South)), Vert(2), Vert(3)]
+                },
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Full, East)),
+                        Vert(3),
+                        Vert(2)
+                    ],
+                    attributes: vec![SegmentAttribute::Dragon {
+                        dragon_location: vec2(0.15, 0.5)
+                    }]
+                }
+            ]
+        )
+        .with_art("dragon_bridge_city.png", 0);
+        pub static ref DRAGON_EDGE_CITY_CROSSROADS: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.45),
+                vec2(0.65, 0.45),
+                vec2(0.65, 0.55),
+                vec2(0.55, 0.65),
+                vec2(0.45, 0.65),
+                vec2(0.35, 0.55),
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+            ],
+            vec![
+                Segment {
+                    stype: Village,
+                    edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4), Vert(5)]
+                },
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(7), Vert(6)],
+                    attributes: vec![SegmentAttribute::Dragon {
+                        dragon_location: vec2(0.25, 0.12)
+                    }]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Vert(6),
+                        Vert(7),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.315))]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, East)), Vert(2), Vert(1)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(4), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, West)),
+                        Vert(5),
+                        Vert(4)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Vert(0), Vert(5)]
+                }
+            ]
+        )
+        .with_art("dragon_edge_city_crossroads.png", 0);
+        pub static ref PORTAL_EDGE_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.3), vec2(0.65, 0.3),],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0),]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Portal {
+                            portal_location: vec2(0.5, 0.6)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.75))
+                    ]
+                },
+            ]
+        )
+        .with_art("portal_edge_city.png", 0);
+        pub static ref PRINCESS_THREE_QUARTER_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.7), vec2(0.65, 0.7),],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Princess {
+                            princess_location: vec2(0.8, 0.2)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.35))
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, South)), Vert(0), Vert(1)]
+                }
+            ]
+        )
+        .with_art("princess_three_quarter_city.png", 0);
+        pub static ref PRINCESS_BRIDGE_CITY: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+                vec2(0.35, 0.7),
+                vec2(0.65, 0.7)
+            ],
+            vec![
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, South)), Vert(2), Vert(3)]
+                },
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Full, East)),
+                        Vert(3),
+                        Vert(2)
+                    ],
+                    attributes: vec![SegmentAttribute::Princess {
+                        princess_location: vec2(0.85, 0.5)
+                    }]
+                }
+            ]
+        )
+        .with_art("princess_bridge_city.png", 0);
+        pub static ref PRINCESS_CORNER_CITY_CURVE_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.45, 0.7),
+                vec2(0.7, 0.45),
+                vec2(0.77071, 0.55),
+                vec2(0.55, 0.77071)
+            ],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![Edge((Full, West)), Edge((Full, North))],
+                    attributes: vec![SegmentAttribute::Princess {
+                        princess_location: vec2(0.15, 0.15)
+                    }]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, South)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Middle, East)),
+                        Vert(2),
+                        Vert(3)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.618, 0.618))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.86, 0.32))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2),
+                    ]
+                }
+            ]
+        )
+        .with_art("princess_corner_city_curve_road.png", 0);
+    }
+}
+
+pub mod tower {
+    use ggez::glam::vec2;
+    use lazy_static::lazy_static;
+
+    use crate::tile::{
+        Orientation, SegmentAttribute, SegmentBorderPiece, SegmentDefinition, SegmentType, Tile,
+        TileEdgeSpan,
+    };
+
+    use Orientation::*;
+    use SegmentBorderPiece::*;
+    use SegmentDefinition::*;
+    use SegmentType::*;
+    use TileEdgeSpan::*;
+
+    lazy_static! {
+        pub static ref TOWER_FARM: Tile = Tile::new(
+            vec![],
+            vec![SpecialSegment {
+                stype: Farm,
+                edges: vec![
+                    Edge((Full, West)),
+                    Edge((Full, North)),
+                    Edge((Full, East)),
+                    Edge((Full, South)),
+                ],
+                attributes: vec![
+                    SegmentAttribute::TowerFoundation {
+                        tower_location: vec2(0.5, 0.45)
+                    },
+                    SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.75))
+                ]
+            }]
+        )
+        .with_art("tower_farm.png", 0);
+        pub static ref TOWER_STRAIGHT_ROAD: Tile = Tile::new(
+            vec![],
+            vec![
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Full, North)),
+                        Edge((Beginning, East))
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::TowerFoundation {
+                            tower_location: vec2(0.5, 0.25)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.25))
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Edge((Middle, East))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Edge((End, East)),
+                        Edge((Full, South))
+                    ]
+                }
+            ]
+        )
+        .with_art("tower_straight_road.png", 0);
+        pub static ref TOWER_CURVE_ROAD: Tile = Tile::new(
+            vec![vec2(0.45, 0.45), vec2(0.55, 0.55)],
+            vec![
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, West)), Edge((Beginning, North)), Vert(0)]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, West)),
+                        Vert(0),
+                        Edge((Middle, North)),
+                        Vert(1)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Vert(1),
+                        Edge((End, North)),
+                        Edge((Full, East)),
+                        Edge((Full, South))
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::TowerFoundation {
+                            tower_location: vec2(0.75, 0.75)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.8, 0.35))
+                    ]
+                }
+            ]
+        )
+        .with_art("tower_curve_road.png", 0);
+        pub static ref TOWER_EDGE_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.3), vec2(0.65, 0.3),],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0),]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::TowerFoundation {
+                            tower_location: vec2(0.5, 0.6)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.75))
+                    ]
+                },
+            ]
+        )
+        .with_art("tower_edge_city.png", 0);
+        pub static ref TOWER_CROSSROADS: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.45),
+                vec2(0.65, 0.45),
+                vec2(0.65, 0.55),
+                vec2(0.55, 0.65),
+                vec2(0.45, 0.65),
+                vec2(0.35, 0.55)
+            ],
+            vec![
+                Segment {
+                    stype: Village,
+                    edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4), Vert(5)]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Full, North)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::TowerFoundation {
+                            tower_location: vec2(0.2, 0.2)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.25))
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, East)), Vert(2), Vert(1)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(4), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, West)),
+                        Vert(5),
+                        Vert(4)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Vert(0), Vert(5)]
+                }
+            ]
+        )
+        .with_art("tower_crossroads.png", 0);
+        pub static ref TOWER_FOUR_WAY_CROSSROADS: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.45),
+                vec2(0.45, 0.35),
+                vec2(0.55, 0.35),
+                vec2(0.65, 0.45),
+                vec2(0.65, 0.55),
+                vec2(0.55, 0.65),
+                vec2(0.45, 0.65),
+                vec2(0.35, 0.55)
+            ],
+            vec![
+                Segment {
+                    stype: Village,
+                    edges: (0..=7).map(Vert).collect()
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Beginning, North)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::TowerFoundation {
+                        tower_location: vec2(0.2, 0.2)
+                    }]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, North)), Vert(2), Vert(1)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, North)),
+                        Edge((Beginning, East)),
+                        Vert(3),
+                        Vert(2)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, East)), Vert(4), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(5),
+                        Vert(4)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(6), Vert(5)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, West)),
+                        Vert(7),
+                        Vert(6)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Vert(0), Vert(7)]
+                }
+            ]
+        )
+        .with_art("tower_four_way_crossroads.png", 0);
+        pub static ref TOWER_CORNER_CITY: Tile = Tile::new(
+            vec![],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, West)), Edge((Full, North))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, East)), Edge((Full, South))],
+                    attributes: vec![SegmentAttribute::TowerFoundation {
+                        tower_location: vec2(0.75, 0.75)
+                    }]
+                }
+            ]
+        )
+        .with_art("tower_corner_city.png", 0);
+        pub static ref TOWER_CORNER_CITY_CURVE_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.45, 0.7),
+                vec2(0.7, 0.45),
+                vec2(0.77071, 0.55),
+                vec2(0.55, 0.77071)
+            ],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, West)), Edge((Full, North))],
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, South)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Middle, East)),
+                        Vert(2),
+                        Vert(3)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.618, 0.618))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.86, 0.32))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2),
+                    ],
+                    attributes: vec![SegmentAttribute::TowerFoundation {
+                        tower_location: vec2(0.85, 0.85)
+                    }]
+                }
+            ]
+        )
+        .with_art("tower_corner_city_curve_road.png", 0);
+        pub static ref TOWER_EDGE_CITY_STRAIGHT_ROAD: Tile = Tile::new(
+            vec![vec2(0.35, 0.3), vec2(0.65, 0.3)],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Edge((Middle, East))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![Edge((End, West)), Vert(0), Vert(1), Edge((Beginning, East))],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.315))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Edge((End, East)),
+                        Edge((Full, South))
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::TowerFoundation {
+                            tower_location: vec2(0.5, 0.75)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.75))
+                    ]
+                }
+            ]
+        )
+        .with_art("tower_edge_city_straight_road.png", 0);
+        pub static ref TOWER_EDGE_CITY_LEFT_CURVE_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+                vec2(0.45, 0.55),
+                vec2(0.55, 0.45)
+            ],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, West)),
+                        Vert(3),
+                        Edge((Middle, South)),
+                        Vert(2)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Full, East)),
+                        Edge((Beginning, South)),
+                        Vert(3)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::TowerFoundation {
+                            tower_location: vec2(0.75, 0.75)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.75, 0.5))
+                    ]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, South)), Edge((Beginning, West)), Vert(2)]
+                }
+            ]
+        )
+        .with_art("tower_edge_city_left_curve_road.png", 0);
+        pub static ref TOWER_OPPOSING_EDGE_CITIES: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.3),
+                vec2(0.65, 0.3),
+                vec2(0.35, 0.7),
+                vec2(0.65, 0.7)
+            ],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+                },
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, South)), Vert(2), Vert(3)]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Full, East)),
+                        Vert(3),
+                        Vert(2)
+                    ],
+                    attributes: vec![SegmentAttribute::TowerFoundation {
+                        tower_location: vec2(0.15, 0.5)
+                    }]
+                }
+            ]
+        )
+        .with_art("tower_opposing_edge_cities.png", 0);
+        pub static ref TOWER_THREE_QUARTER_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.7), vec2(0.65, 0.7),],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.35))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, South)), Vert(0), Vert(1)],
+                    attributes: vec![SegmentAttribute::TowerFoundation {
+                        tower_location: vec2(0.5, 0.88)
+                    }]
+                }
+            ]
+        )
+        .with_art("tower_three_quarter_city.png", 0);
+        pub static ref TOWER_MONASTARY: Tile = Tile::new(
+            vec![
+                vec2(0.3, 0.7),
+                vec2(0.3, 0.3),
+                vec2(0.5, 0.15),
+                vec2(0.7, 0.3),
+                vec2(0.7, 0.7)
+            ],
+            vec![
+                Segment {
+                    stype: Monastary,
+                    edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4),]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Break,
+                        Vert(0),
+                        Vert(4),
+                        Vert(3),
+                        Vert(2),
+                        Vert(1),
+                        Vert(0),
+                        Break,
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::TowerFoundation {
+                            tower_location: vec2(0.5, 0.85)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.5))
+                    ],
+                }
+            ]
+        )
+        .with_art("tower_monastary.png", 0);
+    }
+}
+
+pub mod hills_and_sheep {
+    use ggez::glam::vec2;
+    use lazy_static::lazy_static;
+
+    use crate::tile::{
+        Orientation, SegmentAttribute, SegmentBorderPiece, SegmentDefinition, SegmentType, Tile,
+        TileEdgeSpan,
+    };
+
+    use Orientation::*;
+    use SegmentBorderPiece::*;
+    use SegmentDefinition::*;
+    use SegmentType::*;
+    use TileEdgeSpan::*;
+
+    lazy_static! {
+        pub static ref HILL_STRAIGHT_ROAD: Tile = Tile::new(
+            vec![],
+            vec![
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Full, North)),
+                        Edge((Beginning, East))
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Hill {
+                            hill_location: vec2(0.5, 0.25)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.25))
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Edge((Middle, East))]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Edge((End, East)),
+                        Edge((Full, South))
+                    ]
+                }
+            ]
+        )
+        .with_art("hill_straight_road.png", 0);
+        pub static ref HILL_CURVE_ROAD: Tile = Tile::new(
+            vec![vec2(0.45, 0.45), vec2(0.55, 0.55)],
+            vec![
+                Segment {
+                    stype: Farm,
+                    edges: vec![Edge((End, West)), Edge((Beginning, North)), Vert(0)]
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, West)),
+                        Vert(0),
+                        Edge((Middle, North)),
+                        Vert(1)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.5))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Vert(1),
+                        Edge((End, North)),
+                        Edge((Full, East)),
+                        Edge((Full, South))
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Hill {
+                            hill_location: vec2(0.75, 0.75)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.8, 0.35))
+                    ]
+                }
+            ]
+        )
+        .with_art("hill_curve_road.png", 0);
+        pub static ref HILL_EDGE_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.3), vec2(0.65, 0.3),],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0),]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Edge((Full, West)),
+                        Vert(0),
+                        Vert(1),
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Hill {
+                            hill_location: vec2(0.5, 0.6)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.75))
+                    ]
+                },
+            ]
+        )
+        .with_art("hill_edge_city.png", 0);
+        pub static ref HILL_FARM: Tile = Tile::new(
+            vec![],
+            vec![SpecialSegment {
+                stype: Farm,
+                edges: vec![
+                    Edge((Full, West)),
+                    Edge((Full, North)),
+                    Edge((Full, East)),
+                    Edge((Full, South)),
+                ],
+                attributes: vec![
+                    SegmentAttribute::Hill {
+                        hill_location: vec2(0.5, 0.45)
+                    },
+                    SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.75))
+                ]
+            }]
+        )
+        .with_art("hill_farm.png", 0);
+        pub static ref HILL_CROSSROADS: Tile = Tile::new(
+            vec![
+                vec2(0.35, 0.45),
+                vec2(0.65, 0.45),
+                vec2(0.65, 0.55),
+                vec2(0.55, 0.65),
+                vec2(0.45, 0.65),
+                vec2(0.35, 0.55)
+            ],
+            vec![
+                Segment {
+                    stype: Village,
+                    edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4), Vert(5)]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, West)),
+                        Edge((Full, North)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Hill {
+                            hill_location: vec2(0.2, 0.2)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.25))
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, East)), Vert(2), Vert(1)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, South)), Vert(4), Vert(3)]
+                },
+                Segment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, West)),
+                        Vert(5),
+                        Vert(4)
+                    ]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Vert(0), Vert(5)]
+                }
+            ]
+        )
+        .with_art("hill_crossroads.png", 0);
+        pub static ref HILL_CORNER_CITY: Tile = Tile::new(
+            vec![],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, West)), Edge((Full, North))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, East)), Edge((Full, South))],
+                    attributes: vec![SegmentAttribute::Hill {
+                        hill_location: vec2(0.75, 0.75)
+                    }]
+                }
+            ]
+        )
+        .with_art("hill_corner_city.png", 0);
+        pub static ref HILL_CORNER_CITY_CURVE_ROAD: Tile = Tile::new(
+            vec![
+                vec2(0.45, 0.7),
+                vec2(0.7, 0.45),
+                vec2(0.77071, 0.55),
+                vec2(0.55, 0.77071)
+            ],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, West)), Edge((Full, North))],
+                },
+                SpecialSegment {
+                    stype: Road,
+                    edges: vec![
+                        Edge((Middle, South)),
+                        Vert(0),
+                        Vert(1),
+                        Edge((Middle, East)),
+                        Vert(2),
+                        Vert(3)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.618, 0.618))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, South)),
+                        Edge((Beginning, East)),
+                        Vert(1),
+                        Vert(0),
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.86, 0.32))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((End, East)),
+                        Edge((Beginning, South)),
+                        Vert(3),
+                        Vert(2),
+                    ],
+                    attributes: vec![SegmentAttribute::Hill {
+                        hill_location: vec2(0.85, 0.85)
+                    }]
+                }
+            ]
+        )
+        .with_art("hill_corner_city_curve_road.png", 0);
+        pub static ref HILL_EDGE_CITY_STRAIGHT_ROAD: Tile = Tile::new(
+            vec![vec2(0.35, 0.3), vec2(0.65, 0.3)],
+            vec![
+                Segment {
+                    stype: City,
+                    edges: vec![Edge((Full, North)), Vert(1), Vert(0)]
+                },
+                Segment {
+                    stype: Road,
+                    edges: vec![Edge((Middle, West)), Edge((Middle, East))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![Edge((End, West)), Vert(0), Vert(1), Edge((Beginning, East))],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.315))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Beginning, West)),
+                        Edge((End, East)),
+                        Edge((Full, South))
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Hill {
+                            hill_location: vec2(0.5, 0.75)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.2, 0.75))
+                    ]
+                }
+            ]
+        )
+        .with_art("hill_edge_city_straight_road.png", 0);
+        pub static ref HILL_THREE_QUARTER_CITY: Tile = Tile::new(
+            vec![vec2(0.35, 0.7), vec2(0.65, 0.7),],
+            vec![
+                SpecialSegment {
+                    stype: City,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Vert(1),
+                        Vert(0)
+                    ],
+                    attributes: vec![SegmentAttribute::CustomMeepleSpot(vec2(0.5, 0.35))]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![Edge((Full, South)), Vert(0), Vert(1)],
+                    attributes: vec![SegmentAttribute::Hill {
+                        hill_location: vec2(0.5, 0.88)
+                    }]
+                }
+            ]
+        )
+        .with_art("hill_three_quarter_city.png", 0);
+        pub static ref HILL_MONASTARY: Tile = Tile::new(
+            vec![
+                vec2(0.3, 0.7),
+                vec2(0.3, 0.3),
+                vec2(0.5, 0.15),
+                vec2(0.7, 0.3),
+                vec2(0.7, 0.7)
+            ],
+            vec![
+                Segment {
+                    stype: Monastary,
+                    edges: vec![Vert(0), Vert(1), Vert(2), Vert(3), Vert(4),]
+                },
+                SpecialSegment {
+                    stype: Farm,
+                    edges: vec![
+                        Edge((Full, West)),
+                        Edge((Full, North)),
+                        Edge((Full, East)),
+                        Edge((Full, South)),
+                        Break,
+                        Vert(0),
+                        Vert(4),
+                        Vert(3),
+                        Vert(2),
+                        Vert(1),
+                        Vert(0),
+                        Break,
+                    ],
+                    attributes: vec![
+                        SegmentAttribute::Hill {
+                            hill_location: vec2(0.5, 0.85)
+                        },
+                        SegmentAttribute::CustomMeepleSpot(vec2(0.85, 0.5))
+                    ],
+                }
+            ]
+        )
+        .with_art("hill_monastary.png", 0);
+    }
 }
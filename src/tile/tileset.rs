@@ -0,0 +1,148 @@
+use std::{
+    fs::{create_dir_all, read_dir, File},
+    path::{Path, PathBuf},
+};
+
+use ggez::{GameError, GameResult};
+use ggez_no_re::{line::Line, util::ResultExtToGameError};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    texture_pack::TileArt, validation::definition_defects, SegmentDefinition, Tile, TileAttribute,
+};
+
+/// A tile described in a tileset file, laid out the same way as the definitions in
+/// `tile_definitions`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TileDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub verts: Line,
+    pub segments: Vec<SegmentDefinition>,
+    #[serde(default)]
+    pub attributes: Vec<TileAttribute>,
+    /// How many copies of the tile go in the library.
+    pub count: usize,
+    /// The image drawn for the tile when a texture pack is selected.
+    #[serde(default)]
+    pub art: Option<TileArt>,
+}
+
+impl TileDefinition {
+    /// Checks what building the tile relies on, see [`definition_defects`]. Anything else
+    /// wrong with the tile is left to [`Tile::validate`].
+    pub fn check(&self) -> Result<(), String> {
+        match definition_defects(&self.verts, &self.segments).first() {
+            Some(defect) => Err(defect.to_string()),
+            None => Ok(()),
+        }
+    }
+
+    pub fn tile(&self) -> Tile {
+        let mut tile = Tile::new_with_attributes(
+            self.verts.clone(),
+            self.segments.clone(),
+            self.attributes.clone(),
+        );
+        tile.art = self.art.clone();
+        tile
+    }
+}
+
+/// A set of tiles loaded at runtime, which can be added to a game like any other expansion.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Tileset {
+    pub expansion: String,
+    pub tiles: Vec<TileDefinition>,
+}
+
+impl Tileset {
+    /// Checks that every tile in the set can be built, see [`TileDefinition::check`].
+    pub fn check(&self) -> Result<(), String> {
+        self.tiles.iter().try_for_each(|definition| {
+            definition
+                .check()
+                .map_err(|err| format!("tile '{}' in '{}': {err}", definition.name, self.expansion))
+        })
+    }
+
+    pub fn library_tallies(&self) -> Vec<(Tile, usize)> {
+        self.tiles
+            .iter()
+            .map(|definition| (definition.tile(), definition.count))
+            .collect()
+    }
+
+    pub fn library(&self) -> Vec<Tile> {
+        self.library_tallies()
+            .into_iter()
+            .flat_map(|(tile, count)| (0..count).map(move |_| tile.clone()))
+            .collect()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> GameResult<Tileset> {
+        let tileset = Tileset::read(path)?;
+        tileset.check().map_err(GameError::CustomError)?;
+        Ok(tileset)
+    }
+
+    /// Reads the tileset without checking that its tiles can be built, for reporting
+    /// everything wrong with them.
+    pub fn read(path: impl AsRef<Path>) -> GameResult<Tileset> {
+        let file = File::open(path)?;
+        serde_json::from_reader(file).to_gameerror()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> GameResult<()> {
+        if let Some(parent) = path.as_ref().parent() {
+            create_dir_all(parent)?;
+        }
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(file, self).to_gameerror()
+    }
+
+    /// Loads every `.json` tileset in the directory, skipping any that fail to parse or
+    /// have tiles that can't be built.
+    pub fn load_dir(path: impl AsRef<Path>) -> Vec<Tileset> {
+        Tileset::load_dir_with(path, Tileset::load)
+    }
+
+    /// Reads every `.json` tileset in the directory, skipping any that fail to parse, but
+    /// not those with tiles that can't be built.
+    pub fn read_dir(path: impl AsRef<Path>) -> Vec<Tileset> {
+        Tileset::load_dir_with(path, Tileset::read)
+    }
+
+    fn load_dir_with(
+        path: impl AsRef<Path>,
+        load: impl Fn(PathBuf) -> GameResult<Tileset>,
+    ) -> Vec<Tileset> {
+        let Ok(entries) = read_dir(path.as_ref()) else {
+            debug!("no tilesets found at {}", path.as_ref().display());
+            return Vec::new();
+        };
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .filter_map(|path| match load(path.clone()) {
+                Ok(tileset) => {
+                    debug!(
+                        "loaded tileset '{}' from {}",
+                        tileset.expansion,
+                        path.display()
+                    );
+                    Some(tileset)
+                }
+                Err(err) => {
+                    warn!("failed to load tileset {}: {err}", path.display());
+                    None
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,447 @@
+use ggez::glam::Vec2;
+use ggez_no_re::util::point_in_polygon;
+
+use crate::game::{setup::GameExpansions, SegmentIndex};
+
+use super::{
+    tileset::{TileDefinition, Tileset},
+    Orientation, SegmentBorderPiece, SegmentDefinition, Tile, TileEdge, TileEdgeSpan,
+    MOUNTS_PER_SIDE,
+};
+
+/// Leeway given to coordinates and areas, since vertices are written out by hand.
+const EPSILON: f32 = 1e-4;
+
+/// A mistake in a tile's definition, found by [`Tile::validate`] or
+/// [`definition_defects`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum TileDefect {
+    NoSegments,
+    /// The segment's edges are all breaks, leaving nothing to outline it with.
+    EmptySegment(SegmentIndex),
+    MissingVertex {
+        segment: SegmentIndex,
+        vertex: usize,
+        verts: usize,
+    },
+    /// The segment's polygon has fewer than three corners, or repeats a corner.
+    DegeneratePolygon(SegmentIndex),
+    SelfIntersectingPolygon(SegmentIndex),
+    OverlappingSegments(SegmentIndex, SegmentIndex),
+    /// The segments don't cover the tile exactly once, leaving gaps or overlapping.
+    IncompleteCoverage {
+        area: f32,
+    },
+    /// The segment mounted on part of a side doesn't have an edge along it.
+    InconsistentMount {
+        orientation: Orientation,
+        slot: usize,
+    },
+    /// Part of a side is bordered by no segment, or by more than one.
+    EdgeCoverage {
+        orientation: Orientation,
+        slot: usize,
+        segments: usize,
+    },
+    InconsistentEdgeVerts(TileEdge),
+    AsymmetricAdjacency(SegmentIndex, SegmentIndex),
+    MeepleSpotOutside(SegmentIndex),
+}
+
+impl std::fmt::Display for TileDefect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use TileDefect::*;
+        match self {
+            NoSegments => write!(f, "the tile has no segments"),
+            EmptySegment(seg_index) => write!(f, "segment {seg_index} has no outline"),
+            MissingVertex {
+                segment,
+                vertex,
+                verts,
+            } => write!(
+                f,
+                "segment {segment} refers to vertex {vertex}, but there are only {verts}"
+            ),
+            DegeneratePolygon(seg_index) => {
+                write!(f, "segment {seg_index} has a degenerate polygon")
+            }
+            SelfIntersectingPolygon(seg_index) => {
+                write!(f, "segment {seg_index}'s polygon intersects itself")
+            }
+            OverlappingSegments(a, b) => write!(f, "segments {a} and {b} overlap"),
+            IncompleteCoverage { area } => {
+                write!(f, "segments cover an area of {area:.4} rather than 1")
+            }
+            InconsistentMount { orientation, slot } => write!(
+                f,
+                "segment mounted at {orientation:?} slot {slot} has no edge there"
+            ),
+            EdgeCoverage {
+                orientation,
+                slot,
+                segments,
+            } => write!(
+                f,
+                "{orientation:?} slot {slot} is bordered by {segments} segments rather than 1"
+            ),
+            InconsistentEdgeVerts(edge) => {
+                write!(f, "vertices for edge {edge:?} don't match its segment")
+            }
+            AsymmetricAdjacency(a, b) => {
+                write!(
+                    f,
+                    "segment {a} is adjacent to segment {b}, but not the reverse"
+                )
+            }
+            MeepleSpotOutside(seg_index) => {
+                write!(
+                    f,
+                    "segment {seg_index}'s meeple spot lies outside its polygon"
+                )
+            }
+        }
+    }
+}
+
+/// Checks what building a tile from the definitions relies on: that there are segments,
+/// that each of them has an outline, and that the vertices they're outlined with exist.
+pub fn definition_defects(verts: &[Vec2], segments: &[SegmentDefinition]) -> Vec<TileDefect> {
+    if segments.is_empty() {
+        return vec![TileDefect::NoSegments];
+    }
+    let mut defects = Vec::new();
+    for (seg_index, segment) in segments.iter().enumerate() {
+        let edges = segment.edges();
+        if edges
+            .iter()
+            .all(|piece| matches!(piece, SegmentBorderPiece::Break))
+        {
+            defects.push(TileDefect::EmptySegment(seg_index));
+        }
+        for piece in edges {
+            if let SegmentBorderPiece::Vert(vertex) = *piece {
+                if vertex >= verts.len() {
+                    defects.push(TileDefect::MissingVertex {
+                        segment: seg_index,
+                        vertex,
+                        verts: verts.len(),
+                    });
+                }
+            }
+        }
+    }
+    defects
+}
+
+impl TileDefinition {
+    /// Checks the definition, then the tile built from it in each of its rotations. Defects
+    /// that keep the tile from being built are returned as found in rotation 0.
+    pub fn validate(&self) -> Vec<(usize, TileDefect)> {
+        let defects = definition_defects(&self.verts, &self.segments);
+        if defects.is_empty() {
+            self.tile().validate()
+        } else {
+            defects.into_iter().map(|defect| (0, defect)).collect()
+        }
+    }
+}
+
+impl Tile {
+    /// Checks the tile in each of its rotations, returning every defect found alongside
+    /// the rotation it was found in.
+    pub fn validate(&self) -> Vec<(usize, TileDefect)> {
+        let mut tile = self.clone();
+        let mut defects = Vec::new();
+        for _ in 0..4 {
+            defects.extend(
+                tile.rotation_defects()
+                    .into_iter()
+                    .map(|defect| (tile.rotation, defect)),
+            );
+            tile.rotate_clockwise();
+        }
+        defects
+    }
+
+    fn rotation_defects(&self) -> Vec<TileDefect> {
+        let mut defects = Vec::new();
+        let mut area = 0.0;
+        let mut polygons = Vec::new();
+        for (seg_index, segment) in self.segments.iter().enumerate() {
+            let polygon: Vec<Vec2> = self.segment_polygon(seg_index).collect();
+            let n = polygon.len();
+            if n < 3 || (0..n).any(|i| polygon[i].distance(polygon[(i + 1) % n]) < EPSILON) {
+                defects.push(TileDefect::DegeneratePolygon(seg_index));
+                continue;
+            }
+            if polygon_self_intersects(&polygon) {
+                defects.push(TileDefect::SelfIntersectingPolygon(seg_index));
+            }
+            area += polygon_area(&polygon).abs();
+            if !point_in_polygon(segment.meeple_spot, &polygon) {
+                defects.push(TileDefect::MeepleSpotOutside(seg_index));
+            }
+            polygons.push((seg_index, polygon));
+        }
+        // overlaps and gaps can cancel out in the total area, so check each pair as well
+        for (i, (a, polygon_a)) in polygons.iter().enumerate() {
+            for (b, polygon_b) in &polygons[i + 1..] {
+                if polygons_overlap(polygon_a, polygon_b) {
+                    defects.push(TileDefect::OverlappingSegments(*a, *b));
+                }
+            }
+        }
+        if (area - 1.0).abs() > EPSILON {
+            defects.push(TileDefect::IncompleteCoverage { area });
+        }
+
+        for (orientation, _) in Orientation::iter_with_offsets() {
+            let mount = self.mounts.by_orientation(orientation);
+            for slot in 0..MOUNTS_PER_SIDE {
+                let borders_slot = |seg_index: SegmentIndex| {
+                    self.segments[seg_index]
+                        .edge_definition
+                        .iter()
+                        .filter(|piece| match piece {
+                            SegmentBorderPiece::Edge((span, edge_orientation)) => {
+                                *edge_orientation == orientation && span_covers(*span, slot)
+                            }
+                            _ => false,
+                        })
+                        .count()
+                };
+                if borders_slot(mount[slot]) == 0 {
+                    defects.push(TileDefect::InconsistentMount { orientation, slot });
+                }
+                let segments = (0..self.segments.len()).map(borders_slot).sum();
+                if segments != 1 {
+                    defects.push(TileDefect::EdgeCoverage {
+                        orientation,
+                        slot,
+                        segments,
+                    });
+                }
+            }
+        }
+
+        for segment in &self.segments {
+            for piece in &segment.edge_definition {
+                let SegmentBorderPiece::Edge(edge) = piece else {
+                    continue;
+                };
+                let consistent = self.edge_verts_map.get(edge).is_some_and(|&[a, b]| {
+                    let on_side = |i: usize| {
+                        let vert = self.verts[i];
+                        match edge.1 {
+                            Orientation::North => vert.y.abs() < EPSILON,
+                            Orientation::East => (vert.x - 1.0).abs() < EPSILON,
+                            Orientation::South => (vert.y - 1.0).abs() < EPSILON,
+                            Orientation::West => vert.x.abs() < EPSILON,
+                        }
+                    };
+                    let n = segment.poly.len();
+                    let in_poly =
+                        (0..n).any(|i| segment.poly[i] == a && segment.poly[(i + 1) % n] == b);
+                    a != b && on_side(a) && on_side(b) && in_poly
+                });
+                if !consistent {
+                    defects.push(TileDefect::InconsistentEdgeVerts(*edge));
+                }
+            }
+        }
+
+        let n = self.segments.len();
+        for a in 0..n {
+            for b in 0..n {
+                if self.segment_adjacency[a * n + b] && !self.segment_adjacency[b * n + a] {
+                    defects.push(TileDefect::AsymmetricAdjacency(a, b));
+                }
+            }
+        }
+
+        defects
+    }
+}
+
+/// Every tile that can end up in a game, from the built in expansions and the given
+/// tilesets, labelled for reporting. Tileset tiles that can't be built are left out.
+pub fn all_tiles(tilesets: &[Tileset]) -> Vec<(String, Tile)> {
+    let builtin = [
+        ("Base game", Tile::default_library_tallies()),
+        (
+            "Inns & Cathedrals",
+            Tile::inns_and_cathedrals_library_tallies(),
+        ),
+        (
+            "Traders & Builders",
+            Tile::traders_and_builders_library_tallies(),
+        ),
+        (
+            "Abbots & Gardens",
+            Tile::abbots_and_gardens_library_tallies(),
+        ),
+        (
+            "Princess & Dragon",
+            Tile::princess_and_dragon_library_tallies(),
+        ),
+        ("The Tower", Tile::tower_library_tallies()),
+        ("Hills & Sheep", Tile::hills_and_sheep_library_tallies()),
+    ];
+    let rivers = [
+        (
+            "River Expansion 1",
+            GameExpansions {
+                rivers_1: true,
+                ..Default::default()
+            },
+        ),
+        (
+            "River Expansion 2",
+            GameExpansions {
+                rivers_2: true,
+                ..Default::default()
+            },
+        ),
+    ];
+    builtin
+        .into_iter()
+        .flat_map(|(expansion, tallies)| {
+            tallies
+                .into_iter()
+                .enumerate()
+                .map(move |(i, (tile, _))| (format!("{expansion} tile {i}"), tile.clone()))
+        })
+        .chain(rivers.into_iter().flat_map(|(expansion, expansions)| {
+            expansions
+                .rivers()
+                .unwrap_or_default()
+                .into_iter()
+                .enumerate()
+                .map(move |(i, tile)| (format!("{expansion} tile {i}"), tile))
+        }))
+        .chain(tilesets.iter().flat_map(|tileset| {
+            tileset
+                .tiles
+                .iter()
+                .filter(|definition| {
+                    definition_defects(&definition.verts, &definition.segments).is_empty()
+                })
+                .map(|definition| (tileset_label(tileset, definition), definition.tile()))
+        }))
+        .collect()
+}
+
+/// Validates every tile that can end up in a game, returning each defect with the tile
+/// and rotation it was found in. Tileset tiles are checked with
+/// [`TileDefinition::validate`], so those that can't be built are reported too.
+pub fn validate_all_tiles(tilesets: &[Tileset]) -> Vec<(String, usize, TileDefect)> {
+    let builtin = all_tiles(&[])
+        .into_iter()
+        .map(|(label, tile)| (label, tile.validate()));
+    let tilesets = tilesets.iter().flat_map(|tileset| {
+        tileset
+            .tiles
+            .iter()
+            .map(|definition| (tileset_label(tileset, definition), definition.validate()))
+    });
+    builtin
+        .chain(tilesets)
+        .flat_map(|(label, defects)| {
+            defects
+                .into_iter()
+                .map(move |(rotation, defect)| (label.clone(), rotation, defect))
+        })
+        .collect()
+}
+
+fn tileset_label(tileset: &Tileset, definition: &TileDefinition) -> String {
+    format!("{} tile '{}'", tileset.expansion, definition.name)
+}
+
+fn span_covers(span: TileEdgeSpan, slot: usize) -> bool {
+    match span {
+        TileEdgeSpan::Beginning => slot == 0,
+        TileEdgeSpan::Middle => slot == 1,
+        TileEdgeSpan::End => slot == 2,
+        TileEdgeSpan::Full => true,
+    }
+}
+
+/// Signed area of the polygon, by the shoelace formula.
+fn polygon_area(polygon: &[Vec2]) -> f32 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| polygon[i].perp_dot(polygon[(i + 1) % n]))
+        .sum::<f32>()
+        / 2.0
+}
+
+/// Whether any two non-adjacent sides of the polygon cross. Sides that only touch or run
+/// along each other, as they do where a polygon wraps around a hole, don't count.
+fn polygon_self_intersects(polygon: &[Vec2]) -> bool {
+    let n = polygon.len();
+    let side = |i: usize| (polygon[i], polygon[(i + 1) % n]);
+    (0..n).any(|i| {
+        ((i + 2)..n)
+            .filter(|&j| !(i == 0 && j == n - 1))
+            .any(|j| sides_cross(side(i), side(j)))
+    })
+}
+
+/// Whether the two polygons share any area. Polygons that only meet along their sides or
+/// at their corners, as neighbouring segments do, don't count.
+fn polygons_overlap(a: &[Vec2], b: &[Vec2]) -> bool {
+    sides(a).any(|side| sides(b).any(|other| sides_cross(side, other)))
+        || interior_samples(a).any(|point| strictly_inside(point, b))
+        || interior_samples(b).any(|point| strictly_inside(point, a))
+}
+
+fn sides(polygon: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    let n = polygon.len();
+    (0..n).map(move |i| (polygon[i], polygon[(i + 1) % n]))
+}
+
+/// The polygon's corners, and the middle of each of its sides nudged just inside it. If
+/// another polygon overlaps this one without any of their sides crossing, it has to
+/// contain one of these. Sides the polygon runs back along, which join parts of it
+/// together as it passes under another segment, have no inside to nudge into.
+fn interior_samples(polygon: &[Vec2]) -> impl Iterator<Item = Vec2> + '_ {
+    const NUDGE: f32 = 1e-3;
+    let inwards = polygon_area(polygon).signum();
+    let doubled_back = move |(a, b): (Vec2, Vec2)| sides(polygon).any(|side| side == (b, a));
+    polygon.iter().copied().chain(
+        sides(polygon)
+            .filter(move |&side| !doubled_back(side))
+            .map(move |(a, b)| {
+                (a + b) / 2.0 + (b - a).normalize_or_zero().perp() * NUDGE * inwards
+            }),
+    )
+}
+
+/// Whether the point lies inside the polygon, and not on or right next to its sides.
+fn strictly_inside(point: Vec2, polygon: &[Vec2]) -> bool {
+    point_in_polygon(point, polygon)
+        && sides(polygon).all(|(a, b)| distance_to_side(point, a, b) > EPSILON)
+}
+
+fn distance_to_side(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let side = b - a;
+    let t = ((point - a).dot(side) / side.length_squared()).clamp(0.0, 1.0);
+    point.distance(a + side * t)
+}
+
+fn sides_cross((a, b): (Vec2, Vec2), (c, d): (Vec2, Vec2)) -> bool {
+    let turn = |p: Vec2, q: Vec2, r: Vec2| {
+        let cross = (q - p).perp_dot(r - p);
+        if cross > EPSILON {
+            1
+        } else if cross < -EPSILON {
+            -1
+        } else {
+            0
+        }
+    };
+    let (abc, abd) = (turn(a, b, c), turn(a, b, d));
+    let (cda, cdb) = (turn(c, d, a), turn(c, d, b));
+    abc * abd < 0 && cda * cdb < 0
+}
@@ -0,0 +1,658 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::mpsc::{channel, Receiver, Sender},
+    time::{Duration, Instant},
+};
+
+use ggez::{
+    glam::{vec2, Vec2},
+    graphics::{Canvas, Color, DrawMode, Mesh, Rect, Text},
+    winit::event::MouseButton,
+    Context, GameError,
+};
+use log::{info, trace};
+
+use crate::{
+    game_client::GameClient,
+    main_client::MainEvent,
+    tile::{
+        tileset::{TileDefinition, Tileset},
+        validation::{all_tiles, TileDefect},
+        Orientation, SegmentAttribute, SegmentBorderPiece, SegmentDefinition, SegmentType, Tile,
+        TileEdge, TileEdgeSpan, DEFAULT_MIDDLE_SECTION_WIDTH,
+    },
+    util::{refit_to_rect, AnchorPoint, ContextExt, DrawableWihParamsExt, TextExt},
+    Shared,
+};
+use ggez_no_re::{
+    line::Line,
+    sub_event_handler::SubEventHandler,
+    ui_manager::{
+        button::Button, text_input::TextInput, Bounds, UIElement, UIElementRenderable, UIManager,
+    },
+};
+
+const MESSAGE_DISPLAY_PERIOD: Duration = Duration::from_secs(10);
+/// How close to a side of the tile a click has to be to pick an edge, as a fraction of
+/// the tile's width.
+const EDGE_PICK_DISTANCE: f32 = 0.06;
+/// How close a click has to be to an existing vertex to reuse it rather than place a new one.
+const VERTEX_SNAP_DISTANCE: f32 = 0.03;
+const MAX_LISTED_DEFECTS: usize = 6;
+
+const CUSTOM_TILESET_FILE: &str = "custom_tiles.json";
+const CUSTOM_TILESET_NAME: &str = "Custom Tiles";
+
+const SEGMENT_TYPES: [SegmentType; 7] = [
+    SegmentType::Farm,
+    SegmentType::City,
+    SegmentType::Road,
+    SegmentType::Monastary,
+    SegmentType::Garden,
+    SegmentType::Village,
+    SegmentType::River,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EditorTool {
+    /// Adds the beginning, middle or end of whichever side is clicked.
+    EdgeSpans,
+    /// Adds the whole of whichever side is clicked.
+    FullEdges,
+    Vertices,
+    Shield,
+    MeepleSpot,
+}
+
+impl EditorTool {
+    const ALL: [EditorTool; 5] = [
+        EditorTool::EdgeSpans,
+        EditorTool::FullEdges,
+        EditorTool::Vertices,
+        EditorTool::Shield,
+        EditorTool::MeepleSpot,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            EditorTool::EdgeSpans => "Edge Spans",
+            EditorTool::FullEdges => "Full Edges",
+            EditorTool::Vertices => "Vertices",
+            EditorTool::Shield => "Shield",
+            EditorTool::MeepleSpot => "Meeple Spot",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum TileEditorEvent {
+    MainEvent(MainEvent),
+    NewSegment,
+    NextSegment,
+    CycleSegmentType,
+    CycleTool,
+    UndoPiece,
+    DeleteSegment,
+    CycleMountingTile,
+    Save,
+}
+
+/// A segment as it's being drawn, before it becomes a [`SegmentDefinition`].
+#[derive(Clone, Debug)]
+struct EditorSegment {
+    stype: SegmentType,
+    edges: Vec<SegmentBorderPiece>,
+    shield: Option<Vec2>,
+    meeple_spot: Option<Vec2>,
+}
+
+impl EditorSegment {
+    fn new(stype: SegmentType) -> Self {
+        EditorSegment {
+            stype,
+            edges: Vec::new(),
+            shield: None,
+            meeple_spot: None,
+        }
+    }
+
+    fn definition(&self) -> SegmentDefinition {
+        let mut attributes = Vec::new();
+        if let Some(shield_location) = self.shield {
+            attributes.push(SegmentAttribute::Fortified { shield_location });
+        }
+        if let Some(meeple_spot) = self.meeple_spot {
+            attributes.push(SegmentAttribute::CustomMeepleSpot(meeple_spot));
+        }
+        if attributes.is_empty() {
+            SegmentDefinition::Segment {
+                stype: self.stype,
+                edges: self.edges.clone(),
+            }
+        } else {
+            SegmentDefinition::SpecialSegment {
+                stype: self.stype,
+                attributes,
+                edges: self.edges.clone(),
+            }
+        }
+    }
+}
+
+/// Where along a side of the tile a point is, clockwise from the side's beginning.
+fn point_on_side(orientation: Orientation, along: f32) -> Vec2 {
+    match orientation {
+        Orientation::North => vec2(along, 0.0),
+        Orientation::East => vec2(1.0, along),
+        Orientation::South => vec2(1.0 - along, 1.0),
+        Orientation::West => vec2(0.0, 1.0 - along),
+    }
+}
+
+fn edge_endpoints((span, orientation): TileEdge) -> [Vec2; 2] {
+    let low_middle = 0.5 - DEFAULT_MIDDLE_SECTION_WIDTH / 2.0;
+    let high_middle = 0.5 + DEFAULT_MIDDLE_SECTION_WIDTH / 2.0;
+    let (start, end) = match span {
+        TileEdgeSpan::Beginning => (0.0, low_middle),
+        TileEdgeSpan::Middle => (low_middle, high_middle),
+        TileEdgeSpan::End => (high_middle, 1.0),
+        TileEdgeSpan::Full => (0.0, 1.0),
+    };
+    [
+        point_on_side(orientation, start),
+        point_on_side(orientation, end),
+    ]
+}
+
+/// The side of the tile a point is close enough to to pick, and how far along it the
+/// point is.
+fn nearest_side(pos: Vec2) -> Option<(Orientation, f32)> {
+    [
+        (Orientation::North, pos.y, pos.x),
+        (Orientation::East, 1.0 - pos.x, pos.y),
+        (Orientation::South, 1.0 - pos.y, 1.0 - pos.x),
+        (Orientation::West, pos.x, 1.0 - pos.y),
+    ]
+    .into_iter()
+    .filter(|(_, distance, _)| *distance < EDGE_PICK_DISTANCE)
+    .min_by(|(_, a, _), (_, b, _)| a.total_cmp(b))
+    .map(|(orientation, _, along)| (orientation, along))
+}
+
+pub struct TileEditorClient {
+    parent_channel: Sender<MainEvent>,
+    shared: Shared,
+    _event_sender: Sender<TileEditorEvent>,
+    event_receiver: Receiver<TileEditorEvent>,
+    ui: UIManager<TileEditorEvent, TileEditorEvent>,
+    segment_type_button: Rc<RefCell<Button<TileEditorEvent>>>,
+    tool_button: Rc<RefCell<Button<TileEditorEvent>>>,
+    mounting_button: Rc<RefCell<Button<TileEditorEvent>>>,
+    name_input: Rc<RefCell<TextInput>>,
+    verts: Line,
+    segments: Vec<EditorSegment>,
+    selected_segment: usize,
+    tool: EditorTool,
+    tile: Option<Tile>,
+    defects: Vec<TileDefect>,
+    mounting_tiles: Vec<(String, Tile)>,
+    mounting_index: usize,
+    message: Option<(String, Instant)>,
+}
+
+impl TileEditorClient {
+    const PANEL: Rect = Rect::new(0.65, 0.15, 0.0, 0.0);
+
+    pub fn new(parent_channel: Sender<MainEvent>, shared: Shared) -> TileEditorClient {
+        let (event_sender, event_receiver) = channel();
+        let panel_button = |y: f32, text: &str, event: TileEditorEvent| {
+            UIElement::Button(Button::new(
+                Bounds {
+                    relative: Self::PANEL,
+                    absolute: Rect::new(0.0, y, 300.0, 40.0),
+                },
+                Text::new(text),
+                event,
+            ))
+        };
+        let (
+            ui,
+            [_, _, _, UIElement::Button(segment_type_button), UIElement::Button(tool_button), _, _, UIElement::Button(mounting_button), UIElement::TextInput(name_input), _],
+        ) = UIManager::new_and_rc_elements(
+            event_sender.clone(),
+            [
+                UIElement::Button(Button::new(
+                    Bounds::absolute(Rect::new(30.0, 30.0, 120.0, 40.0)),
+                    Text::new("Back"),
+                    TileEditorEvent::MainEvent(MainEvent::MainMenu),
+                )),
+                panel_button(0.0, "New Segment", TileEditorEvent::NewSegment),
+                panel_button(50.0, "Next Segment", TileEditorEvent::NextSegment),
+                panel_button(100.0, "", TileEditorEvent::CycleSegmentType),
+                panel_button(150.0, "", TileEditorEvent::CycleTool),
+                panel_button(200.0, "Undo Point", TileEditorEvent::UndoPiece),
+                panel_button(250.0, "Delete Segment", TileEditorEvent::DeleteSegment),
+                panel_button(300.0, "", TileEditorEvent::CycleMountingTile),
+                UIElement::TextInput(TextInput::new(Bounds {
+                    relative: Self::PANEL,
+                    absolute: Rect::new(80.0, 360.0, 220.0, 24.0),
+                })),
+                panel_button(400.0, "Save Tile", TileEditorEvent::Save),
+            ],
+        )
+        else {
+            panic!()
+        };
+        let mounting_tiles = all_tiles(&shared.tilesets.borrow());
+        let mut this = TileEditorClient {
+            parent_channel,
+            shared,
+            _event_sender: event_sender,
+            event_receiver,
+            ui,
+            segment_type_button,
+            tool_button,
+            mounting_button,
+            name_input,
+            verts: Vec::new(),
+            segments: vec![EditorSegment::new(SegmentType::Farm)],
+            selected_segment: 0,
+            tool: EditorTool::EdgeSpans,
+            tile: None,
+            defects: Vec::new(),
+            mounting_tiles,
+            mounting_index: 0,
+            message: None,
+        };
+        this.update_buttons();
+        this
+    }
+
+    fn editor_bounds(ctx: &Context) -> Rect {
+        let res = ctx.res();
+        let size = (res.y * 0.6).min(res.x * 0.45);
+        let center = res * vec2(0.35, 0.45);
+        Rect::new(center.x - size / 2.0, center.y - size / 2.0, size, size)
+    }
+
+    fn definitions(&self) -> Vec<SegmentDefinition> {
+        self.segments
+            .iter()
+            .map(EditorSegment::definition)
+            .collect()
+    }
+
+    /// Rebuilds the tile being edited, as long as every segment has enough of an outline
+    /// to draw.
+    fn rebuild(&mut self) {
+        let drawable = !self.segments.is_empty()
+            && self
+                .segments
+                .iter()
+                .all(|segment| !segment.edges.is_empty());
+        let tile = drawable
+            .then(|| Tile::new_with_attributes(self.verts.clone(), self.definitions(), Vec::new()));
+        self.defects = tile
+            .iter()
+            .flat_map(Tile::validate)
+            .filter_map(|(rotation, defect)| (rotation == 0).then_some(defect))
+            .collect();
+        let degenerate = self
+            .defects
+            .iter()
+            .any(|defect| matches!(defect, TileDefect::DegeneratePolygon(_)));
+        self.tile = tile.filter(|_| !degenerate);
+    }
+
+    fn update_buttons(&mut self) {
+        self.segment_type_button.borrow_mut().text =
+            Text::new(match self.segments.get(self.selected_segment) {
+                Some(segment) => format!("Type: {}", segment.stype.name()),
+                None => "Type: -".to_string(),
+            });
+        self.tool_button.borrow_mut().text = Text::new(format!("Tool: {}", self.tool.name()));
+        self.mounting_button.borrow_mut().text =
+            Text::new(match self.mounting_tiles.get(self.mounting_index) {
+                Some((label, _)) => format!("Mount: {label}"),
+                None => "Mount: -".to_string(),
+            });
+    }
+
+    fn click(&mut self, pos: Vec2) {
+        let Some(segment) = self.segments.get_mut(self.selected_segment) else {
+            return;
+        };
+        match self.tool {
+            EditorTool::EdgeSpans | EditorTool::FullEdges => {
+                let Some((orientation, along)) = nearest_side(pos) else {
+                    return;
+                };
+                let span = if self.tool == EditorTool::FullEdges {
+                    TileEdgeSpan::Full
+                } else if along < 0.5 - DEFAULT_MIDDLE_SECTION_WIDTH / 2.0 {
+                    TileEdgeSpan::Beginning
+                } else if along > 0.5 + DEFAULT_MIDDLE_SECTION_WIDTH / 2.0 {
+                    TileEdgeSpan::End
+                } else {
+                    TileEdgeSpan::Middle
+                };
+                segment
+                    .edges
+                    .push(SegmentBorderPiece::Edge((span, orientation)));
+            }
+            EditorTool::Vertices => {
+                let index = match self
+                    .verts
+                    .iter()
+                    .position(|vert| vert.distance(pos) < VERTEX_SNAP_DISTANCE)
+                {
+                    Some(index) => index,
+                    None => {
+                        self.verts.push(pos);
+                        self.verts.len() - 1
+                    }
+                };
+                segment.edges.push(SegmentBorderPiece::Vert(index));
+            }
+            EditorTool::Shield => segment.shield = Some(pos),
+            EditorTool::MeepleSpot => segment.meeple_spot = Some(pos),
+        }
+        self.rebuild();
+    }
+
+    fn save(&mut self) -> Result<String, String> {
+        if self.tile.is_none() {
+            return Err("Every segment needs an outline before the tile can be saved".to_string());
+        }
+        // a tile with defects would be turned away, or break games, once it's loaded again
+        if !self.defects.is_empty() {
+            return Err("Fix the problems listed before saving the tile".to_string());
+        }
+        let name = self.name_input.borrow().text.trim().to_string();
+        if name.is_empty() {
+            return Err("The tile needs a name".to_string());
+        }
+        let path = self.shared.args.tilesets.join(CUSTOM_TILESET_FILE);
+        let mut tileset = if path.exists() {
+            Tileset::load(&path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?
+        } else {
+            Tileset {
+                expansion: CUSTOM_TILESET_NAME.to_string(),
+                tiles: Vec::new(),
+            }
+        };
+        // saving under the name of an existing tile replaces it, keeping its art
+        let art = tileset
+            .tiles
+            .iter()
+            .find(|definition| definition.name == name)
+            .and_then(|definition| definition.art.clone());
+        tileset.tiles.retain(|definition| definition.name != name);
+        tileset.tiles.push(TileDefinition {
+            name: name.clone(),
+            verts: self.verts.clone(),
+            segments: self.definitions(),
+            attributes: Vec::new(),
+            count: 1,
+            art,
+        });
+        tileset
+            .save(&path)
+            .map_err(|e| format!("Failed to save {}: {e}", path.display()))?;
+        info!("saved tile '{name}' to {}", path.display());
+        *self.shared.tilesets.borrow_mut() = Tileset::load_dir(&self.shared.args.tilesets);
+        Ok(format!("Saved '{name}' to {}", path.display()))
+    }
+
+    fn handle_event(&mut self, event: TileEditorEvent) -> Result<(), GameError> {
+        trace!("event = {event:?}");
+        match event {
+            TileEditorEvent::MainEvent(event) => self.parent_channel.send(event).unwrap(),
+            TileEditorEvent::NewSegment => {
+                self.segments.push(EditorSegment::new(SegmentType::Farm));
+                self.selected_segment = self.segments.len() - 1;
+            }
+            TileEditorEvent::NextSegment => {
+                self.selected_segment = (self.selected_segment + 1) % self.segments.len().max(1);
+            }
+            TileEditorEvent::CycleSegmentType => {
+                if let Some(segment) = self.segments.get_mut(self.selected_segment) {
+                    let index = SEGMENT_TYPES
+                        .iter()
+                        .position(|stype| *stype == segment.stype)
+                        .unwrap();
+                    segment.stype = SEGMENT_TYPES[(index + 1) % SEGMENT_TYPES.len()];
+                }
+            }
+            TileEditorEvent::CycleTool => {
+                let index = EditorTool::ALL
+                    .iter()
+                    .position(|tool| *tool == self.tool)
+                    .unwrap();
+                self.tool = EditorTool::ALL[(index + 1) % EditorTool::ALL.len()];
+            }
+            TileEditorEvent::UndoPiece => {
+                if let Some(segment) = self.segments.get_mut(self.selected_segment) {
+                    segment.edges.pop();
+                }
+            }
+            TileEditorEvent::DeleteSegment => {
+                if self.selected_segment < self.segments.len() {
+                    self.segments.remove(self.selected_segment);
+                    self.selected_segment = self.selected_segment.saturating_sub(1);
+                }
+            }
+            TileEditorEvent::CycleMountingTile => {
+                self.mounting_index = (self.mounting_index + 1) % self.mounting_tiles.len().max(1);
+            }
+            TileEditorEvent::Save => {
+                let message = match self.save() {
+                    Ok(message) | Err(message) => message,
+                };
+                self.message = Some((message, Instant::now()));
+            }
+        }
+        self.rebuild();
+        self.update_buttons();
+        Ok(())
+    }
+
+    fn draw_editor(&self, ctx: &Context, canvas: &mut Canvas) -> Result<(), GameError> {
+        let bounds = Self::editor_bounds(ctx);
+        let to_screen = |pos: Vec2| refit_to_rect(pos, bounds);
+        Mesh::new_rectangle(
+            ctx,
+            DrawMode::fill(),
+            bounds,
+            Color::from_rgb(220, 220, 220),
+        )?
+        .draw(canvas);
+        if let Some(tile) = &self.tile {
+            tile.render(ctx, canvas, bounds)?;
+
+            // sides that fit against the chosen library tile
+            if let Some((_, mounting_tile)) = self.mounting_tiles.get(self.mounting_index) {
+                for (orientation, _) in Orientation::iter_with_offsets() {
+                    let color = match tile.validate_mounting(mounting_tile, orientation) {
+                        Some(_) => Color::from_rgb(40, 180, 40),
+                        None => Color::from_rgb(200, 40, 40),
+                    };
+                    let [start, end] = edge_endpoints((TileEdgeSpan::Full, orientation));
+                    Mesh::new_line(ctx, &[to_screen(start), to_screen(end)], 6.0, color)?
+                        .draw(canvas);
+                }
+            }
+        }
+
+        // guides where each side's middle section begins and ends
+        for (orientation, _) in Orientation::iter_with_offsets() {
+            let [start, end] = edge_endpoints((TileEdgeSpan::Middle, orientation));
+            for point in [start, end] {
+                Mesh::new_circle(
+                    ctx,
+                    DrawMode::fill(),
+                    to_screen(point),
+                    4.0,
+                    1.0,
+                    Color::from_rgb(96, 96, 96),
+                )?
+                .draw(canvas);
+            }
+        }
+
+        for vert in &self.verts {
+            Mesh::new_circle(
+                ctx,
+                DrawMode::fill(),
+                to_screen(*vert),
+                5.0,
+                1.0,
+                Color::from_rgb(64, 64, 64),
+            )?
+            .draw(canvas);
+        }
+
+        if let Some(segment) = self.segments.get(self.selected_segment) {
+            let outline: Vec<Vec2> = segment
+                .edges
+                .iter()
+                .flat_map(|piece| match piece {
+                    SegmentBorderPiece::Edge(edge) => edge_endpoints(*edge).to_vec(),
+                    SegmentBorderPiece::Vert(index) => {
+                        self.verts.get(*index).copied().into_iter().collect()
+                    }
+                    SegmentBorderPiece::Break => Vec::new(),
+                })
+                .map(to_screen)
+                .collect();
+            if outline.len() >= 2 {
+                Mesh::new_line(ctx, &outline, 3.0, Color::BLACK)?.draw(canvas);
+            }
+            if let Some(meeple_spot) = segment.meeple_spot {
+                GameClient::draw_meeple(ctx, canvas, to_screen(meeple_spot), Color::WHITE, 0.1)?;
+            }
+        }
+
+        // every rotation of the tile, below the editor
+        if let Some(tile) = &self.tile {
+            let preview_size = bounds.w / 4.0 - 15.0;
+            let mut rotated = tile.clone();
+            for i in 0..4 {
+                rotated.render(
+                    ctx,
+                    canvas,
+                    Rect::new(
+                        bounds.x + i as f32 * (preview_size + 20.0),
+                        bounds.bottom() + 20.0,
+                        preview_size,
+                        preview_size,
+                    ),
+                )?;
+                rotated.rotate_clockwise();
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_panel(&self, ctx: &Context, canvas: &mut Canvas) -> Result<(), GameError> {
+        let res = ctx.res();
+        let panel = res * Vec2::from(Self::PANEL.point());
+
+        Text::new(format!(
+            "Segment {} of {}",
+            (self.selected_segment + 1).min(self.segments.len()),
+            self.segments.len()
+        ))
+        .size(24.0)
+        .anchored_by(ctx, panel - vec2(0.0, 10.0), AnchorPoint::SouthWest)?
+        .color(Color::BLACK)
+        .draw(canvas);
+
+        if let Some((_, mounting_tile)) = self.mounting_tiles.get(self.mounting_index) {
+            mounting_tile.render(
+                ctx,
+                canvas,
+                Rect::new(panel.x + 310.0, panel.y + 300.0, 40.0, 40.0),
+            )?;
+        }
+
+        self.name_input.borrow().render_label(
+            ctx,
+            canvas,
+            &Text::new("Name:"),
+            AnchorPoint::CenterWest,
+        )?;
+
+        let mut line = panel + vec2(0.0, 460.0);
+        if self.tile.is_some() && self.defects.is_empty() {
+            Text::new("No problems found")
+                .anchored_by(ctx, line, AnchorPoint::NorthWest)?
+                .color(Color::from_rgb(0, 96, 0))
+                .draw(canvas);
+        }
+        for defect in self.defects.iter().take(MAX_LISTED_DEFECTS) {
+            Text::new(defect.to_string())
+                .anchored_by(ctx, line, AnchorPoint::NorthWest)?
+                .color(Color::from_rgb(96, 0, 0))
+                .draw(canvas);
+            line += vec2(0.0, 20.0);
+        }
+
+        if let Some((message, _)) = &self.message {
+            Text::new(message)
+                .anchored_by(
+                    ctx,
+                    res * vec2(0.5, 1.0) - vec2(0.0, 20.0),
+                    AnchorPoint::SouthCenter,
+                )?
+                .color(Color::BLACK)
+                .draw(canvas);
+        }
+        Ok(())
+    }
+}
+
+impl SubEventHandler for TileEditorClient {
+    fn update(&mut self, ctx: &mut Context) -> Result<(), GameError> {
+        self.ui.update(ctx)?;
+        while let Ok(event) = self.event_receiver.try_recv() {
+            self.handle_event(event)?;
+        }
+
+        if ctx.mouse.button_just_pressed(MouseButton::Left) {
+            let bounds = Self::editor_bounds(ctx);
+            let pos = (Vec2::from(ctx.mouse.position()) - Vec2::from(bounds.point())) / bounds.w;
+            if (0.0..=1.0).contains(&pos.x) && (0.0..=1.0).contains(&pos.y) {
+                self.click(pos);
+            }
+        }
+
+        if let Some((_, shown)) = self.message {
+            if Instant::now() - shown > MESSAGE_DISPLAY_PERIOD {
+                self.message = None;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> Result<(), GameError> {
+        let res = ctx.res();
+
+        Text::new("Tile Editor")
+            .size(64.0)
+            .anchored_by(
+                ctx,
+                res * vec2(0.5, 0.0) + vec2(0.0, 20.0),
+                AnchorPoint::NorthCenter,
+            )?
+            .color(Color::BLACK)
+            .draw(canvas);
+
+        self.draw_editor(ctx, canvas)?;
+        self.draw_panel(ctx, canvas)?;
+        self.ui.draw(ctx, canvas)?;
+        Ok(())
+    }
+}